@@ -5,7 +5,10 @@
 
 //! Functionality related to creating new HTTP Connectors
 
+use aws_smithy_async::rt::sleep::SharedAsyncSleep;
 use aws_smithy_client::erase::DynConnector;
+use aws_smithy_client::http_connector::ConnectorSettings;
+use aws_types::os_shim_internal::Env;
 
 // unused when all crate features are disabled
 /// Unwrap an [`Option<DynConnector>`](aws_smithy_client::erase::DynConnector), and panic with a helpful error message if it's `None`
@@ -13,17 +16,88 @@ pub(crate) fn expect_connector(connector: Option<DynConnector>) -> DynConnector
     connector.expect("No HTTP connector was available. Enable the `rustls` crate feature or set a connector to fix this.")
 }
 
-#[cfg(feature = "client-hyper")]
-pub use aws_smithy_client::conns::default_connector;
-
 #[cfg(all(feature = "native-tls", not(feature = "allow-compilation")))]
 compile_error!("Feature native-tls has been removed. For upgrade instructions, see: https://awslabs.github.io/smithy-rs/design/transport/connector.html");
 
-/// Given `ConnectorSettings` and a [`SharedAsyncSleep`](aws_smithy_async::rt::sleep::SharedAsyncSleep), create a `DynConnector` from defaults depending on what cargo features are activated.
-#[cfg(not(feature = "client-hyper"))]
+/// Given `ConnectorSettings` and a [`SharedAsyncSleep`], create a `DynConnector` from defaults depending on what cargo features are activated.
+///
+/// The connector sends requests through the proxy configured in the environment, if any. See
+/// [`ProxyConfig::from_env`](aws_smithy_client::proxy::ProxyConfig::from_env) for details.
 pub fn default_connector(
-    _settings: &aws_smithy_client::http_connector::ConnectorSettings,
-    _sleep: Option<aws_smithy_async::rt::sleep::SharedAsyncSleep>,
+    settings: &ConnectorSettings,
+    sleep: Option<SharedAsyncSleep>,
+) -> Option<DynConnector> {
+    default_connector_with_env(settings, sleep, &Env::real())
+}
+
+/// Like [`default_connector`], but the proxy is loaded from `env`
+#[cfg(feature = "client-hyper")]
+pub(crate) fn default_connector_with_env(
+    settings: &ConnectorSettings,
+    sleep: Option<SharedAsyncSleep>,
+    env: &Env,
+) -> Option<DynConnector> {
+    aws_smithy_client::conns::default_connector_with_proxy(settings, sleep, proxy_from_env(env))
+}
+
+#[cfg(not(feature = "client-hyper"))]
+pub(crate) fn default_connector_with_env(
+    _settings: &ConnectorSettings,
+    _sleep: Option<SharedAsyncSleep>,
+    _env: &Env,
 ) -> Option<DynConnector> {
     None
 }
+
+/// Like [`default_connector_with_env`], but the connector uses `tls_options` for TLS
+#[cfg(feature = "rustls")]
+pub(crate) fn default_connector_with_tls_options(
+    settings: &ConnectorSettings,
+    sleep: Option<SharedAsyncSleep>,
+    tls_options: &aws_smithy_client::tls::TlsOptions,
+    env: &Env,
+) -> DynConnector {
+    aws_smithy_client::conns::default_connector_with_tls_options(
+        settings,
+        sleep,
+        tls_options,
+        proxy_from_env(env),
+    )
+}
+
+/// Load the proxy configuration from `env`, ignoring it if it is invalid
+#[cfg(feature = "client-hyper")]
+fn proxy_from_env(env: &Env) -> Option<aws_smithy_client::proxy::ProxyConfig> {
+    use aws_smithy_types::error::display::DisplayErrorContext;
+    aws_smithy_client::proxy::ProxyConfig::from_env_vars(|name| env.get(name).ok()).unwrap_or_else(
+        |err| {
+            tracing::warn!(err = %DisplayErrorContext(&err), "ignoring the proxy configured in the environment");
+            None
+        },
+    )
+}
+
+#[cfg(all(test, feature = "client-hyper"))]
+mod test {
+    use super::proxy_from_env;
+    use aws_types::os_shim_internal::Env;
+
+    #[test]
+    fn proxy_is_loaded_from_the_provided_env() {
+        let target = "https://sts.us-east-1.amazonaws.com".parse().unwrap();
+        let proxy = proxy_from_env(&Env::from_slice(&[("HTTPS_PROXY", "http://proxy:3128")]))
+            .expect("proxy is configured");
+        assert_eq!(
+            Some("http://proxy:3128/"),
+            proxy
+                .proxy_uri(&target)
+                .map(|uri| uri.to_string())
+                .as_deref()
+        );
+        let imds = "http://169.254.169.254/latest/api/token".parse().unwrap();
+        assert_eq!(None, proxy.proxy_uri(&imds));
+
+        assert!(proxy_from_env(&Env::from_slice(&[])).is_none());
+        assert!(proxy_from_env(&Env::from_slice(&[("HTTPS_PROXY", "https://proxy")])).is_none());
+    }
+}
@@ -6,7 +6,8 @@
 //! Providers that implement the default AWS provider chain
 //!
 //! Default Provider chains for [`region`](crate::default_provider::region), [`credentials`](crate::default_provider::credentials),
-//! [retries](crate::default_provider::retry_config), [timeouts](crate::default_provider::timeout_config) and
+//! [retries](crate::default_provider::retry_config), [timeouts](crate::default_provider::timeout_config),
+//! [defaults mode](crate::default_provider::defaults_mode), [endpoint URL](crate::default_provider::endpoint_url) and
 //! [app name](crate::default_provider::app_name).
 //!
 //! Typically, this module is used via [`load_from_env`](crate::load_from_env) or [`from_env`](crate::from_env). It should only be used directly
@@ -36,6 +37,12 @@ pub mod app_name;
 /// if you need to set custom configuration options to override the default resolution chain.
 pub mod timeout_config;
 
+/// Default [defaults mode](crate::defaults_mode::DefaultsMode) provider chain
+///
+/// Typically, this module is used via [`load_from_env`](crate::load_from_env) or [`from_env`](crate::from_env). It should only be used directly
+/// if you need to set custom configuration options to override the default resolution chain.
+pub mod defaults_mode;
+
 /// Default credentials provider chain
 ///
 /// Typically, this module is used via [`load_from_env`](crate::load_from_env) or [`from_env`](crate::from_env). It should only be used directly
@@ -47,3 +54,16 @@ pub mod use_fips;
 
 /// Default dual-stack provider chain
 pub mod use_dual_stack;
+
+/// Default endpoint URL provider chain
+pub mod endpoint_url;
+
+/// Default "disable request compression" provider chain
+pub mod disable_request_compression;
+
+/// Default request minimum compression size provider chain
+pub mod request_min_compression_size_bytes;
+
+/// Default CA bundle provider chain
+#[cfg(feature = "rustls")]
+pub mod ca_bundle;
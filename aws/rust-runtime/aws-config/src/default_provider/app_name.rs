@@ -5,6 +5,7 @@
 
 use crate::environment::app_name::EnvironmentVariableAppNameProvider;
 use crate::profile::app_name;
+use crate::provenance::ValueSource;
 use crate::provider_config::ProviderConfig;
 use aws_types::app_name::AppName;
 
@@ -43,9 +44,21 @@ impl Builder {
 
     /// Build an [`AppName`] from the default chain
     pub async fn app_name(self) -> Option<AppName> {
-        self.env_provider
-            .app_name()
-            .or(self.profile_file.build().app_name().await)
+        self.app_name_with_source()
+            .await
+            .map(|(app_name, _source)| app_name)
+    }
+
+    /// Build an [`AppName`] from the default chain, along with where it came from
+    pub(crate) async fn app_name_with_source(self) -> Option<(AppName, ValueSource)> {
+        if let Some(app_name) = self.env_provider.app_name() {
+            return Some((app_name, ValueSource::environment("AWS_SDK_UA_APP_ID")));
+        }
+        let profile_file = self.profile_file.build();
+        profile_file
+            .app_name_with_profile()
+            .await
+            .map(|(app_name, profile)| (app_name, ValueSource::profile(profile, "sdk-ua-app-id")))
     }
 }
 
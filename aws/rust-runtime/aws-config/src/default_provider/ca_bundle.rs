@@ -0,0 +1,130 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::provenance::ValueSource;
+use crate::provider_config::ProviderConfig;
+use crate::standard_property::StandardProperty;
+use aws_smithy_client::tls::TlsOptions;
+use aws_smithy_types::error::display::DisplayErrorContext;
+
+mod env {
+    pub(super) const CA_BUNDLE: &str = "AWS_CA_BUNDLE";
+}
+
+mod profile_key {
+    pub(super) const CA_BUNDLE: &str = "ca_bundle";
+}
+
+/// Load the TLS options for a custom certificate authority bundle
+///
+/// This checks the following sources:
+/// 1. The environment variable `AWS_CA_BUNDLE=/path/to/bundle.pem`
+/// 2. The profile key `ca_bundle=/path/to/bundle.pem`
+///
+/// The bundle holds PEM encoded certificates, which are trusted in addition to the native root
+/// certificates. If the bundle can't be read or is invalid, the provider will return None and an
+/// error will be logged.
+pub async fn ca_bundle_provider(provider_config: &ProviderConfig) -> Option<TlsOptions> {
+    ca_bundle_with_source(provider_config)
+        .await
+        .map(|((_path, tls_options), _source)| tls_options)
+}
+
+/// Load the TLS options for a custom certificate authority bundle, along with the path of the
+/// bundle and where it came from
+pub(crate) async fn ca_bundle_with_source(
+    provider_config: &ProviderConfig,
+) -> Option<((String, TlsOptions), ValueSource)> {
+    let property = StandardProperty::new()
+        .env(env::CA_BUNDLE)
+        .profile(profile_key::CA_BUNDLE);
+    let (path, source) = property.load(provider_config).await?;
+    let bundle = match provider_config.fs().read_to_end(path.as_ref()).await {
+        Ok(bundle) => bundle,
+        Err(err) => {
+            tracing::warn!(err = %DisplayErrorContext(&err), path = %path, source = %source, "failed to read the CA bundle");
+            return None;
+        }
+    };
+    match TlsOptions::builder().ca_bundle(bundle).build() {
+        Ok(tls_options) => Some(((path.into_owned(), tls_options), source.into())),
+        Err(err) => {
+            tracing::warn!(err = %DisplayErrorContext(&err), path = %path, source = %source, "invalid CA bundle");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::default_provider::ca_bundle::{ca_bundle_provider, ca_bundle_with_source};
+    use crate::profile::profile_file::{ProfileFileKind, ProfileFiles};
+    use crate::provenance::ValueSource;
+    use crate::provider_config::ProviderConfig;
+    use aws_types::os_shim_internal::{Env, Fs};
+    use tracing_test::traced_test;
+
+    const CA: &str = include_str!("../../test-data/tls/ca.pem");
+
+    fn profile(contents: &str) -> ProviderConfig {
+        ProviderConfig::empty()
+            .with_profile_config(
+                Some(
+                    ProfileFiles::builder()
+                        .with_file(ProfileFileKind::Config, "conf")
+                        .build(),
+                ),
+                None,
+            )
+            .with_fs(Fs::from_slice(&[
+                ("conf", contents),
+                ("/etc/ca.pem", CA),
+                ("/etc/other-ca.pem", CA),
+                ("/etc/invalid.pem", "not a certificate"),
+            ]))
+    }
+
+    #[tokio::test]
+    async fn no_ca_bundle() {
+        let conf = profile("[default]\nregion = us-east-1").with_env(Env::from_slice(&[]));
+        assert!(ca_bundle_provider(&conf).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn environment_priority() {
+        let conf = profile("[default]\nca_bundle = /etc/other-ca.pem")
+            .with_env(Env::from_slice(&[("AWS_CA_BUNDLE", "/etc/ca.pem")]));
+        let ((path, _), source) = ca_bundle_with_source(&conf).await.unwrap();
+        assert_eq!("/etc/ca.pem", path);
+        assert_eq!(ValueSource::environment("AWS_CA_BUNDLE"), source);
+
+        let conf =
+            profile("[default]\nca_bundle = /etc/other-ca.pem").with_env(Env::from_slice(&[]));
+        let ((path, tls_options), source) = ca_bundle_with_source(&conf).await.unwrap();
+        assert_eq!("/etc/other-ca.pem", path);
+        assert_eq!(ValueSource::profile("default", "ca_bundle"), source);
+        assert!(format!("{:?}", tls_options).contains("ca_certificates: 1"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn log_error_on_missing_file() {
+        let conf = profile("[default]")
+            .with_env(Env::from_slice(&[("AWS_CA_BUNDLE", "/etc/missing.pem")]));
+        assert!(ca_bundle_provider(&conf).await.is_none());
+        assert!(logs_contain("failed to read the CA bundle"));
+        assert!(logs_contain("/etc/missing.pem"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn log_error_on_invalid_bundle() {
+        let conf =
+            profile("[default]\nca_bundle = /etc/invalid.pem").with_env(Env::from_slice(&[]));
+        assert!(ca_bundle_provider(&conf).await.is_none());
+        assert!(logs_contain("invalid CA bundle"));
+        assert!(logs_contain("ca_bundle"));
+    }
+}
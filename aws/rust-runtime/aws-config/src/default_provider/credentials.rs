@@ -4,6 +4,7 @@
  */
 
 use std::borrow::Cow;
+use std::time::Duration;
 
 use aws_credential_types::provider::{self, future, ProvideCredentials};
 use aws_credential_types::Credentials;
@@ -100,6 +101,7 @@ pub struct Builder {
     region_override: Option<Box<dyn ProvideRegion>>,
     region_chain: crate::default_provider::region::Builder,
     conf: Option<ProviderConfig>,
+    probe_deadline: Option<Duration>,
 }
 
 impl Builder {
@@ -160,6 +162,19 @@ impl Builder {
         self
     }
 
+    /// Probe the ECS and IMDS providers at the same time, waiting at most `deadline` for them
+    ///
+    /// Both providers make network requests that can take a while to fail when they aren't
+    /// available. With this option, they are started together once the chain reaches the ECS
+    /// provider, and a provider that hasn't finished when the deadline elapses is skipped.
+    /// The resolution order still decides which credentials are returned.
+    ///
+    /// See [`CredentialsProviderChain::probe_concurrently`].
+    pub fn probe_concurrently(mut self, deadline: Duration) -> Self {
+        self.probe_deadline = Some(deadline);
+        self
+    }
+
     /// Override the configuration used for this provider
     pub fn configure(mut self, config: ProviderConfig) -> Self {
         self.region_chain = self.region_chain.configure(&config);
@@ -186,11 +201,17 @@ impl Builder {
         let imds_provider = self.imds_builder.configure(&conf).build();
         let ecs_provider = self.ecs_builder.configure(&conf).build();
 
-        let provider_chain = CredentialsProviderChain::first_try("Environment", env_provider)
+        let mut provider_chain = CredentialsProviderChain::first_try("Environment", env_provider)
             .or_else("Profile", profile_provider)
             .or_else("WebIdentityToken", web_identity_token_provider)
-            .or_else("EcsContainer", ecs_provider)
-            .or_else("Ec2InstanceMetadata", imds_provider);
+            .or_else_concurrent("EcsContainer", ecs_provider)
+            .or_else_concurrent("Ec2InstanceMetadata", imds_provider);
+        if let Some(deadline) = self.probe_deadline {
+            provider_chain = provider_chain.probe_concurrently(deadline);
+            if let Some(sleep) = conf.sleep() {
+                provider_chain = provider_chain.sleep_impl(sleep);
+            }
+        }
 
         DefaultCredentialsChain { provider_chain }
     }
@@ -359,4 +380,52 @@ mod test {
             creds
         )
     }
+
+    #[tokio::test]
+    #[cfg(feature = "client-hyper")]
+    async fn concurrent_probe_skips_slow_network_providers() {
+        use crate::meta::credentials::ChainNotLoaded;
+        use crate::provider_config::ProviderConfig;
+        use aws_credential_types::time_source::TimeSource;
+        use aws_smithy_async::rt::sleep::TokioSleep;
+        use aws_smithy_client::erase::boxclone::BoxCloneService;
+        use aws_smithy_client::never::NeverConnected;
+        use std::error::Error;
+        use std::time::Duration;
+
+        tokio::time::pause();
+        let conf = ProviderConfig::no_configuration()
+            .with_tcp_connector(BoxCloneService::new(NeverConnected::new()))
+            .with_time_source(TimeSource::default())
+            .with_sleep(TokioSleep::new());
+        let provider = DefaultCredentialsChain::builder()
+            .probe_concurrently(Duration::from_millis(100))
+            .configure(conf)
+            .build()
+            .await;
+        let start = tokio::time::Instant::now();
+        let err = provider
+            .provide_credentials()
+            .await
+            .expect_err("no providers enabled");
+        assert!(
+            start.elapsed() < Duration::from_secs(1),
+            "{:?}",
+            start.elapsed()
+        );
+        let not_loaded = err
+            .source()
+            .and_then(|s| s.downcast_ref::<ChainNotLoaded>())
+            .expect("chain diagnostics");
+        let imds = not_loaded
+            .skipped()
+            .iter()
+            .find(|skipped| skipped.name() == "Ec2InstanceMetadata")
+            .expect("IMDS was skipped");
+        assert!(
+            format!("{}", imds.error().source().expect("has a source")).contains("timed out"),
+            "{:?}",
+            imds.error()
+        );
+    }
 }
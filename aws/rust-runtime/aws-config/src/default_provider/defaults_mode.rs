@@ -0,0 +1,291 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::defaults_mode::DefaultsMode;
+use crate::imds;
+use crate::imds::region::ImdsRegionProvider;
+use crate::provenance::ValueSource;
+use crate::provider_config::ProviderConfig;
+use crate::standard_property::StandardProperty;
+use aws_smithy_async::future::timeout::Timeout;
+use aws_smithy_async::rt::sleep::AsyncSleep;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_types::region::Region;
+use std::str::FromStr;
+use std::time::Duration;
+
+mod env {
+    pub(super) const DEFAULTS_MODE: &str = "AWS_DEFAULTS_MODE";
+    pub(super) const EXECUTION_ENV: &str = "AWS_EXECUTION_ENV";
+    pub(super) const REGION: &str = "AWS_REGION";
+    pub(super) const DEFAULT_REGION: &str = "AWS_DEFAULT_REGION";
+    pub(super) const EC2_METADATA_DISABLED: &str = "AWS_EC2_METADATA_DISABLED";
+}
+
+mod profile_key {
+    pub(super) const DEFAULTS_MODE: &str = "defaults_mode";
+}
+
+/// How long `auto` mode waits for IMDS to return the region the application is running in
+const IMDS_REGION_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Default [`DefaultsMode`] provider chain
+///
+/// This provider will check the following sources in order:
+/// 1. The environment variable `AWS_DEFAULTS_MODE`
+/// 2. The profile key `defaults_mode`
+///
+/// If neither is set, [`DefaultsMode::Legacy`] is used. Invalid values are logged and ignored.
+///
+/// [`DefaultsMode::Auto`] is resolved to a concrete mode. On mobile platforms, it resolves to
+/// [`DefaultsMode::Mobile`]. Otherwise, the [configured region](Builder::region) is compared with the
+/// region the application is running in, taken from `AWS_REGION`/`AWS_DEFAULT_REGION` when
+/// `AWS_EXECUTION_ENV` is set (e.g. in AWS Lambda), or from IMDS. A matching region selects
+/// [`DefaultsMode::InRegion`], a different one [`DefaultsMode::CrossRegion`]. If the region the
+/// application runs in can't be determined, [`DefaultsMode::Standard`] is used.
+pub fn default_provider() -> Builder {
+    Builder::default()
+}
+
+/// Builder for the default [`DefaultsMode`] provider chain
+#[derive(Debug, Default)]
+pub struct Builder {
+    provider_config: ProviderConfig,
+    region: Option<Region>,
+}
+
+impl Builder {
+    /// Configure the default chain
+    ///
+    /// Exposed for overriding the environment when unit-testing providers
+    pub fn configure(mut self, configuration: &ProviderConfig) -> Self {
+        self.provider_config = configuration.clone();
+        self
+    }
+
+    /// Override the profile name used by this provider
+    pub fn profile_name(mut self, name: &str) -> Self {
+        self.provider_config = self.provider_config.with_profile_name(name.to_string());
+        self
+    }
+
+    /// Set the region clients will be configured with
+    ///
+    /// This region is used to resolve [`DefaultsMode::Auto`]. When unset, the region of the
+    /// [`ProviderConfig`] is used.
+    pub fn region(mut self, region: impl Into<Option<Region>>) -> Self {
+        self.region = region.into();
+        self
+    }
+
+    /// Resolve the [`DefaultsMode`]
+    pub async fn defaults_mode(self) -> DefaultsMode {
+        self.defaults_mode_with_source().await.0
+    }
+
+    pub(crate) async fn defaults_mode_with_source(self) -> (DefaultsMode, ValueSource) {
+        let configured = StandardProperty::new()
+            .env(env::DEFAULTS_MODE)
+            .profile(profile_key::DEFAULTS_MODE)
+            .validate_with_source(&self.provider_config, DefaultsMode::from_str)
+            .await
+            .map_err(|err| {
+                tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for defaults mode")
+            })
+            .unwrap_or(None);
+        match configured {
+            Some((DefaultsMode::Auto, source)) => {
+                let region = self.region.or_else(|| self.provider_config.region());
+                let mode = resolve_auto(&self.provider_config, region.as_ref()).await;
+                tracing::debug!(mode = %mode, "resolved `auto` defaults mode");
+                (mode, source)
+            }
+            Some(configured) => configured,
+            None => (DefaultsMode::Legacy, ValueSource::Default),
+        }
+    }
+}
+
+async fn resolve_auto(provider_config: &ProviderConfig, region: Option<&Region>) -> DefaultsMode {
+    if cfg!(any(target_os = "ios", target_os = "android")) {
+        return DefaultsMode::Mobile;
+    }
+    let region = match region {
+        Some(region) => region,
+        None => return DefaultsMode::Standard,
+    };
+    match current_region(provider_config).await {
+        Some(current) if &current == region => DefaultsMode::InRegion,
+        Some(_) => DefaultsMode::CrossRegion,
+        None => DefaultsMode::Standard,
+    }
+}
+
+/// The region the application is running in, if it can be determined
+async fn current_region(provider_config: &ProviderConfig) -> Option<Region> {
+    let env = provider_config.env();
+    if env.get(env::EXECUTION_ENV).is_ok() {
+        if let Ok(region) = env
+            .get(env::REGION)
+            .or_else(|_| env.get(env::DEFAULT_REGION))
+        {
+            return Some(Region::new(region));
+        }
+    }
+    let imds_disabled = env
+        .get(env::EC2_METADATA_DISABLED)
+        .map(|value| value.eq_ignore_ascii_case("true"))
+        .unwrap_or(false);
+    if imds_disabled {
+        return None;
+    }
+    let sleep = provider_config.sleep()?;
+    let client = imds::Client::builder()
+        .configure(provider_config)
+        .max_attempts(1)
+        .connect_timeout(IMDS_REGION_TIMEOUT)
+        .read_timeout(IMDS_REGION_TIMEOUT)
+        .build()
+        .await
+        .ok()?;
+    let provider = ImdsRegionProvider::builder()
+        .configure(provider_config)
+        .imds_client(client)
+        .build();
+    let sleep = sleep.sleep(IMDS_REGION_TIMEOUT);
+    Timeout::new(provider.region(), sleep).await.ok().flatten()
+}
+
+#[cfg(test)]
+mod test {
+    use crate::default_provider::defaults_mode::default_provider;
+    use crate::defaults_mode::DefaultsMode;
+    use crate::imds::client::test::{imds_request, imds_response, token_request, token_response};
+    use crate::provenance::ValueSource;
+    use crate::provider_config::ProviderConfig;
+    use aws_smithy_async::rt::sleep::TokioSleep;
+    use aws_smithy_client::erase::DynConnector;
+    use aws_smithy_client::test_connection::TestConnection;
+    use aws_types::os_shim_internal::{Env, Fs};
+    use aws_types::region::Region;
+    use tracing_test::traced_test;
+
+    async fn resolve(conf: &ProviderConfig) -> (DefaultsMode, ValueSource) {
+        default_provider()
+            .configure(conf)
+            .region(Region::new("us-east-1"))
+            .defaults_mode_with_source()
+            .await
+    }
+
+    #[tokio::test]
+    async fn legacy_when_unset() {
+        let conf = ProviderConfig::empty();
+        assert_eq!(
+            resolve(&conf).await,
+            (DefaultsMode::Legacy, ValueSource::Default)
+        );
+    }
+
+    #[tokio::test]
+    async fn environment_priority() {
+        let conf = ProviderConfig::empty()
+            .with_env(Env::from_slice(&[
+                ("AWS_DEFAULTS_MODE", "in-region"),
+                ("AWS_CONFIG_FILE", "conf"),
+            ]))
+            .with_fs(Fs::from_slice(&[(
+                "conf",
+                "[default]\ndefaults_mode = mobile",
+            )]));
+        assert_eq!(
+            resolve(&conf).await,
+            (
+                DefaultsMode::InRegion,
+                ValueSource::environment("AWS_DEFAULTS_MODE")
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn profile_works() {
+        let conf = ProviderConfig::empty()
+            .with_env(Env::from_slice(&[("AWS_CONFIG_FILE", "conf")]))
+            .with_fs(Fs::from_slice(&[(
+                "conf",
+                "[default]\ndefaults_mode = cross-region",
+            )]));
+        assert_eq!(
+            resolve(&conf).await,
+            (
+                DefaultsMode::CrossRegion,
+                ValueSource::profile("default", "defaults_mode")
+            )
+        );
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn log_error_on_invalid_value() {
+        let conf =
+            ProviderConfig::empty().with_env(Env::from_slice(&[("AWS_DEFAULTS_MODE", "fast")]));
+        assert_eq!(resolve(&conf).await.0, DefaultsMode::Legacy);
+        assert!(logs_contain("invalid value for defaults mode"));
+        assert!(logs_contain("AWS_DEFAULTS_MODE"));
+    }
+
+    #[tokio::test]
+    async fn auto_uses_execution_environment_region() {
+        let env = |region| {
+            Env::from_slice(&[
+                ("AWS_DEFAULTS_MODE", "auto"),
+                ("AWS_EXECUTION_ENV", "AWS_Lambda_rust"),
+                ("AWS_REGION", region),
+            ])
+        };
+        let conf = ProviderConfig::empty().with_env(env("us-east-1"));
+        assert_eq!(resolve(&conf).await.0, DefaultsMode::InRegion);
+        let conf = ProviderConfig::empty().with_env(env("eu-west-1"));
+        assert_eq!(resolve(&conf).await.0, DefaultsMode::CrossRegion);
+    }
+
+    #[tokio::test]
+    async fn auto_uses_imds_region() {
+        let conn = TestConnection::new(vec![
+            (
+                token_request("http://169.254.169.254", 21600),
+                token_response(21600, "token"),
+            ),
+            (
+                imds_request(
+                    "http://169.254.169.254/latest/meta-data/placement/region",
+                    "token",
+                ),
+                imds_response("eu-west-1"),
+            ),
+        ]);
+        let conf = ProviderConfig::no_configuration()
+            .with_env(Env::from_slice(&[("AWS_DEFAULTS_MODE", "auto")]))
+            .with_http_connector(DynConnector::new(conn.clone()))
+            .with_sleep(TokioSleep::new());
+        assert_eq!(resolve(&conf).await.0, DefaultsMode::CrossRegion);
+        conn.assert_requests_match(&[]);
+    }
+
+    #[tokio::test]
+    async fn auto_falls_back_to_standard() {
+        let conf = ProviderConfig::empty().with_env(Env::from_slice(&[
+            ("AWS_DEFAULTS_MODE", "auto"),
+            ("AWS_EC2_METADATA_DISABLED", "true"),
+        ]));
+        assert_eq!(
+            resolve(&conf).await,
+            (
+                DefaultsMode::Standard,
+                ValueSource::environment("AWS_DEFAULTS_MODE")
+            )
+        );
+    }
+}
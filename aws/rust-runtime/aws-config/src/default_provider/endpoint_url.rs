@@ -0,0 +1,83 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::provenance::ValueSource;
+use crate::provider_config::ProviderConfig;
+use crate::standard_property::StandardProperty;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use http::Uri;
+
+mod env {
+    pub(super) const ENDPOINT_URL: &str = "AWS_ENDPOINT_URL";
+}
+
+mod profile_key {
+    pub(super) const ENDPOINT_URL: &str = "endpoint_url";
+}
+
+/// Load the endpoint URL used for all services
+///
+/// This checks the following sources:
+/// 1. The environment variable `AWS_ENDPOINT_URL`
+/// 2. The profile key `endpoint_url`
+///
+/// If an invalid URL is found, the provider will return None and an error will be logged.
+pub async fn endpoint_url_provider(provider_config: &ProviderConfig) -> Option<String> {
+    endpoint_url_with_source(provider_config)
+        .await
+        .map(|(url, _source)| url)
+}
+
+/// Load the endpoint URL used for all services, along with where it came from
+pub(crate) async fn endpoint_url_with_source(
+    provider_config: &ProviderConfig,
+) -> Option<(String, ValueSource)> {
+    StandardProperty::new()
+        .env(env::ENDPOINT_URL)
+        .profile(profile_key::ENDPOINT_URL)
+        .validate_with_source(provider_config, |url| {
+            url.parse::<Uri>().map(|_| url.to_string())
+        })
+        .await
+        .map_err(
+            |err| tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for endpoint URL"),
+        )
+        .unwrap_or(None)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::default_provider::endpoint_url::endpoint_url_provider;
+    use crate::provider_config::ProviderConfig;
+    use aws_types::os_shim_internal::{Env, Fs};
+    use tracing_test::traced_test;
+
+    #[tokio::test]
+    #[traced_test]
+    async fn log_error_on_invalid_value() {
+        let conf =
+            ProviderConfig::empty().with_env(Env::from_slice(&[("AWS_ENDPOINT_URL", "not a url")]));
+        assert_eq!(endpoint_url_provider(&conf).await, None);
+        assert!(logs_contain("invalid value for endpoint URL"));
+        assert!(logs_contain("AWS_ENDPOINT_URL"));
+    }
+
+    #[tokio::test]
+    async fn environment_priority() {
+        let conf = ProviderConfig::empty()
+            .with_env(Env::from_slice(&[
+                ("AWS_ENDPOINT_URL", "http://localhost:4566"),
+                ("AWS_CONFIG_FILE", "conf"),
+            ]))
+            .with_fs(Fs::from_slice(&[(
+                "conf",
+                "[default]\nendpoint_url = http://localhost:8000",
+            )]));
+        assert_eq!(
+            endpoint_url_provider(&conf).await.as_deref(),
+            Some("http://localhost:4566")
+        );
+    }
+}
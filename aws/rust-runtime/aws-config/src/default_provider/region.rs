@@ -6,7 +6,10 @@
 use aws_types::region::Region;
 
 use crate::environment::region::EnvironmentVariableRegionProvider;
-use crate::meta::region::{ProvideRegion, RegionProviderChain};
+use crate::imds::region::ImdsRegionProvider;
+use crate::meta::region::{future, ProvideRegion};
+use crate::profile::ProfileFileRegionProvider;
+use crate::provenance::ValueSource;
 use crate::provider_config::ProviderConfig;
 use crate::{imds, profile};
 
@@ -22,12 +25,32 @@ pub fn default_provider() -> impl ProvideRegion {
 
 /// Default region provider chain
 #[derive(Debug)]
-pub struct DefaultRegionChain(RegionProviderChain);
+pub struct DefaultRegionChain {
+    env_provider: EnvironmentVariableRegionProvider,
+    profile_file: ProfileFileRegionProvider,
+    imds: ImdsRegionProvider,
+}
 
 impl DefaultRegionChain {
     /// Load a region from this chain
     pub async fn region(&self) -> Option<Region> {
-        self.0.region().await
+        self.region_with_source()
+            .await
+            .map(|(region, _source)| region)
+    }
+
+    /// Load a region from this chain, along with where it came from
+    pub(crate) async fn region_with_source(&self) -> Option<(Region, ValueSource)> {
+        if let Some((region, variable)) = self.env_provider.region_with_variable() {
+            return Some((region, ValueSource::environment(variable)));
+        }
+        if let Some((region, profile)) = self.profile_file.region_with_profile().await {
+            return Some((region, ValueSource::profile(profile, "region")));
+        }
+        self.imds
+            .region()
+            .await
+            .map(|region| (region, ValueSource::provider("IMDS")))
     }
 
     /// Builder for [`DefaultRegionChain`]
@@ -64,16 +87,16 @@ impl Builder {
 
     /// Build a [DefaultRegionChain]
     pub fn build(self) -> DefaultRegionChain {
-        DefaultRegionChain(
-            RegionProviderChain::first_try(self.env_provider)
-                .or_else(self.profile_file.build())
-                .or_else(self.imds.build()),
-        )
+        DefaultRegionChain {
+            env_provider: self.env_provider,
+            profile_file: self.profile_file.build(),
+            imds: self.imds.build(),
+        }
     }
 }
 
 impl ProvideRegion for DefaultRegionChain {
-    fn region(&self) -> crate::meta::region::future::ProvideRegion<'_> {
-        ProvideRegion::region(&self.0)
+    fn region(&self) -> future::ProvideRegion<'_> {
+        future::ProvideRegion::new(self.region())
     }
 }
@@ -0,0 +1,136 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::provenance::ValueSource;
+use crate::provider_config::ProviderConfig;
+use crate::standard_property::StandardProperty;
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::request_compression::MAX_MIN_COMPRESSION_SIZE_BYTES;
+use std::error::Error;
+use std::fmt;
+
+mod env {
+    pub(super) const REQUEST_MIN_COMPRESSION_SIZE_BYTES: &str =
+        "AWS_REQUEST_MIN_COMPRESSION_SIZE_BYTES";
+}
+
+mod profile_key {
+    pub(super) const REQUEST_MIN_COMPRESSION_SIZE_BYTES: &str =
+        "request_min_compression_size_bytes";
+}
+
+#[derive(Debug)]
+struct InvalidMinCompressionSize {
+    value: String,
+}
+
+impl fmt::Display for InvalidMinCompressionSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} was not a valid minimum compression size; it must be an integer between 0 and {}",
+            self.value, MAX_MIN_COMPRESSION_SIZE_BYTES
+        )
+    }
+}
+
+impl Error for InvalidMinCompressionSize {}
+
+fn parse_min_compression_size(value: &str) -> Result<u32, InvalidMinCompressionSize> {
+    match value.parse::<u32>() {
+        Ok(size) if size <= MAX_MIN_COMPRESSION_SIZE_BYTES => Ok(size),
+        _ => Err(InvalidMinCompressionSize {
+            value: value.to_string(),
+        }),
+    }
+}
+
+/// Load the value for "request minimum compression size", along with where it came from
+pub(crate) async fn request_min_compression_size_bytes_with_source(
+    provider_config: &ProviderConfig,
+) -> Option<(u32, ValueSource)> {
+    StandardProperty::new()
+        .env(env::REQUEST_MIN_COMPRESSION_SIZE_BYTES)
+        .profile(profile_key::REQUEST_MIN_COMPRESSION_SIZE_BYTES)
+        .validate_with_source(provider_config, parse_min_compression_size)
+        .await
+        .map_err(
+            |err| tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for request minimum compression size setting"),
+        )
+        .unwrap_or(None)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::default_provider::request_min_compression_size_bytes::request_min_compression_size_bytes_with_source;
+    use crate::profile::profile_file::{ProfileFileKind, ProfileFiles};
+    use crate::provider_config::ProviderConfig;
+    use aws_types::os_shim_internal::{Env, Fs};
+    use tracing_test::traced_test;
+
+    async fn min_size_provider(conf: &ProviderConfig) -> Option<u32> {
+        request_min_compression_size_bytes_with_source(conf)
+            .await
+            .map(|(value, _source)| value)
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn log_error_on_invalid_value() {
+        for invalid in ["not-a-number", "-1", "10485761"] {
+            let conf = ProviderConfig::empty().with_env(Env::from_slice(&[(
+                "AWS_REQUEST_MIN_COMPRESSION_SIZE_BYTES",
+                invalid,
+            )]));
+            assert_eq!(min_size_provider(&conf).await, None);
+        }
+        assert!(logs_contain(
+            "invalid value for request minimum compression size setting"
+        ));
+        assert!(logs_contain("AWS_REQUEST_MIN_COMPRESSION_SIZE_BYTES"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn environment_priority() {
+        let conf = ProviderConfig::empty()
+            .with_env(Env::from_slice(&[(
+                "AWS_REQUEST_MIN_COMPRESSION_SIZE_BYTES",
+                "10485760",
+            )]))
+            .with_profile_config(
+                Some(
+                    ProfileFiles::builder()
+                        .with_file(ProfileFileKind::Config, "conf")
+                        .build(),
+                ),
+                None,
+            )
+            .with_fs(Fs::from_slice(&[(
+                "conf",
+                "[default]\nrequest_min_compression_size_bytes = 0",
+            )]));
+        assert_eq!(min_size_provider(&conf).await, Some(10_485_760));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn profile_works() {
+        let conf = ProviderConfig::empty()
+            .with_profile_config(
+                Some(
+                    ProfileFiles::builder()
+                        .with_file(ProfileFileKind::Config, "conf")
+                        .build(),
+                ),
+                None,
+            )
+            .with_fs(Fs::from_slice(&[(
+                "conf",
+                "[default]\nrequest_min_compression_size_bytes = 128",
+            )]));
+        assert_eq!(min_size_provider(&conf).await, Some(128));
+    }
+}
@@ -3,6 +3,9 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::default_provider::defaults_mode;
+use crate::defaults_mode::DefaultsMode;
+use crate::provenance::{Provenance, ValueSource};
 use crate::provider_config::ProviderConfig;
 use crate::retry::error::{RetryConfigError, RetryConfigErrorKind};
 use crate::standard_property::{PropertyResolutionError, StandardProperty};
@@ -18,6 +21,9 @@ use std::str::FromStr;
 /// This provider will check the following sources in order:
 /// 1. Environment variables: `AWS_MAX_ATTEMPTS` & `AWS_RETRY_MODE`
 /// 2. Profile file: `max_attempts` and `retry_mode`
+/// 3. The retry mode of the [`DefaultsMode`], which is resolved with
+///    [`default_provider::defaults_mode`](crate::default_provider::defaults_mode) unless
+///    [set explicitly](Builder::defaults_mode)
 ///
 /// # Example
 ///
@@ -64,6 +70,7 @@ mod profile_keys {
 #[derive(Debug, Default)]
 pub struct Builder {
     provider_config: ProviderConfig,
+    defaults_mode: Option<DefaultsMode>,
 }
 
 impl Builder {
@@ -81,10 +88,19 @@ impl Builder {
         self
     }
 
+    /// Use the retry mode of `defaults_mode` when no retry mode is configured
+    ///
+    /// When unset, the defaults mode is resolved from the environment and the AWS profile.
+    pub fn defaults_mode(mut self, defaults_mode: DefaultsMode) -> Self {
+        self.defaults_mode = Some(defaults_mode);
+        self
+    }
+
     /// Attempt to create a [RetryConfig](aws_smithy_types::retry::RetryConfig) from following sources in order:
     /// 1. Environment variables: `AWS_MAX_ATTEMPTS` & `AWS_RETRY_MODE`
     /// 2. Profile file: `max_attempts` and `retry_mode`
-    /// 3. [RetryConfig::standard()](aws_smithy_types::retry::RetryConfig::standard)
+    /// 3. The retry mode of the [`DefaultsMode`]
+    /// 4. [RetryConfig::standard()](aws_smithy_types::retry::RetryConfig::standard)
     ///
     /// Precedence is considered on a per-field basis
     ///
@@ -93,41 +109,77 @@ impl Builder {
     /// - Panics if the `AWS_MAX_ATTEMPTS` env var or `max_attempts` profile var is set to 0
     /// - Panics if the `AWS_RETRY_MODE` env var or `retry_mode` profile var is set to "adaptive" (it's not yet supported)
     pub async fn retry_config(self) -> RetryConfig {
-        match self.try_retry_config().await {
-            Ok(conf) => conf,
+        self.retry_config_with_provenance().await.0
+    }
+
+    /// Resolve the [RetryConfig](aws_smithy_types::retry::RetryConfig), along with where each field came from
+    ///
+    /// See [`retry_config`](Builder::retry_config) for the resolution order and panics.
+    pub async fn retry_config_with_provenance(self) -> (RetryConfig, Provenance) {
+        match self.try_retry_config_with_provenance().await {
+            Ok(resolved) => resolved,
             Err(e) => panic!("{}", DisplayErrorContext(e)),
         }
     }
 
-    pub(crate) async fn try_retry_config(
+    pub(crate) async fn try_retry_config_with_provenance(
         self,
-    ) -> Result<RetryConfig, PropertyResolutionError<RetryConfigError>> {
+    ) -> Result<(RetryConfig, Provenance), PropertyResolutionError<RetryConfigError>> {
         // Both of these can return errors due to invalid config settings and we want to surface those as early as possible
         // hence, we'll panic if any config values are invalid (missing values are OK though)
         // We match this instead of unwrapping so we can print the error with the `Display` impl instead of the `Debug` impl that unwrap uses
         let mut retry_config = RetryConfig::standard();
+        let mut provenance = Provenance::default();
         let max_attempts = StandardProperty::new()
             .env(env::MAX_ATTEMPTS)
             .profile(profile_keys::MAX_ATTEMPTS)
-            .validate(&self.provider_config, validate_max_attempts);
+            .validate_with_source(&self.provider_config, validate_max_attempts);
 
         let retry_mode = StandardProperty::new()
             .env(env::RETRY_MODE)
             .profile(profile_keys::RETRY_MODE)
-            .validate(&self.provider_config, |s| {
+            .validate_with_source(&self.provider_config, |s| {
                 RetryMode::from_str(s)
                     .map_err(|err| RetryConfigErrorKind::InvalidRetryMode { source: err }.into())
             });
 
-        if let Some(max_attempts) = max_attempts.await? {
-            retry_config = retry_config.with_max_attempts(max_attempts);
+        match max_attempts.await? {
+            Some((max_attempts, source)) => {
+                retry_config = retry_config.with_max_attempts(max_attempts);
+                provenance.record("max_attempts", source);
+            }
+            None => provenance.record("max_attempts", ValueSource::Default),
         }
 
-        if let Some(retry_mode) = retry_mode.await? {
-            retry_config = retry_config.with_retry_mode(retry_mode);
+        let retry_mode = match retry_mode.await? {
+            Some(retry_mode) => Some(retry_mode),
+            None => {
+                let defaults_mode = match self.defaults_mode {
+                    Some(defaults_mode) => defaults_mode,
+                    None => {
+                        defaults_mode::default_provider()
+                            .configure(&self.provider_config)
+                            .defaults_mode()
+                            .await
+                    }
+                };
+                defaults_mode.defaults().map(|defaults| {
+                    (
+                        defaults.retry_mode,
+                        ValueSource::DefaultsMode(defaults_mode),
+                    )
+                })
+            }
+        };
+        match retry_mode {
+            Some((retry_mode, source)) => {
+                retry_config = retry_config.with_retry_mode(retry_mode);
+                provenance.record("retry_mode", source);
+            }
+            None => provenance.record("retry_mode", ValueSource::Default),
         }
 
-        Ok(retry_config)
+        Ok((retry_config, provenance))
     }
 }
 
@@ -144,6 +196,8 @@ fn validate_max_attempts(max_attempts: &str) -> Result<u32, RetryConfigError> {
 #[cfg(test)]
 mod test {
     use crate::default_provider::retry_config::env;
+    use crate::defaults_mode::DefaultsMode;
+    use crate::provenance::ValueSource;
     use crate::provider_config::ProviderConfig;
     use crate::retry::{
         error::RetryConfigError, error::RetryConfigErrorKind, RetryConfig, RetryMode,
@@ -156,8 +210,9 @@ mod test {
     ) -> Result<RetryConfig, PropertyResolutionError<RetryConfigError>> {
         super::Builder::default()
             .configure(&ProviderConfig::no_configuration().with_env(Env::from_slice(vars)))
-            .try_retry_config()
+            .try_retry_config_with_provenance()
             .await
+            .map(|(retry_config, _provenance)| retry_config)
     }
 
     #[tokio::test]
@@ -325,6 +380,31 @@ max_attempts = potato
         );
     }
 
+    #[tokio::test]
+    async fn records_where_each_field_came_from() {
+        let env = Env::from_slice(&[
+            ("AWS_CONFIG_FILE", "config"),
+            ("AWS_DEFAULTS_MODE", "cross-region"),
+        ]);
+        let fs = Fs::from_slice(&[("config", "[default]\nmax_attempts = 5\n")]);
+        let provider_config = ProviderConfig::no_configuration().with_env(env).with_fs(fs);
+
+        let (retry_config, provenance) = super::default_provider()
+            .configure(&provider_config)
+            .retry_config_with_provenance()
+            .await;
+
+        assert_eq!(retry_config, RetryConfig::standard().with_max_attempts(5));
+        assert_eq!(
+            provenance.source("max_attempts"),
+            Some(&ValueSource::profile("default", "max_attempts"))
+        );
+        assert_eq!(
+            provenance.source("retry_mode"),
+            Some(&ValueSource::DefaultsMode(DefaultsMode::CrossRegion))
+        );
+    }
+
     #[tokio::test]
     async fn disallow_zero_max_attempts() {
         let err = test_provider(&[(env::MAX_ATTEMPTS, "0")])
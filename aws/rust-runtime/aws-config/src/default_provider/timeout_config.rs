@@ -3,39 +3,323 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::default_provider::defaults_mode;
+use crate::defaults_mode::{DefaultsMode, ModeDefaults};
+use crate::provenance::{Provenance, ValueSource};
 use crate::provider_config::ProviderConfig;
-use aws_smithy_types::timeout::TimeoutConfig;
+use crate::standard_property::{PropertyResolutionError, StandardProperty};
+use crate::timeout::error::{TimeoutConfigError, TimeoutConfigErrorKind};
+use aws_smithy_types::error::display::DisplayErrorContext;
+use aws_smithy_types::timeout::{TimeoutConfig, TimeoutConfigBuilder};
 use std::time::Duration;
 
-const SDK_DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_millis(3100);
-
 /// Default [`TimeoutConfig`] provider chain
 ///
 /// Unlike other credentials and region, [`TimeoutConfig`] has no related `TimeoutConfigProvider` trait. Instead,
 /// a builder struct is returned which has a similar API.
 ///
+/// Each timeout is resolved from the following sources in order:
+/// 1. Environment variables: `AWS_CONNECT_TIMEOUT`, `AWS_READ_TIMEOUT`, `AWS_OPERATION_TIMEOUT` &
+///    `AWS_OPERATION_ATTEMPT_TIMEOUT`
+/// 2. Profile file: `connect_timeout`, `read_timeout`, `operation_timeout` and `operation_attempt_timeout`
+/// 3. The defaults of the [`DefaultsMode`], which is resolved with
+///    [`default_provider::defaults_mode`](crate::default_provider::defaults_mode) unless
+///    [set explicitly](Builder::defaults_mode)
+///
+/// Timeouts are given as a number of seconds, for example `AWS_CONNECT_TIMEOUT=0.5`.
 pub fn default_provider() -> Builder {
     Builder::default()
 }
 
+/// A timeout that can be configured through the environment and the AWS profile
+struct TimeoutProperty {
+    field: &'static str,
+    env: &'static str,
+    profile_key: &'static str,
+    mode_default: fn(&ModeDefaults) -> Option<Duration>,
+    set: fn(&mut TimeoutConfigBuilder, Option<Duration>) -> &mut TimeoutConfigBuilder,
+}
+
+const TIMEOUT_PROPERTIES: &[TimeoutProperty] = &[
+    TimeoutProperty {
+        field: "connect_timeout",
+        env: "AWS_CONNECT_TIMEOUT",
+        profile_key: "connect_timeout",
+        mode_default: |defaults| defaults.connect_timeout,
+        set: TimeoutConfigBuilder::set_connect_timeout,
+    },
+    TimeoutProperty {
+        field: "read_timeout",
+        env: "AWS_READ_TIMEOUT",
+        profile_key: "read_timeout",
+        mode_default: |_| None,
+        set: TimeoutConfigBuilder::set_read_timeout,
+    },
+    TimeoutProperty {
+        field: "operation_timeout",
+        env: "AWS_OPERATION_TIMEOUT",
+        profile_key: "operation_timeout",
+        mode_default: |_| None,
+        set: TimeoutConfigBuilder::set_operation_timeout,
+    },
+    TimeoutProperty {
+        field: "operation_attempt_timeout",
+        env: "AWS_OPERATION_ATTEMPT_TIMEOUT",
+        profile_key: "operation_attempt_timeout",
+        mode_default: |_| None,
+        set: TimeoutConfigBuilder::set_operation_attempt_timeout,
+    },
+];
+
 /// Builder for [`TimeoutConfig`] that resolves the default timeout configuration
 #[non_exhaustive]
 #[derive(Debug, Default)]
-pub struct Builder;
+pub struct Builder {
+    provider_config: ProviderConfig,
+    defaults_mode: Option<DefaultsMode>,
+}
 
 impl Builder {
     /// Configure the default chain
     ///
     /// Exposed for overriding the environment when unit-testing providers
-    pub fn configure(self, _configuration: &ProviderConfig) -> Self {
+    pub fn configure(mut self, configuration: &ProviderConfig) -> Self {
+        self.provider_config = configuration.clone();
+        self
+    }
+
+    /// Override the profile name used by this provider
+    pub fn profile_name(mut self, name: &str) -> Self {
+        self.provider_config = self.provider_config.with_profile_name(name.to_string());
+        self
+    }
+
+    /// Use the defaults of `defaults_mode` for timeouts that aren't configured
+    ///
+    /// When unset, the defaults mode is resolved from the environment and the AWS profile.
+    pub fn defaults_mode(mut self, defaults_mode: DefaultsMode) -> Self {
+        self.defaults_mode = Some(defaults_mode);
         self
     }
 
     /// Resolve default timeout configuration
+    ///
+    /// # Panics
+    ///
+    /// Panics if a timeout in the environment or the profile is not a finite, non-negative number of seconds
     pub async fn timeout_config(self) -> TimeoutConfig {
-        // TODO(https://github.com/awslabs/smithy-rs/issues/1732): Implement complete timeout defaults specification
-        TimeoutConfig::builder()
-            .connect_timeout(SDK_DEFAULT_CONNECT_TIMEOUT)
-            .build()
+        self.timeout_config_with_provenance().await.0
+    }
+
+    /// Resolve default timeout configuration, along with where each timeout came from
+    ///
+    /// # Panics
+    ///
+    /// Panics if a timeout in the environment or the profile is not a finite, non-negative number of seconds
+    pub async fn timeout_config_with_provenance(self) -> (TimeoutConfig, Provenance) {
+        match self.try_timeout_config().await {
+            Ok(resolved) => resolved,
+            Err(e) => panic!("{}", DisplayErrorContext(e)),
+        }
+    }
+
+    pub(crate) async fn try_timeout_config(
+        self,
+    ) -> Result<(TimeoutConfig, Provenance), PropertyResolutionError<TimeoutConfigError>> {
+        let defaults_mode = match self.defaults_mode {
+            Some(defaults_mode) => defaults_mode,
+            None => {
+                defaults_mode::default_provider()
+                    .configure(&self.provider_config)
+                    .defaults_mode()
+                    .await
+            }
+        };
+        let mode_defaults = defaults_mode.defaults();
+
+        let mut builder = TimeoutConfig::builder();
+        let mut provenance = Provenance::default();
+        for property in TIMEOUT_PROPERTIES {
+            let configured = StandardProperty::new()
+                .env(property.env)
+                .profile(property.profile_key)
+                .validate_with_source(&self.provider_config, parse_timeout)
+                .await?;
+            let resolved = configured.or_else(|| {
+                mode_defaults
+                    .and_then(property.mode_default)
+                    .map(|timeout| (timeout, ValueSource::DefaultsMode(defaults_mode)))
+            });
+            if let Some((timeout, source)) = resolved {
+                (property.set)(&mut builder, Some(timeout));
+                provenance.record(property.field, source);
+            }
+        }
+        Ok((builder.build(), provenance))
+    }
+}
+
+fn parse_timeout(timeout: &str) -> Result<Duration, TimeoutConfigError> {
+    let seconds = timeout
+        .trim()
+        .parse::<f64>()
+        .map_err(|source| TimeoutConfigErrorKind::FailedToParseTimeout { source })?;
+    Duration::try_from_secs_f64(seconds).map_err(|_| TimeoutConfigErrorKind::InvalidTimeout.into())
+}
+
+#[cfg(test)]
+mod test {
+    use crate::defaults_mode::DefaultsMode;
+    use crate::provenance::{Provenance, ValueSource};
+    use crate::provider_config::ProviderConfig;
+    use crate::standard_property::PropertyResolutionError;
+    use crate::timeout::error::{TimeoutConfigError, TimeoutConfigErrorKind};
+    use aws_smithy_types::timeout::TimeoutConfig;
+    use aws_types::os_shim_internal::{Env, Fs};
+    use std::time::Duration;
+
+    async fn test_provider(
+        vars: &[(&str, &str)],
+    ) -> Result<(TimeoutConfig, Provenance), PropertyResolutionError<TimeoutConfigError>> {
+        super::default_provider()
+            .configure(&ProviderConfig::no_configuration().with_env(Env::from_slice(vars)))
+            .try_timeout_config()
+            .await
+    }
+
+    #[tokio::test]
+    async fn defaults() {
+        let (config, provenance) = test_provider(&[]).await.unwrap();
+        assert_eq!(
+            config,
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_millis(3100))
+                .build()
+        );
+        assert_eq!(
+            provenance.source("connect_timeout"),
+            Some(&ValueSource::DefaultsMode(DefaultsMode::Legacy))
+        );
+        assert_eq!(provenance.source("read_timeout"), None);
+    }
+
+    #[tokio::test]
+    async fn defaults_mode_selects_connect_timeout() {
+        for (mode, expected) in [
+            ("standard", 3100),
+            ("in-region", 1100),
+            ("cross-region", 3100),
+            ("mobile", 30000),
+        ] {
+            let (config, provenance) = test_provider(&[("AWS_DEFAULTS_MODE", mode)]).await.unwrap();
+            assert_eq!(
+                config.connect_timeout(),
+                Some(Duration::from_millis(expected)),
+                "{}",
+                mode
+            );
+            assert_eq!(
+                provenance.source("connect_timeout"),
+                Some(&ValueSource::DefaultsMode(mode.parse().unwrap()))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn explicit_defaults_mode_overrides_environment() {
+        let (config, _) = super::default_provider()
+            .configure(
+                &ProviderConfig::no_configuration()
+                    .with_env(Env::from_slice(&[("AWS_DEFAULTS_MODE", "mobile")])),
+            )
+            .defaults_mode(DefaultsMode::InRegion)
+            .try_timeout_config()
+            .await
+            .unwrap();
+        assert_eq!(config.connect_timeout(), Some(Duration::from_millis(1100)));
+    }
+
+    #[tokio::test]
+    async fn env_timeouts_take_precedence_over_profile_timeouts() {
+        let env = Env::from_slice(&[
+            ("AWS_CONFIG_FILE", "config"),
+            ("AWS_DEFAULTS_MODE", "in-region"),
+            ("AWS_CONNECT_TIMEOUT", "0.5"),
+            ("AWS_OPERATION_TIMEOUT", "30"),
+        ]);
+        let fs = Fs::from_slice(&[(
+            "config",
+            r#"[default]
+connect_timeout = 2
+read_timeout = 10
+operation_timeout = 60
+            "#,
+        )]);
+
+        let provider_config = ProviderConfig::no_configuration().with_env(env).with_fs(fs);
+        let (config, provenance) = super::default_provider()
+            .configure(&provider_config)
+            .timeout_config_with_provenance()
+            .await;
+
+        assert_eq!(
+            config,
+            TimeoutConfig::builder()
+                .connect_timeout(Duration::from_millis(500))
+                .read_timeout(Duration::from_secs(10))
+                .operation_timeout(Duration::from_secs(30))
+                .build()
+        );
+        assert_eq!(
+            provenance.iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    "connect_timeout",
+                    &ValueSource::environment("AWS_CONNECT_TIMEOUT")
+                ),
+                (
+                    "read_timeout",
+                    &ValueSource::profile("default", "read_timeout")
+                ),
+                (
+                    "operation_timeout",
+                    &ValueSource::environment("AWS_OPERATION_TIMEOUT")
+                ),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn invalid_timeouts_are_rejected() {
+        assert!(matches!(
+            test_provider(&[("AWS_READ_TIMEOUT", "soon")])
+                .await
+                .unwrap_err()
+                .err,
+            TimeoutConfigError {
+                kind: TimeoutConfigErrorKind::FailedToParseTimeout { .. }
+            }
+        ));
+        assert!(matches!(
+            test_provider(&[("AWS_OPERATION_ATTEMPT_TIMEOUT", "-1")])
+                .await
+                .unwrap_err()
+                .err,
+            TimeoutConfigError {
+                kind: TimeoutConfigErrorKind::InvalidTimeout
+            }
+        ));
+    }
+
+    #[tokio::test]
+    #[should_panic = "failed to parse timeout as a number of seconds. source: profile `default`, key: `connect_timeout`"]
+    async fn invalid_profile_timeout_panics() {
+        let env = Env::from_slice(&[("AWS_CONFIG_FILE", "config")]);
+        let fs = Fs::from_slice(&[("config", "[default]\nconnect_timeout = potato\n")]);
+        let provider_config = ProviderConfig::no_configuration().with_env(env).with_fs(fs);
+        super::default_provider()
+            .configure(&provider_config)
+            .timeout_config()
+            .await;
     }
 }
@@ -4,6 +4,7 @@
  */
 
 use crate::environment::parse_bool;
+use crate::provenance::ValueSource;
 use crate::provider_config::ProviderConfig;
 use crate::standard_property::StandardProperty;
 use aws_smithy_types::error::display::DisplayErrorContext;
@@ -16,11 +17,14 @@ mod profile_key {
     pub(super) const USE_DUAL_STACK: &str = "use_dualstack_endpoint";
 }
 
-pub(crate) async fn use_dual_stack_provider(provider_config: &ProviderConfig) -> Option<bool> {
+/// Load the value for "use dual-stack", along with where it came from
+pub(crate) async fn use_dual_stack_with_source(
+    provider_config: &ProviderConfig,
+) -> Option<(bool, ValueSource)> {
     StandardProperty::new()
         .env(env::USE_DUAL_STACK)
         .profile(profile_key::USE_DUAL_STACK)
-        .validate(provider_config, parse_bool)
+        .validate_with_source(provider_config, parse_bool)
         .await
         .map_err(
             |err| tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for dual-stack setting"),
@@ -30,12 +34,18 @@ pub(crate) async fn use_dual_stack_provider(provider_config: &ProviderConfig) ->
 
 #[cfg(test)]
 mod test {
-    use crate::default_provider::use_dual_stack::use_dual_stack_provider;
+    use crate::default_provider::use_dual_stack::use_dual_stack_with_source;
     use crate::profile::profile_file::{ProfileFileKind, ProfileFiles};
     use crate::provider_config::ProviderConfig;
     use aws_types::os_shim_internal::{Env, Fs};
     use tracing_test::traced_test;
 
+    async fn use_dual_stack_provider(conf: &ProviderConfig) -> Option<bool> {
+        use_dual_stack_with_source(conf)
+            .await
+            .map(|(value, _source)| value)
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn log_error_on_invalid_value() {
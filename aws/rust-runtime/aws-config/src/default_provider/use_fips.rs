@@ -4,6 +4,7 @@
  */
 
 use crate::environment::parse_bool;
+use crate::provenance::ValueSource;
 use crate::provider_config::ProviderConfig;
 use crate::standard_property::StandardProperty;
 use aws_smithy_types::error::display::DisplayErrorContext;
@@ -24,10 +25,19 @@ mod profile_key {
 ///
 /// If invalid values are found, the provider will return None and an error will be logged.
 pub async fn use_fips_provider(provider_config: &ProviderConfig) -> Option<bool> {
+    use_fips_with_source(provider_config)
+        .await
+        .map(|(value, _source)| value)
+}
+
+/// Load the value for "use FIPS", along with where it came from
+pub(crate) async fn use_fips_with_source(
+    provider_config: &ProviderConfig,
+) -> Option<(bool, ValueSource)> {
     StandardProperty::new()
         .env(env::USE_FIPS)
         .profile(profile_key::USE_FIPS)
-        .validate(provider_config, parse_bool)
+        .validate_with_source(provider_config, parse_bool)
         .await
         .map_err(
             |err| tracing::warn!(err = %DisplayErrorContext(&err), "invalid value for FIPS setting"),
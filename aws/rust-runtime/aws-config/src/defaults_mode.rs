@@ -0,0 +1,191 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Defaults modes
+//!
+//! A defaults mode selects a set of default values for configuration that hasn't been set
+//! explicitly, in the environment, or in the AWS profile. The mode is read from the
+//! `AWS_DEFAULTS_MODE` environment variable or the `defaults_mode` profile key. See
+//! [`default_provider::defaults_mode`](crate::default_provider::defaults_mode) for details.
+
+use aws_smithy_types::retry::RetryMode;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// A set of default values for configuration
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DefaultsMode {
+    /// The defaults this SDK used before defaults modes were introduced
+    #[default]
+    Legacy,
+    /// Defaults that are safe for most applications
+    Standard,
+    /// Defaults for applications calling AWS services from within the same region
+    InRegion,
+    /// Defaults for applications calling AWS services in a different region
+    CrossRegion,
+    /// Defaults for applications running on mobile devices, with higher latency tolerance
+    Mobile,
+    /// Select one of the other modes based on the environment the application is running in
+    ///
+    /// `Auto` is resolved to [`InRegion`](DefaultsMode::InRegion) or
+    /// [`CrossRegion`](DefaultsMode::CrossRegion) by comparing the configured region with the region
+    /// the application runs in, to [`Mobile`](DefaultsMode::Mobile) on mobile platforms, and to
+    /// [`Standard`](DefaultsMode::Standard) otherwise.
+    Auto,
+}
+
+impl DefaultsMode {
+    /// The name of this mode, as accepted by `AWS_DEFAULTS_MODE`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DefaultsMode::Legacy => "legacy",
+            DefaultsMode::Standard => "standard",
+            DefaultsMode::InRegion => "in-region",
+            DefaultsMode::CrossRegion => "cross-region",
+            DefaultsMode::Mobile => "mobile",
+            DefaultsMode::Auto => "auto",
+        }
+    }
+
+    /// Default values for this mode
+    ///
+    /// Returns `None` for [`Auto`](DefaultsMode::Auto), which must be resolved to a concrete mode first.
+    pub(crate) fn defaults(self) -> Option<&'static ModeDefaults> {
+        MODE_DEFAULTS
+            .iter()
+            .find(|(mode, _)| *mode == self)
+            .map(|(_, defaults)| defaults)
+    }
+}
+
+impl fmt::Display for DefaultsMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DefaultsMode {
+    type Err = DefaultsModeParseError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let trimmed = value.trim();
+        [
+            DefaultsMode::Legacy,
+            DefaultsMode::Standard,
+            DefaultsMode::InRegion,
+            DefaultsMode::CrossRegion,
+            DefaultsMode::Mobile,
+            DefaultsMode::Auto,
+        ]
+        .into_iter()
+        .find(|mode| mode.as_str().eq_ignore_ascii_case(trimmed))
+        .ok_or_else(|| DefaultsModeParseError {
+            value: trimmed.to_string(),
+        })
+    }
+}
+
+/// Failure to parse a [`DefaultsMode`] from a string
+#[derive(Debug)]
+pub struct DefaultsModeParseError {
+    value: String,
+}
+
+impl fmt::Display for DefaultsModeParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` is not a valid defaults mode. Valid values are: legacy, standard, in-region, cross-region, mobile, auto",
+            self.value
+        )
+    }
+}
+
+impl Error for DefaultsModeParseError {}
+
+/// Default values selected by a [`DefaultsMode`]
+#[derive(Debug)]
+pub(crate) struct ModeDefaults {
+    pub(crate) retry_mode: RetryMode,
+    pub(crate) connect_timeout: Option<Duration>,
+}
+
+const MODE_DEFAULTS: &[(DefaultsMode, ModeDefaults)] = &[
+    (
+        DefaultsMode::Legacy,
+        ModeDefaults {
+            retry_mode: RetryMode::Standard,
+            connect_timeout: Some(Duration::from_millis(3100)),
+        },
+    ),
+    (
+        DefaultsMode::Standard,
+        ModeDefaults {
+            retry_mode: RetryMode::Standard,
+            connect_timeout: Some(Duration::from_millis(3100)),
+        },
+    ),
+    (
+        DefaultsMode::InRegion,
+        ModeDefaults {
+            retry_mode: RetryMode::Standard,
+            connect_timeout: Some(Duration::from_millis(1100)),
+        },
+    ),
+    (
+        DefaultsMode::CrossRegion,
+        ModeDefaults {
+            retry_mode: RetryMode::Standard,
+            connect_timeout: Some(Duration::from_millis(3100)),
+        },
+    ),
+    (
+        DefaultsMode::Mobile,
+        ModeDefaults {
+            retry_mode: RetryMode::Standard,
+            connect_timeout: Some(Duration::from_millis(30000)),
+        },
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use super::DefaultsMode;
+
+    #[test]
+    fn parse_defaults_mode() {
+        assert_eq!(
+            "in-region".parse::<DefaultsMode>().unwrap(),
+            DefaultsMode::InRegion
+        );
+        assert_eq!(
+            " Cross-Region ".parse::<DefaultsMode>().unwrap(),
+            DefaultsMode::CrossRegion
+        );
+        assert_eq!("AUTO".parse::<DefaultsMode>().unwrap(), DefaultsMode::Auto);
+        let err = "in_region".parse::<DefaultsMode>().unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("`in_region` is not a valid defaults mode"));
+    }
+
+    #[test]
+    fn every_concrete_mode_has_defaults() {
+        for mode in [
+            DefaultsMode::Legacy,
+            DefaultsMode::Standard,
+            DefaultsMode::InRegion,
+            DefaultsMode::CrossRegion,
+            DefaultsMode::Mobile,
+        ] {
+            assert!(mode.defaults().is_some(), "{} has no defaults", mode);
+        }
+        assert!(DefaultsMode::Auto.defaults().is_none());
+    }
+}
@@ -31,14 +31,25 @@ impl EnvironmentVariableRegionProvider {
     }
 }
 
+impl EnvironmentVariableRegionProvider {
+    /// Load the region, along with the name of the environment variable it was read from
+    pub(crate) fn region_with_variable(&self) -> Option<(Region, &'static str)> {
+        ["AWS_REGION", "AWS_DEFAULT_REGION"]
+            .into_iter()
+            .find_map(|variable| {
+                self.env
+                    .get(variable)
+                    .ok()
+                    .map(|region| (Region::new(region), variable))
+            })
+    }
+}
+
 impl ProvideRegion for EnvironmentVariableRegionProvider {
     fn region(&self) -> future::ProvideRegion<'_> {
         let region = self
-            .env
-            .get("AWS_REGION")
-            .or_else(|_| self.env.get("AWS_DEFAULT_REGION"))
-            .map(Region::new)
-            .ok();
+            .region_with_variable()
+            .map(|(region, _variable)| region);
         future::ProvideRegion::ready(region)
     }
 }
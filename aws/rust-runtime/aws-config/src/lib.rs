@@ -115,11 +115,13 @@ mod json_credentials;
 pub mod connector;
 pub mod credential_process;
 pub mod default_provider;
+pub mod defaults_mode;
 pub mod ecs;
 pub mod environment;
 pub mod imds;
 pub mod meta;
 pub mod profile;
+pub mod provenance;
 pub mod provider_config;
 pub mod retry;
 #[cfg(feature = "credentials-sso")]
@@ -163,12 +165,21 @@ mod loader {
     use aws_types::docs_for;
     use aws_types::SdkConfig;
 
-    use crate::connector::default_connector;
-    use crate::default_provider::use_dual_stack::use_dual_stack_provider;
-    use crate::default_provider::use_fips::use_fips_provider;
-    use crate::default_provider::{app_name, credentials, region, retry_config, timeout_config};
+    use crate::connector;
+    #[cfg(feature = "rustls")]
+    use crate::default_provider::ca_bundle;
+    use crate::default_provider::disable_request_compression::disable_request_compression_with_source;
+    use crate::default_provider::endpoint_url::endpoint_url_with_source;
+    use crate::default_provider::request_min_compression_size_bytes::request_min_compression_size_bytes_with_source;
+    use crate::default_provider::use_dual_stack::use_dual_stack_with_source;
+    use crate::default_provider::use_fips::use_fips_with_source;
+    use crate::default_provider::{
+        app_name, credentials, defaults_mode, region, retry_config, timeout_config,
+    };
+    use crate::defaults_mode::DefaultsMode;
     use crate::meta::region::ProvideRegion;
     use crate::profile::profile_file::ProfileFiles;
+    use crate::provenance::{ConfigReport, ValueSource};
     use crate::provider_config::ProviderConfig;
 
     #[derive(Default, Debug)]
@@ -193,6 +204,7 @@ mod loader {
         app_name: Option<AppName>,
         credentials_cache: Option<CredentialsCache>,
         credentials_provider: CredentialsProviderOption,
+        defaults_mode: Option<DefaultsMode>,
         endpoint_url: Option<String>,
         region: Option<Box<dyn ProvideRegion>>,
         retry_config: Option<RetryConfig>,
@@ -204,6 +216,8 @@ mod loader {
         profile_files_override: Option<ProfileFiles>,
         use_fips: Option<bool>,
         use_dual_stack: Option<bool>,
+        disable_request_compression: Option<bool>,
+        request_min_compression_size_bytes: Option<u32>,
         time_source: Option<SharedTimeSource>,
     }
 
@@ -267,6 +281,27 @@ mod loader {
             self
         }
 
+        /// Override the [`DefaultsMode`] used to select default timeouts and retry behavior.
+        ///
+        /// When unset, the defaults mode is loaded from `AWS_DEFAULTS_MODE` or the `defaults_mode`
+        /// profile key. See [`default_provider::defaults_mode`](crate::default_provider::defaults_mode).
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # async fn create_config() {
+        /// use aws_config::defaults_mode::DefaultsMode;
+        ///
+        /// let config = aws_config::from_env()
+        ///     .defaults_mode(DefaultsMode::InRegion)
+        ///     .load()
+        ///     .await;
+        /// # }
+        /// ```
+        pub fn defaults_mode(mut self, defaults_mode: DefaultsMode) -> Self {
+            self.defaults_mode = Some(defaults_mode);
+            self
+        }
+
         /// Override the sleep implementation for this [`ConfigLoader`]. The sleep implementation
         /// is used to create timeout futures.
         pub fn sleep_impl(mut self, sleep: impl AsyncSleep + 'static) -> Self {
@@ -513,6 +548,21 @@ mod loader {
             self
         }
 
+        #[doc = docs_for!(disable_request_compression)]
+        pub fn disable_request_compression(mut self, disable_request_compression: bool) -> Self {
+            self.disable_request_compression = Some(disable_request_compression);
+            self
+        }
+
+        #[doc = docs_for!(request_min_compression_size_bytes)]
+        pub fn request_min_compression_size_bytes(
+            mut self,
+            request_min_compression_size_bytes: u32,
+        ) -> Self {
+            self.request_min_compression_size_bytes = Some(request_min_compression_size_bytes);
+            self
+        }
+
         /// Set configuration for all sub-loaders (credentials, region etc.)
         ///
         /// Update the `ProviderConfig` used for all nested loaders. This can be used to override
@@ -547,37 +597,213 @@ mod loader {
         /// This means that if you provide a region provider that does not return a region, no region will
         /// be set in the resulting [`SdkConfig`](aws_types::SdkConfig)
         pub async fn load(self) -> SdkConfig {
+            self.load_with_report().await.0
+        }
+
+        /// Load the default configuration chain, along with a report of where each value came from
+        ///
+        /// This behaves exactly like [`load`](ConfigLoader::load). The returned
+        /// [`ConfigReport`] can be printed to see whether, for example, the region was taken from
+        /// `AWS_REGION`, the AWS profile, or IMDS.
+        ///
+        /// # Examples
+        /// ```no_run
+        /// # async fn create_config() {
+        /// let (config, report) = aws_config::from_env().load_with_report().await;
+        /// println!("{}", report);
+        /// # }
+        /// ```
+        pub async fn load_with_report(self) -> (SdkConfig, ConfigReport) {
+            let mut report = ConfigReport::default();
             let conf = self
                 .provider_config
                 .unwrap_or_default()
                 .with_profile_config(self.profile_files_override, self.profile_name_override);
+
+            // The CA bundle applies to the connectors that load the rest of the configuration
+            #[cfg(feature = "rustls")]
+            let (conf, tls_options, ca_bundle) = match ca_bundle::ca_bundle_with_source(&conf).await
+            {
+                Some(((path, tls_options), source)) => (
+                    conf.with_default_tls_options(tls_options.clone()),
+                    Some(tls_options),
+                    Some((path, source)),
+                ),
+                None => (conf, None, None),
+            };
+
             let region = if let Some(provider) = self.region {
-                provider.region().await
+                provider
+                    .region()
+                    .await
+                    .map(|region| (region, ValueSource::Programmatic))
             } else {
                 region::Builder::default()
                     .configure(&conf)
                     .build()
-                    .region()
+                    .region_with_source()
                     .await
             };
+            report.set_option("region", region.clone());
+            let region = region.map(|(region, _source)| region);
+
+            let credentials_provider = match self.credentials_provider {
+                CredentialsProviderOption::Set(provider) => {
+                    report.set(
+                        "credentials_provider_type",
+                        "custom",
+                        ValueSource::Programmatic,
+                    );
+                    Some(provider)
+                }
+                CredentialsProviderOption::NotSet => {
+                    let mut builder =
+                        credentials::DefaultCredentialsChain::builder().configure(conf.clone());
+                    builder.set_region(region.clone());
+                    report.set(
+                        "credentials_provider_type",
+                        "DefaultCredentialsChain",
+                        ValueSource::Default,
+                    );
+                    Some(SharedCredentialsProvider::new(builder.build().await))
+                }
+                CredentialsProviderOption::ExplicitlyUnset => {
+                    report.set_option("credentials_provider_type", None::<(String, ValueSource)>);
+                    None
+                }
+            };
+
+            let (defaults_mode, defaults_mode_source) = match self.defaults_mode {
+                Some(defaults_mode) => (defaults_mode, ValueSource::Programmatic),
+                None => {
+                    defaults_mode::default_provider()
+                        .configure(&conf)
+                        .region(region.clone())
+                        .defaults_mode_with_source()
+                        .await
+                }
+            };
+            report.set("defaults_mode", defaults_mode, defaults_mode_source);
 
             let retry_config = if let Some(retry_config) = self.retry_config {
+                report.set(
+                    "max_attempts",
+                    retry_config.max_attempts(),
+                    ValueSource::Programmatic,
+                );
+                report.set(
+                    "retry_mode",
+                    retry_config.mode().to_string(),
+                    ValueSource::Programmatic,
+                );
                 retry_config
             } else {
-                retry_config::default_provider()
+                let (retry_config, provenance) = retry_config::default_provider()
                     .configure(&conf)
-                    .retry_config()
-                    .await
+                    .defaults_mode(defaults_mode)
+                    .retry_config_with_provenance()
+                    .await;
+                let source = |field| {
+                    provenance
+                        .source(field)
+                        .cloned()
+                        .unwrap_or(ValueSource::Default)
+                };
+                report.set(
+                    "max_attempts",
+                    retry_config.max_attempts(),
+                    source("max_attempts"),
+                );
+                report.set(
+                    "retry_mode",
+                    retry_config.mode().to_string(),
+                    source("retry_mode"),
+                );
+                retry_config
+            };
+
+            let timeout_config = if let Some(timeout_config) = self.timeout_config {
+                report_timeouts(&mut report, &timeout_config, |_| {
+                    Some(ValueSource::Programmatic)
+                });
+                timeout_config
+            } else {
+                let (timeout_config, provenance) = timeout_config::default_provider()
+                    .configure(&conf)
+                    .defaults_mode(defaults_mode)
+                    .timeout_config_with_provenance()
+                    .await;
+                report_timeouts(&mut report, &timeout_config, |field| {
+                    provenance.source(field).cloned()
+                });
+                timeout_config
             };
 
-            let app_name = if self.app_name.is_some() {
-                self.app_name
+            let endpoint_url = if let Some(endpoint_url) = self.endpoint_url {
+                Some((endpoint_url, ValueSource::Programmatic))
+            } else {
+                endpoint_url_with_source(&conf).await
+            };
+            report.set_option("endpoint_url", endpoint_url.clone());
+            let endpoint_url = endpoint_url.map(|(endpoint_url, _source)| endpoint_url);
+
+            let use_fips = if let Some(use_fips) = self.use_fips {
+                Some((use_fips, ValueSource::Programmatic))
+            } else {
+                use_fips_with_source(&conf).await
+            };
+            report.set_option("use_fips", use_fips.clone());
+            let use_fips = use_fips.map(|(use_fips, _source)| use_fips);
+
+            let use_dual_stack = if let Some(use_dual_stack) = self.use_dual_stack {
+                Some((use_dual_stack, ValueSource::Programmatic))
+            } else {
+                use_dual_stack_with_source(&conf).await
+            };
+            report.set_option("use_dual_stack", use_dual_stack.clone());
+            let use_dual_stack = use_dual_stack.map(|(use_dual_stack, _source)| use_dual_stack);
+
+            let disable_request_compression =
+                if let Some(disable_request_compression) = self.disable_request_compression {
+                    Some((disable_request_compression, ValueSource::Programmatic))
+                } else {
+                    disable_request_compression_with_source(&conf).await
+                };
+            report.set_option(
+                "disable_request_compression",
+                disable_request_compression.clone(),
+            );
+            let disable_request_compression =
+                disable_request_compression.map(|(disable, _source)| disable);
+
+            let request_min_compression_size_bytes =
+                if let Some(min_size) = self.request_min_compression_size_bytes {
+                    Some((min_size, ValueSource::Programmatic))
+                } else {
+                    request_min_compression_size_bytes_with_source(&conf).await
+                };
+            report.set_option(
+                "request_min_compression_size_bytes",
+                request_min_compression_size_bytes.clone(),
+            );
+            let request_min_compression_size_bytes =
+                request_min_compression_size_bytes.map(|(min_size, _source)| min_size);
+
+            let app_name = if let Some(app_name) = self.app_name {
+                Some((app_name, ValueSource::Programmatic))
             } else {
                 app_name::default_provider()
                     .configure(&conf)
-                    .app_name()
+                    .app_name_with_source()
                     .await
             };
+            report.set_option("app_name", app_name.clone());
+            let app_name = app_name.map(|(app_name, _source)| app_name);
+
+            #[cfg(feature = "rustls")]
+            if let Some((path, source)) = ca_bundle {
+                report.set("ca_bundle", path, source);
+            }
 
             let sleep_impl = if self.sleep.is_some() {
                 self.sleep
@@ -594,29 +820,23 @@ mod loader {
                 default_async_sleep()
             };
 
-            let timeout_config = if let Some(timeout_config) = self.timeout_config {
-                timeout_config
-            } else {
-                timeout_config::default_provider()
-                    .configure(&conf)
-                    .timeout_config()
-                    .await
-            };
-
-            let http_connector = self
-                .http_connector
-                .unwrap_or_else(|| HttpConnector::ConnectorFn(Arc::new(default_connector)));
-
-            let credentials_provider = match self.credentials_provider {
-                CredentialsProviderOption::Set(provider) => Some(provider),
-                CredentialsProviderOption::NotSet => {
-                    let mut builder =
-                        credentials::DefaultCredentialsChain::builder().configure(conf.clone());
-                    builder.set_region(region.clone());
-                    Some(SharedCredentialsProvider::new(builder.build().await))
+            let http_connector = self.http_connector.unwrap_or_else(|| {
+                let env = conf.env();
+                #[cfg(feature = "rustls")]
+                if let Some(tls_options) = tls_options {
+                    return HttpConnector::ConnectorFn(Arc::new(move |settings, sleep| {
+                        Some(connector::default_connector_with_tls_options(
+                            settings,
+                            sleep,
+                            &tls_options,
+                            &env,
+                        ))
+                    }));
                 }
-                CredentialsProviderOption::ExplicitlyUnset => None,
-            };
+                HttpConnector::ConnectorFn(Arc::new(move |settings, sleep| {
+                    connector::default_connector_with_env(settings, sleep, &env)
+                }))
+            });
 
             let credentials_cache = if credentials_provider.is_some() {
                 Some(self.credentials_cache.unwrap_or_else(|| {
@@ -630,18 +850,6 @@ mod loader {
                 None
             };
 
-            let use_fips = if let Some(use_fips) = self.use_fips {
-                Some(use_fips)
-            } else {
-                use_fips_provider(&conf).await
-            };
-
-            let use_dual_stack = if let Some(use_dual_stack) = self.use_dual_stack {
-                Some(use_dual_stack)
-            } else {
-                use_dual_stack_provider(&conf).await
-            };
-
             let ts = self.time_source.unwrap_or_default();
 
             let mut builder = SdkConfig::builder()
@@ -655,10 +863,32 @@ mod loader {
             builder.set_credentials_cache(credentials_cache);
             builder.set_credentials_provider(credentials_provider);
             builder.set_sleep_impl(sleep_impl);
-            builder.set_endpoint_url(self.endpoint_url);
+            builder.set_endpoint_url(endpoint_url);
             builder.set_use_fips(use_fips);
             builder.set_use_dual_stack(use_dual_stack);
-            builder.build()
+            builder.set_disable_request_compression(disable_request_compression);
+            builder.set_request_min_compression_size_bytes(request_min_compression_size_bytes);
+            (builder.build(), report)
+        }
+    }
+
+    fn report_timeouts(
+        report: &mut ConfigReport,
+        timeout_config: &TimeoutConfig,
+        source: impl Fn(&'static str) -> Option<ValueSource>,
+    ) {
+        for (field, timeout) in [
+            ("connect_timeout", timeout_config.connect_timeout()),
+            ("read_timeout", timeout_config.read_timeout()),
+            ("operation_timeout", timeout_config.operation_timeout()),
+            (
+                "operation_attempt_timeout",
+                timeout_config.operation_attempt_timeout(),
+            ),
+        ] {
+            let resolved = timeout
+                .and_then(|timeout| source(field).map(|source| (format!("{:?}", timeout), source)));
+            report.set_option(field, resolved);
         }
     }
 
@@ -672,7 +902,11 @@ mod loader {
         use aws_types::os_shim_internal::{Env, Fs};
         use tracing_test::traced_test;
 
+        use std::time::Duration;
+
+        use crate::defaults_mode::DefaultsMode;
         use crate::profile::profile_file::{ProfileFileKind, ProfileFiles};
+        use crate::provenance::ValueSource;
         use crate::provider_config::ProviderConfig;
         use crate::test_case::{no_traffic_connector, InstantSleep};
         use crate::{from_env, ConfigLoader};
@@ -757,6 +991,21 @@ mod loader {
             assert_eq!(None, conf.use_dual_stack());
         }
 
+        #[tokio::test]
+        async fn load_request_compression() {
+            let conf = base_conf()
+                .disable_request_compression(true)
+                .request_min_compression_size_bytes(128)
+                .load()
+                .await;
+            assert_eq!(Some(true), conf.disable_request_compression());
+            assert_eq!(Some(128), conf.request_min_compression_size_bytes());
+
+            let conf = base_conf().load().await;
+            assert_eq!(None, conf.disable_request_compression());
+            assert_eq!(None, conf.request_min_compression_size_bytes());
+        }
+
         #[tokio::test]
         async fn app_name() {
             let app_name = AppName::new("my-app-name").unwrap();
@@ -764,6 +1013,127 @@ mod loader {
             assert_eq!(Some(&app_name), conf.app_name());
         }
 
+        #[tokio::test]
+        async fn defaults_mode_and_endpoint_url_from_env() {
+            let env = Env::from_slice(&[
+                ("AWS_DEFAULTS_MODE", "in-region"),
+                ("AWS_ENDPOINT_URL", "http://localhost:4566"),
+            ]);
+            let conf = from_env()
+                .configure(
+                    ProviderConfig::empty()
+                        .with_sleep(InstantSleep)
+                        .with_env(env)
+                        .with_http_connector(no_traffic_connector()),
+                )
+                .load()
+                .await;
+            assert_eq!(
+                Some(Duration::from_millis(1100)),
+                conf.timeout_config().unwrap().connect_timeout()
+            );
+            assert_eq!(Some("http://localhost:4566"), conf.endpoint_url());
+
+            let conf = base_conf().defaults_mode(DefaultsMode::Mobile).load().await;
+            assert_eq!(
+                Some(Duration::from_secs(30)),
+                conf.timeout_config().unwrap().connect_timeout()
+            );
+        }
+
+        #[tokio::test]
+        async fn report_where_values_came_from() {
+            let env = Env::from_slice(&[
+                ("AWS_CONFIG_FILE", "test_config"),
+                ("AWS_PROFILE", "dev"),
+                ("AWS_DEFAULTS_MODE", "in-region"),
+                ("AWS_USE_FIPS_ENDPOINT", "true"),
+            ]);
+            let fs = Fs::from_slice(&[(
+                "test_config",
+                "[profile dev]\nregion = us-west-2\nmax_attempts = 5\nread_timeout = 2",
+            )]);
+            let (config, report) = from_env()
+                .configure(
+                    ProviderConfig::empty()
+                        .with_sleep(InstantSleep)
+                        .with_env(env)
+                        .with_fs(fs)
+                        .with_http_connector(no_traffic_connector()),
+                )
+                .endpoint_url("http://localhost:4566")
+                .load_with_report()
+                .await;
+            assert_eq!("us-west-2", config.region().unwrap().as_ref());
+
+            let source = |field| report.get(field).unwrap().source().cloned();
+            assert_eq!(
+                Some(ValueSource::profile("dev", "region")),
+                source("region")
+            );
+            assert_eq!(
+                Some(ValueSource::environment("AWS_DEFAULTS_MODE")),
+                source("defaults_mode")
+            );
+            assert_eq!(
+                Some(ValueSource::DefaultsMode(DefaultsMode::InRegion)),
+                source("connect_timeout")
+            );
+            assert_eq!(Some(ValueSource::Programmatic), source("endpoint_url"));
+            assert_eq!(None, source("app_name"));
+            assert_eq!(
+                "\
+region                             = us-west-2 (profile `dev`, key: `region`)
+credentials_provider_type          = DefaultCredentialsChain (SDK default)
+defaults_mode                      = in-region (environment variable `AWS_DEFAULTS_MODE`)
+max_attempts                       = 5 (profile `dev`, key: `max_attempts`)
+retry_mode                         = standard (defaults mode `in-region`)
+connect_timeout                    = 1.1s (defaults mode `in-region`)
+read_timeout                       = 2s (profile `dev`, key: `read_timeout`)
+operation_timeout                  = not set
+operation_attempt_timeout          = not set
+endpoint_url                       = http://localhost:4566 (set in code)
+use_fips                           = true (environment variable `AWS_USE_FIPS_ENDPOINT`)
+use_dual_stack                     = not set
+disable_request_compression        = not set
+request_min_compression_size_bytes = not set
+app_name                           = not set
+",
+                report.to_string()
+            );
+        }
+
+        #[cfg(feature = "rustls")]
+        #[tokio::test]
+        async fn load_ca_bundle() {
+            let env = Env::from_slice(&[
+                ("AWS_CONFIG_FILE", "test_config"),
+                ("AWS_REGION", "us-east-1"),
+            ]);
+            let fs = Fs::from_slice(&[
+                ("test_config", "[default]\nca_bundle = /etc/ca.pem"),
+                ("/etc/ca.pem", include_str!("../test-data/tls/ca.pem")),
+            ]);
+            let (config, report) = from_env()
+                .configure(
+                    ProviderConfig::empty()
+                        .with_sleep(InstantSleep)
+                        .with_env(env)
+                        .with_fs(fs)
+                        .with_http_connector(no_traffic_connector()),
+                )
+                .no_credentials()
+                .load_with_report()
+                .await;
+            assert!(config.http_connector().is_some());
+            let ca_bundle = report.get("ca_bundle").unwrap();
+            assert_eq!(Some("/etc/ca.pem"), ca_bundle.value());
+            assert_eq!(
+                Some(&ValueSource::profile("default", "ca_bundle")),
+                ca_bundle.source()
+            );
+        }
+
         #[cfg(aws_sdk_orchestrator_mode)]
         #[tokio::test]
         async fn disable_default_credentials() {
@@ -7,8 +7,15 @@ use aws_credential_types::{
     provider::{self, error::CredentialsError, future, ProvideCredentials},
     Credentials,
 };
+use aws_smithy_async::rt::sleep::{default_async_sleep, AsyncSleep, SharedAsyncSleep};
 use aws_smithy_types::error::display::DisplayErrorContext;
 use std::borrow::Cow;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Poll;
+use std::time::Duration;
 use tracing::Instrument;
 
 /// Credentials provider that checks a series of inner providers
@@ -21,6 +28,14 @@ use tracing::Instrument;
 ///   the next provider will be checked.
 /// * Finally, if a provider returns any other error condition, an error will be returned immediately.
 ///
+/// If no provider returns credentials, the returned `CredentialsNotLoaded` error has a
+/// [`ChainNotLoaded`] as its source, listing each provider and why it was skipped.
+///
+/// Slow providers without side effects, such as those that call IMDS or ECS, can be added with
+/// [`or_else_concurrent`](CredentialsProviderChain::or_else_concurrent) and probed at the same
+/// time with [`probe_concurrently`](CredentialsProviderChain::probe_concurrently). The evaluation
+/// order described above still decides which credentials are returned.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -35,7 +50,16 @@ use tracing::Instrument;
 /// ```
 #[derive(Debug)]
 pub struct CredentialsProviderChain {
-    providers: Vec<(Cow<'static, str>, Box<dyn ProvideCredentials>)>,
+    providers: Vec<ChainedProvider>,
+    probe_deadline: Option<Duration>,
+    sleep: Option<SharedAsyncSleep>,
+}
+
+#[derive(Debug)]
+struct ChainedProvider {
+    name: Cow<'static, str>,
+    provider: Box<dyn ProvideCredentials>,
+    concurrent: bool,
 }
 
 impl CredentialsProviderChain {
@@ -45,7 +69,13 @@ impl CredentialsProviderChain {
         provider: impl ProvideCredentials + 'static,
     ) -> Self {
         CredentialsProviderChain {
-            providers: vec![(name.into(), Box::new(provider))],
+            providers: vec![ChainedProvider {
+                name: name.into(),
+                provider: Box::new(provider),
+                concurrent: false,
+            }],
+            probe_deadline: None,
+            sleep: None,
         }
     }
 
@@ -55,7 +85,31 @@ impl CredentialsProviderChain {
         name: impl Into<Cow<'static, str>>,
         provider: impl ProvideCredentials + 'static,
     ) -> Self {
-        self.providers.push((name.into(), Box::new(provider)));
+        self.providers.push(ChainedProvider {
+            name: name.into(),
+            provider: Box::new(provider),
+            concurrent: false,
+        });
+        self
+    }
+
+    /// Add a fallback provider that may be probed at the same time as the chain's other
+    /// concurrent providers
+    ///
+    /// Only use this for providers that are slow but have no side effects, like those that call
+    /// IMDS or ECS, since the provider may be run even when an earlier one in the chain provides
+    /// credentials. Unless [`probe_concurrently`](CredentialsProviderChain::probe_concurrently) is
+    /// set, the provider is evaluated like any other.
+    pub fn or_else_concurrent(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        provider: impl ProvideCredentials + 'static,
+    ) -> Self {
+        self.providers.push(ChainedProvider {
+            name: name.into(),
+            provider: Box::new(provider),
+            concurrent: true,
+        });
         self
     }
 
@@ -77,26 +131,141 @@ impl CredentialsProviderChain {
         )
     }
 
+    /// Probe the concurrent providers in the chain at the same time, waiting at most `deadline`
+    /// for them
+    ///
+    /// When the chain reaches a provider added with
+    /// [`or_else_concurrent`](CredentialsProviderChain::or_else_concurrent), it starts that
+    /// provider along with every later concurrent provider. Other providers are only run once
+    /// every provider before them has declined to provide credentials, as usual.
+    ///
+    /// Credentials are still selected in chain order: credentials from a provider are only
+    /// returned once every provider before it has declined to provide credentials. Concurrent
+    /// providers that have not finished when the deadline elapses are skipped as if they had
+    /// timed out. Errors returned by the providers themselves are reported as they are.
+    ///
+    /// The deadline is enforced with the default sleep implementation unless one is set with
+    /// [`sleep_impl`](CredentialsProviderChain::sleep_impl). Without a sleep implementation,
+    /// there is no deadline.
+    pub fn probe_concurrently(mut self, deadline: Duration) -> Self {
+        self.probe_deadline = Some(deadline);
+        self
+    }
+
+    /// Set the sleep implementation used to enforce the deadline of
+    /// [`probe_concurrently`](CredentialsProviderChain::probe_concurrently)
+    pub fn sleep_impl(mut self, sleep: impl AsyncSleep + 'static) -> Self {
+        self.sleep = Some(SharedAsyncSleep::new(sleep));
+        self
+    }
+
     async fn credentials(&self) -> provider::Result {
-        for (name, provider) in &self.providers {
-            let span = tracing::debug_span!("load_credentials", provider = %name);
-            match provider.provide_credentials().instrument(span).await {
-                Ok(credentials) => {
-                    tracing::debug!(provider = %name, "loaded credentials");
-                    return Ok(credentials);
+        match self.probe_deadline {
+            Some(deadline) => self.credentials_concurrently(deadline).await,
+            None => self.credentials_sequentially().await,
+        }
+    }
+
+    async fn credentials_sequentially(&self) -> provider::Result {
+        let mut skipped = Vec::new();
+        for chained in &self.providers {
+            let result = chained.load().await;
+            if let Some(result) = Self::evaluate(chained.name.clone(), result, &mut skipped) {
+                return result;
+            }
+        }
+        Err(CredentialsError::not_loaded(ChainNotLoaded { skipped }))
+    }
+
+    async fn credentials_concurrently(&self, probe_deadline: Duration) -> provider::Result {
+        let mut probes: Vec<Probe<'_>> = self.providers.iter().map(|_| Probe::NotStarted).collect();
+        let sleep = self.sleep.clone().or_else(default_async_sleep);
+        let mut deadline = None;
+        // `deadline` is dropped once it has elapsed so that the completed future is never polled again
+        let mut deadline_elapsed = false;
+        let mut next = 0;
+        let mut skipped = Vec::new();
+        std::future::poll_fn(|cx| loop {
+            let chained = match self.providers.get(next) {
+                Some(chained) => chained,
+                None => {
+                    return Poll::Ready(Err(CredentialsError::not_loaded(ChainNotLoaded {
+                        skipped: std::mem::take(&mut skipped),
+                    })))
+                }
+            };
+            if let Probe::NotStarted = probes[next] {
+                if chained.concurrent {
+                    // reaching the first concurrent provider starts all of them
+                    for (probe, later) in probes[next..].iter_mut().zip(&self.providers[next..]) {
+                        if later.concurrent {
+                            if let Probe::NotStarted = probe {
+                                *probe = Probe::Running(later.load());
+                            }
+                        }
+                    }
+                    if deadline.is_none() && !deadline_elapsed {
+                        deadline = sleep.as_ref().map(|sleep| sleep.sleep(probe_deadline));
+                    }
+                } else {
+                    probes[next] = Probe::Running(chained.load());
+                }
+            }
+            for probe in probes.iter_mut() {
+                if let Probe::Running(future) = probe {
+                    if let Poll::Ready(result) = future.as_mut().poll(cx) {
+                        *probe = Probe::Done(result);
+                    }
+                }
+            }
+            if let Some(sleep) = deadline.as_mut() {
+                if Pin::new(sleep).poll(cx).is_ready() {
+                    deadline_elapsed = true;
+                    deadline = None;
                 }
-                Err(err @ CredentialsError::CredentialsNotLoaded(_)) => {
-                    tracing::debug!(provider = %name, context = %DisplayErrorContext(&err), "provider in chain did not provide credentials");
+            }
+            let result = match std::mem::replace(&mut probes[next], Probe::Evaluated) {
+                Probe::Done(result) => result,
+                Probe::Running(_) if chained.concurrent && deadline_elapsed => {
+                    tracing::debug!(provider = %chained.name, deadline = ?probe_deadline, "provider did not finish before the deadline");
+                    Err(CredentialsError::not_loaded(
+                        CredentialsError::provider_timed_out(probe_deadline),
+                    ))
                 }
-                Err(err) => {
-                    tracing::warn!(provider = %name, error = %DisplayErrorContext(&err), "provider failed to provide credentials");
-                    return Err(err);
+                probe => {
+                    probes[next] = probe;
+                    return Poll::Pending;
                 }
+            };
+            next += 1;
+            if let Some(result) = Self::evaluate(chained.name.clone(), result, &mut skipped) {
+                return Poll::Ready(result);
+            }
+        })
+        .await
+    }
+
+    /// Returns the final result of the chain, or `None` if the next provider should be checked
+    fn evaluate(
+        name: Cow<'static, str>,
+        result: provider::Result,
+        skipped: &mut Vec<SkippedProvider>,
+    ) -> Option<provider::Result> {
+        match result {
+            Ok(credentials) => {
+                tracing::debug!(provider = %name, "loaded credentials");
+                Some(Ok(credentials))
+            }
+            Err(err @ CredentialsError::CredentialsNotLoaded(_)) => {
+                tracing::debug!(provider = %name, context = %DisplayErrorContext(&err), "provider in chain did not provide credentials");
+                skipped.push(SkippedProvider { name, error: err });
+                None
+            }
+            Err(err) => {
+                tracing::warn!(provider = %name, error = %DisplayErrorContext(&err), "provider failed to provide credentials");
+                Some(Err(err))
             }
         }
-        Err(CredentialsError::not_loaded(
-            "no providers in chain provided credentials",
-        ))
     }
 }
 
@@ -109,8 +278,8 @@ impl ProvideCredentials for CredentialsProviderChain {
     }
 
     fn fallback_on_interrupt(&self) -> Option<Credentials> {
-        for (_, provider) in &self.providers {
-            match provider.fallback_on_interrupt() {
+        for chained in &self.providers {
+            match chained.provider.fallback_on_interrupt() {
                 creds @ Some(_) => return creds,
                 None => {}
             }
@@ -119,6 +288,96 @@ impl ProvideCredentials for CredentialsProviderChain {
     }
 }
 
+impl ChainedProvider {
+    fn load(&self) -> ProbeFuture<'_> {
+        let span = tracing::debug_span!("load_credentials", provider = %self.name);
+        Box::pin(self.provider.provide_credentials().instrument(span))
+    }
+}
+
+type ProbeFuture<'a> = Pin<Box<dyn Future<Output = provider::Result> + Send + 'a>>;
+
+/// The progress of a provider while the chain is probed concurrently
+enum Probe<'a> {
+    NotStarted,
+    Running(ProbeFuture<'a>),
+    Done(provider::Result),
+    Evaluated,
+}
+
+/// No provider in a [`CredentialsProviderChain`] provided credentials
+///
+/// This is the source of the `CredentialsNotLoaded` error returned by the chain, and lists why
+/// each provider was skipped:
+///
+/// ```no_run
+/// # async fn example(chain: aws_config::meta::credentials::CredentialsProviderChain) {
+/// use aws_config::meta::credentials::ChainNotLoaded;
+/// use aws_credential_types::provider::ProvideCredentials;
+/// use std::error::Error;
+///
+/// if let Err(err) = chain.provide_credentials().await {
+///     if let Some(not_loaded) = err.source().and_then(|s| s.downcast_ref::<ChainNotLoaded>()) {
+///         for skipped in not_loaded.skipped() {
+///             println!("{}: {}", skipped.name(), skipped.error());
+///         }
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ChainNotLoaded {
+    skipped: Vec<SkippedProvider>,
+}
+
+impl ChainNotLoaded {
+    /// The providers that were skipped, in chain order
+    pub fn skipped(&self) -> &[SkippedProvider] {
+        &self.skipped
+    }
+}
+
+impl fmt::Display for ChainNotLoaded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "no providers in chain provided credentials")?;
+        for (idx, skipped) in self.skipped.iter().enumerate() {
+            let separator = if idx == 0 { " [" } else { "; " };
+            write!(
+                f,
+                "{}{}: {}",
+                separator,
+                skipped.name,
+                DisplayErrorContext(&skipped.error)
+            )?;
+        }
+        if !self.skipped.is_empty() {
+            write!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ChainNotLoaded {}
+
+/// A provider in a [`CredentialsProviderChain`] that did not provide credentials
+#[derive(Debug)]
+pub struct SkippedProvider {
+    name: Cow<'static, str>,
+    error: CredentialsError,
+}
+
+impl SkippedProvider {
+    /// The name of the provider in the chain
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Why the provider did not provide credentials
+    pub fn error(&self) -> &CredentialsError {
+        &self.error
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -129,8 +388,13 @@ mod tests {
         Credentials,
     };
     use aws_smithy_async::future::timeout::Timeout;
+    use aws_smithy_async::rt::sleep::{AsyncSleep, Sleep, TokioSleep};
+    use aws_smithy_types::error::display::DisplayErrorContext;
+    use std::error::Error;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
-    use crate::meta::credentials::CredentialsProviderChain;
+    use crate::meta::credentials::{ChainNotLoaded, CredentialsProviderChain};
 
     #[derive(Debug)]
     struct FallbackCredentials(Credentials);
@@ -216,4 +480,211 @@ mod tests {
             },
         };
     }
+
+    fn not_loaded(message: &'static str) -> impl ProvideCredentials {
+        provide_credentials_fn(move || async move { Err(CredentialsError::not_loaded(message)) })
+    }
+
+    fn slow(delay: Duration, access_key_id: &'static str) -> impl ProvideCredentials {
+        provide_credentials_fn(move || async move {
+            tokio::time::sleep(delay).await;
+            Ok(Credentials::new(
+                access_key_id,
+                "secret",
+                None,
+                None,
+                "test",
+            ))
+        })
+    }
+
+    #[tokio::test]
+    async fn reports_why_each_provider_was_skipped() {
+        let chain = CredentialsProviderChain::first_try("Environment", not_loaded("no env vars"))
+            .or_else("Profile", not_loaded("no profile"));
+        let err = chain
+            .provide_credentials()
+            .await
+            .expect_err("no credentials");
+        let not_loaded = err
+            .source()
+            .and_then(|s| s.downcast_ref::<ChainNotLoaded>())
+            .expect("chain diagnostics");
+        let names: Vec<_> = not_loaded.skipped().iter().map(|s| s.name()).collect();
+        assert_eq!(vec!["Environment", "Profile"], names);
+        let message = format!("{}", DisplayErrorContext(&err));
+        assert!(
+            message.contains("no providers in chain provided credentials [Environment: the credential provider was not enabled: no env vars"),
+            "{}",
+            message
+        );
+        assert!(message.contains("; Profile: "), "{}", message);
+    }
+
+    fn counted(
+        calls: &Arc<AtomicUsize>,
+        delay: Duration,
+        access_key_id: &'static str,
+    ) -> impl ProvideCredentials {
+        let calls = calls.clone();
+        provide_credentials_fn(move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            async move {
+                tokio::time::sleep(delay).await;
+                Ok(Credentials::new(
+                    access_key_id,
+                    "secret",
+                    None,
+                    None,
+                    "test",
+                ))
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn concurrent_probe_respects_chain_order() {
+        tokio::time::pause();
+        let chain = CredentialsProviderChain::first_try("Environment", not_loaded("no env vars"))
+            .or_else_concurrent("Ecs", slow(Duration::from_secs(2), "ecs"))
+            .or_else_concurrent("Imds", slow(Duration::from_secs(1), "imds"))
+            .probe_concurrently(Duration::from_secs(5))
+            .sleep_impl(TokioSleep::new());
+        let start = tokio::time::Instant::now();
+        let creds = chain.provide_credentials().await.expect("credentials");
+        assert_eq!("ecs", creds.access_key_id());
+        // both providers ran at the same time
+        assert!(
+            start.elapsed() < Duration::from_secs(3),
+            "{:?}",
+            start.elapsed()
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_probe_only_starts_concurrent_providers_early() {
+        tokio::time::pause();
+        let process_calls = Arc::new(AtomicUsize::new(0));
+        let imds_calls = Arc::new(AtomicUsize::new(0));
+        let chain = CredentialsProviderChain::first_try("Environment", not_loaded("no env vars"))
+            .or_else_concurrent("Ecs", slow(Duration::from_secs(1), "ecs"))
+            .or_else(
+                "Process",
+                counted(&process_calls, Duration::ZERO, "process"),
+            )
+            .or_else_concurrent("Imds", counted(&imds_calls, Duration::ZERO, "imds"))
+            .probe_concurrently(Duration::from_secs(5))
+            .sleep_impl(TokioSleep::new());
+        let creds = chain.provide_credentials().await.expect("credentials");
+        assert_eq!("ecs", creds.access_key_id());
+        assert_eq!(0, process_calls.load(Ordering::SeqCst));
+        assert_eq!(1, imds_calls.load(Ordering::SeqCst));
+
+        // concurrent providers aren't started before the chain reaches them
+        let imds_calls = Arc::new(AtomicUsize::new(0));
+        let chain =
+            CredentialsProviderChain::first_try("Environment", slow(Duration::from_secs(1), "env"))
+                .or_else_concurrent("Imds", counted(&imds_calls, Duration::ZERO, "imds"))
+                .probe_concurrently(Duration::from_secs(5))
+                .sleep_impl(TokioSleep::new());
+        let creds = chain.provide_credentials().await.expect("credentials");
+        assert_eq!("env", creds.access_key_id());
+        assert_eq!(0, imds_calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn concurrent_probe_skips_providers_past_the_deadline() {
+        tokio::time::pause();
+        let chain = CredentialsProviderChain::first_try("Environment", not_loaded("no env vars"))
+            .or_else_concurrent("Ecs", slow(Duration::from_secs(10), "ecs"))
+            .or_else_concurrent("Imds", slow(Duration::from_secs(1), "imds"))
+            .probe_concurrently(Duration::from_secs(3))
+            .sleep_impl(TokioSleep::new());
+        let start = tokio::time::Instant::now();
+        let creds = chain.provide_credentials().await.expect("credentials");
+        assert_eq!("imds", creds.access_key_id());
+        assert!(
+            start.elapsed() < Duration::from_secs(4),
+            "{:?}",
+            start.elapsed()
+        );
+
+        let chain = CredentialsProviderChain::first_try("Environment", not_loaded("no env vars"))
+            .or_else_concurrent("Ecs", slow(Duration::from_secs(10), "ecs"))
+            .or_else("Profile", not_loaded("no profile"))
+            .probe_concurrently(Duration::from_secs(3))
+            .sleep_impl(TokioSleep::new());
+        let err = chain
+            .provide_credentials()
+            .await
+            .expect_err("no credentials");
+        let message = format!("{}", DisplayErrorContext(&err));
+        assert!(
+            message.contains("Ecs: the credential provider was not enabled: credentials provider timed out after 3 seconds"),
+            "{}",
+            message
+        );
+    }
+
+    /// Sleep implemented with an `async` block, which panics if it's polled after completing
+    #[derive(Debug)]
+    struct AsyncBlockSleep;
+
+    impl AsyncSleep for AsyncBlockSleep {
+        fn sleep(&self, duration: Duration) -> Sleep {
+            Sleep::new(async move { tokio::time::sleep(duration).await })
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_probe_never_polls_an_elapsed_deadline_again() {
+        tokio::time::pause();
+        let chain = CredentialsProviderChain::first_try("Environment", not_loaded("no env vars"))
+            .or_else_concurrent("Ecs", slow(Duration::from_secs(10), "ecs"))
+            .or_else_concurrent("Imds", slow(Duration::from_secs(10), "imds"))
+            .or_else("Profile", not_loaded("no profile"))
+            .probe_concurrently(Duration::from_secs(3))
+            .sleep_impl(AsyncBlockSleep);
+        let err = chain
+            .provide_credentials()
+            .await
+            .expect_err("no credentials");
+        let message = format!("{}", DisplayErrorContext(&err));
+        assert!(
+            message.contains("Ecs: the credential provider was not enabled: credentials provider timed out after 3 seconds"),
+            "{}",
+            message
+        );
+        assert!(
+            message.contains("Imds: the credential provider was not enabled: credentials provider timed out after 3 seconds"),
+            "{}",
+            message
+        );
+    }
+
+    #[tokio::test]
+    async fn concurrent_probe_keeps_provider_errors() {
+        tokio::time::pause();
+        // Imds times out on its own before the deadline, but is only evaluated after it
+        let chain = CredentialsProviderChain::first_try("Environment", not_loaded("no env vars"))
+            .or_else_concurrent("Ecs", slow(Duration::from_secs(10), "ecs"))
+            .or_else_concurrent(
+                "Imds",
+                provide_credentials_fn(|| async {
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    Err(CredentialsError::provider_timed_out(Duration::from_secs(1)))
+                }),
+            )
+            .probe_concurrently(Duration::from_secs(3))
+            .sleep_impl(TokioSleep::new());
+        let err = chain
+            .provide_credentials()
+            .await
+            .expect_err("no credentials");
+        assert!(
+            matches!(err, CredentialsError::ProviderTimedOut(_)),
+            "{:?}",
+            err
+        );
+    }
 }
@@ -54,9 +54,17 @@ impl ProfileFileAppNameProvider {
 
     /// Parses the profile config and attempts to find an app name.
     pub async fn app_name(&self) -> Option<AppName> {
-        let app_id = self.provider_config.profile().await?.get("sdk-ua-app-id")?;
+        self.app_name_with_profile()
+            .await
+            .map(|(app_name, _profile)| app_name)
+    }
+
+    /// Load the app name, along with the name of the profile it was found in
+    pub(crate) async fn app_name_with_profile(&self) -> Option<(AppName, &str)> {
+        let profile = self.provider_config.profile().await?;
+        let app_id = profile.get("sdk-ua-app-id")?;
         match AppName::new(app_id.to_owned()) {
-            Ok(app_name) => Some(app_name),
+            Ok(app_name) => Some((app_name, profile.selected_profile())),
             Err(err) => {
                 tracing::warn!(err = %err, "`sdk-ua-app-id` property `{}` was invalid", app_id);
                 None
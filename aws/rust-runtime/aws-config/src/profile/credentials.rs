@@ -118,6 +118,22 @@ impl ProvideCredentials for ProfileFileCredentialsProvider {
 ///
 /// Other more complex configurations are possible, consult `test-data/assume-role-tests.json`.
 ///
+/// ### Configuring the role session
+/// ```ini
+/// [default]
+/// role_arn = arn:aws:iam::123456789:role/RoleA
+/// source_profile = base
+/// duration_seconds = 7200
+/// source_identity = helen
+/// role_session_tags = team=storage,env=prod
+/// policy_arns = arn:aws:iam::aws:policy/ReadOnlyAccess
+/// ```
+///
+/// When the selected profile does not set them, these parameters are loaded from the
+/// `AWS_ROLE_DURATION_SECONDS`, `AWS_ROLE_SOURCE_IDENTITY`, `AWS_ROLE_SESSION_TAGS` and
+/// `AWS_ROLE_POLICY_ARNS` environment variables. Environment variables only apply to the last role
+/// in the chain: the one whose credentials are returned.
+///
 /// ### Credentials loaded from an external process
 /// ```ini
 /// [default]
@@ -259,6 +275,17 @@ pub enum ProfileFileError {
         name: String,
     },
 
+    /// An assume role session parameter, e.g. `duration_seconds`, was invalid
+    #[non_exhaustive]
+    InvalidSessionParameter {
+        /// Where the parameter was loaded from, e.g. ``profile `A` `` or `environment`
+        origin: String,
+        /// The name of the profile key or environment variable
+        key: &'static str,
+        /// Error message
+        message: Cow<'static, str>,
+    },
+
     /// Feature not enabled
     #[non_exhaustive]
     FeatureNotEnabled {
@@ -311,6 +338,11 @@ impl Display for ProfileFileError {
                 "profile referenced `{}` provider but that provider is not supported",
                 name
             ),
+            ProfileFileError::InvalidSessionParameter {
+                origin,
+                key,
+                message,
+            } => write!(f, "invalid `{}` in {}: {}", key, origin, message),
             ProfileFileError::NoProfilesDefined => write!(f, "No profiles were defined"),
             ProfileFileError::ProfileDidNotContainCredentials { profile } => write!(
                 f,
@@ -13,16 +13,29 @@ use crate::sts;
 use crate::web_identity_token::{StaticConfiguration, WebIdentityTokenCredentialsProvider};
 use aws_credential_types::provider::{self, error::CredentialsError, ProvideCredentials};
 use aws_sdk_sts::config::{Builder as StsConfigBuilder, Credentials};
+use aws_sdk_sts::types::{PolicyDescriptorType, Tag};
 use aws_sdk_sts::Client as StsClient;
 use aws_smithy_async::time::SharedTimeSource;
+use aws_types::os_shim_internal::Env;
+use std::borrow::Cow;
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
+
+const ENV_VAR_DURATION_SECONDS: &str = "AWS_ROLE_DURATION_SECONDS";
+const ENV_VAR_SOURCE_IDENTITY: &str = "AWS_ROLE_SOURCE_IDENTITY";
+const ENV_VAR_SESSION_TAGS: &str = "AWS_ROLE_SESSION_TAGS";
+const ENV_VAR_POLICY_ARNS: &str = "AWS_ROLE_POLICY_ARNS";
 
 #[derive(Debug)]
 pub(super) struct AssumeRoleProvider {
     role_arn: String,
     external_id: Option<String>,
     session_name: Option<String>,
+    session_length: Option<Duration>,
+    source_identity: Option<String>,
+    tags: Vec<(String, String)>,
+    policy_arns: Vec<String>,
     time_source: SharedTimeSource,
 }
 
@@ -45,12 +58,87 @@ impl AssumeRoleProvider {
             .role_arn(&self.role_arn)
             .set_external_id(self.external_id.clone())
             .role_session_name(session_name)
+            .set_duration_seconds(self.session_length.map(|dur| dur.as_secs() as i32))
+            .set_source_identity(self.source_identity.clone())
+            .set_tags(self.session_tags())
+            .set_policy_arns(self.policy_arns())
             .send()
             .await
             .map_err(CredentialsError::provider_error)?
             .credentials;
         sts::util::into_credentials(assume_role_creds, "AssumeRoleProvider")
     }
+
+    fn session_tags(&self) -> Option<Vec<Tag>> {
+        if self.tags.is_empty() {
+            return None;
+        }
+        Some(
+            self.tags
+                .iter()
+                .map(|(key, value)| Tag::builder().key(key).value(value).build())
+                .collect(),
+        )
+    }
+
+    fn policy_arns(&self) -> Option<Vec<PolicyDescriptorType>> {
+        if self.policy_arns.is_empty() {
+            return None;
+        }
+        Some(
+            self.policy_arns
+                .iter()
+                .map(|arn| PolicyDescriptorType::builder().arn(arn).build())
+                .collect(),
+        )
+    }
+
+    /// Fill in session parameters that the profile did not set from the environment
+    fn apply_env_defaults(&mut self, env: &Env) -> Result<(), ProfileFileError> {
+        let invalid = |key: &'static str| {
+            move |message: Cow<'static, str>| ProfileFileError::InvalidSessionParameter {
+                origin: "environment".into(),
+                key,
+                message,
+            }
+        };
+        if self.session_length.is_none() {
+            if let Ok(value) = env.get(ENV_VAR_DURATION_SECONDS) {
+                self.session_length = Some(
+                    repr::parse_duration_seconds(&value)
+                        .map_err(invalid(ENV_VAR_DURATION_SECONDS))?,
+                );
+            }
+        }
+        if self.source_identity.is_none() {
+            if let Ok(value) = env.get(ENV_VAR_SOURCE_IDENTITY) {
+                self.source_identity = Some(
+                    repr::validate_source_identity(&value)
+                        .map_err(invalid(ENV_VAR_SOURCE_IDENTITY))?
+                        .to_string(),
+                );
+            }
+        }
+        if self.tags.is_empty() {
+            if let Ok(value) = env.get(ENV_VAR_SESSION_TAGS) {
+                self.tags = repr::parse_session_tags(&value)
+                    .map_err(invalid(ENV_VAR_SESSION_TAGS))?
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect();
+            }
+        }
+        if self.policy_arns.is_empty() {
+            if let Ok(value) = env.get(ENV_VAR_POLICY_ARNS) {
+                self.policy_arns = repr::parse_policy_arns(&value)
+                    .map_err(invalid(ENV_VAR_POLICY_ARNS))?
+                    .into_iter()
+                    .map(Into::into)
+                    .collect();
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
@@ -85,7 +173,9 @@ impl ProviderChain {
             }
             BaseProvider::AccessKey(key) => Arc::new(key.clone()),
             BaseProvider::CredentialProcess(credential_process) => Arc::new(
-                CredentialProcessProvider::new(credential_process.unredacted().into()),
+                CredentialProcessProvider::builder(credential_process.unredacted())
+                    .configure(provider_config)
+                    .build(),
             ),
             BaseProvider::WebIdentityTokenRole {
                 role_arn,
@@ -136,7 +226,7 @@ impl ProviderChain {
             }
         };
         tracing::info!(base = ?repr.base(), "first credentials will be loaded from {:?}", repr.base());
-        let chain = repr
+        let mut chain: Vec<_> = repr
             .chain()
             .iter()
             .map(|role_arn| {
@@ -145,10 +235,22 @@ impl ProviderChain {
                     role_arn: role_arn.role_arn.into(),
                     external_id: role_arn.external_id.map(|id| id.into()),
                     session_name: role_arn.session_name.map(|id| id.into()),
+                    session_length: role_arn.session_length,
+                    source_identity: role_arn.source_identity.map(|id| id.into()),
+                    tags: role_arn
+                        .tags
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                    policy_arns: role_arn.policy_arns.iter().map(|&arn| arn.into()).collect(),
                     time_source: provider_config.time_source(),
                 }
             })
             .collect();
+        // environment overrides only apply to the role whose credentials are ultimately returned
+        if let Some(last) = chain.last_mut() {
+            last.apply_env_defaults(&provider_config.env())?;
+        }
         Ok(ProviderChain { base, chain })
     }
 }
@@ -193,13 +295,15 @@ pub(super) mod named {
 mod test {
     use crate::profile::credentials::exec::named::NamedProviderFactory;
     use crate::profile::credentials::exec::ProviderChain;
-    use crate::profile::credentials::repr::{BaseProvider, ProfileChain};
+    use crate::profile::credentials::repr::{BaseProvider, ProfileChain, RoleArn};
     use crate::provider_config::ProviderConfig;
     use crate::test_case::no_traffic_connector;
 
     use aws_credential_types::Credentials;
+    use aws_types::os_shim_internal::Env;
     use std::collections::HashMap;
     use std::sync::Arc;
+    use std::time::Duration;
 
     #[test]
     fn providers_case_insensitive() {
@@ -215,6 +319,67 @@ mod test {
         assert!(provider.provider("Environment").is_some());
     }
 
+    fn role(role_arn: &str) -> RoleArn<'_> {
+        RoleArn {
+            role_arn,
+            external_id: None,
+            session_name: None,
+            session_length: None,
+            source_identity: None,
+            tags: vec![],
+            policy_arns: vec![],
+        }
+    }
+
+    #[test]
+    fn env_session_parameters_apply_to_last_role() {
+        let env = Env::from_slice(&[
+            ("AWS_ROLE_DURATION_SECONDS", "7200"),
+            ("AWS_ROLE_SOURCE_IDENTITY", "helen"),
+            ("AWS_ROLE_SESSION_TAGS", "team=storage"),
+        ]);
+        let mut last = role("arn:aws:iam::123456789:role/RoleB");
+        last.source_identity = Some("from-profile");
+        let chain = ProviderChain::from_repr(
+            &ProviderConfig::empty()
+                .with_env(env)
+                .with_http_connector(no_traffic_connector()),
+            ProfileChain {
+                base: BaseProvider::AccessKey(Credentials::for_tests()),
+                chain: vec![role("arn:aws:iam::123456789:role/RoleA"), last],
+            },
+            &NamedProviderFactory::new(HashMap::new()),
+        )
+        .expect("valid chain");
+        let (first, last) = (&chain.chain()[0], &chain.chain()[1]);
+        assert_eq!(None, first.session_length);
+        assert!(first.tags.is_empty());
+        assert_eq!(Some(Duration::from_secs(7200)), last.session_length);
+        assert_eq!(Some("from-profile"), last.source_identity.as_deref());
+        assert_eq!(vec![("team".to_string(), "storage".to_string())], last.tags);
+    }
+
+    #[test]
+    fn error_on_invalid_env_session_parameter() {
+        let env = Env::from_slice(&[("AWS_ROLE_DURATION_SECONDS", "60")]);
+        let err = ProviderChain::from_repr(
+            &ProviderConfig::empty()
+                .with_env(env)
+                .with_http_connector(no_traffic_connector()),
+            ProfileChain {
+                base: BaseProvider::AccessKey(Credentials::for_tests()),
+                chain: vec![role("arn:aws:iam::123456789:role/RoleA")],
+            },
+            &NamedProviderFactory::new(HashMap::new()),
+        )
+        .expect_err("duration is too short");
+        assert!(
+            format!("{}", err).contains("invalid `AWS_ROLE_DURATION_SECONDS` in environment"),
+            "`{}` did not match expected error",
+            err
+        );
+    }
+
     #[test]
     fn error_on_unknown_provider() {
         let factory = NamedProviderFactory::new(HashMap::new());
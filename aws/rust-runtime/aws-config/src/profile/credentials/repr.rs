@@ -16,6 +16,8 @@ use crate::credential_process::CommandWithSensitiveArgs;
 use crate::profile::credentials::ProfileFileError;
 use crate::profile::{Profile, ProfileSet};
 use aws_credential_types::Credentials;
+use std::borrow::Cow;
+use std::time::Duration;
 
 /// Chain of Profile Providers
 ///
@@ -105,6 +107,18 @@ pub(super) struct RoleArn<'a> {
 
     /// session name parameter to pass to the assume role provider
     pub(super) session_name: Option<&'a str>,
+
+    /// duration of the role session, loaded from `duration_seconds`
+    pub(super) session_length: Option<Duration>,
+
+    /// source identity to attach to the role session
+    pub(super) source_identity: Option<&'a str>,
+
+    /// session tags to attach to the role session, as `(key, value)` pairs
+    pub(super) tags: Vec<(&'a str, &'a str)>,
+
+    /// ARNs of managed policies to use as session policies
+    pub(super) policy_arns: Vec<&'a str>,
 }
 
 /// Resolve a ProfileChain from a ProfileSet or return an error
@@ -168,6 +182,7 @@ pub(super) fn resolve_chain(
             // We check for one here and then process the profile accordingly as either a "chain provider"
             // or a "base provider"
             if let Some(role_provider) = role_arn_from_profile(profile) {
+                let role_provider = role_provider?;
                 let next = chain_provider(profile)?;
                 chain.push(role_provider);
                 next
@@ -206,6 +221,10 @@ mod role {
     pub(super) const ROLE_ARN: &str = "role_arn";
     pub(super) const EXTERNAL_ID: &str = "external_id";
     pub(super) const SESSION_NAME: &str = "role_session_name";
+    pub(super) const DURATION_SECONDS: &str = "duration_seconds";
+    pub(super) const SOURCE_IDENTITY: &str = "source_identity";
+    pub(super) const SESSION_TAGS: &str = "role_session_tags";
+    pub(super) const POLICY_ARNS: &str = "policy_arns";
 
     pub(super) const CREDENTIAL_SOURCE: &str = "credential_source";
     pub(super) const SOURCE_PROFILE: &str = "source_profile";
@@ -277,7 +296,7 @@ fn chain_provider(profile: &Profile) -> Result<NextProfile<'_>, ProfileFileError
     }
 }
 
-fn role_arn_from_profile(profile: &Profile) -> Option<RoleArn<'_>> {
+fn role_arn_from_profile(profile: &Profile) -> Option<Result<RoleArn<'_>, ProfileFileError>> {
     // Web Identity Tokens are root providers, not chained roles
     if profile.get(web_identity_token::TOKEN_FILE).is_some() {
         return None;
@@ -285,11 +304,160 @@ fn role_arn_from_profile(profile: &Profile) -> Option<RoleArn<'_>> {
     let role_arn = profile.get(role::ROLE_ARN)?;
     let session_name = profile.get(role::SESSION_NAME);
     let external_id = profile.get(role::EXTERNAL_ID);
-    Some(RoleArn {
-        role_arn,
-        external_id,
-        session_name,
-    })
+    let invalid = |key: &'static str| {
+        move |message: Cow<'static, str>| ProfileFileError::InvalidSessionParameter {
+            origin: format!("profile `{}`", profile.name()),
+            key,
+            message,
+        }
+    };
+    let parse_profile = || {
+        let session_length = profile
+            .get(role::DURATION_SECONDS)
+            .map(parse_duration_seconds)
+            .transpose()
+            .map_err(invalid(role::DURATION_SECONDS))?;
+        let source_identity = profile
+            .get(role::SOURCE_IDENTITY)
+            .map(validate_source_identity)
+            .transpose()
+            .map_err(invalid(role::SOURCE_IDENTITY))?;
+        let tags = profile
+            .get(role::SESSION_TAGS)
+            .map(parse_session_tags)
+            .transpose()
+            .map_err(invalid(role::SESSION_TAGS))?
+            .unwrap_or_default();
+        let policy_arns = profile
+            .get(role::POLICY_ARNS)
+            .map(parse_policy_arns)
+            .transpose()
+            .map_err(invalid(role::POLICY_ARNS))?
+            .unwrap_or_default();
+        Ok(RoleArn {
+            role_arn,
+            external_id,
+            session_name,
+            session_length,
+            source_identity,
+            tags,
+            policy_arns,
+        })
+    };
+    Some(parse_profile())
+}
+
+/// Parse a role session duration, e.g. `duration_seconds = 3600`
+///
+/// STS accepts durations between 15 minutes and 12 hours. The upper bound is further limited by
+/// the maximum session duration configured on the role, which can only be checked by STS.
+pub(super) fn parse_duration_seconds(value: &str) -> Result<Duration, Cow<'static, str>> {
+    const MIN_SECONDS: u64 = 900;
+    const MAX_SECONDS: u64 = 43200;
+    let seconds: u64 = value
+        .trim()
+        .parse()
+        .map_err(|_| format!("`{}` is not a valid number of seconds", value))?;
+    if !(MIN_SECONDS..=MAX_SECONDS).contains(&seconds) {
+        return Err(format!(
+            "session duration must be between {} and {} seconds but was {}",
+            MIN_SECONDS, MAX_SECONDS, seconds
+        )
+        .into());
+    }
+    Ok(Duration::from_secs(seconds))
+}
+
+/// Validate a source identity, e.g. `source_identity = helen@example.com`
+///
+/// Source identities are 2 to 64 characters long and may only contain alphanumeric characters
+/// and `_+=,.@-`.
+pub(super) fn validate_source_identity(value: &str) -> Result<&str, Cow<'static, str>> {
+    let value = value.trim();
+    if !(2..=64).contains(&value.len()) {
+        return Err(format!(
+            "source identity must be between 2 and 64 characters but was {}",
+            value.len()
+        )
+        .into());
+    }
+    if let Some(invalid) = value
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || "_+=,.@-".contains(*c)))
+    {
+        return Err(format!("source identity contained invalid character `{}`", invalid).into());
+    }
+    Ok(value)
+}
+
+/// Parse a comma-delimited list of session tags, e.g. `role_session_tags = team=storage,env=prod`
+///
+/// Tag keys must be unique (ignoring case), at most 128 characters long and tag values at most
+/// 256 characters long. STS limits a session to 50 tags.
+pub(super) fn parse_session_tags(value: &str) -> Result<Vec<(&str, &str)>, Cow<'static, str>> {
+    const MAX_TAGS: usize = 50;
+    let mut tags: Vec<(&str, &str)> = vec![];
+    for tag in value
+        .split(',')
+        .map(str::trim)
+        .filter(|tag| !tag.is_empty())
+    {
+        let (key, tag_value) = tag
+            .split_once('=')
+            .map(|(k, v)| (k.trim(), v.trim()))
+            .ok_or_else(|| format!("session tag `{}` must be of the form `key=value`", tag))?;
+        if key.is_empty() || key.len() > 128 {
+            return Err(format!(
+                "session tag key `{}` must be between 1 and 128 characters",
+                key
+            )
+            .into());
+        }
+        if tag_value.len() > 256 {
+            return Err(format!(
+                "value of session tag `{}` must be at most 256 characters",
+                key
+            )
+            .into());
+        }
+        if tags.iter().any(|(k, _)| k.eq_ignore_ascii_case(key)) {
+            return Err(format!("session tag `{}` was specified more than once", key).into());
+        }
+        tags.push((key, tag_value));
+    }
+    if tags.len() > MAX_TAGS {
+        return Err(format!(
+            "at most {} session tags may be specified but {} were",
+            MAX_TAGS,
+            tags.len()
+        )
+        .into());
+    }
+    Ok(tags)
+}
+
+/// Parse a comma-delimited list of managed policy ARNs
+///
+/// STS accepts at most 10 managed session policies.
+pub(super) fn parse_policy_arns(value: &str) -> Result<Vec<&str>, Cow<'static, str>> {
+    const MAX_POLICY_ARNS: usize = 10;
+    let policy_arns: Vec<&str> = value
+        .split(',')
+        .map(str::trim)
+        .filter(|arn| !arn.is_empty())
+        .collect();
+    if let Some(invalid) = policy_arns.iter().find(|arn| !arn.starts_with("arn:")) {
+        return Err(format!("`{}` is not a valid policy ARN", invalid).into());
+    }
+    if policy_arns.len() > MAX_POLICY_ARNS {
+        return Err(format!(
+            "at most {} policy ARNs may be specified but {} were",
+            MAX_POLICY_ARNS,
+            policy_arns.len()
+        )
+        .into());
+    }
+    Ok(policy_arns)
 }
 
 fn sso_from_profile(profile: &Profile) -> Option<Result<BaseProvider<'_>, ProfileFileError>> {
@@ -500,6 +668,14 @@ mod tests {
                 role_arn: role.role_arn.into(),
                 external_id: role.external_id.map(ToString::to_string),
                 role_session_name: role.session_name.map(ToString::to_string),
+                duration_seconds: role.session_length.map(|dur| dur.as_secs()),
+                source_identity: role.source_identity.map(ToString::to_string),
+                session_tags: role
+                    .tags
+                    .into_iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+                policy_arns: role.policy_arns.into_iter().map(Into::into).collect(),
             })
         }
         output
@@ -517,6 +693,12 @@ mod tests {
             role_arn: String,
             external_id: Option<String>,
             role_session_name: Option<String>,
+            duration_seconds: Option<u64>,
+            source_identity: Option<String>,
+            #[serde(default)]
+            session_tags: Vec<(String, String)>,
+            #[serde(default)]
+            policy_arns: Vec<String>,
         },
         AccessKey {
             access_key_id: String,
@@ -95,13 +95,20 @@ impl ProfileFileRegionProvider {
     }
 
     async fn region(&self) -> Option<Region> {
+        self.region_with_profile()
+            .await
+            .map(|(region, _profile)| region)
+    }
+
+    /// Load the region, along with the name of the profile it was found in
+    pub(crate) async fn region_with_profile(&self) -> Option<(Region, &str)> {
         let profile_set = self.provider_config.profile().await?;
 
         resolve_profile_chain_for_region(profile_set)
     }
 }
 
-fn resolve_profile_chain_for_region(profile_set: &'_ ProfileSet) -> Option<Region> {
+fn resolve_profile_chain_for_region(profile_set: &'_ ProfileSet) -> Option<(Region, &'_ str)> {
     if profile_set.is_empty() {
         return None;
     }
@@ -129,7 +136,7 @@ fn resolve_profile_chain_for_region(profile_set: &'_ ProfileSet) -> Option<Regio
         match (selected_profile_region, source_profile) {
             // Profile had a region specified, return it :D
             (Some(region), _) => {
-                return Some(region);
+                return Some((region, selected_profile));
             }
             // No region specified, source_profile is self-referential so we return to avoid infinite loop
             (None, Some(source_profile)) if source_profile == selected_profile => {
@@ -0,0 +1,271 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Provenance of resolved configuration values
+//!
+//! [`ConfigLoader::load_with_report`](crate::ConfigLoader::load_with_report) returns a
+//! [`ConfigReport`] alongside the [`SdkConfig`](aws_types::SdkConfig) it loads. The report lists the
+//! resolved value of each setting and where that value came from. Its `Display` output is meant to
+//! be shared when debugging configuration issues:
+//!
+//! ```text
+//! region                    = us-west-2 (profile `dev`, key: `region`)
+//! credentials_provider_type = DefaultCredentialsChain (SDK default)
+//! defaults_mode             = in-region (environment variable `AWS_DEFAULTS_MODE`)
+//! max_attempts              = 3 (SDK default)
+//! retry_mode                = standard (defaults mode `in-region`)
+//! connect_timeout           = 1.1s (defaults mode `in-region`)
+//! endpoint_url              = not set
+//! ```
+//!
+//! Credentials are loaded lazily, so the report only says which kind of credentials provider was
+//! configured. Which provider of the default chain supplies credentials is only known once they
+//! are loaded; when none does, the [`ChainNotLoaded`](crate::meta::credentials::ChainNotLoaded)
+//! error lists why each provider was skipped.
+
+use crate::defaults_mode::DefaultsMode;
+use std::borrow::Cow;
+use std::fmt;
+
+/// Where a resolved configuration value came from
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValueSource {
+    /// The value was read from an environment variable
+    #[non_exhaustive]
+    Environment {
+        /// Name of the environment variable
+        variable: Cow<'static, str>,
+    },
+    /// The value was read from the selected AWS profile
+    #[non_exhaustive]
+    Profile {
+        /// Name of the profile
+        profile: String,
+        /// Key within the profile
+        key: Cow<'static, str>,
+    },
+    /// The value was loaded by a provider, such as IMDS
+    #[non_exhaustive]
+    Provider {
+        /// Name of the provider
+        name: Cow<'static, str>,
+    },
+    /// The value is a default of the given [`DefaultsMode`]
+    DefaultsMode(DefaultsMode),
+    /// The value was set in code, on the [`ConfigLoader`](crate::ConfigLoader)
+    Programmatic,
+    /// The value is a built-in default of the SDK
+    Default,
+}
+
+impl ValueSource {
+    pub(crate) fn environment(variable: impl Into<Cow<'static, str>>) -> Self {
+        ValueSource::Environment {
+            variable: variable.into(),
+        }
+    }
+
+    pub(crate) fn profile(profile: impl Into<String>, key: impl Into<Cow<'static, str>>) -> Self {
+        ValueSource::Profile {
+            profile: profile.into(),
+            key: key.into(),
+        }
+    }
+
+    pub(crate) fn provider(name: impl Into<Cow<'static, str>>) -> Self {
+        ValueSource::Provider { name: name.into() }
+    }
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueSource::Environment { variable } => {
+                write!(f, "environment variable `{}`", variable)
+            }
+            ValueSource::Profile { profile, key } => {
+                write!(f, "profile `{}`, key: `{}`", profile, key)
+            }
+            ValueSource::Provider { name } => write!(f, "provider `{}`", name),
+            ValueSource::DefaultsMode(mode) => write!(f, "defaults mode `{}`", mode),
+            ValueSource::Programmatic => write!(f, "set in code"),
+            ValueSource::Default => write!(f, "SDK default"),
+        }
+    }
+}
+
+/// The [`ValueSource`] of each field of a resolved configuration
+///
+/// Fields that were not set by any source are not recorded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Provenance {
+    fields: Vec<(&'static str, ValueSource)>,
+}
+
+impl Provenance {
+    /// Where the value of `field` came from
+    pub fn source(&self, field: &str) -> Option<&ValueSource> {
+        self.fields
+            .iter()
+            .find(|(name, _)| *name == field)
+            .map(|(_, source)| source)
+    }
+
+    /// Iterate over every recorded field and its source, in resolution order
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, &ValueSource)> {
+        self.fields.iter().map(|(name, source)| (*name, source))
+    }
+
+    /// Record the source of `field`, replacing any previously recorded source
+    pub(crate) fn record(&mut self, field: &'static str, source: ValueSource) {
+        match self.fields.iter_mut().find(|(name, _)| *name == field) {
+            Some(entry) => entry.1 = source,
+            None => self.fields.push((field, source)),
+        }
+    }
+}
+
+/// A resolved configuration setting, as it appears in a [`ConfigReport`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReportEntry {
+    field: &'static str,
+    value: Option<String>,
+    source: Option<ValueSource>,
+}
+
+impl ReportEntry {
+    /// Name of the setting, e.g. `region`
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+
+    /// The resolved value, formatted for display, or `None` if the setting is not set
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Where the value came from, or `None` if the setting is not set
+    pub fn source(&self) -> Option<&ValueSource> {
+        self.source.as_ref()
+    }
+}
+
+/// Report of where each value of a loaded [`SdkConfig`](aws_types::SdkConfig) came from
+///
+/// Created by [`ConfigLoader::load_with_report`](crate::ConfigLoader::load_with_report). The `Display`
+/// implementation prints one setting per line and never includes secrets: credentials are
+/// represented by the name of their provider.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigReport {
+    entries: Vec<ReportEntry>,
+}
+
+impl ConfigReport {
+    /// The entry for `field`, if it is part of the report
+    pub fn get(&self, field: &str) -> Option<&ReportEntry> {
+        self.entries.iter().find(|entry| entry.field == field)
+    }
+
+    /// Iterate over every entry, in resolution order
+    pub fn entries(&self) -> impl Iterator<Item = &ReportEntry> {
+        self.entries.iter()
+    }
+
+    /// Record a setting that was resolved to `value`
+    pub(crate) fn set(
+        &mut self,
+        field: &'static str,
+        value: impl fmt::Display,
+        source: ValueSource,
+    ) {
+        self.push(ReportEntry {
+            field,
+            value: Some(value.to_string()),
+            source: Some(source),
+        });
+    }
+
+    /// Record a setting that is either resolved or not set at all
+    pub(crate) fn set_option(
+        &mut self,
+        field: &'static str,
+        resolved: Option<(impl fmt::Display, ValueSource)>,
+    ) {
+        match resolved {
+            Some((value, source)) => self.set(field, value, source),
+            None => self.push(ReportEntry {
+                field,
+                value: None,
+                source: None,
+            }),
+        }
+    }
+
+    fn push(&mut self, entry: ReportEntry) {
+        match self.entries.iter_mut().find(|e| e.field == entry.field) {
+            Some(existing) => *existing = entry,
+            None => self.entries.push(entry),
+        }
+    }
+}
+
+impl fmt::Display for ConfigReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .entries
+            .iter()
+            .map(|entry| entry.field.len())
+            .max()
+            .unwrap_or_default();
+        for entry in &self.entries {
+            write!(f, "{:width$} = ", entry.field, width = width)?;
+            match (&entry.value, &entry.source) {
+                (Some(value), Some(source)) => writeln!(f, "{} ({})", value, source)?,
+                _ => writeln!(f, "not set")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{ConfigReport, ValueSource};
+    use crate::defaults_mode::DefaultsMode;
+    use aws_types::region::Region;
+
+    #[test]
+    fn report_display() {
+        let mut report = ConfigReport::default();
+        report.set_option(
+            "region",
+            Some((
+                Region::new("us-west-2"),
+                ValueSource::profile("dev", "region"),
+            )),
+        );
+        report.set(
+            "defaults_mode",
+            DefaultsMode::InRegion,
+            ValueSource::environment("AWS_DEFAULTS_MODE"),
+        );
+        report.set_option("endpoint_url", None::<(String, ValueSource)>);
+        report.set("max_attempts", 3, ValueSource::Default);
+        report.set("max_attempts", 5, ValueSource::Programmatic);
+        assert_eq!(
+            report.to_string(),
+            "region        = us-west-2 (profile `dev`, key: `region`)\n\
+             defaults_mode = in-region (environment variable `AWS_DEFAULTS_MODE`)\n\
+             endpoint_url  = not set\n\
+             max_attempts  = 5 (set in code)\n"
+        );
+        assert_eq!(report.get("endpoint_url").unwrap().value(), None);
+        assert_eq!(
+            report.get("max_attempts").unwrap().source(),
+            Some(&ValueSource::Programmatic)
+        );
+    }
+}
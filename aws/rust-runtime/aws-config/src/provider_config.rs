@@ -21,7 +21,7 @@ use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 use tokio::sync::OnceCell;
 
-use crate::connector::default_connector;
+use crate::connector::default_connector_with_env;
 use crate::profile;
 
 use crate::profile::profile_file::ProfileFiles;
@@ -41,6 +41,11 @@ pub struct ProviderConfig {
     fs: Fs,
     time_source: SharedTimeSource,
     connector: HttpConnector,
+    /// Whether `connector` is the default connector, as opposed to one set by the user
+    default_connector: bool,
+    /// TLS options for the default connector
+    #[cfg(feature = "rustls")]
+    default_tls_options: Option<aws_smithy_client::tls::TlsOptions>,
     sleep: Option<SharedAsyncSleep>,
     region: Option<Region>,
     /// An AWS profile created from `ProfileFiles` and a `profile_name`
@@ -64,17 +69,15 @@ impl Debug for ProviderConfig {
 
 impl Default for ProviderConfig {
     fn default() -> Self {
-        let connector = HttpConnector::ConnectorFn(Arc::new(
-            |settings: &ConnectorSettings, sleep: Option<SharedAsyncSleep>| {
-                default_connector(settings, sleep)
-            },
-        ));
-
+        let env = Env::default();
         Self {
-            env: Env::default(),
+            connector: default_http_connector(&env),
+            env,
             fs: Fs::default(),
             time_source: SharedTimeSource::default(),
-            connector,
+            default_connector: true,
+            #[cfg(feature = "rustls")]
+            default_tls_options: None,
             sleep: default_async_sleep(),
             region: None,
             parsed_profile: Default::default(),
@@ -84,6 +87,16 @@ impl Default for ProviderConfig {
     }
 }
 
+/// The default connector, which sends requests through the proxy configured in `env`, if any
+fn default_http_connector(env: &Env) -> HttpConnector {
+    let env = env.clone();
+    HttpConnector::ConnectorFn(Arc::new(
+        move |settings: &ConnectorSettings, sleep: Option<SharedAsyncSleep>| {
+            default_connector_with_env(settings, sleep, &env)
+        },
+    ))
+}
+
 #[cfg(test)]
 impl ProviderConfig {
     /// ProviderConfig with all configuration removed
@@ -103,6 +116,9 @@ impl ProviderConfig {
             fs,
             time_source: SharedTimeSource::new(StaticTimeSource::new(UNIX_EPOCH)),
             connector: HttpConnector::Prebuilt(None),
+            default_connector: false,
+            #[cfg(feature = "rustls")]
+            default_tls_options: None,
             sleep: None,
             region: None,
             profile_name_override: None,
@@ -143,6 +159,9 @@ impl ProviderConfig {
             fs: Fs::default(),
             time_source: SharedTimeSource::default(),
             connector: HttpConnector::Prebuilt(None),
+            default_connector: false,
+            #[cfg(feature = "rustls")]
+            default_tls_options: None,
             sleep: None,
             region: None,
             parsed_profile: Default::default(),
@@ -289,6 +308,7 @@ impl ProviderConfig {
             env,
             ..self
         }
+        .with_default_connector()
     }
 
     #[doc(hidden)]
@@ -309,6 +329,7 @@ impl ProviderConfig {
     pub fn with_http_connector(self, connector: DynConnector) -> Self {
         ProviderConfig {
             connector: HttpConnector::Prebuilt(Some(connector)),
+            default_connector: false,
             ..self
         }
     }
@@ -343,6 +364,53 @@ impl ProviderConfig {
         };
         ProviderConfig {
             connector: HttpConnector::ConnectorFn(Arc::new(connector_fn)),
+            default_connector: false,
+            ..self
+        }
+    }
+
+    /// Use `tls_options` for the default connector
+    ///
+    /// Connectors that were set with [`ProviderConfig::with_http_connector`] or
+    /// [`ProviderConfig::with_tcp_connector`] are left unchanged.
+    #[cfg(feature = "rustls")]
+    pub(crate) fn with_default_tls_options(
+        self,
+        tls_options: aws_smithy_client::tls::TlsOptions,
+    ) -> Self {
+        ProviderConfig {
+            default_tls_options: Some(tls_options),
+            ..self
+        }
+        .with_default_connector()
+    }
+
+    /// Rebuild the default connector, if it's in use, from the current `env` and TLS options
+    ///
+    /// The default connector loads its proxy from `env`.
+    fn with_default_connector(self) -> Self {
+        if !self.default_connector {
+            return self;
+        }
+        #[cfg(feature = "rustls")]
+        if let Some(tls_options) = self.default_tls_options.clone() {
+            let env = self.env.clone();
+            let connector_fn =
+                move |settings: &ConnectorSettings, sleep: Option<SharedAsyncSleep>| {
+                    Some(crate::connector::default_connector_with_tls_options(
+                        settings,
+                        sleep,
+                        &tls_options,
+                        &env,
+                    ))
+                };
+            return ProviderConfig {
+                connector: HttpConnector::ConnectorFn(Arc::new(connector_fn)),
+                ..self
+            };
+        }
+        ProviderConfig {
+            connector: default_http_connector(&self.env),
             ..self
         }
     }
@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::provenance::ValueSource;
 use crate::provider_config::ProviderConfig;
 use std::borrow::Cow;
 use std::error::Error;
@@ -14,6 +15,15 @@ pub(crate) enum PropertySource<'a> {
     Profile { name: &'a str, key: &'a str },
 }
 
+impl From<PropertySource<'_>> for ValueSource {
+    fn from(source: PropertySource<'_>) -> Self {
+        match source {
+            PropertySource::Environment { name } => ValueSource::environment(name.to_string()),
+            PropertySource::Profile { name, key } => ValueSource::profile(name, key.to_string()),
+        }
+    }
+}
+
 impl Display for PropertySource<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -72,19 +82,20 @@ impl StandardProperty {
         self
     }
 
-    /// Load the value from `provider_config`, validating with `validator`
-    pub(crate) async fn validate<T, E: Error + Send + Sync + 'static>(
+    /// Load the value from `provider_config`, validating with `validator`, along with where it came from
+    pub(crate) async fn validate_with_source<T, E: Error + Send + Sync + 'static>(
         self,
         provider_config: &ProviderConfig,
         validator: impl Fn(&str) -> Result<T, E>,
-    ) -> Result<Option<T>, PropertyResolutionError<E>> {
+    ) -> Result<Option<(T, ValueSource)>, PropertyResolutionError<E>> {
         let value = self.load(provider_config).await;
         value
-            .map(|(v, ctx)| {
-                validator(v.as_ref()).map_err(|err| PropertyResolutionError {
+            .map(|(v, ctx)| match validator(v.as_ref()) {
+                Ok(value) => Ok((value, ctx.into())),
+                Err(err) => Err(PropertyResolutionError {
                     property_source: format!("{}", ctx),
                     err,
-                })
+                }),
             })
             .transpose()
     }
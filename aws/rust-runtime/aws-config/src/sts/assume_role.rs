@@ -11,7 +11,7 @@ use aws_credential_types::cache::CredentialsCache;
 use aws_credential_types::provider::{self, error::CredentialsError, future, ProvideCredentials};
 use aws_sdk_sts::operation::assume_role::builders::AssumeRoleFluentBuilder;
 use aws_sdk_sts::operation::assume_role::AssumeRoleError;
-use aws_sdk_sts::types::PolicyDescriptorType;
+use aws_sdk_sts::types::{PolicyDescriptorType, Tag};
 use aws_sdk_sts::Client as StsClient;
 use aws_smithy_client::erase::DynConnector;
 use aws_smithy_http::result::SdkError;
@@ -77,6 +77,8 @@ pub struct AssumeRoleProviderBuilder {
     session_length: Option<Duration>,
     policy: Option<String>,
     policy_arns: Option<Vec<PolicyDescriptorType>>,
+    source_identity: Option<String>,
+    tags: Option<Vec<Tag>>,
     credentials_cache: Option<CredentialsCache>,
 }
 
@@ -98,6 +100,8 @@ impl AssumeRoleProviderBuilder {
             conf: None,
             policy: None,
             policy_arns: None,
+            source_identity: None,
+            tags: None,
             credentials_cache: None,
         }
     }
@@ -143,6 +147,27 @@ impl AssumeRoleProviderBuilder {
         self
     }
 
+    /// Set the source identity specified by the principal that is calling `AssumeRole`.
+    ///
+    /// Once set, the source identity is recorded in CloudTrail and persists across chained role
+    /// sessions. This parameter is optional.
+    /// For more information, see
+    /// [source_identity](aws_sdk_sts::operation::assume_role::builders::AssumeRoleInputBuilder::source_identity)
+    pub fn source_identity(mut self, source_identity: impl Into<String>) -> Self {
+        self.source_identity = Some(source_identity.into());
+        self
+    }
+
+    /// Set the session tags to pass to the role session.
+    ///
+    /// This parameter is optional.
+    /// For more information, see
+    /// [tags](aws_sdk_sts::operation::assume_role::builders::AssumeRoleInputBuilder::tags)
+    pub fn tags(mut self, tags: Vec<Tag>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
     /// Set the expiration time of the role session.
     ///
     /// When unset, this value defaults to 1 hour.
@@ -228,6 +253,8 @@ impl AssumeRoleProviderBuilder {
             .set_role_session_name(Some(session_name))
             .set_policy(self.policy)
             .set_policy_arns(self.policy_arns)
+            .set_source_identity(self.source_identity)
+            .set_tags(self.tags)
             .set_duration_seconds(self.session_length.map(|dur| dur.as_secs() as i32));
 
         AssumeRoleProvider {
@@ -290,6 +317,7 @@ mod test {
     use aws_credential_types::provider::ProvideCredentials;
     use aws_credential_types::time_source::{TestingTimeSource, TimeSource};
     use aws_credential_types::Credentials;
+    use aws_sdk_sts::types::Tag;
     use aws_smithy_async::rt::sleep::TokioSleep;
     use aws_smithy_client::erase::DynConnector;
     use aws_smithy_client::test_connection::{capture_request, TestConnection};
@@ -319,6 +347,32 @@ mod test {
         assert!(str_body.contains("1234567"), "{}", str_body);
     }
 
+    #[tokio::test]
+    async fn configures_source_identity_and_tags() {
+        let (server, request) = capture_request(None);
+        let provider_conf = ProviderConfig::empty()
+            .with_sleep(TokioSleep::new())
+            .with_http_connector(DynConnector::new(server));
+        let provider = AssumeRoleProvider::builder("myrole")
+            .configure(&provider_conf)
+            .region(Region::new("us-east-1"))
+            .source_identity("helen")
+            .tags(vec![Tag::builder().key("team").value("storage").build()])
+            .build(provide_credentials_fn(|| async {
+                Ok(Credentials::for_tests())
+            }));
+        let _ = provider.provide_credentials().await;
+        let req = request.expect_request();
+        let str_body = std::str::from_utf8(req.body().bytes().unwrap()).unwrap();
+        assert!(str_body.contains("SourceIdentity=helen"), "{}", str_body);
+        assert!(str_body.contains("Tags.member.1.Key=team"), "{}", str_body);
+        assert!(
+            str_body.contains("Tags.member.1.Value=storage"),
+            "{}",
+            str_body
+        );
+    }
+
     #[tokio::test]
     async fn provider_does_not_cache_credentials_by_default() {
         let conn = TestConnection::new(vec![
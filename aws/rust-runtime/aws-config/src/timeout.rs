@@ -9,3 +9,56 @@
 pub use aws_smithy_types::timeout::OperationTimeoutConfig;
 pub use aws_smithy_types::timeout::TimeoutConfig;
 pub use aws_smithy_types::timeout::TimeoutConfigBuilder;
+
+/// Errors for timeout configuration
+pub mod error {
+    use std::fmt;
+    use std::num::ParseFloatError;
+
+    #[derive(Debug)]
+    pub(crate) enum TimeoutConfigErrorKind {
+        /// The timeout couldn't be parsed as a number of seconds.
+        FailedToParseTimeout {
+            /// Cause of the error.
+            source: ParseFloatError,
+        },
+        /// The timeout was negative, infinite, or not a number.
+        InvalidTimeout,
+    }
+
+    /// Failure to parse timeout config from profile file or environment variable.
+    #[derive(Debug)]
+    pub struct TimeoutConfigError {
+        pub(crate) kind: TimeoutConfigErrorKind,
+    }
+
+    impl fmt::Display for TimeoutConfigError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            use TimeoutConfigErrorKind::*;
+            match &self.kind {
+                FailedToParseTimeout { .. } => {
+                    write!(f, "failed to parse timeout as a number of seconds")
+                }
+                InvalidTimeout => {
+                    write!(f, "invalid configuration: timeouts must be a finite, non-negative number of seconds")
+                }
+            }
+        }
+    }
+
+    impl std::error::Error for TimeoutConfigError {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            use TimeoutConfigErrorKind::*;
+            match &self.kind {
+                FailedToParseTimeout { source } => Some(source),
+                InvalidTimeout => None,
+            }
+        }
+    }
+
+    impl From<TimeoutConfigErrorKind> for TimeoutConfigError {
+        fn from(kind: TimeoutConfigErrorKind) -> Self {
+            Self { kind }
+        }
+    }
+}
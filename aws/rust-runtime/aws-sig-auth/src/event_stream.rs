@@ -8,9 +8,13 @@
 
 use crate::middleware::Signature;
 use aws_credential_types::Credentials;
-use aws_sigv4::event_stream::{sign_empty_message, sign_message};
+use aws_sigv4::event_stream::{
+    sign_empty_message, sign_message, verify_message, VerificationParams,
+};
 use aws_sigv4::SigningParams;
-use aws_smithy_eventstream::frame::{Message, SignMessage, SignMessageError};
+use aws_smithy_eventstream::frame::{
+    Message, SignMessage, SignMessageError, VerifyMessage, VerifyMessageError,
+};
 use aws_smithy_http::property_bag::{PropertyBag, SharedPropertyBag};
 use aws_types::region::SigningRegion;
 use aws_types::SigningService;
@@ -76,11 +80,59 @@ impl SignMessage for SigV4MessageSigner {
     }
 }
 
+/// Event Stream SigV4 signature verification implementation.
+///
+/// Verifies the `:chunk-signature` chain of the messages a client sent, starting from the
+/// signature of the initial HTTP request that opened the stream.
+#[derive(Debug)]
+pub struct SigV4MessageVerifier {
+    last_signature: String,
+    credentials: Credentials,
+    signing_region: SigningRegion,
+    signing_service: SigningService,
+}
+
+impl SigV4MessageVerifier {
+    /// Creates a verifier for the messages of a stream whose initial request was signed with
+    /// `seed_signature`, using the `credentials` the request was signed with.
+    pub fn new(
+        seed_signature: String,
+        credentials: Credentials,
+        signing_region: SigningRegion,
+        signing_service: SigningService,
+    ) -> Self {
+        Self {
+            last_signature: seed_signature,
+            credentials,
+            signing_region,
+            signing_service,
+        }
+    }
+}
+
+impl VerifyMessage for SigV4MessageVerifier {
+    fn verify(&mut self, message: Message) -> Result<Option<Message>, VerifyMessageError> {
+        let params = VerificationParams::new(
+            self.credentials.secret_access_key(),
+            self.signing_region.as_ref(),
+            self.signing_service.as_ref(),
+        );
+        let (message, signature) =
+            verify_message(&message, &self.last_signature, &params)?.into_parts();
+        self.last_signature = signature;
+        Ok(message)
+    }
+
+    fn requires_empty_end_message(&self) -> bool {
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::event_stream::SigV4MessageSigner;
+    use crate::event_stream::{SigV4MessageSigner, SigV4MessageVerifier};
     use aws_credential_types::Credentials;
-    use aws_smithy_eventstream::frame::{HeaderValue, Message, SignMessage};
+    use aws_smithy_eventstream::frame::{HeaderValue, Message, SignMessage, VerifyMessage};
     use aws_types::region::Region;
     use aws_types::region::SigningRegion;
     use aws_types::SigningService;
@@ -90,6 +142,38 @@ mod tests {
         value
     }
 
+    #[test]
+    fn verify_signed_messages() {
+        let region = SigningRegion::from(Region::new("us-east-1"));
+        let service = SigningService::from_static("transcribe");
+        let mut signer = SigV4MessageSigner::new(
+            "initial-signature".into(),
+            Credentials::for_tests(),
+            region.clone(),
+            service.clone(),
+            Some(UNIX_EPOCH + Duration::new(1611160427, 0)),
+        );
+        let mut verifier = check_send_sync(SigV4MessageVerifier::new(
+            "initial-signature".into(),
+            Credentials::for_tests(),
+            region,
+            service,
+        ));
+        for payload in [&b"first"[..], &b"second"[..]] {
+            let signed = signer.sign(Message::new(payload)).unwrap();
+            assert_eq!(
+                Some(Message::new(payload)),
+                verifier.verify(signed).unwrap()
+            );
+        }
+        let end = signer.sign_empty().unwrap().unwrap();
+        assert_eq!(None, verifier.verify(end.clone()).unwrap());
+        assert!(verifier.requires_empty_end_message());
+
+        // The signature of a message can't be verified twice
+        assert!(verifier.verify(end).is_err());
+    }
+
     #[test]
     fn sign_message() {
         let region = Region::new("us-east-1");
@@ -35,12 +35,45 @@
 //! let (signed, signature) =
 //!     sign_message(&message_to_sign, &last_signature, &params).into_parts();
 //! ```
+//!
+//! # Example: Verifying a received event stream message
+//!
+//! ```rust
+//! use aws_sigv4::event_stream::{verify_message, VerificationParams};
+//! # use aws_sigv4::event_stream::{sign_message, SigningParams};
+//! # use aws_smithy_eventstream::frame::Message;
+//! # use std::time::SystemTime;
+//! # let seed_signature = "example298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+//! # let params = SigningParams::builder()
+//! #     .access_key("example access key")
+//! #     .secret_key("example secret key")
+//! #     .region("us-east-1")
+//! #     .service_name("exampleservice")
+//! #     .time(SystemTime::now())
+//! #     .settings(())
+//! #     .build()
+//! #     .unwrap();
+//! # let (signed, _) = sign_message(&Message::new(&b"example"[..]), seed_signature, &params).into_parts();
+//!
+//! // The secret key of the access key that signed the initial HTTP request
+//! let params = VerificationParams::new("example secret key", "us-east-1", "exampleservice");
+//!
+//! // Verify the first message against the signature of the initial HTTP request, and every
+//! // following message against the signature of the previous one.
+//! let (message, signature) = verify_message(&signed, seed_signature, &params)
+//!     .expect("signature is valid")
+//!     .into_parts();
+//! assert_eq!(&b"example"[..], message.unwrap().payload());
+//! ```
 
 use crate::date_time::{format_date, format_date_time, truncate_subsecs};
 use crate::sign::{calculate_signature, generate_signing_key, sha256_hex_string};
 use crate::SigningOutput;
 use aws_smithy_eventstream::frame::{write_headers_to, Header, HeaderValue, Message};
 use bytes::Bytes;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
 use std::io::Write;
 use std::time::SystemTime;
 
@@ -52,7 +85,8 @@ fn calculate_string_to_sign(
     message_payload: &[u8],
     last_signature: &str,
     time: SystemTime,
-    params: &SigningParams<'_>,
+    region: &str,
+    service_name: &str,
 ) -> Vec<u8> {
     // Event Stream string to sign format is documented here:
     // https://docs.aws.amazon.com/transcribe/latest/dg/how-streaming.html
@@ -62,12 +96,7 @@ fn calculate_string_to_sign(
     let mut sts: Vec<u8> = Vec::new();
     writeln!(sts, "AWS4-HMAC-SHA256-PAYLOAD").unwrap();
     writeln!(sts, "{}", date_time_str).unwrap();
-    writeln!(
-        sts,
-        "{}/{}/{}/aws4_request",
-        date_str, params.region, params.service_name
-    )
-    .unwrap();
+    writeln!(sts, "{}/{}/{}/aws4_request", date_str, region, service_name).unwrap();
     writeln!(sts, "{}", last_signature).unwrap();
 
     let date_header = Header::new(":date", HeaderValue::Timestamp(time.into()));
@@ -123,7 +152,8 @@ fn sign_payload<'a>(
         message_payload.as_ref().map(|v| &v[..]).unwrap_or(&[]),
         last_signature,
         time,
-        params,
+        params.region,
+        params.service_name,
     );
     let signature = calculate_signature(signing_key, &string_to_sign);
     tracing::trace!(canonical_request = ?message_payload, string_to_sign = ?string_to_sign, "calculated signing parameters");
@@ -140,6 +170,170 @@ fn sign_payload<'a>(
     )
 }
 
+/// Event stream signature verification parameters
+///
+/// The `secret_key` must belong to the access key that signed the initial request of the stream.
+#[non_exhaustive]
+pub struct VerificationParams<'a> {
+    secret_key: &'a str,
+    region: &'a str,
+    service_name: &'a str,
+}
+
+impl<'a> VerificationParams<'a> {
+    /// Creates verification parameters
+    pub fn new(secret_key: &'a str, region: &'a str, service_name: &'a str) -> Self {
+        Self {
+            secret_key,
+            region,
+            service_name,
+        }
+    }
+}
+
+impl<'a> fmt::Debug for VerificationParams<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerificationParams")
+            .field("secret_key", &"** redacted **")
+            .field("region", &self.region)
+            .field("service_name", &self.service_name)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+enum VerificationErrorKind {
+    MissingHeader(&'static str),
+    InvalidHeader(&'static str),
+    InvalidPayload(aws_smithy_eventstream::error::Error),
+    SignatureMismatch,
+}
+
+/// Error returned when an Event Stream message fails signature verification
+#[derive(Debug)]
+pub struct VerificationError {
+    kind: VerificationErrorKind,
+}
+
+impl VerificationError {
+    /// Returns true if the message was well formed, but its signature was incorrect
+    pub fn is_signature_mismatch(&self) -> bool {
+        matches!(self.kind, VerificationErrorKind::SignatureMismatch)
+    }
+}
+
+impl From<VerificationErrorKind> for VerificationError {
+    fn from(kind: VerificationErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use VerificationErrorKind::*;
+        match &self.kind {
+            MissingHeader(name) => write!(f, "signed message is missing the `{}` header", name),
+            InvalidHeader(name) => write!(f, "signed message has an invalid `{}` header", name),
+            InvalidPayload(_) => write!(f, "signed message payload is not a valid message"),
+            SignatureMismatch => write!(f, "message signature does not match"),
+        }
+    }
+}
+
+impl Error for VerificationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            VerificationErrorKind::InvalidPayload(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// A message that passed signature verification
+#[derive(Debug)]
+pub struct VerifiedMessage {
+    message: Option<Message>,
+    signature: String,
+}
+
+impl VerifiedMessage {
+    /// The message wrapped by the signed message, or `None` for the empty signed message that
+    /// terminates the stream
+    pub fn message(&self) -> Option<&Message> {
+        self.message.as_ref()
+    }
+
+    /// The hex encoded signature of the signed message, used to verify the next message
+    pub fn signature(&self) -> &str {
+        &self.signature
+    }
+
+    /// Decomposes this into the wrapped message and the signature
+    pub fn into_parts(self) -> (Option<Message>, String) {
+        (self.message, self.signature)
+    }
+}
+
+/// Verifies the signature of an Event Stream message signed with [`sign_message`] or
+/// [`sign_empty_message`].
+///
+/// `last_signature` is the signature of the previous message, or the signature of the initial
+/// HTTP request for the first message. The signature is calculated with the time of the message's
+/// `:date` header.
+pub fn verify_message(
+    signed: &Message,
+    last_signature: &str,
+    params: &VerificationParams<'_>,
+) -> Result<VerifiedMessage, VerificationError> {
+    let header = |name: &'static str| {
+        signed
+            .headers()
+            .iter()
+            .find(|header| header.name().as_str() == name)
+            .map(|header| header.value())
+            .ok_or(VerificationErrorKind::MissingHeader(name))
+    };
+    let expected = header(":chunk-signature")?
+        .as_byte_array()
+        .map_err(|_| VerificationErrorKind::InvalidHeader(":chunk-signature"))?;
+    let time = header(":date")?
+        .as_timestamp()
+        .ok()
+        .and_then(|date| SystemTime::try_from(date).ok())
+        .ok_or(VerificationErrorKind::InvalidHeader(":date"))?;
+
+    let signing_key =
+        generate_signing_key(params.secret_key, time, params.region, params.service_name);
+    let string_to_sign = calculate_string_to_sign(
+        signed.payload(),
+        last_signature,
+        time,
+        params.region,
+        params.service_name,
+    );
+    let signature = calculate_signature(signing_key, &string_to_sign);
+    let calculated = hex::decode(&signature).expect("signatures are hex encoded");
+    if !constant_time_eq(&calculated, expected) {
+        tracing::debug!(string_to_sign = ?string_to_sign, "event stream message signature does not match");
+        return Err(VerificationErrorKind::SignatureMismatch.into());
+    }
+
+    let message = if signed.payload().is_empty() {
+        None
+    } else {
+        Some(
+            Message::read_from(&signed.payload()[..])
+                .map_err(VerificationErrorKind::InvalidPayload)?,
+        )
+    };
+    Ok(VerifiedMessage { message, signature })
+}
+
+/// Compares two byte slices without short-circuiting on the first difference
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,7 +374,8 @@ mod tests {
                 &message_payload,
                 &last_signature,
                 params.time,
-                &params
+                params.region,
+                params.service_name
             ))
             .unwrap()
         );
@@ -220,4 +415,111 @@ mod tests {
             panic!("expected timestamp for :date header");
         }
     }
+
+    fn verification_params() -> VerificationParams<'static> {
+        VerificationParams::new("fake secret key", "us-east-1", "testservice")
+    }
+
+    fn signing_params(secs: u64) -> SigningParams<'static> {
+        SigningParams {
+            access_key: "fake access key",
+            secret_key: "fake secret key",
+            security_token: None,
+            region: "us-east-1",
+            service_name: "testservice",
+            time: (UNIX_EPOCH + Duration::new(secs, 1234u32)),
+            settings: (),
+        }
+    }
+
+    #[test]
+    fn verify_signature_chain() {
+        let seed = sha256_hex_string(b"initial request");
+        let messages = [
+            Message::new(&b"first"[..]),
+            Message::new(&b"second"[..]).add_header(Header::new(
+                "some-header",
+                HeaderValue::String("value".into()),
+            )),
+        ];
+
+        // Sign the messages at different times, like a client would
+        let mut last_signature = seed.clone();
+        let mut signed = Vec::new();
+        for (i, message) in messages.iter().enumerate() {
+            let (message, signature) = sign_message(
+                message,
+                &last_signature,
+                &signing_params(123_456_789 + i as u64),
+            )
+            .into_parts();
+            signed.push(message);
+            last_signature = signature;
+        }
+        let (end, _) =
+            sign_empty_message(&last_signature, &signing_params(123_456_800)).into_parts();
+        signed.push(end);
+
+        let mut last_signature = seed;
+        let mut verified = Vec::new();
+        for message in &signed {
+            let (message, signature) =
+                verify_message(message, &last_signature, &verification_params())
+                    .unwrap()
+                    .into_parts();
+            verified.push(message);
+            last_signature = signature;
+        }
+        assert_eq!(
+            vec![Some(messages[0].clone()), Some(messages[1].clone()), None],
+            verified
+        );
+
+        // Messages can't be reordered or verified against the wrong seed
+        let err = verify_message(
+            &signed[1],
+            &sha256_hex_string(b"initial request"),
+            &verification_params(),
+        )
+        .unwrap_err();
+        assert!(err.is_signature_mismatch());
+        assert_eq!("message signature does not match", err.to_string());
+    }
+
+    #[test]
+    fn verify_rejects_tampered_messages() {
+        let seed = sha256_hex_string(b"initial request");
+        let (signed, _) = sign_message(
+            &Message::new(&b"payload"[..]),
+            &seed,
+            &signing_params(123_456_789),
+        )
+        .into_parts();
+
+        let wrong_key = VerificationParams::new("other secret key", "us-east-1", "testservice");
+        assert!(verify_message(&signed, &seed, &wrong_key)
+            .unwrap_err()
+            .is_signature_mismatch());
+
+        // Replace the date
+        let headers = vec![
+            signed.headers()[0].clone(),
+            Header::new(
+                ":date",
+                HeaderValue::Timestamp((UNIX_EPOCH + Duration::from_secs(123_456_790)).into()),
+            ),
+        ];
+        let tampered = Message::new_from_parts(headers, signed.payload().clone());
+        assert!(verify_message(&tampered, &seed, &verification_params())
+            .unwrap_err()
+            .is_signature_mismatch());
+
+        let unsigned = Message::new(signed.payload().clone());
+        assert_eq!(
+            "signed message is missing the `:chunk-signature` header",
+            verify_message(&unsigned, &seed, &verification_params())
+                .unwrap_err()
+                .to_string()
+        );
+    }
 }
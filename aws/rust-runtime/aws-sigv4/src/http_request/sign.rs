@@ -101,6 +101,15 @@ pub enum SignableBody<'a> {
 
     /// Set when a streaming body has checksum trailers.
     StreamingUnsignedPayloadTrailer,
+
+    /// A streaming body sent with `aws-chunked` encoding where every chunk is signed
+    ///
+    /// The signature of the request is the seed for the chunk signatures, see
+    /// [`aws_chunked`](crate::aws_chunked).
+    StreamingSignedPayload,
+
+    /// Like [`StreamingSignedPayload`](SignableBody::StreamingSignedPayload), with signed trailers
+    StreamingSignedPayloadTrailer,
 }
 
 /// Instructions for applying a signature to an HTTP request.
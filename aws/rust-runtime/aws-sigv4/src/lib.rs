@@ -28,6 +28,9 @@ pub mod event_stream;
 #[cfg(feature = "sign-http")]
 pub mod http_request;
 
+#[cfg(feature = "sign-http")]
+pub mod aws_chunked;
+
 /// Parameters to use when signing.
 #[non_exhaustive]
 pub struct SigningParams<'a, S> {
@@ -40,6 +40,17 @@ If no dual-stack endpoint is available the request MAY return an error.
 these services, this setting has no effect"
         };
 
+        (disable_request_compression) => {
+"When true, request bodies are never compressed, even for operations that support request compression."
+        };
+        (request_min_compression_size_bytes) => {
+"The minimum size, in bytes, that a request body must have to be compressed for operations that support
+request compression. Streaming request bodies are always compressed, unless the operation requires
+their length up front.
+
+Must be between 0 and 10485760 (10 MiB), and defaults to 10240."
+        };
+
         (time_source) => { "The time source use to use for this client. This only needs to be required for creating deterministic tests or platforms where `SystemTime::now()` is not supported." };
     }
 }
@@ -59,6 +70,8 @@ pub struct SdkConfig {
     http_connector: Option<HttpConnector>,
     use_fips: Option<bool>,
     use_dual_stack: Option<bool>,
+    disable_request_compression: Option<bool>,
+    request_min_compression_size_bytes: Option<u32>,
 }
 
 /// Builder for AWS Shared Configuration
@@ -80,6 +93,8 @@ pub struct Builder {
     http_connector: Option<HttpConnector>,
     use_fips: Option<bool>,
     use_dual_stack: Option<bool>,
+    disable_request_compression: Option<bool>,
+    request_min_compression_size_bytes: Option<u32>,
 }
 
 impl Builder {
@@ -501,6 +516,39 @@ impl Builder {
         self
     }
 
+    #[doc = docs_for!(disable_request_compression)]
+    pub fn disable_request_compression(mut self, disable_request_compression: bool) -> Self {
+        self.set_disable_request_compression(Some(disable_request_compression));
+        self
+    }
+
+    #[doc = docs_for!(disable_request_compression)]
+    pub fn set_disable_request_compression(
+        &mut self,
+        disable_request_compression: Option<bool>,
+    ) -> &mut Self {
+        self.disable_request_compression = disable_request_compression;
+        self
+    }
+
+    #[doc = docs_for!(request_min_compression_size_bytes)]
+    pub fn request_min_compression_size_bytes(
+        mut self,
+        request_min_compression_size_bytes: u32,
+    ) -> Self {
+        self.set_request_min_compression_size_bytes(Some(request_min_compression_size_bytes));
+        self
+    }
+
+    #[doc = docs_for!(request_min_compression_size_bytes)]
+    pub fn set_request_min_compression_size_bytes(
+        &mut self,
+        request_min_compression_size_bytes: Option<u32>,
+    ) -> &mut Self {
+        self.request_min_compression_size_bytes = request_min_compression_size_bytes;
+        self
+    }
+
     #[doc = docs_for!(time_source)]
     pub fn time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
         self.set_time_source(Some(SharedTimeSource::new(time_source)));
@@ -527,6 +575,8 @@ impl Builder {
             http_connector: self.http_connector,
             use_fips: self.use_fips,
             use_dual_stack: self.use_dual_stack,
+            disable_request_compression: self.disable_request_compression,
+            request_min_compression_size_bytes: self.request_min_compression_size_bytes,
             time_source: self.time_source,
         }
     }
@@ -594,6 +644,16 @@ impl SdkConfig {
         self.use_dual_stack
     }
 
+    /// Disable request compression
+    pub fn disable_request_compression(&self) -> Option<bool> {
+        self.disable_request_compression
+    }
+
+    /// Minimum request body size for compression
+    pub fn request_min_compression_size_bytes(&self) -> Option<u32> {
+        self.request_min_compression_size_bytes
+    }
+
     /// Config builder
     ///
     /// _Important:_ Using the `aws-config` crate to configure the SDK is preferred to invoking this
@@ -56,6 +56,7 @@ val DECORATORS: List<ClientCodegenDecorator> = listOf(
         RecursionDetectionDecorator(),
         InvocationIdDecorator(),
         RetryInformationHeaderDecorator(),
+        RequestCompressionSdkConfigDecorator(),
     ),
 
     // Service specific decorators
@@ -0,0 +1,37 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk
+
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customizations.usesRequestCompression
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
+import software.amazon.smithy.rust.codegen.core.smithy.customize.AdHocCustomization
+import software.amazon.smithy.rust.codegen.core.smithy.customize.adhocCustomization
+import software.amazon.smithy.rust.codegen.core.util.letIf
+
+/**
+ * Copies the request compression settings from `SdkConfig` into the service config for services
+ * that have operations modeled with the `@requestCompression` trait.
+ */
+class RequestCompressionSdkConfigDecorator : ClientCodegenDecorator {
+    override val name: String = "RequestCompressionSdkConfig"
+    override val order: Byte = 0
+
+    override fun extraSections(codegenContext: ClientCodegenContext): List<AdHocCustomization> =
+        listOf<AdHocCustomization>().letIf(
+            codegenContext.smithyRuntimeMode.defaultToOrchestrator && codegenContext.usesRequestCompression(),
+        ) {
+            it + adhocCustomization<SdkConfigSection.CopySdkConfigToClientConfig> { section ->
+                rust(
+                    """
+                    ${section.serviceConfigBuilder}.set_disable_request_compression(${section.sdkConfig}.disable_request_compression());
+                    ${section.serviceConfigBuilder}.set_request_min_compression_size_bytes(${section.sdkConfig}.request_min_compression_size_bytes());
+                    """,
+                )
+            }
+        }
+}
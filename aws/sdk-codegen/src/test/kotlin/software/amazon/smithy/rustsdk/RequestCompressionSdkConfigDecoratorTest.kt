@@ -0,0 +1,84 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rustsdk
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.model.Model
+import software.amazon.smithy.model.loader.ModelAssembler
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.unitTest
+
+internal class RequestCompressionSdkConfigDecoratorTest {
+    // `@requestCompression` isn't in the prelude of the Smithy version this project builds against
+    private val model = Model.assembler()
+        .putProperty(ModelAssembler.ALLOW_UNKNOWN_TRAITS, true)
+        .addUnparsedModel(
+            "test.smithy",
+            """
+            ${"$"}version: "2.0"
+            namespace com.example
+            use aws.protocols#awsJson1_0
+            use aws.api#service
+            use smithy.rules#endpointRuleSet
+
+            @service(sdkId: "Some Value")
+            @awsJson1_0
+            @endpointRuleSet({
+                "version": "1.0",
+                "rules": [{
+                              "type": "endpoint",
+                              "conditions": [{"fn": "isSet", "argv": [{"ref": "Region"}]}],
+                              "endpoint": { "url": "https://example.com" }
+                          }],
+                "parameters": {
+                    "Region": { "required": false, "type": "String", "builtIn": "AWS::Region" },
+                }
+            })
+            service HelloService {
+                operations: [SayHello],
+                version: "1"
+            }
+
+            @optionalAuth
+            @smithy.api#requestCompression(encodings: ["gzip"])
+            operation SayHello { input: TestInput }
+            structure TestInput {
+               foo: String,
+            }
+            """.trimIndent(),
+        )
+        .discoverModels()
+        .assemble()
+        .unwrap()
+
+    @Test
+    fun `copy request compression settings from SdkConfig`() {
+        awsSdkIntegrationTest(model, defaultToOrchestrator = true) { clientCodegenContext, rustCrate ->
+            val runtimeConfig = clientCodegenContext.runtimeConfig
+            rustCrate.testModule {
+                unitTest("request_compression_settings_are_copied_from_sdk_config") {
+                    rustTemplate(
+                        """
+                        let sdk_config = #{SdkConfig}::builder()
+                            .disable_request_compression(true)
+                            .request_min_compression_size_bytes(128)
+                            .build();
+                        let config = crate::config::Config::new(&sdk_config);
+                        assert!(config.disable_request_compression());
+                        assert_eq!(128, config.request_min_compression_size_bytes());
+
+                        let config = crate::config::Config::new(&#{SdkConfig}::builder().build());
+                        assert!(!config.disable_request_compression());
+                        assert_eq!(10240, config.request_min_compression_size_bytes());
+                        """,
+                        "SdkConfig" to AwsRuntimeType.awsTypes(runtimeConfig).resolve("sdk_config::SdkConfig"),
+                    )
+                }
+            }
+        }
+    }
+}
@@ -21,6 +21,7 @@ object CrateSet {
         "aws-smithy-async",
         "aws-smithy-checksums",
         "aws-smithy-client",
+        "aws-smithy-endpoint-rules",
         "aws-smithy-eventstream",
         "aws-smithy-http",
         "aws-smithy-http-auth",
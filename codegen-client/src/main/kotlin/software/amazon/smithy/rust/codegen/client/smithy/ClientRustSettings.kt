@@ -114,6 +114,8 @@ enum class SmithyRuntimeMode {
  * [includeFluentClient]: Generate a `client` module in the generated SDK (currently the AWS SDK sets this to `false`
  *   and generates its own client)
  * [addMessageToErrors]: Adds a `message` field automatically to all error shapes
+ * [endpointRuleTrace]: Generate an endpoint resolver that can record a trace of the evaluated endpoint rules. This
+ *   compiles the source of every condition and rule into the client, so it is off by default.
  */
 data class ClientCodegenConfig(
     override val formatTimeoutSeconds: Int = defaultFormatTimeoutSeconds,
@@ -125,6 +127,7 @@ data class ClientCodegenConfig(
     val eventStreamAllowList: Set<String> = defaultEventStreamAllowList,
     // TODO(SmithyRuntime): Remove this once we commit to switch to aws-smithy-runtime and aws-smithy-runtime-api
     val enableNewSmithyRuntime: SmithyRuntimeMode = defaultEnableNewSmithyRuntime,
+    val endpointRuleTrace: Boolean = defaultEndpointRuleTrace,
 ) : CoreCodegenConfig(
     formatTimeoutSeconds, debugMode,
 ) {
@@ -134,6 +137,7 @@ data class ClientCodegenConfig(
         private const val defaultAddMessageToErrors = true
         private val defaultEventStreamAllowList: Set<String> = emptySet()
         private val defaultEnableNewSmithyRuntime = SmithyRuntimeMode.Middleware
+        private const val defaultEndpointRuleTrace = false
 
         fun fromCodegenConfigAndNode(coreCodegenConfig: CoreCodegenConfig, node: Optional<ObjectNode>) =
             if (node.isPresent) {
@@ -149,6 +153,7 @@ data class ClientCodegenConfig(
                     enableNewSmithyRuntime = SmithyRuntimeMode.fromString(
                         node.get().getStringMemberOrDefault("enableNewSmithyRuntime", "middleware"),
                     ),
+                    endpointRuleTrace = node.get().getBooleanMemberOrDefault("endpointRuleTrace", defaultEndpointRuleTrace),
                 )
             } else {
                 ClientCodegenConfig(
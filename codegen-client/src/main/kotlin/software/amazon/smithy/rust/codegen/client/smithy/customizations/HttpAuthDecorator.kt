@@ -23,6 +23,7 @@ import software.amazon.smithy.rust.codegen.client.smithy.generators.config.Confi
 import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
 import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rust
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.rustlang.writable
 import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
@@ -207,7 +208,14 @@ private class HttpAuthServiceRuntimePluginCustomization(
                     registerNamedAuthScheme("BearerAuthScheme")
                 }
                 if (authSchemes.digest) {
-                    registerNamedAuthScheme("DigestAuthScheme")
+                    // The digest auth interceptor answers the challenges of the auth scheme it was created from
+                    rustTemplate("let digest_auth_scheme = #{DigestAuthScheme}::new();", *codegenScope)
+                    section.registerInterceptor(codegenContext.runtimeConfig, this) {
+                        rust("digest_auth_scheme.interceptor()")
+                    }
+                    registerAuthScheme {
+                        rust("digest_auth_scheme")
+                    }
                 }
             }
 
@@ -40,9 +40,9 @@ private class HttpConnectorConfigCustomization(
     private val codegenScope = arrayOf(
         *preludeScope,
         "Connection" to RuntimeType.smithyRuntimeApi(runtimeConfig).resolve("client::orchestrator::Connection"),
-        "ConnectorSettings" to RuntimeType.smithyClient(runtimeConfig).resolve("http_connector::ConnectorSettings"),
-        "default_connector" to RuntimeType.smithyClient(runtimeConfig).resolve("conns::default_connector"),
-        "DynConnectorAdapter" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::connectors::adapter::DynConnectorAdapter"),
+        "ConnectorSettings" to RuntimeType.smithyRuntimeApi(runtimeConfig).resolve("client::connectors::ConnectorSettings"),
+        "default_connector" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::connectors::default_connector"),
+        "DynConnectorAdapter" to RuntimeType.smithyClient(runtimeConfig).resolve("erase::DynConnectorAdapter"),
         "HttpConnector" to RuntimeType.smithyClient(runtimeConfig).resolve("http_connector::HttpConnector"),
         "Resolver" to RuntimeType.smithyRuntime(runtimeConfig).resolve("client::config_override::Resolver"),
         "SharedAsyncSleep" to RuntimeType.smithyAsync(runtimeConfig).resolve("rt::sleep::SharedAsyncSleep"),
@@ -58,26 +58,31 @@ private class HttpConnectorConfigCustomization(
                 // should always get into the condition below.
                 //
                 // Override configuration should set the connector if the override config
-                // contains a connector, sleep impl, or a timeout config since these are all
-                // incorporated into the final connector.
+                // contains a connector, sleep impl, connector settings, or a timeout config since
+                // these are all incorporated into the final connector.
                 let must_set_connector = resolver.is_initial()
                     || resolver.is_latest_set::<#{HttpConnector}>()
                     || resolver.latest_sleep_impl().is_some()
+                    || resolver.is_latest_set::<#{ConnectorSettings}>()
                     || resolver.is_latest_set::<#{TimeoutConfig}>();
                 if must_set_connector {
                     let sleep_impl = resolver.sleep_impl();
-                    let timeout_config = resolver.resolve_config::<#{TimeoutConfig}>()
+                    let connector_settings = resolver.resolve_config::<#{ConnectorSettings}>()
                         .cloned()
-                        .unwrap_or_else(#{TimeoutConfig}::disabled);
-                    let connector_settings = #{ConnectorSettings}::from_timeout_config(&timeout_config);
+                        .unwrap_or_else(|| {
+                            let timeout_config = resolver.resolve_config::<#{TimeoutConfig}>()
+                                .cloned()
+                                .unwrap_or_else(#{TimeoutConfig}::disabled);
+                            #{ConnectorSettings}::from_timeout_config(&timeout_config)
+                        });
                     let http_connector = resolver.resolve_config::<#{HttpConnector}>();
 
                     // TODO(enableNewSmithyRuntimeCleanup): Replace the tower-based DynConnector and remove DynConnectorAdapter when deleting the middleware implementation
                     let connector =
                         http_connector
-                            .and_then(|c| c.connector(&connector_settings, sleep_impl.clone()))
-                            .or_else(|| #{default_connector}(&connector_settings, sleep_impl))
-                            .map(|c| #{SharedConnector}::new(#{DynConnectorAdapter}::new(c)));
+                            .and_then(|c| c.connector(&connector_settings, sleep_impl))
+                            .map(|c| #{SharedConnector}::new(#{DynConnectorAdapter}::new(c)))
+                            .or_else(|| #{default_connector}(resolver));
 
                     resolver.runtime_components_mut().set_connector(connector);
                 }
@@ -0,0 +1,184 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import software.amazon.smithy.model.Model
+import software.amazon.smithy.model.knowledge.TopDownIndex
+import software.amazon.smithy.model.shapes.OperationShape
+import software.amazon.smithy.model.shapes.ShapeId
+import software.amazon.smithy.model.shapes.StructureShape
+import software.amazon.smithy.model.traits.RequiresLengthTrait
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.smithy.customize.ClientCodegenDecorator
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.OperationSection
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ConfigCustomization
+import software.amazon.smithy.rust.codegen.client.smithy.generators.config.ServiceConfig
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.Writable
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.rustlang.writable
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeConfig
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.util.dq
+import software.amazon.smithy.rust.codegen.core.util.findStreamingMember
+import software.amazon.smithy.rust.codegen.core.util.letIf
+import software.amazon.smithy.rust.codegen.core.util.orNull
+
+// The `@requestCompression` trait was added to the prelude after the Smithy version this project builds against,
+// so it is looked up by ID rather than by trait class.
+private val RequestCompressionTraitId = ShapeId.from("smithy.api#requestCompression")
+
+/**
+ * Returns the encodings listed by this operation's `@requestCompression` trait in order of preference,
+ * or `null` if the operation doesn't support request compression.
+ *
+ * Operations with a streaming input that has the `@requiresLength` trait are never compressed, since the
+ * length of the compressed stream isn't known until it has been sent.
+ */
+fun OperationShape.requestCompressionEncodings(model: Model): List<String>? {
+    val trait = findTrait(RequestCompressionTraitId).orNull() ?: return null
+    val requiresLength = input.orNull()
+        ?.let { model.expectShape(it, StructureShape::class.java).findStreamingMember(model) }
+        ?.getMemberTrait(model, RequiresLengthTrait::class.java)?.isPresent ?: false
+    if (requiresLength) {
+        return null
+    }
+    return trait.toNode().expectObjectNode().getArrayMember("encodings").orNull()
+        ?.elements?.map { it.expectStringNode().value }
+        ?: listOf()
+}
+
+/** Returns true if any operation of the service being generated supports request compression */
+fun ClientCodegenContext.usesRequestCompression(): Boolean =
+    TopDownIndex.of(model).getContainedOperations(serviceShape).any { it.requestCompressionEncodings(model) != null }
+
+/**
+ * Compresses request bodies for operations modeled with the `@requestCompression` trait, and adds config
+ * to disable compression or change the minimum body size that gets compressed.
+ *
+ * Both are only generated when the orchestrator is the default, since the config is stored in the config bag.
+ */
+class RequestCompressionDecorator : ClientCodegenDecorator {
+    override val name: String = "RequestCompression"
+    override val order: Byte = 0
+
+    private fun applies(codegenContext: ClientCodegenContext): Boolean =
+        codegenContext.smithyRuntimeMode.defaultToOrchestrator && codegenContext.usesRequestCompression()
+
+    override fun configCustomizations(
+        codegenContext: ClientCodegenContext,
+        baseCustomizations: List<ConfigCustomization>,
+    ): List<ConfigCustomization> =
+        baseCustomizations.letIf(applies(codegenContext)) {
+            it + RequestCompressionConfigCustomization(codegenContext)
+        }
+
+    override fun operationCustomizations(
+        codegenContext: ClientCodegenContext,
+        operation: OperationShape,
+        baseCustomizations: List<OperationCustomization>,
+    ): List<OperationCustomization> =
+        baseCustomizations.letIf(applies(codegenContext)) {
+            it + RequestCompressionOperationCustomization(codegenContext, operation)
+        }
+}
+
+// `aws-smithy-runtime` only pulls in the compression dependencies when this feature is enabled
+private fun requestCompressionInterceptors(runtimeConfig: RuntimeConfig) =
+    CargoDependency.smithyRuntime(runtimeConfig).withFeature("request-compression").toType()
+        .resolve("client::orchestrator::interceptors")
+
+private class RequestCompressionConfigCustomization(codegenContext: ClientCodegenContext) : ConfigCustomization() {
+    private val interceptors = requestCompressionInterceptors(codegenContext.runtimeConfig)
+    private val codegenScope = arrayOf(
+        *preludeScope,
+        "DisableRequestCompression" to interceptors.resolve("DisableRequestCompression"),
+        "RequestMinCompressionSizeBytes" to interceptors.resolve("RequestMinCompressionSizeBytes"),
+    )
+
+    override fun section(section: ServiceConfig): Writable =
+        when (section) {
+            is ServiceConfig.BuilderImpl -> writable {
+                rustTemplate(
+                    """
+                    /// When true, request bodies are never compressed, even for operations that support request compression.
+                    pub fn disable_request_compression(mut self, disable_request_compression: bool) -> Self {
+                        self.set_disable_request_compression(#{Some}(disable_request_compression));
+                        self
+                    }
+
+                    /// When true, request bodies are never compressed, even for operations that support request compression.
+                    pub fn set_disable_request_compression(&mut self, disable_request_compression: #{Option}<bool>) -> &mut Self {
+                        self.config.store_or_unset(disable_request_compression.map(#{DisableRequestCompression}));
+                        self
+                    }
+
+                    /// Sets the minimum size, in bytes, that a request body must have to be compressed.
+                    ///
+                    /// Streaming request bodies are always compressed, unless the operation requires their length
+                    /// up front. Must be between 0 and 10485760 (10 MiB), and defaults to 10240. Requests fail if
+                    /// it is larger than 10485760.
+                    pub fn request_min_compression_size_bytes(mut self, request_min_compression_size_bytes: u32) -> Self {
+                        self.set_request_min_compression_size_bytes(#{Some}(request_min_compression_size_bytes));
+                        self
+                    }
+
+                    /// Sets the minimum size, in bytes, that a request body must have to be compressed.
+                    ///
+                    /// Streaming request bodies are always compressed, unless the operation requires their length
+                    /// up front. Must be between 0 and 10485760 (10 MiB), and defaults to 10240. Requests fail if
+                    /// it is larger than 10485760.
+                    pub fn set_request_min_compression_size_bytes(&mut self, request_min_compression_size_bytes: #{Option}<u32>) -> &mut Self {
+                        self.config.store_or_unset(request_min_compression_size_bytes.map(#{RequestMinCompressionSizeBytes}));
+                        self
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+
+            is ServiceConfig.ConfigImpl -> writable {
+                rustTemplate(
+                    """
+                    /// Returns true if request compression is disabled.
+                    pub fn disable_request_compression(&self) -> bool {
+                        self.config.load::<#{DisableRequestCompression}>().map(|disable| disable.0).unwrap_or_default()
+                    }
+
+                    /// Returns the minimum size, in bytes, that a request body must have to be compressed.
+                    pub fn request_min_compression_size_bytes(&self) -> u32 {
+                        self.config.load::<#{RequestMinCompressionSizeBytes}>().copied().unwrap_or_default().0
+                    }
+                    """,
+                    *codegenScope,
+                )
+            }
+
+            else -> emptySection
+        }
+}
+
+private class RequestCompressionOperationCustomization(
+    codegenContext: ClientCodegenContext,
+    private val operationShape: OperationShape,
+) : OperationCustomization() {
+    private val model = codegenContext.model
+    private val runtimeConfig = codegenContext.runtimeConfig
+
+    override fun section(section: OperationSection): Writable = writable {
+        val encodings = operationShape.requestCompressionEncodings(model) ?: return@writable
+        if (section is OperationSection.AdditionalInterceptors) {
+            section.registerInterceptor(runtimeConfig, this) {
+                rustTemplate(
+                    "#{RequestCompressionInterceptor}::new(&[${encodings.joinToString { it.dq() }}])",
+                    "RequestCompressionInterceptor" to requestCompressionInterceptors(runtimeConfig)
+                        .resolve("RequestCompressionInterceptor"),
+                )
+            }
+        }
+    }
+}
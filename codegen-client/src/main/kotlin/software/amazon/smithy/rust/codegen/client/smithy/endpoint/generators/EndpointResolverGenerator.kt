@@ -25,6 +25,7 @@ import software.amazon.smithy.rust.codegen.client.smithy.endpoint.rulesgen.Owner
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.rustName
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute
 import software.amazon.smithy.rust.codegen.core.rustlang.Attribute.Companion.allow
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
 import software.amazon.smithy.rust.codegen.core.rustlang.RustWriter
 import software.amazon.smithy.rust.codegen.core.rustlang.Writable
 import software.amazon.smithy.rust.codegen.core.rustlang.comment
@@ -124,13 +125,15 @@ internal class EndpointResolverGenerator(
     stdlib: List<CustomRuntimeFunction>,
 ) {
     private val runtimeConfig = codegenContext.runtimeConfig
+    private val trace = codegenContext.settings.codegenConfig.endpointRuleTrace
     private val registry: FunctionRegistry = FunctionRegistry(stdlib)
     private val types = Types(runtimeConfig)
     private val codegenScope = arrayOf(
         "endpoint" to types.smithyHttpEndpointModule,
         "SmithyEndpoint" to types.smithyEndpoint,
         "EndpointError" to types.resolveEndpointError,
-        "DiagnosticCollector" to endpointsLib("diagnostic").toType().resolve("DiagnosticCollector"),
+        "DiagnosticCollector" to endpointsLib("diagnostic", CargoDependency.Tracing).toType()
+            .resolve("DiagnosticCollector"),
     )
 
     private val allowLintsForResolver = listOf(
@@ -174,29 +177,74 @@ internal class EndpointResolverGenerator(
                 /// The default endpoint resolver
                 ##[derive(Debug, Default)]
                 pub struct DefaultResolver {
+                    #{trace_field:W}
                     #{custom_fields:W}
                 }
 
                 impl DefaultResolver {
                     /// Create a new endpoint resolver with default settings
                     pub fn new() -> Self {
-                        Self { #{custom_fields_init:W} }
+                        Self { #{trace_field_init:W} #{custom_fields_init:W} }
                     }
+                    #{trace_setter:W}
                 }
 
                 impl #{endpoint}::ResolveEndpoint<#{Params}> for DefaultResolver {
                     fn resolve_endpoint(&self, params: &Params) -> #{endpoint}::Result {
-                        let mut diagnostic_collector = #{DiagnosticCollector}::new();
-                        #{resolver_fn}(params, &mut diagnostic_collector, #{additional_args})
-                            .map_err(|err|err.with_source(diagnostic_collector.take_last_error()))
+                        #{resolve:W}
                     }
                 }
                 """,
+                "trace_field" to writable { if (trace) rust("trace: bool,") },
+                "trace_field_init" to writable { if (trace) rust("trace: false,") },
+                "trace_setter" to writable {
+                    if (trace) {
+                        rustTemplate(
+                            """
+                            /// Record a trace of the conditions and rules that are evaluated to resolve an endpoint
+                            ///
+                            /// The trace is emitted as a `debug` event and attached to endpoint resolution errors as their source.
+                            pub fn with_trace(mut self, trace: bool) -> Self {
+                                self.trace = trace;
+                                self
+                            }
+                            """,
+                        )
+                    }
+                },
+                "resolve" to writable {
+                    if (trace) {
+                        rustTemplate(
+                            """
+                            let mut diagnostic_collector = if self.trace {
+                                #{DiagnosticCollector}::with_trace()
+                            } else {
+                                #{DiagnosticCollector}::new()
+                            };
+                            let result = #{resolver_fn}(params, &mut diagnostic_collector, #{additional_args});
+                            diagnostic_collector.emit_trace();
+                            result.map_err(|err|err.with_source(diagnostic_collector.take_last_error()))
+                            """,
+                            *codegenScope,
+                            "resolver_fn" to resolverFn(endpointRuleSet, fnsUsed),
+                            "additional_args" to fnsUsed.mapNotNull { it.additionalArgsInvocation("self") }.join(","),
+                        )
+                    } else {
+                        rustTemplate(
+                            """
+                            let mut diagnostic_collector = #{DiagnosticCollector}::new();
+                            #{resolver_fn}(params, &mut diagnostic_collector, #{additional_args})
+                                .map_err(|err|err.with_source(diagnostic_collector.take_last_error()))
+                            """,
+                            *codegenScope,
+                            "resolver_fn" to resolverFn(endpointRuleSet, fnsUsed),
+                            "additional_args" to fnsUsed.mapNotNull { it.additionalArgsInvocation("self") }.join(","),
+                        )
+                    }
+                },
                 "custom_fields" to fnsUsed.mapNotNull { it.structField() }.join(","),
                 "custom_fields_init" to fnsUsed.mapNotNull { it.structFieldInit() }.join(","),
                 "Params" to EndpointParamsGenerator(codegenContext, endpointRuleSet.parameters).paramsStruct(),
-                "additional_args" to fnsUsed.mapNotNull { it.additionalArgsInvocation("self") }.join(","),
-                "resolver_fn" to resolverFn(endpointRuleSet, fnsUsed),
                 *codegenScope,
             )
         }
@@ -289,7 +337,17 @@ internal class EndpointResolverGenerator(
                 // 3. the RHS is infallible (e.g. uriEncode)
                 val resultName =
                     (condition.result.orNull() ?: (fn as? Reference)?.name)?.rustName() ?: "_"
-                val target = generator.generate(fn)
+                // record the result of the condition in the trace, if codegen enabled it
+                val target = when (trace) {
+                    true -> writable {
+                        rustTemplate(
+                            "#{DiagnosticCollector}::record_condition(${escape(condition.toString().dq())}, #{target:W}, $DiagnosticCollector)",
+                            *codegenScope,
+                            "target" to generator.generate(fn),
+                        )
+                    }
+                    false -> generator.generate(fn)
+                }
                 val next = generateRuleInternal(rule, rest)
                 when {
                     fn.type() is Type.Option -> {
@@ -326,8 +384,8 @@ internal class EndpointResolverGenerator(
                             let $resultName = #{target:W};
                             #{next:W}
                             """,
-                            "target" to generator.generate(fn),
-                            "next" to generateRuleInternal(rule, rest),
+                            "target" to target,
+                            "next" to next,
                         )
                     }
                 }
@@ -339,6 +397,9 @@ internal class EndpointResolverGenerator(
         override fun visitTreeRule(rules: List<Rule>) = generateRulesList(rules)
 
         override fun visitErrorRule(error: Expression) = writable {
+            if (trace) {
+                rust("$DiagnosticCollector.record_rule(${escape("error $error".dq())});")
+            }
             rustTemplate(
                 "return Err(#{EndpointError}::message(#{message:W}));",
                 *codegenScope,
@@ -347,6 +408,9 @@ internal class EndpointResolverGenerator(
         }
 
         override fun visitEndpointRule(endpoint: Endpoint): Writable = writable {
+            if (trace) {
+                rust("$DiagnosticCollector.record_rule(${escape("endpoint ${endpoint.url}".dq())});")
+            }
             rust("return Ok(#W);", generateEndpoint(endpoint))
         }
     }
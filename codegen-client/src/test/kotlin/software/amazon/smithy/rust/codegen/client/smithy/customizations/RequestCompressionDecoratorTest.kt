@@ -0,0 +1,150 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+package software.amazon.smithy.rust.codegen.client.smithy.customizations
+
+import org.junit.jupiter.api.Test
+import software.amazon.smithy.model.Model
+import software.amazon.smithy.model.loader.ModelAssembler
+import software.amazon.smithy.rust.codegen.client.testutil.TestCodegenSettings
+import software.amazon.smithy.rust.codegen.client.testutil.clientIntegrationTest
+import software.amazon.smithy.rust.codegen.core.rustlang.CargoDependency
+import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType
+import software.amazon.smithy.rust.codegen.core.smithy.RuntimeType.Companion.preludeScope
+import software.amazon.smithy.rust.codegen.core.testutil.IntegrationTestParams
+import software.amazon.smithy.rust.codegen.core.testutil.testModule
+import software.amazon.smithy.rust.codegen.core.testutil.tokioTest
+
+class RequestCompressionDecoratorTest {
+    // `@requestCompression` isn't in the prelude of the Smithy version this project builds against
+    private val model = Model.assembler()
+        .putProperty(ModelAssembler.ALLOW_UNKNOWN_TRAITS, true)
+        .addUnparsedModel(
+            "test.smithy",
+            """
+            ${"$"}version: "2.0"
+            namespace com.example
+            use aws.protocols#restJson1
+
+            @restJson1
+            service HelloService {
+                operations: [PutData, PutSizedStream],
+                version: "1"
+            }
+
+            @optionalAuth
+            @http(method: "POST", uri: "/data")
+            @smithy.api#requestCompression(encodings: ["gzip"])
+            operation PutData { input: PutDataInput }
+
+            structure PutDataInput {
+                @httpPayload
+                body: Blob,
+            }
+
+            @optionalAuth
+            @http(method: "POST", uri: "/sized-stream")
+            @smithy.api#requestCompression(encodings: ["gzip"])
+            operation PutSizedStream { input: PutSizedStreamInput }
+
+            structure PutSizedStreamInput {
+                @httpPayload
+                @required
+                body: SizedStream,
+            }
+
+            @streaming
+            @requiresLength
+            blob SizedStream
+            """.trimIndent(),
+        )
+        .discoverModels()
+        .assemble()
+        .unwrap()
+
+    @Test
+    fun `compress requests unless the streaming input requires a length`() {
+        clientIntegrationTest(
+            model,
+            params = IntegrationTestParams(additionalSettings = TestCodegenSettings.orchestratorMode()),
+        ) { clientCodegenContext, rustCrate ->
+            val runtimeConfig = clientCodegenContext.runtimeConfig
+            val codegenScope = arrayOf(
+                *preludeScope,
+                "Blob" to RuntimeType.blob(runtimeConfig),
+                "ByteStream" to RuntimeType.byteStream(runtimeConfig),
+                "capture_request" to RuntimeType.captureRequest(runtimeConfig),
+            )
+            rustCrate.testModule {
+                addDependency(CargoDependency.Tokio.toDevDependency().withFeature("test-util"))
+                tokioTest("compresses_operations_with_the_request_compression_trait") {
+                    rustTemplate(
+                        """
+                        let (conn, request) = #{capture_request}(#{None});
+                        let config = crate::config::Config::builder()
+                            .endpoint_resolver("http://localhost:1234/")
+                            .http_connector(conn)
+                            .request_min_compression_size_bytes(0)
+                            .build();
+                        let client = crate::client::Client::from_conf(config);
+                        let _ = client.put_data().body(#{Blob}::new("hello world")).send().await;
+
+                        let request = request.expect_request();
+                        assert_eq!("gzip", request.headers()["content-encoding"]);
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                tokioTest("does_not_compress_streams_that_require_a_length") {
+                    rustTemplate(
+                        """
+                        let (conn, request) = #{capture_request}(#{None});
+                        let config = crate::config::Config::builder()
+                            .endpoint_resolver("http://localhost:1234/")
+                            .http_connector(conn)
+                            .request_min_compression_size_bytes(0)
+                            .build();
+                        let client = crate::client::Client::from_conf(config);
+                        let _ = client
+                            .put_sized_stream()
+                            .body(#{ByteStream}::from(b"hello world".to_vec()))
+                            .send()
+                            .await;
+
+                        let request = request.expect_request();
+                        assert!(request.headers().get("content-encoding").is_none());
+                        assert_eq!(#{Some}(&b"hello world"[..]), request.body().bytes());
+                        """,
+                        *codegenScope,
+                    )
+                }
+
+                tokioTest("rejects_min_compression_sizes_over_the_limit") {
+                    rustTemplate(
+                        """
+                        let (conn, _request) = #{capture_request}(#{None});
+                        let config = crate::config::Config::builder()
+                            .endpoint_resolver("http://localhost:1234/")
+                            .http_connector(conn)
+                            .request_min_compression_size_bytes(10_485_761)
+                            .build();
+                        let client = crate::client::Client::from_conf(config);
+                        let err = client
+                            .put_data()
+                            .body(#{Blob}::new("hello world"))
+                            .send()
+                            .await
+                            .expect_err("the minimum compression size is invalid");
+                        assert!(format!("{:?}", err).contains("InvalidMinCompressionSizeError"), "{:?}", err);
+                        """,
+                        *codegenScope,
+                    )
+                }
+            }
+        }
+    }
+}
@@ -6,6 +6,7 @@
 package software.amazon.smithy.rust.codegen.client.smithy.endpoint
 
 import io.kotest.matchers.shouldBe
+import io.kotest.matchers.string.shouldNotContain
 import org.junit.jupiter.api.Test
 import org.junit.jupiter.params.ParameterizedTest
 import org.junit.jupiter.params.provider.MethodSource
@@ -13,17 +14,20 @@ import software.amazon.smithy.codegen.core.CodegenException
 import software.amazon.smithy.model.Model
 import software.amazon.smithy.model.node.Node
 import software.amazon.smithy.rulesengine.language.Endpoint
+import software.amazon.smithy.rulesengine.language.EndpointRuleSet
 import software.amazon.smithy.rulesengine.language.eval.Scope
 import software.amazon.smithy.rulesengine.language.eval.Type
 import software.amazon.smithy.rulesengine.language.syntax.expr.Expression
 import software.amazon.smithy.rulesengine.language.syntax.expr.Literal
 import software.amazon.smithy.rulesengine.testutil.TestDiscovery
+import software.amazon.smithy.rust.codegen.client.smithy.ClientCodegenConfig
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.generators.EndpointParamsGenerator
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.generators.EndpointResolverGenerator
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.generators.EndpointTestGenerator
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.rulesgen.SmithyEndpointsStdLib
 import software.amazon.smithy.rust.codegen.client.smithy.endpoint.rulesgen.awsStandardLib
 import software.amazon.smithy.rust.codegen.client.testutil.testClientCodegenContext
+import software.amazon.smithy.rust.codegen.client.testutil.testClientRustSettings
 import software.amazon.smithy.rust.codegen.core.rustlang.rustTemplate
 import software.amazon.smithy.rust.codegen.core.testutil.TestRuntimeConfig
 import software.amazon.smithy.rust.codegen.core.testutil.TestWorkspace
@@ -105,6 +109,85 @@ class EndpointResolverGeneratorTest {
         )
     }
 
+    private val traceRuleSet = EndpointRuleSet.fromNode(
+        Node.parse(
+            """
+            {
+                "version": "1.0",
+                "parameters": {
+                    "Region": { "type": "String", "required": false }
+                },
+                "rules": [
+                    {
+                        "conditions": [{ "fn": "isSet", "argv": [{ "ref": "Region" }] }],
+                        "type": "endpoint",
+                        "endpoint": { "url": "https://{Region}.example.com" }
+                    },
+                    {
+                        "conditions": [],
+                        "type": "error",
+                        "error": "Missing Region"
+                    }
+                ]
+            }
+            """,
+        ),
+    )
+
+    @Test
+    fun `record a trace of the evaluated rules when enabled`() {
+        val project = TestWorkspace.testProject()
+        val context = testClientCodegenContext(
+            settings = testClientRustSettings(codegenConfig = ClientCodegenConfig(endpointRuleTrace = true)),
+        )
+        traceRuleSet.typecheck()
+        val resolver = EndpointResolverGenerator(context, SmithyEndpointsStdLib).defaultEndpointResolver(traceRuleSet)
+        val params = EndpointParamsGenerator(context, traceRuleSet.parameters).paramsStruct()
+        project.unitTest {
+            rustTemplate(
+                """
+                use #{ResolveEndpoint};
+                let resolver = #{DefaultResolver}::new().with_trace(true);
+                let err = resolver
+                    .resolve_endpoint(&#{Params}::builder().build().unwrap())
+                    .expect_err("the region is missing");
+                let trace = std::error::Error::source(&err).expect("the trace is attached").to_string();
+                assert!(trace.contains("=> None"), "{}", trace);
+                assert!(trace.contains("selected error"), "{}", trace);
+
+                let endpoint = resolver
+                    .resolve_endpoint(&#{Params}::builder().region("us-east-1").build().unwrap())
+                    .unwrap();
+                assert_eq!("https://us-east-1.example.com", endpoint.url());
+                """,
+                "ResolveEndpoint" to Types(TestRuntimeConfig).resolveEndpoint,
+                "DefaultResolver" to resolver,
+                "Params" to params,
+            )
+        }
+        project.compileAndTest(runClippy = true)
+    }
+
+    @Test
+    fun `only record a trace when enabled`() {
+        val project = TestWorkspace.testProject()
+        val context = testClientCodegenContext()
+        traceRuleSet.typecheck()
+        val resolver = EndpointResolverGenerator(context, SmithyEndpointsStdLib).defaultEndpointResolver(traceRuleSet)
+        project.unitTest {
+            rustTemplate("let _ = #{DefaultResolver}::new();", "DefaultResolver" to resolver)
+        }
+        project.compileAndTest()
+        project.generatedFiles()
+            .filter { it.toString().endsWith(".rs") && !it.toString().contains("endpoint_lib/") }
+            .forEach { file ->
+                val contents = project.baseDir.resolve(file).toFile().readText()
+                contents shouldNotContain "record_condition"
+                contents shouldNotContain "record_rule"
+                contents shouldNotContain "with_trace"
+            }
+    }
+
     @Test
     fun generateEndpoints() {
         val endpoint = Endpoint.builder().url(Expression.of("https://{Region}.amazonaws.com"))
@@ -5,7 +5,10 @@
 
 //! Test utilities for time and sleep
 
-use std::sync::{Arc, Mutex};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Weak};
+use std::task::{Context, Poll, Waker};
 use std::time::{Duration, SystemTime};
 
 use tokio::sync::oneshot;
@@ -251,6 +254,179 @@ pub fn instant_time_and_sleep(start_time: SystemTime) -> (ManualTimeSource, Inst
     (ManualTimeSource { start_time, log }, sleep)
 }
 
+/// Time source for a virtual clock that only moves forward when told to.
+///
+/// Create a [`TickAdvanceTime`] with [`tick_advance_time_and_sleep`]
+#[derive(Debug, Clone)]
+pub struct TickAdvanceTime {
+    clock: Arc<Mutex<VirtualClock>>,
+}
+
+/// A sleep implementation where sleeps complete once the paired [`TickAdvanceTime`] has been
+/// advanced past their deadline.
+///
+/// Create a [`TickAdvanceSleep`] with [`tick_advance_time_and_sleep`]
+#[derive(Debug, Clone)]
+pub struct TickAdvanceSleep {
+    clock: Arc<Mutex<VirtualClock>>,
+}
+
+#[derive(Debug)]
+struct VirtualClock {
+    now: SystemTime,
+    sleeps: Vec<Weak<PendingSleep>>,
+}
+
+#[derive(Debug)]
+struct PendingSleep {
+    deadline: SystemTime,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl VirtualClock {
+    /// Returns the sleeps that haven't completed or been dropped yet, forgetting the others.
+    fn pending_sleeps(&mut self) -> Vec<Arc<PendingSleep>> {
+        let now = self.now;
+        let pending: Vec<_> = self
+            .sleeps
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|sleep| sleep.deadline > now)
+            .collect();
+        self.sleeps = pending.iter().map(Arc::downgrade).collect();
+        pending
+    }
+
+    fn advance_to(&mut self, time: SystemTime) {
+        assert!(time >= self.now, "the virtual clock can't go backwards");
+        let due: Vec<_> = self
+            .pending_sleeps()
+            .into_iter()
+            .filter(|sleep| sleep.deadline <= time)
+            .collect();
+        self.now = time;
+        for sleep in due {
+            if let Some(waker) = sleep.waker.lock().unwrap().take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl TickAdvanceTime {
+    /// Advances the clock by `duration`, completing any sleeps that are due by then.
+    pub fn tick(&self, duration: Duration) {
+        let mut clock = self.clock.lock().unwrap();
+        let time = clock.now + duration;
+        clock.advance_to(time);
+    }
+
+    /// Advances the clock to the deadline of the earliest pending sleep and completes it.
+    ///
+    /// Returns how far the clock moved, or `None` if there are no pending sleeps.
+    pub fn advance_to_next_sleep(&self) -> Option<Duration> {
+        let mut clock = self.clock.lock().unwrap();
+        let next = clock
+            .pending_sleeps()
+            .iter()
+            .map(|sleep| sleep.deadline)
+            .min()?;
+        let advanced_by = next
+            .duration_since(clock.now)
+            .expect("deadline is in the future");
+        clock.advance_to(next);
+        Some(advanced_by)
+    }
+
+    /// Returns the number of sleeps that are waiting for the clock to advance.
+    pub fn pending_sleep_count(&self) -> usize {
+        self.clock.lock().unwrap().pending_sleeps().len()
+    }
+}
+
+impl TimeSource for TickAdvanceTime {
+    fn now(&self) -> SystemTime {
+        self.clock.lock().unwrap().now
+    }
+}
+
+struct TickAdvanceSleepFuture {
+    clock: Arc<Mutex<VirtualClock>>,
+    pending: Arc<PendingSleep>,
+}
+
+impl Future for TickAdvanceSleepFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Holding the clock lock while registering the waker ensures a concurrent advance can't be missed.
+        let clock = self.clock.lock().unwrap();
+        if clock.now >= self.pending.deadline {
+            return Poll::Ready(());
+        }
+        *self.pending.waker.lock().unwrap() = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl AsyncSleep for TickAdvanceSleep {
+    fn sleep(&self, duration: Duration) -> Sleep {
+        let pending = {
+            let mut clock = self.clock.lock().unwrap();
+            let pending = Arc::new(PendingSleep {
+                deadline: clock.now + duration,
+                waker: Mutex::new(None),
+            });
+            clock.sleeps.push(Arc::downgrade(&pending));
+            pending
+        };
+        Sleep::new(TickAdvanceSleepFuture {
+            clock: self.clock.clone(),
+            pending,
+        })
+    }
+}
+
+/// Returns a duo of tools that share a virtual clock starting at `start_time`.
+///
+/// Time doesn't pass on its own: [`TickAdvanceTime::tick`] and
+/// [`TickAdvanceTime::advance_to_next_sleep`] move the clock forward and complete the sleeps
+/// created by the [`TickAdvanceSleep`] that become due. Unlike [`instant_time_and_sleep`], this
+/// keeps sleeps that race each other (such as a timeout racing a slow response) in order.
+///
+/// # Examples
+/// ```rust
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use aws_smithy_async::rt::sleep::AsyncSleep;
+/// use aws_smithy_async::test_util::tick_advance_time_and_sleep;
+/// use aws_smithy_async::time::TimeSource;
+///
+/// let (time, sleep) = tick_advance_time_and_sleep(UNIX_EPOCH);
+/// let _short = sleep.sleep(Duration::from_secs(1));
+/// let _long = sleep.sleep(Duration::from_secs(5));
+/// assert_eq!(time.pending_sleep_count(), 2);
+///
+/// assert_eq!(time.advance_to_next_sleep(), Some(Duration::from_secs(1)));
+/// assert_eq!(time.now(), UNIX_EPOCH + Duration::from_secs(1));
+/// assert_eq!(time.pending_sleep_count(), 1);
+///
+/// time.tick(Duration::from_secs(10));
+/// assert_eq!(time.pending_sleep_count(), 0);
+/// assert_eq!(time.now(), UNIX_EPOCH + Duration::from_secs(11));
+/// ```
+pub fn tick_advance_time_and_sleep(start_time: SystemTime) -> (TickAdvanceTime, TickAdvanceSleep) {
+    let clock = Arc::new(Mutex::new(VirtualClock {
+        now: start_time,
+        sleeps: Vec::new(),
+    }));
+    (
+        TickAdvanceTime {
+            clock: clock.clone(),
+        },
+        TickAdvanceSleep { clock },
+    )
+}
+
 impl TimeSource for SystemTime {
     fn now(&self) -> SystemTime {
         *self
@@ -269,10 +445,16 @@ impl From<ManualTimeSource> for SharedTimeSource {
     }
 }
 
+impl From<TickAdvanceTime> for SharedTimeSource {
+    fn from(value: TickAdvanceTime) -> Self {
+        SharedTimeSource::new(value)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::rt::sleep::AsyncSleep;
-    use crate::test_util::controlled_time_and_sleep;
+    use crate::test_util::{controlled_time_and_sleep, tick_advance_time_and_sleep};
     use crate::time::TimeSource;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
@@ -313,4 +495,45 @@ mod test {
             .expect("no timeout")
             .expect("successful completion");
     }
+
+    #[tokio::test]
+    async fn test_tick_advance_sleep() {
+        use std::time::{Duration, UNIX_EPOCH};
+        let (time, sleep) = tick_advance_time_and_sleep(UNIX_EPOCH);
+        let progress = Arc::new(AtomicUsize::new(0));
+        let task_progress = progress.clone();
+        let task_time = time.clone();
+        let task = tokio::spawn(async move {
+            sleep.sleep(Duration::from_secs(1)).await;
+            assert_eq!(task_time.now(), UNIX_EPOCH + Duration::from_secs(1));
+            task_progress.store(1, Ordering::Release);
+            sleep.sleep(Duration::from_secs(2)).await;
+            assert_eq!(task_time.now(), UNIX_EPOCH + Duration::from_secs(3));
+            task_progress.store(2, Ordering::Release);
+        });
+        while time.pending_sleep_count() != 1 {
+            yield_now().await
+        }
+
+        // Not far enough for the first sleep to complete
+        time.tick(Duration::from_millis(500));
+        yield_now().await;
+        assert_eq!(progress.load(Ordering::Acquire), 0);
+
+        assert_eq!(
+            time.advance_to_next_sleep(),
+            Some(Duration::from_millis(500))
+        );
+        while progress.load(Ordering::Acquire) != 1 || time.pending_sleep_count() != 1 {
+            yield_now().await
+        }
+
+        assert_eq!(time.advance_to_next_sleep(), Some(Duration::from_secs(2)));
+        timeout(Duration::from_secs(1), task)
+            .await
+            .expect("no timeout")
+            .expect("successful completion");
+        assert_eq!(progress.load(Ordering::Acquire), 2);
+        assert_eq!(time.advance_to_next_sleep(), None);
+    }
 }
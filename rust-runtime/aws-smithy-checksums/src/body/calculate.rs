@@ -98,7 +98,10 @@ impl http_body::Body for ChecksumBody<SdkBody> {
 #[cfg(test)]
 mod tests {
     use super::ChecksumBody;
-    use crate::{http::CRC_32_HEADER_NAME, ChecksumAlgorithm, CRC_32_NAME};
+    use crate::{
+        http::{CRC_32_HEADER_NAME, CRC_64_NVME_HEADER_NAME},
+        ChecksumAlgorithm, CRC_32_NAME, CRC_64_NVME_NAME,
+    };
     use aws_smithy_http::body::SdkBody;
     use aws_smithy_types::base64;
     use bytes::Buf;
@@ -152,4 +155,30 @@ mod tests {
         // Known correct checksum for the input "This is some test text for an SdkBody"
         assert_eq!("0x99B01F72", checksum_trailer);
     }
+
+    #[tokio::test]
+    async fn test_crc64nvme_checksum_body() {
+        let input_text = "This is some test text for an SdkBody";
+        let checksum = CRC_64_NVME_NAME
+            .parse::<ChecksumAlgorithm>()
+            .unwrap()
+            .into_impl();
+        let mut body = ChecksumBody::new(SdkBody::from(input_text), checksum);
+        while let Some(buf) = body.data().await {
+            buf.unwrap();
+        }
+
+        let trailers = body
+            .trailers()
+            .await
+            .expect("checksum generation was without error")
+            .expect("trailers were set");
+        let checksum_trailer = trailers
+            .get(&CRC_64_NVME_HEADER_NAME)
+            .expect("trailers contain crc64nvme checksum");
+        let checksum_trailer = header_value_as_checksum_string(checksum_trailer);
+
+        // Known correct checksum for the input "This is some test text for an SdkBody"
+        assert_eq!("0xAC00378F515760D5", checksum_trailer);
+    }
 }
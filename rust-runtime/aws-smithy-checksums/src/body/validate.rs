@@ -218,4 +218,28 @@ mod tests {
         // Verify data is complete and unaltered
         assert_eq!(input_text, output_text);
     }
+
+    #[tokio::test]
+    async fn test_sha512_checksum_validated_body_errors_on_mismatch() {
+        let input_text = "This is some test text for an SdkBody";
+        let http_checksum = "sha512".parse::<ChecksumAlgorithm>().unwrap().into_impl();
+        let mut body = ChecksumBody::new(
+            SdkBody::from(input_text),
+            http_checksum,
+            Bytes::from_static(&[0; 64]),
+        );
+
+        while let Some(data) = body.data().await {
+            if let Err(e) = data {
+                match e.downcast_ref::<Error>().unwrap() {
+                    Error::ChecksumMismatch { actual, .. } => {
+                        assert_eq!(64, actual.len());
+                    }
+                }
+                return;
+            }
+        }
+
+        panic!("didn't hit expected error condition");
+    }
 }
@@ -0,0 +1,167 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! CRC64-NVME calculation and combination of CRC checksums.
+//!
+//! All CRCs supported by this crate are reflected, with an initial value and a final XOR of all
+//! ones. That allows the checksums of two consecutive pieces of data to be combined without access
+//! to the data itself.
+
+/// Parameters of a reflected CRC with an all-ones initial value and final XOR
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct CrcParams {
+    /// Width of the CRC, in bits
+    width: u32,
+    /// The bit-reversed generator polynomial
+    reflected_poly: u64,
+}
+
+pub(crate) const CRC_32: CrcParams = CrcParams {
+    width: 32,
+    reflected_poly: 0xEDB8_8320,
+};
+
+pub(crate) const CRC_32_C: CrcParams = CrcParams {
+    width: 32,
+    reflected_poly: 0x82F6_3B78,
+};
+
+pub(crate) const CRC_64_NVME: CrcParams = CrcParams {
+    width: 64,
+    reflected_poly: 0x9A6C_9329_AC4B_C9B5,
+};
+
+const CRC_64_NVME_TABLE: [u64; 256] = crc64_table(CRC_64_NVME.reflected_poly);
+
+const fn crc64_table(reflected_poly: u64) -> [u64; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ reflected_poly
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+/// Update a CRC64-NVME `crc` with `bytes`
+///
+/// `crc` is the finalized value of the data processed so far, `0` for no data.
+pub(crate) fn crc64_nvme_append(crc: u64, bytes: &[u8]) -> u64 {
+    let mut crc = !crc;
+    for byte in bytes {
+        crc = CRC_64_NVME_TABLE[((crc ^ *byte as u64) & 0xFF) as usize] ^ (crc >> 8);
+    }
+    !crc
+}
+
+impl CrcParams {
+    /// The polynomial `x^0`, in reflected representation
+    fn one(&self) -> u64 {
+        1 << (self.width - 1)
+    }
+
+    /// Multiply `a` and `b` modulo the generator polynomial
+    fn multiply(&self, a: u64, mut b: u64) -> u64 {
+        let mut mask = self.one();
+        let mut product = 0;
+        while mask != 0 {
+            if a & mask != 0 {
+                product ^= b;
+            }
+            mask >>= 1;
+            b = if b & 1 == 1 {
+                (b >> 1) ^ self.reflected_poly
+            } else {
+                b >> 1
+            };
+        }
+        product
+    }
+
+    /// `x^(8 * len)` modulo the generator polynomial, i.e. the operator that appends `len` zero bytes
+    fn shift_operator(&self, mut len: u64) -> u64 {
+        // x^8 == x^(2^3)
+        let mut square = self.one() >> 8;
+        let mut operator = self.one();
+        while len != 0 {
+            if len & 1 == 1 {
+                operator = self.multiply(square, operator);
+            }
+            square = self.multiply(square, square);
+            len >>= 1;
+        }
+        operator
+    }
+
+    /// Given the CRCs of two consecutive pieces of data, calculate the CRC of their concatenation
+    ///
+    /// `second_len` is the length in bytes of the data `second` was calculated over.
+    pub(crate) fn combine(&self, first: u64, second: u64, second_len: u64) -> u64 {
+        self.multiply(self.shift_operator(second_len), first) ^ second
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crc64_nvme_append, CrcParams, CRC_32, CRC_32_C, CRC_64_NVME};
+
+    const CHECK_INPUT: &[u8] = b"123456789";
+
+    #[test]
+    fn crc64_nvme_check_value() {
+        assert_eq!(0xAE8B_1486_0A79_9888, crc64_nvme_append(0, CHECK_INPUT));
+        assert_eq!(0, crc64_nvme_append(0, b""));
+    }
+
+    #[test]
+    fn crc64_nvme_is_incremental() {
+        let (first, second) = CHECK_INPUT.split_at(4);
+        assert_eq!(
+            crc64_nvme_append(0, CHECK_INPUT),
+            crc64_nvme_append(crc64_nvme_append(0, first), second)
+        );
+    }
+
+    fn assert_combines(params: CrcParams, crc: impl Fn(&[u8]) -> u64) {
+        let data = b"The quick brown fox jumps over the lazy dog, again and again and again";
+        for split in [0, 1, 7, 8, 33, data.len()] {
+            let (first, second) = data.split_at(split);
+            assert_eq!(
+                crc(data),
+                params.combine(crc(first), crc(second), second.len() as u64),
+                "split at {}",
+                split
+            );
+        }
+    }
+
+    #[test]
+    fn combine_crc32() {
+        assert_combines(CRC_32, |data| crc32fast::hash(data) as u64);
+    }
+
+    // TODO(https://github.com/zowens/crc32c/issues/34)
+    // TODO(https://github.com/awslabs/smithy-rs/issues/1857)
+    #[cfg(not(any(target_arch = "powerpc", target_arch = "powerpc64")))]
+    #[test]
+    fn combine_crc32c() {
+        assert_combines(CRC_32_C, |data| crc32c::crc32c(data) as u64);
+    }
+
+    #[test]
+    fn combine_crc64_nvme() {
+        assert_combines(CRC_64_NVME, |data| crc64_nvme_append(0, data));
+    }
+}
@@ -29,10 +29,55 @@ impl fmt::Display for UnknownChecksumAlgorithmError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            r#"unknown checksum algorithm "{}", please pass a known algorithm name ("crc32", "crc32c", "sha1", "sha256", "md5")"#,
+            r#"unknown checksum algorithm "{}", please pass a known algorithm name ("crc32", "crc32c", "crc64nvme", "sha1", "sha256", "sha512", "md5")"#,
             self.checksum_algorithm
         )
     }
 }
 
 impl Error for UnknownChecksumAlgorithmError {}
+
+#[derive(Debug)]
+enum CombineChecksumsErrorKind {
+    NotCombinable { checksum_algorithm: &'static str },
+    InvalidLength { expected: usize, actual: usize },
+}
+
+/// Checksums couldn't be combined
+#[derive(Debug)]
+pub struct CombineChecksumsError {
+    kind: CombineChecksumsErrorKind,
+}
+
+impl CombineChecksumsError {
+    pub(crate) fn not_combinable(checksum_algorithm: &'static str) -> Self {
+        Self {
+            kind: CombineChecksumsErrorKind::NotCombinable { checksum_algorithm },
+        }
+    }
+
+    pub(crate) fn invalid_length(expected: usize, actual: usize) -> Self {
+        Self {
+            kind: CombineChecksumsErrorKind::InvalidLength { expected, actual },
+        }
+    }
+}
+
+impl fmt::Display for CombineChecksumsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            CombineChecksumsErrorKind::NotCombinable { checksum_algorithm } => write!(
+                f,
+                r#"checksums of algorithm "{}" can't be combined"#,
+                checksum_algorithm
+            ),
+            CombineChecksumsErrorKind::InvalidLength { expected, actual } => write!(
+                f,
+                "expected a checksum of {} bytes but got {} bytes",
+                expected, actual
+            ),
+        }
+    }
+}
+
+impl Error for CombineChecksumsError {}
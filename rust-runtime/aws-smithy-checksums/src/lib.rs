@@ -13,29 +13,35 @@
 
 //! Checksum calculation and verification callbacks.
 
-use crate::error::UnknownChecksumAlgorithmError;
+use crate::error::{CombineChecksumsError, UnknownChecksumAlgorithmError};
 use bytes::Bytes;
 use std::str::FromStr;
 
 pub mod body;
+mod crc;
 pub mod error;
 pub mod http;
 
 // Valid checksum algorithm names
 pub const CRC_32_NAME: &str = "crc32";
 pub const CRC_32_C_NAME: &str = "crc32c";
+pub const CRC_64_NVME_NAME: &str = "crc64nvme";
 pub const SHA_1_NAME: &str = "sha1";
 pub const SHA_256_NAME: &str = "sha256";
+pub const SHA_512_NAME: &str = "sha512";
 pub const MD5_NAME: &str = "md5";
 
 /// We only support checksum calculation and validation for these checksum algorithms.
+#[non_exhaustive]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ChecksumAlgorithm {
     Crc32,
     Crc32c,
+    Crc64Nvme,
     Md5,
     Sha1,
     Sha256,
+    Sha512,
 }
 
 impl FromStr for ChecksumAlgorithm {
@@ -44,8 +50,10 @@ impl FromStr for ChecksumAlgorithm {
     /// Create a new `ChecksumAlgorithm` from an algorithm name. Valid algorithm names are:
     /// - "crc32"
     /// - "crc32c"
+    /// - "crc64nvme"
     /// - "sha1"
     /// - "sha256"
+    /// - "sha512"
     /// - "md5"
     ///
     /// Passing an invalid name will return an error.
@@ -54,10 +62,14 @@ impl FromStr for ChecksumAlgorithm {
             Ok(Self::Crc32)
         } else if checksum_algorithm.eq_ignore_ascii_case(CRC_32_C_NAME) {
             Ok(Self::Crc32c)
+        } else if checksum_algorithm.eq_ignore_ascii_case(CRC_64_NVME_NAME) {
+            Ok(Self::Crc64Nvme)
         } else if checksum_algorithm.eq_ignore_ascii_case(SHA_1_NAME) {
             Ok(Self::Sha1)
         } else if checksum_algorithm.eq_ignore_ascii_case(SHA_256_NAME) {
             Ok(Self::Sha256)
+        } else if checksum_algorithm.eq_ignore_ascii_case(SHA_512_NAME) {
+            Ok(Self::Sha512)
         } else if checksum_algorithm.eq_ignore_ascii_case(MD5_NAME) {
             Ok(Self::Md5)
         } else {
@@ -72,9 +84,11 @@ impl ChecksumAlgorithm {
         match self {
             Self::Crc32 => Box::<Crc32>::default(),
             Self::Crc32c => Box::<Crc32c>::default(),
+            Self::Crc64Nvme => Box::<Crc64Nvme>::default(),
             Self::Md5 => Box::<Md5>::default(),
             Self::Sha1 => Box::<Sha1>::default(),
             Self::Sha256 => Box::<Sha256>::default(),
+            Self::Sha512 => Box::<Sha512>::default(),
         }
     }
 
@@ -83,9 +97,58 @@ impl ChecksumAlgorithm {
         match self {
             Self::Crc32 => CRC_32_NAME,
             Self::Crc32c => CRC_32_C_NAME,
+            Self::Crc64Nvme => CRC_64_NVME_NAME,
             Self::Md5 => MD5_NAME,
             Self::Sha1 => SHA_1_NAME,
             Self::Sha256 => SHA_256_NAME,
+            Self::Sha512 => SHA_512_NAME,
+        }
+    }
+
+    /// Return `true` if checksums of this algorithm can be [combined](ChecksumAlgorithm::combine)
+    ///
+    /// Only the CRC algorithms are combinable.
+    pub fn is_combinable(&self) -> bool {
+        self.crc_params().is_some()
+    }
+
+    /// Combine the checksums of two consecutive pieces of data into the checksum of their concatenation
+    ///
+    /// `first` and `second` are finalized checksums, as returned by [`Checksum::finalize`].
+    /// `second_len` is the length in bytes of the data that `second` was calculated over. This
+    /// makes it possible to calculate the checksum of a whole object from the checksums of its
+    /// parts, for example when uploading parts in parallel.
+    ///
+    /// Returns an error if this algorithm [isn't combinable](ChecksumAlgorithm::is_combinable) or if
+    /// either checksum doesn't have the size of this algorithm's checksums.
+    pub fn combine(
+        &self,
+        first: &[u8],
+        second: &[u8],
+        second_len: u64,
+    ) -> Result<Bytes, CombineChecksumsError> {
+        let params = self
+            .crc_params()
+            .ok_or_else(|| CombineChecksumsError::not_combinable(self.as_str()))?;
+        let size = Checksum::size(&*self.into_impl()) as usize;
+        let parse = |checksum: &[u8]| {
+            if checksum.len() != size {
+                return Err(CombineChecksumsError::invalid_length(size, checksum.len()));
+            }
+            Ok(checksum
+                .iter()
+                .fold(0u64, |value, byte| (value << 8) | *byte as u64))
+        };
+        let combined = params.combine(parse(first)?, parse(second)?, second_len);
+        Ok(Bytes::copy_from_slice(&combined.to_be_bytes()[8 - size..]))
+    }
+
+    fn crc_params(&self) -> Option<crc::CrcParams> {
+        match self {
+            Self::Crc32 => Some(crc::CRC_32),
+            Self::Crc32c => Some(crc::CRC_32_C),
+            Self::Crc64Nvme => Some(crc::CRC_64_NVME),
+            Self::Md5 | Self::Sha1 | Self::Sha256 | Self::Sha512 => None,
         }
     }
 }
@@ -180,6 +243,38 @@ impl Checksum for Crc32c {
     }
 }
 
+#[derive(Debug, Default)]
+struct Crc64Nvme {
+    state: u64,
+}
+
+impl Crc64Nvme {
+    fn update(&mut self, bytes: &[u8]) {
+        self.state = crc::crc64_nvme_append(self.state, bytes);
+    }
+
+    fn finalize(self) -> Bytes {
+        Bytes::copy_from_slice(self.state.to_be_bytes().as_slice())
+    }
+
+    // Size of the checksum in bytes
+    fn size() -> u64 {
+        8
+    }
+}
+
+impl Checksum for Crc64Nvme {
+    fn update(&mut self, bytes: &[u8]) {
+        Self::update(self, bytes)
+    }
+    fn finalize(self: Box<Self>) -> Bytes {
+        Self::finalize(*self)
+    }
+    fn size(&self) -> u64 {
+        Self::size()
+    }
+}
+
 #[derive(Debug, Default)]
 struct Sha1 {
     hasher: sha1::Sha1,
@@ -251,6 +346,41 @@ impl Checksum for Sha256 {
     }
 }
 
+#[derive(Debug, Default)]
+struct Sha512 {
+    hasher: sha2::Sha512,
+}
+
+impl Sha512 {
+    fn update(&mut self, bytes: &[u8]) {
+        use sha2::Digest;
+        self.hasher.update(bytes);
+    }
+
+    fn finalize(self) -> Bytes {
+        use sha2::Digest;
+        Bytes::copy_from_slice(self.hasher.finalize().as_slice())
+    }
+
+    // Size of the checksum in bytes
+    fn size() -> u64 {
+        use sha2::Digest;
+        sha2::Sha512::output_size() as u64
+    }
+}
+
+impl Checksum for Sha512 {
+    fn update(&mut self, bytes: &[u8]) {
+        Self::update(self, bytes);
+    }
+    fn finalize(self: Box<Self>) -> Bytes {
+        Self::finalize(*self)
+    }
+    fn size(&self) -> u64 {
+        Self::size()
+    }
+}
+
 #[derive(Debug, Default)]
 struct Md5 {
     hasher: md5::Md5,
@@ -290,10 +420,10 @@ impl Checksum for Md5 {
 mod tests {
     use super::{
         http::{
-            CRC_32_C_HEADER_NAME, CRC_32_HEADER_NAME, MD5_HEADER_NAME, SHA_1_HEADER_NAME,
-            SHA_256_HEADER_NAME,
+            CRC_32_C_HEADER_NAME, CRC_32_HEADER_NAME, CRC_64_NVME_HEADER_NAME, MD5_HEADER_NAME,
+            SHA_1_HEADER_NAME, SHA_256_HEADER_NAME, SHA_512_HEADER_NAME,
         },
-        Crc32, Crc32c, Md5, Sha1, Sha256,
+        Crc32, Crc32c, Crc64Nvme, Md5, Sha1, Sha256, Sha512,
     };
 
     use crate::http::HttpChecksum;
@@ -343,6 +473,19 @@ mod tests {
         assert_eq!(decoded_checksum, expected_checksum);
     }
 
+    #[test]
+    fn test_crc64nvme_checksum() {
+        let mut checksum = Crc64Nvme::default();
+        checksum.update(TEST_DATA.as_bytes());
+        let checksum_result = Box::new(checksum).headers();
+        let encoded_checksum = checksum_result.get(&CRC_64_NVME_HEADER_NAME).unwrap();
+        let decoded_checksum = base64_encoded_checksum_to_hex_string(encoded_checksum);
+
+        let expected_checksum = "0xAECAF3AF9C98A855";
+
+        assert_eq!(decoded_checksum, expected_checksum);
+    }
+
     #[test]
     fn test_sha1_checksum() {
         let mut checksum = Sha1::default();
@@ -370,6 +513,19 @@ mod tests {
         assert_eq!(decoded_checksum, expected_checksum);
     }
 
+    #[test]
+    fn test_sha512_checksum() {
+        let mut checksum = Sha512::default();
+        checksum.update(TEST_DATA.as_bytes());
+        let checksum_result = Box::new(checksum).headers();
+        let encoded_checksum = checksum_result.get(&SHA_512_HEADER_NAME).unwrap();
+        let decoded_checksum = base64_encoded_checksum_to_hex_string(encoded_checksum);
+
+        let expected_checksum = "0x0E1E21ECF105EC853D24D728867AD70613C21663A4693074B2A3619C1BD39D66B588C33723BB466C72424E80E3CA63C249078AB347BAB9428500E7EE43059D0D";
+
+        assert_eq!(decoded_checksum, expected_checksum);
+    }
+
     #[test]
     fn test_md5_checksum() {
         let mut checksum = Md5::default();
@@ -383,6 +539,50 @@ mod tests {
         assert_eq!(decoded_checksum, expected_checksum);
     }
 
+    #[test]
+    fn test_combine_crc_checksums() {
+        let (first, second) = TEST_DATA.as_bytes().split_at(4);
+        for algorithm in [ChecksumAlgorithm::Crc32, ChecksumAlgorithm::Crc64Nvme] {
+            let checksum_of = |data: &[u8]| {
+                let mut checksum = algorithm.into_impl();
+                checksum.update(data);
+                checksum.finalize()
+            };
+            let combined = algorithm
+                .combine(
+                    &checksum_of(first),
+                    &checksum_of(second),
+                    second.len() as u64,
+                )
+                .unwrap();
+            assert_eq!(
+                checksum_of(TEST_DATA.as_bytes()),
+                combined,
+                "{:?}",
+                algorithm
+            );
+        }
+    }
+
+    #[test]
+    fn test_combine_rejects_invalid_input() {
+        assert!(!ChecksumAlgorithm::Sha256.is_combinable());
+        let error = ChecksumAlgorithm::Sha256
+            .combine(&[0; 32], &[0; 32], 1)
+            .expect_err("sha256 is not combinable");
+        assert_eq!(
+            "checksums of algorithm \"sha256\" can't be combined",
+            error.to_string()
+        );
+        let error = ChecksumAlgorithm::Crc32
+            .combine(&[0; 4], &[0; 8], 1)
+            .expect_err("the second checksum is too long");
+        assert_eq!(
+            "expected a checksum of 4 bytes but got 8 bytes",
+            error.to_string()
+        );
+    }
+
     #[test]
     fn test_checksum_algorithm_returns_error_for_unknown() {
         let error = "some invalid checksum algorithm"
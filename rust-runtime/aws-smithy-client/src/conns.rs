@@ -17,7 +17,14 @@ pub type Https = hyper_rustls::HttpsConnector<hyper::client::HttpConnector>;
 pub type Rustls = crate::hyper_ext::Adapter<Https>;
 
 #[cfg(feature = "rustls")]
-use hyper_rustls::ConfigBuilderExt;
+/// A `hyper` connector that uses the `rustls` crate for TLS and connects through an HTTP proxy, if
+/// one is configured. To use this in a smithy client, wrap it in a [hyper_ext::Adapter](crate::hyper_ext::Adapter)
+/// that is configured with the same proxy.
+///
+/// It connects to dual-stack hosts with [Happy Eyeballs](crate::happy_eyeballs).
+pub type HttpsWithProxy = hyper_rustls::HttpsConnector<
+    crate::proxy::ProxyConnector<crate::happy_eyeballs::HappyEyeballsConnector>,
+>;
 
 // Creating a `with_native_roots` HTTP client takes 300ms on OS X. Cache this so that we
 // don't need to repeatedly incur that cost.
@@ -25,25 +32,7 @@ use hyper_rustls::ConfigBuilderExt;
 lazy_static::lazy_static! {
     static ref HTTPS_NATIVE_ROOTS: Https = {
         hyper_rustls::HttpsConnectorBuilder::new()
-            .with_tls_config(
-                rustls::ClientConfig::builder()
-                    .with_cipher_suites(&[
-                        // TLS1.3 suites
-                        rustls::cipher_suite::TLS13_AES_256_GCM_SHA384,
-                        rustls::cipher_suite::TLS13_AES_128_GCM_SHA256,
-                        // TLS1.2 suites
-                        rustls::cipher_suite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
-                        rustls::cipher_suite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
-                        rustls::cipher_suite::TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384,
-                        rustls::cipher_suite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
-                        rustls::cipher_suite::TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
-                    ])
-                    .with_safe_default_kx_groups()
-                    .with_safe_default_protocol_versions()
-                    .expect("Error with the TLS configuration. Please file a bug report under https://github.com/awslabs/smithy-rs/issues.")
-                    .with_native_roots()
-                    .with_no_client_auth()
-            )
+            .with_tls_config(crate::tls::native_roots_client_config().clone())
             .https_or_http()
             .enable_http1()
             .enable_http2()
@@ -54,8 +43,17 @@ lazy_static::lazy_static! {
 mod default_connector {
     use crate::erase::DynConnector;
     use crate::http_connector::ConnectorSettings;
+    use crate::proxy::ProxyConfig;
     use aws_smithy_async::rt::sleep::SharedAsyncSleep;
 
+    fn proxy_from_env() -> Option<ProxyConfig> {
+        use aws_smithy_types::error::display::DisplayErrorContext;
+        ProxyConfig::from_env().unwrap_or_else(|err| {
+            tracing::warn!(err = %DisplayErrorContext(&err), "ignoring the proxy configured in the environment");
+            None
+        })
+    }
+
     #[cfg(feature = "rustls")]
     fn base(
         settings: &ConnectorSettings,
@@ -69,24 +67,55 @@ mod default_connector {
     }
 
     /// Given `ConnectorSettings` and an `SharedAsyncSleep`, create a `DynConnector` from defaults depending on what cargo features are activated.
+    ///
+    /// The connector sends requests through the proxy configured in the environment, if any. See
+    /// [`ProxyConfig::from_env`](crate::proxy::ProxyConfig::from_env) for details.
     pub fn default_connector(
         settings: &ConnectorSettings,
         sleep: Option<SharedAsyncSleep>,
+    ) -> Option<DynConnector> {
+        default_connector_with_proxy(settings, sleep, proxy_from_env())
+    }
+
+    /// Like [`default_connector`], but the connector sends requests through `proxy` instead of the
+    /// proxy configured in the environment.
+    pub fn default_connector_with_proxy(
+        settings: &ConnectorSettings,
+        sleep: Option<SharedAsyncSleep>,
+        proxy: Option<ProxyConfig>,
     ) -> Option<DynConnector> {
         #[cfg(feature = "rustls")]
         {
-            tracing::trace!(settings = ?settings, sleep = ?sleep, "creating a new default connector");
-            let hyper = base(settings, sleep).build(super::https());
+            tracing::trace!(settings = ?settings, sleep = ?sleep, proxy = ?proxy, "creating a new default connector");
+            let mut hyper = base(settings, sleep);
+            hyper.set_proxy(proxy.clone());
+            let hyper = hyper.build(super::https_with_proxy(proxy));
             Some(DynConnector::new(hyper))
         }
         #[cfg(not(feature = "rustls"))]
         {
-            tracing::trace!(settings = ?settings, sleep = ?sleep, "no default connector available");
+            tracing::trace!(settings = ?settings, sleep = ?sleep, proxy = ?proxy, "no default connector available");
             None
         }
     }
+
+    /// Like [`default_connector_with_proxy`], but the connector uses `tls_options` for TLS.
+    #[cfg(feature = "rustls")]
+    pub fn default_connector_with_tls_options(
+        settings: &ConnectorSettings,
+        sleep: Option<SharedAsyncSleep>,
+        tls_options: &crate::tls::TlsOptions,
+        proxy: Option<ProxyConfig>,
+    ) -> DynConnector {
+        tracing::trace!(settings = ?settings, sleep = ?sleep, tls_options = ?tls_options, proxy = ?proxy, "creating a new default connector");
+        let mut hyper = base(settings, sleep);
+        hyper.set_proxy(proxy.clone());
+        DynConnector::new(hyper.build(super::https_with_tls_options(tls_options, proxy)))
+    }
 }
-pub use default_connector::default_connector;
+#[cfg(feature = "rustls")]
+pub use default_connector::default_connector_with_tls_options;
+pub use default_connector::{default_connector, default_connector_with_proxy};
 
 /// Error that indicates a connector is required.
 #[non_exhaustive]
@@ -117,6 +146,53 @@ pub fn https() -> Https {
     HTTPS_NATIVE_ROOTS.clone()
 }
 
+#[cfg(feature = "rustls")]
+/// Return a default HTTPS connector backed by the `rustls` crate that connects through `proxy`
+///
+/// Like [`https`], it requires a minimum TLS version of 1.2 and allows you to connect to both
+/// `http` and `https` URLs. Connections are made directly when `proxy` is `None`.
+pub fn https_with_proxy(proxy: Option<crate::proxy::ProxyConfig>) -> HttpsWithProxy {
+    https_with_tcp_connector(Default::default(), None, proxy)
+}
+
+#[cfg(feature = "rustls")]
+/// Return an HTTPS connector backed by the `rustls` crate that uses `tls_options` for TLS and
+/// connects through `proxy`
+///
+/// Connections are made directly when `proxy` is `None`. See [`TlsOptions`](crate::tls::TlsOptions)
+/// for how to trust private certificate authorities, present a client certificate or pin
+/// certificates.
+pub fn https_with_tls_options(
+    tls_options: &crate::tls::TlsOptions,
+    proxy: Option<crate::proxy::ProxyConfig>,
+) -> HttpsWithProxy {
+    https_with_tcp_connector(Default::default(), Some(tls_options), proxy)
+}
+
+#[cfg(feature = "rustls")]
+/// Return an HTTPS connector backed by the `rustls` crate that connects with `tcp_connector` and
+/// through `proxy`
+///
+/// Use a [`HappyEyeballsConnector`](crate::happy_eyeballs::HappyEyeballsConnector) with a custom
+/// [DNS resolver](crate::dns) to control how host names are resolved. When `tls_options` is
+/// `None`, the native root certificates are trusted.
+pub fn https_with_tcp_connector(
+    tcp_connector: crate::happy_eyeballs::HappyEyeballsConnector,
+    tls_options: Option<&crate::tls::TlsOptions>,
+    proxy: Option<crate::proxy::ProxyConfig>,
+) -> HttpsWithProxy {
+    let config = match tls_options {
+        Some(tls_options) => tls_options.client_config().clone(),
+        None => crate::tls::native_roots_client_config().clone(),
+    };
+    hyper_rustls::HttpsConnectorBuilder::new()
+        .with_tls_config(config)
+        .https_or_http()
+        .enable_http1()
+        .enable_http2()
+        .wrap_connector(crate::proxy::ProxyConnector::new(tcp_connector, proxy))
+}
+
 #[cfg(all(test, feature = "rustls"))]
 mod tests {
     use crate::erase::DynConnector;
@@ -6,11 +6,16 @@
 //! Type-erased variants of [`Client`] and friends.
 
 use std::fmt;
+use std::sync::{Arc, Mutex};
 
 use tower::{Layer, Service, ServiceExt};
 
 use aws_smithy_http::body::SdkBody;
 use aws_smithy_http::result::ConnectorError;
+use aws_smithy_runtime_api::client::connectors::Connector;
+use aws_smithy_runtime_api::client::orchestrator::{
+    BoxFuture as OrchestratorFuture, HttpRequest, HttpResponse,
+};
 use boxclone::*;
 
 use crate::{bounds, retry, Client};
@@ -193,6 +198,30 @@ impl Service<http::Request<SdkBody>> for DynConnector {
     }
 }
 
+/// Adapter from a [`DynConnector`] to a [`Connector`] of the orchestrator
+// TODO(enableNewSmithyRuntimeCleanup): Delete this adapter along with `DynConnector`
+#[derive(Debug)]
+pub struct DynConnectorAdapter {
+    // `DynConnector` requires `&mut self`, so we need interior mutability to adapt to it
+    dyn_connector: Arc<Mutex<DynConnector>>,
+}
+
+impl DynConnectorAdapter {
+    /// Wraps `dyn_connector` so that the orchestrator can send requests with it.
+    pub fn new(dyn_connector: DynConnector) -> Self {
+        Self {
+            dyn_connector: Arc::new(Mutex::new(dyn_connector)),
+        }
+    }
+}
+
+impl Connector for DynConnectorAdapter {
+    fn call(&self, request: HttpRequest) -> OrchestratorFuture<HttpResponse> {
+        let future = self.dyn_connector.lock().unwrap().call_lite(request);
+        future
+    }
+}
+
 /// A Smithy middleware that uses dynamic dispatch.
 ///
 /// This type allows you to pay a small runtime cost to avoid having to name the exact middleware
@@ -9,10 +9,10 @@
 use crate::erase::DynConnector;
 use aws_smithy_async::rt::sleep::SharedAsyncSleep;
 use aws_smithy_types::config_bag::{Storable, StoreReplace};
-use aws_smithy_types::timeout::TimeoutConfig;
-use std::time::Duration;
 use std::{fmt::Debug, sync::Arc};
 
+pub use aws_smithy_runtime_api::client::connectors::{ConnectorSettings, ConnectorSettingsBuilder};
+
 /// Type alias for a Connector factory function.
 pub type MakeConnectorFn =
     dyn Fn(&ConnectorSettings, Option<SharedAsyncSleep>) -> Option<DynConnector> + Send + Sync;
@@ -60,100 +60,3 @@ impl HttpConnector {
         }
     }
 }
-
-/// Builder for [`ConnectorSettings`].
-#[non_exhaustive]
-#[derive(Default, Debug)]
-pub struct ConnectorSettingsBuilder {
-    connect_timeout: Option<Duration>,
-    read_timeout: Option<Duration>,
-}
-
-impl ConnectorSettingsBuilder {
-    /// Creates a new builder.
-    pub fn new() -> Self {
-        Default::default()
-    }
-
-    /// Sets the connect timeout that should be used.
-    ///
-    /// The connect timeout is a limit on the amount of time it takes to initiate a socket connection.
-    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
-        self.connect_timeout = Some(connect_timeout);
-        self
-    }
-
-    /// Sets the connect timeout that should be used.
-    ///
-    /// The connect timeout is a limit on the amount of time it takes to initiate a socket connection.
-    pub fn set_connect_timeout(&mut self, connect_timeout: Option<Duration>) -> &mut Self {
-        self.connect_timeout = connect_timeout;
-        self
-    }
-
-    /// Sets the read timeout that should be used.
-    ///
-    /// The read timeout is the limit on the amount of time it takes to read the first byte of a response
-    /// from the time the request is initiated.
-    pub fn read_timeout(mut self, read_timeout: Duration) -> Self {
-        self.read_timeout = Some(read_timeout);
-        self
-    }
-
-    /// Sets the read timeout that should be used.
-    ///
-    /// The read timeout is the limit on the amount of time it takes to read the first byte of a response
-    /// from the time the request is initiated.
-    pub fn set_read_timeout(&mut self, read_timeout: Option<Duration>) -> &mut Self {
-        self.read_timeout = read_timeout;
-        self
-    }
-
-    /// Builds the [`ConnectorSettings`].
-    pub fn build(self) -> ConnectorSettings {
-        ConnectorSettings {
-            connect_timeout: self.connect_timeout,
-            read_timeout: self.read_timeout,
-        }
-    }
-}
-
-/// Settings for HTTP Connectors
-#[non_exhaustive]
-#[derive(Clone, Default, Debug)]
-pub struct ConnectorSettings {
-    connect_timeout: Option<Duration>,
-    read_timeout: Option<Duration>,
-}
-
-impl ConnectorSettings {
-    /// Returns a builder for `ConnectorSettings`.
-    pub fn builder() -> ConnectorSettingsBuilder {
-        Default::default()
-    }
-
-    /// Returns the connect timeout that should be used.
-    ///
-    /// The connect timeout is a limit on the amount of time it takes to initiate a socket connection.
-    pub fn connect_timeout(&self) -> Option<Duration> {
-        self.connect_timeout
-    }
-
-    /// Returns the read timeout that should be used.
-    ///
-    /// The read timeout is the limit on the amount of time it takes to read the first byte of a response
-    /// from the time the request is initiated.
-    pub fn read_timeout(&self) -> Option<Duration> {
-        self.read_timeout
-    }
-
-    // This function may be removed/refactored in the future if other non-timeout
-    // properties are added to the `ConnectorSettings` struct.
-    #[doc(hidden)]
-    pub fn from_timeout_config(timeout_config: &TimeoutConfig) -> Self {
-        Self {
-            connect_timeout: timeout_config.connect_timeout(),
-            read_timeout: timeout_config.read_timeout(),
-        }
-    }
-}
@@ -77,26 +77,20 @@
 //!     .build();
 //! ```
 
+use crate::erase::boxclone::BoxFuture;
 use crate::http_connector::ConnectorSettings;
-use crate::hyper_ext::timeout_middleware::{ConnectTimeout, HttpReadTimeout, HttpTimeoutError};
 use crate::never::stream::EmptyStream;
-use aws_smithy_async::future::timeout::TimedOutError;
-use aws_smithy_async::rt::sleep::{default_async_sleep, SharedAsyncSleep};
+use crate::proxy::ProxyConfig;
+use aws_smithy_async::rt::sleep::SharedAsyncSleep;
 use aws_smithy_http::body::SdkBody;
-
 use aws_smithy_http::result::ConnectorError;
-use aws_smithy_types::error::display::DisplayErrorContext;
-use aws_smithy_types::retry::ErrorKind;
-use http::{Extensions, Uri};
-use hyper::client::connect::{
-    capture_connection, CaptureConnection, Connected, Connection, HttpInfo,
+use aws_smithy_runtime::client::connectors::hyper_connector::{
+    HyperConnector, HyperConnectorBuilder,
 };
-
-use std::error::Error;
-use std::fmt::Debug;
-
-use crate::erase::boxclone::BoxFuture;
-use aws_smithy_http::connection::{CaptureSmithyConnection, ConnectionMetadata};
+use aws_smithy_runtime_api::client::connectors::Connector;
+use http::Uri;
+use hyper::client::connect::{Connected, Connection};
+use std::marker::PhantomData;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tower::{BoxError, Service};
 
@@ -104,40 +98,15 @@ use tower::{BoxError, Service};
 ///
 /// This adapter also enables TCP `CONNECT` and HTTP `READ` timeouts via [`Adapter::builder`]. For examples
 /// see [the module documentation](crate::hyper_ext).
+///
+/// The requests are sent by a [`HyperConnector`], which this adapter makes usable as a tower [`Service`].
 #[derive(Clone, Debug)]
 pub struct Adapter<C> {
-    client: HttpReadTimeout<hyper::Client<ConnectTimeout<C>, SdkBody>>,
+    connector: HyperConnector,
+    _connector_type: PhantomData<fn() -> C>,
 }
 
-/// Extract a smithy connection from a hyper CaptureConnection
-fn extract_smithy_connection(capture_conn: &CaptureConnection) -> Option<ConnectionMetadata> {
-    let capture_conn = capture_conn.clone();
-    if let Some(conn) = capture_conn.clone().connection_metadata().as_ref() {
-        let mut extensions = Extensions::new();
-        conn.get_extras(&mut extensions);
-        let http_info = extensions.get::<HttpInfo>();
-        let smithy_connection = ConnectionMetadata::new(
-            conn.is_proxied(),
-            http_info.map(|info| info.remote_addr()),
-            move || match capture_conn.connection_metadata().as_ref() {
-                Some(conn) => conn.poison(),
-                None => tracing::trace!("no connection existed to poison"),
-            },
-        );
-        Some(smithy_connection)
-    } else {
-        None
-    }
-}
-
-impl<C> Service<http::Request<SdkBody>> for Adapter<C>
-where
-    C: Clone + Send + Sync + 'static,
-    C: Service<Uri>,
-    C::Response: Connection + AsyncRead + AsyncWrite + Send + Unpin + 'static,
-    C::Future: Unpin + Send + 'static,
-    C::Error: Into<BoxError>,
-{
+impl<C> Service<http::Request<SdkBody>> for Adapter<C> {
     type Response = http::Response<SdkBody>;
     type Error = ConnectorError;
 
@@ -145,19 +114,22 @@ where
 
     fn poll_ready(
         &mut self,
-        cx: &mut std::task::Context<'_>,
+        _cx: &mut std::task::Context<'_>,
     ) -> std::task::Poll<Result<(), Self::Error>> {
-        self.client.poll_ready(cx).map_err(downcast_error)
+        std::task::Poll::Ready(Ok(()))
     }
 
-    fn call(&mut self, mut req: http::Request<SdkBody>) -> Self::Future {
-        let capture_connection = capture_connection(&mut req);
-        if let Some(capture_smithy_connection) = req.extensions().get::<CaptureSmithyConnection>() {
-            capture_smithy_connection
-                .set_connection_retriever(move || extract_smithy_connection(&capture_connection));
-        }
-        let fut = self.client.call(req);
-        Box::pin(async move { Ok(fut.await.map_err(downcast_error)?.map(SdkBody::from)) })
+    fn call(&mut self, req: http::Request<SdkBody>) -> Self::Future {
+        let fut = self.connector.call(req);
+        Box::pin(async move { fut.await.map_err(to_connector_error) })
+    }
+}
+
+/// The [`HyperConnector`] fails with `ConnectorError`s, but returns them as boxed errors
+fn to_connector_error(err: BoxError) -> ConnectorError {
+    match err.downcast::<ConnectorError>() {
+        Ok(connector_error) => *connector_error,
+        Err(box_error) => ConnectorError::other(box_error, None),
     }
 }
 
@@ -171,58 +143,6 @@ impl Adapter<()> {
     }
 }
 
-/// Downcast errors coming out of hyper into an appropriate `ConnectorError`
-fn downcast_error(err: BoxError) -> ConnectorError {
-    // is a `TimedOutError` (from aws_smithy_async::timeout) in the chain? if it is, this is a timeout
-    if find_source::<TimedOutError>(err.as_ref()).is_some() {
-        return ConnectorError::timeout(err);
-    }
-    // is the top of chain error actually already a `ConnectorError`? return that directly
-    let err = match err.downcast::<ConnectorError>() {
-        Ok(connector_error) => return *connector_error,
-        Err(box_error) => box_error,
-    };
-    // generally, the top of chain will probably be a hyper error. Go through a set of hyper specific
-    // error classifications
-    let err = match err.downcast::<hyper::Error>() {
-        Ok(hyper_error) => return to_connector_error(*hyper_error),
-        Err(box_error) => box_error,
-    };
-
-    // otherwise, we have no idea!
-    ConnectorError::other(err, None)
-}
-
-/// Convert a [`hyper::Error`] into a [`ConnectorError`]
-fn to_connector_error(err: hyper::Error) -> ConnectorError {
-    if err.is_timeout() || find_source::<HttpTimeoutError>(&err).is_some() {
-        ConnectorError::timeout(err.into())
-    } else if err.is_user() {
-        ConnectorError::user(err.into())
-    } else if err.is_closed() || err.is_canceled() || find_source::<std::io::Error>(&err).is_some()
-    {
-        ConnectorError::io(err.into())
-    }
-    // We sometimes receive this from S3: hyper::Error(IncompleteMessage)
-    else if err.is_incomplete_message() {
-        ConnectorError::other(err.into(), Some(ErrorKind::TransientError))
-    } else {
-        tracing::warn!(err = %DisplayErrorContext(&err), "unrecognized error from Hyper. If this error should be retried, please file an issue.");
-        ConnectorError::other(err.into(), None)
-    }
-}
-
-fn find_source<'a, E: Error + 'static>(err: &'a (dyn Error + 'static)) -> Option<&'a E> {
-    let mut next = Some(err);
-    while let Some(err) = next {
-        if let Some(matching_err) = err.downcast_ref::<E>() {
-            return Some(matching_err);
-        }
-        next = err.source();
-    }
-    None
-}
-
 /// Builder for [`hyper_ext::Adapter`](Adapter)
 ///
 /// Unlike a Smithy client, the [`Service`] inside a [`hyper_ext::Adapter`](Adapter) is actually a service that
@@ -249,9 +169,7 @@ fn find_source<'a, E: Error + 'static>(err: &'a (dyn Error + 'static)) -> Option
 /// ```
 #[derive(Default, Debug)]
 pub struct Builder {
-    connector_settings: Option<ConnectorSettings>,
-    sleep_impl: Option<SharedAsyncSleep>,
-    client_builder: Option<hyper::client::Builder>,
+    inner: HyperConnectorBuilder,
 }
 
 impl Builder {
@@ -264,59 +182,33 @@ impl Builder {
         C::Future: Unpin + Send + 'static,
         C::Error: Into<BoxError>,
     {
-        let client_builder = self.client_builder.unwrap_or_default();
-        let sleep_impl = self.sleep_impl.or_else(default_async_sleep);
-        let (connect_timeout, read_timeout) = self
-            .connector_settings
-            .map(|c| (c.connect_timeout(), c.read_timeout()))
-            .unwrap_or((None, None));
-
-        // if we are using Hyper, Tokio must already be enabled so we can fallback to Tokio.
-        let connector = match connect_timeout {
-            Some(duration) => ConnectTimeout::new(
-                connector,
-                sleep_impl
-                    .clone()
-                    .expect("a sleep impl must be provided in order to have a connect timeout"),
-                duration,
-            ),
-            None => ConnectTimeout::no_timeout(connector),
-        };
-        let base = client_builder.build(connector);
-        let read_timeout = match read_timeout {
-            Some(duration) => HttpReadTimeout::new(
-                base,
-                sleep_impl.expect("a sleep impl must be provided in order to have a read timeout"),
-                duration,
-            ),
-            None => HttpReadTimeout::no_timeout(base),
-        };
         Adapter {
-            client: read_timeout,
+            connector: self.inner.build(connector),
+            _connector_type: PhantomData,
         }
     }
 
     /// Set the async sleep implementation used for timeouts
     ///
     /// Calling this is only necessary for testing or to use something other than
-    /// [`default_async_sleep`].
+    /// [`default_async_sleep`](aws_smithy_async::rt::sleep::default_async_sleep).
     pub fn sleep_impl(mut self, sleep_impl: SharedAsyncSleep) -> Self {
-        self.sleep_impl = Some(sleep_impl);
+        self.inner.set_sleep_impl(Some(sleep_impl));
         self
     }
 
     /// Set the async sleep implementation used for timeouts
     ///
     /// Calling this is only necessary for testing or to use something other than
-    /// [`default_async_sleep`].
+    /// [`default_async_sleep`](aws_smithy_async::rt::sleep::default_async_sleep).
     pub fn set_sleep_impl(&mut self, sleep_impl: Option<SharedAsyncSleep>) -> &mut Self {
-        self.sleep_impl = sleep_impl;
+        self.inner.set_sleep_impl(sleep_impl);
         self
     }
 
     /// Configure the HTTP settings for the `HyperAdapter`
     pub fn connector_settings(mut self, connector_settings: ConnectorSettings) -> Self {
-        self.connector_settings = Some(connector_settings);
+        self.inner.set_connector_settings(Some(connector_settings));
         self
     }
 
@@ -325,7 +217,7 @@ impl Builder {
         &mut self,
         connector_settings: Option<ConnectorSettings>,
     ) -> &mut Self {
-        self.connector_settings = connector_settings;
+        self.inner.set_connector_settings(connector_settings);
         self
     }
 
@@ -333,7 +225,7 @@ impl Builder {
     ///
     /// This enables changing settings like forcing HTTP2 and modifying other default client behavior.
     pub fn hyper_builder(mut self, hyper_builder: hyper::client::Builder) -> Self {
-        self.client_builder = Some(hyper_builder);
+        self.inner.set_hyper_builder(Some(hyper_builder));
         self
     }
 
@@ -344,309 +236,27 @@ impl Builder {
         &mut self,
         hyper_builder: Option<hyper::client::Builder>,
     ) -> &mut Self {
-        self.client_builder = hyper_builder;
+        self.inner.set_hyper_builder(hyper_builder);
         self
     }
-}
-
-mod timeout_middleware {
-    use std::error::Error;
-    use std::fmt::Formatter;
-    use std::future::Future;
-    use std::pin::Pin;
-    use std::task::{Context, Poll};
-    use std::time::Duration;
-
-    use http::Uri;
-    use pin_project_lite::pin_project;
-    use tower::BoxError;
-
-    use aws_smithy_async::future::timeout::{TimedOutError, Timeout};
-    use aws_smithy_async::rt::sleep::Sleep;
-    use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
-
-    #[derive(Debug)]
-    pub(crate) struct HttpTimeoutError {
-        kind: &'static str,
-        duration: Duration,
-    }
 
-    impl std::fmt::Display for HttpTimeoutError {
-        fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-            write!(
-                f,
-                "{} timeout occurred after {:?}",
-                self.kind, self.duration
-            )
-        }
-    }
-
-    impl Error for HttpTimeoutError {
-        // We implement the `source` function as returning a `TimedOutError` because when `downcast_error`
-        // or `find_source` is called with an `HttpTimeoutError` (or another error wrapping an `HttpTimeoutError`)
-        // this method will be checked to determine if it's a timeout-related error.
-        fn source(&self) -> Option<&(dyn Error + 'static)> {
-            Some(&TimedOutError)
-        }
-    }
-
-    /// Timeout wrapper that will timeout on the initial TCP connection
+    /// Configure the HTTP proxy that the connector given to [`build`](Self::build) connects through
     ///
-    /// # Stability
-    /// This interface is unstable.
-    #[derive(Clone, Debug)]
-    pub(super) struct ConnectTimeout<I> {
-        inner: I,
-        timeout: Option<(SharedAsyncSleep, Duration)>,
-    }
-
-    impl<I> ConnectTimeout<I> {
-        /// Create a new `ConnectTimeout` around `inner`.
-        ///
-        /// Typically, `I` will implement [`hyper::client::connect::Connect`].
-        pub(crate) fn new(inner: I, sleep: SharedAsyncSleep, timeout: Duration) -> Self {
-            Self {
-                inner,
-                timeout: Some((sleep, timeout)),
-            }
-        }
-
-        pub(crate) fn no_timeout(inner: I) -> Self {
-            Self {
-                inner,
-                timeout: None,
-            }
-        }
-    }
-
-    #[derive(Clone, Debug)]
-    pub(crate) struct HttpReadTimeout<I> {
-        inner: I,
-        timeout: Option<(SharedAsyncSleep, Duration)>,
-    }
-
-    impl<I> HttpReadTimeout<I> {
-        /// Create a new `HttpReadTimeout` around `inner`.
-        ///
-        /// Typically, `I` will implement [`tower::Service<http::Request<SdkBody>>`].
-        pub(crate) fn new(inner: I, sleep: SharedAsyncSleep, timeout: Duration) -> Self {
-            Self {
-                inner,
-                timeout: Some((sleep, timeout)),
-            }
-        }
-
-        pub(crate) fn no_timeout(inner: I) -> Self {
-            Self {
-                inner,
-                timeout: None,
-            }
-        }
-    }
-
-    pin_project! {
-        /// Timeout future for Tower services
-        ///
-        /// Timeout future to handle timing out, mapping errors, and the possibility of not timing out
-        /// without incurring an additional allocation for each timeout layer.
-        #[project = MaybeTimeoutFutureProj]
-        pub enum MaybeTimeoutFuture<F> {
-            Timeout {
-                #[pin]
-                timeout: Timeout<F, Sleep>,
-                error_type: &'static str,
-                duration: Duration,
-            },
-            NoTimeout {
-                #[pin]
-                future: F
-            }
-        }
-    }
-
-    impl<F, T, E> Future for MaybeTimeoutFuture<F>
-    where
-        F: Future<Output = Result<T, E>>,
-        E: Into<BoxError>,
-    {
-        type Output = Result<T, BoxError>;
-
-        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-            let (timeout_future, kind, &mut duration) = match self.project() {
-                MaybeTimeoutFutureProj::NoTimeout { future } => {
-                    return future.poll(cx).map_err(|err| err.into());
-                }
-                MaybeTimeoutFutureProj::Timeout {
-                    timeout,
-                    error_type,
-                    duration,
-                } => (timeout, error_type, duration),
-            };
-            match timeout_future.poll(cx) {
-                Poll::Ready(Ok(response)) => Poll::Ready(response.map_err(|err| err.into())),
-                Poll::Ready(Err(_timeout)) => {
-                    Poll::Ready(Err(HttpTimeoutError { kind, duration }.into()))
-                }
-                Poll::Pending => Poll::Pending,
-            }
-        }
-    }
-
-    impl<I> tower::Service<Uri> for ConnectTimeout<I>
-    where
-        I: tower::Service<Uri>,
-        I::Error: Into<BoxError>,
-    {
-        type Response = I::Response;
-        type Error = BoxError;
-        type Future = MaybeTimeoutFuture<I::Future>;
-
-        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-            self.inner.poll_ready(cx).map_err(|err| err.into())
-        }
-
-        fn call(&mut self, req: Uri) -> Self::Future {
-            match &self.timeout {
-                Some((sleep, duration)) => {
-                    let sleep = sleep.sleep(*duration);
-                    MaybeTimeoutFuture::Timeout {
-                        timeout: Timeout::new(self.inner.call(req), sleep),
-                        error_type: "HTTP connect",
-                        duration: *duration,
-                    }
-                }
-                None => MaybeTimeoutFuture::NoTimeout {
-                    future: self.inner.call(req),
-                },
-            }
-        }
-    }
-
-    impl<I, B> tower::Service<http::Request<B>> for HttpReadTimeout<I>
-    where
-        I: tower::Service<http::Request<B>, Error = hyper::Error>,
-    {
-        type Response = I::Response;
-        type Error = BoxError;
-        type Future = MaybeTimeoutFuture<I::Future>;
-
-        fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-            self.inner.poll_ready(cx).map_err(|err| err.into())
-        }
-
-        fn call(&mut self, req: http::Request<B>) -> Self::Future {
-            match &self.timeout {
-                Some((sleep, duration)) => {
-                    let sleep = sleep.sleep(*duration);
-                    MaybeTimeoutFuture::Timeout {
-                        timeout: Timeout::new(self.inner.call(req), sleep),
-                        error_type: "HTTP read",
-                        duration: *duration,
-                    }
-                }
-                None => MaybeTimeoutFuture::NoTimeout {
-                    future: self.inner.call(req),
-                },
-            }
-        }
+    /// This doesn't change how the connector connects: it must already go through the proxy, e.g.
+    /// by wrapping a [`ProxyConnector`](crate::proxy::ProxyConnector) like
+    /// [`conns::https_with_proxy`](crate::conns::https_with_proxy) does. The adapter sends the
+    /// proxy's credentials with the plain HTTP requests that the proxy forwards.
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.inner.set_proxy(Some(proxy));
+        self
     }
 
-    #[cfg(test)]
-    mod test {
-        use crate::http_connector::ConnectorSettings;
-        use crate::hyper_ext::Adapter;
-        use crate::never::{NeverConnected, NeverReplies};
-        use aws_smithy_async::assert_elapsed;
-        use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
-        use aws_smithy_http::body::SdkBody;
-        use aws_smithy_types::error::display::DisplayErrorContext;
-        use aws_smithy_types::timeout::TimeoutConfig;
-        use std::time::Duration;
-        use tower::Service;
-
-        #[allow(unused)]
-        fn connect_timeout_is_correct<T: Send + Sync + Clone + 'static>() {
-            is_send_sync::<super::ConnectTimeout<T>>();
-        }
-
-        #[allow(unused)]
-        fn is_send_sync<T: Send + Sync>() {}
-
-        #[tokio::test]
-        async fn http_connect_timeout_works() {
-            let inner = NeverConnected::new();
-            let connector_settings = ConnectorSettings::from_timeout_config(
-                &TimeoutConfig::builder()
-                    .connect_timeout(Duration::from_secs(1))
-                    .build(),
-            );
-            let mut hyper = Adapter::builder()
-                .connector_settings(connector_settings)
-                .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
-                .build(inner);
-            let now = tokio::time::Instant::now();
-            tokio::time::pause();
-            let resp = hyper
-                .call(
-                    http::Request::builder()
-                        .uri("http://foo.com")
-                        .body(SdkBody::empty())
-                        .unwrap(),
-                )
-                .await
-                .unwrap_err();
-            assert!(
-                resp.is_timeout(),
-                "expected resp.is_timeout() to be true but it was false, resp == {:?}",
-                resp
-            );
-            let message = DisplayErrorContext(&resp).to_string();
-            let expected =
-                "timeout: error trying to connect: HTTP connect timeout occurred after 1s";
-            assert!(
-                message.contains(expected),
-                "expected '{message}' to contain '{expected}'"
-            );
-            assert_elapsed!(now, Duration::from_secs(1));
-        }
-
-        #[tokio::test]
-        async fn http_read_timeout_works() {
-            let inner = NeverReplies::new();
-            let connector_settings = ConnectorSettings::from_timeout_config(
-                &TimeoutConfig::builder()
-                    .connect_timeout(Duration::from_secs(1))
-                    .read_timeout(Duration::from_secs(2))
-                    .build(),
-            );
-            let mut hyper = Adapter::builder()
-                .connector_settings(connector_settings)
-                .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
-                .build(inner);
-            let now = tokio::time::Instant::now();
-            tokio::time::pause();
-            let resp = hyper
-                .call(
-                    http::Request::builder()
-                        .uri("http://foo.com")
-                        .body(SdkBody::empty())
-                        .unwrap(),
-                )
-                .await
-                .unwrap_err();
-            assert!(
-                resp.is_timeout(),
-                "expected resp.is_timeout() to be true but it was false, resp == {:?}",
-                resp
-            );
-            let message = format!("{}", DisplayErrorContext(&resp));
-            let expected = "timeout: HTTP read timeout occurred after 2s";
-            assert!(
-                message.contains(expected),
-                "expected '{message}' to contain '{expected}'"
-            );
-            assert_elapsed!(now, Duration::from_secs(2));
-        }
+    /// Configure the HTTP proxy that the connector given to [`build`](Self::build) connects through
+    ///
+    /// See [`proxy`](Self::proxy) for details.
+    pub fn set_proxy(&mut self, proxy: Option<ProxyConfig>) -> &mut Self {
+        self.inner.set_proxy(proxy);
+        self
     }
 }
 
@@ -659,15 +269,97 @@ impl Connection for EmptyStream {
 
 #[cfg(test)]
 mod test {
+    use crate::http_connector::ConnectorSettings;
     use crate::hyper_ext::Adapter;
+    use crate::never::{NeverConnected, NeverReplies};
+    use aws_smithy_async::assert_elapsed;
+    use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
     use aws_smithy_http::body::SdkBody;
+    use aws_smithy_types::error::display::DisplayErrorContext;
+    use aws_smithy_types::timeout::TimeoutConfig;
     use http::Uri;
     use hyper::client::connect::{Connected, Connection};
     use std::io::{Error, ErrorKind};
     use std::pin::Pin;
     use std::task::{Context, Poll};
+    use std::time::Duration;
     use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
-    use tower::BoxError;
+    use tower::{BoxError, Service};
+
+    #[tokio::test]
+    async fn http_connect_timeout_works() {
+        let inner = NeverConnected::new();
+        let connector_settings = ConnectorSettings::from_timeout_config(
+            &TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(1))
+                .build(),
+        );
+        let mut hyper = Adapter::builder()
+            .connector_settings(connector_settings)
+            .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
+            .build(inner);
+        let now = tokio::time::Instant::now();
+        tokio::time::pause();
+        let resp = hyper
+            .call(
+                http::Request::builder()
+                    .uri("http://foo.com")
+                    .body(SdkBody::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            resp.is_timeout(),
+            "expected resp.is_timeout() to be true but it was false, resp == {:?}",
+            resp
+        );
+        let message = DisplayErrorContext(&resp).to_string();
+        let expected = "timeout: error trying to connect: HTTP connect timeout occurred after 1s";
+        assert!(
+            message.contains(expected),
+            "expected '{message}' to contain '{expected}'"
+        );
+        assert_elapsed!(now, Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn http_read_timeout_works() {
+        let inner = NeverReplies::new();
+        let connector_settings = ConnectorSettings::from_timeout_config(
+            &TimeoutConfig::builder()
+                .connect_timeout(Duration::from_secs(1))
+                .read_timeout(Duration::from_secs(2))
+                .build(),
+        );
+        let mut hyper = Adapter::builder()
+            .connector_settings(connector_settings)
+            .sleep_impl(SharedAsyncSleep::new(TokioSleep::new()))
+            .build(inner);
+        let now = tokio::time::Instant::now();
+        tokio::time::pause();
+        let resp = hyper
+            .call(
+                http::Request::builder()
+                    .uri("http://foo.com")
+                    .body(SdkBody::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap_err();
+        assert!(
+            resp.is_timeout(),
+            "expected resp.is_timeout() to be true but it was false, resp == {:?}",
+            resp
+        );
+        let message = format!("{}", DisplayErrorContext(&resp));
+        let expected = "timeout: HTTP read timeout occurred after 2s";
+        assert!(
+            message.contains(expected),
+            "expected '{message}' to contain '{expected}'"
+        );
+        assert_elapsed!(now, Duration::from_secs(2));
+    }
 
     #[tokio::test]
     async fn hyper_io_error() {
@@ -675,7 +367,6 @@ mod test {
             inner: HangupStream,
         };
         let mut adapter = Adapter::builder().build(connector);
-        use tower::Service;
         let err = adapter
             .call(
                 http::Request::builder()
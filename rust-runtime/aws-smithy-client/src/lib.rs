@@ -43,6 +43,12 @@ pub mod test_connection;
 pub mod conns;
 #[cfg(feature = "client-hyper")]
 pub mod hyper_ext;
+#[cfg(feature = "rustls")]
+pub use aws_smithy_runtime::client::connectors::hyper_connector::tls;
+#[cfg(feature = "client-hyper")]
+pub use aws_smithy_runtime::client::connectors::hyper_connector::{
+    connection_events, dns, happy_eyeballs, proxy,
+};
 
 // The types in this module are only used to write the bounds in [`Client::check`]. Customers will
 // not need them. But the module and its types must be public so that we can call `check` from
@@ -501,6 +501,7 @@ pub mod wire_mock {
         }
     }
 
+    use crate::dns::{DnsAnswer, ResolveDns};
     use crate::erase::boxclone::BoxFuture;
     use crate::http_connector::HttpConnector;
     use crate::hyper_ext;
@@ -642,6 +643,7 @@ pub mod wire_mock {
     /// DNS resolver that keeps a log of all lookups
     ///
     /// Regardless of what hostname is requested, it will always return the same socket address.
+    /// It can be plugged into a hyper `HttpConnector`, or used as a [`ResolveDns`].
     #[derive(Clone, Debug)]
     pub struct LoggingDnsResolver {
         log: Arc<Mutex<Vec<RecordedEvent>>>,
@@ -669,6 +671,17 @@ pub mod wire_mock {
             })
         }
     }
+
+    impl ResolveDns for LoggingDnsResolver {
+        fn resolve_dns(&self, name: &str) -> BoxFuture<DnsAnswer, tower::BoxError> {
+            self.log
+                .lock()
+                .unwrap()
+                .push(RecordedEvent::DnsLookup(name.to_owned()));
+            let answer = DnsAnswer::new(vec![self.socket_addr.ip()]);
+            Box::pin(async move { Ok(answer) })
+        }
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,173 @@
+/*
+ *  Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ *  SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::error::Error;
+use std::fmt;
+
+/// Diagnostic collector for endpoint resolution
+///
+/// Endpoint functions return `Option<T>`—to enable diagnostic information to flow, we capture the
+/// last error that occurred.
+///
+/// When created with [`DiagnosticCollector::with_trace`], the collector also records every
+/// condition that was evaluated and the rule that was selected.
+#[derive(Debug, Default)]
+pub(crate) struct DiagnosticCollector {
+    last_error: Option<Box<dyn Error + Send + Sync>>,
+    trace: Option<Vec<String>>,
+}
+
+impl DiagnosticCollector {
+    #[allow(unused)]
+    /// Report an error to the collector
+    pub(crate) fn report_error(&mut self, err: impl Into<Box<dyn Error + Send + Sync>>) {
+        self.last_error = Some(err.into());
+    }
+
+    #[allow(unused)]
+    /// Capture a result, returning Some(t) when the input was `Ok` and `None` otherwise
+    pub(crate) fn capture<T, E: Into<Box<dyn Error + Send + Sync>>>(
+        &mut self,
+        err: Result<T, E>,
+    ) -> Option<T> {
+        match err {
+            Ok(res) => Some(res),
+            Err(e) => {
+                self.report_error(e);
+                None
+            }
+        }
+    }
+
+    /// Take the last error that occurred
+    ///
+    /// When a trace was recorded, the trace is returned instead, with the last error as its source.
+    pub(crate) fn take_last_error(&mut self) -> Option<Box<dyn Error + Send + Sync>> {
+        match self.trace.take() {
+            Some(events) => Some(Box::new(EvaluationTrace {
+                events,
+                source: self.last_error.take(),
+            })),
+            None => self.last_error.take(),
+        }
+    }
+
+    /// Create a new diagnostic collector
+    pub(crate) fn new() -> Self {
+        Self {
+            last_error: None,
+            trace: None,
+        }
+    }
+
+    #[allow(unused)]
+    /// Create a new diagnostic collector that records a trace of the evaluated rules
+    pub(crate) fn with_trace() -> Self {
+        Self {
+            last_error: None,
+            trace: Some(Vec::new()),
+        }
+    }
+
+    #[allow(unused)]
+    /// Record the `result` of evaluating `condition` and return it
+    ///
+    /// The collector is the last argument so that `result` can be evaluated with it. Nothing is
+    /// formatted unless a trace is being recorded.
+    pub(crate) fn record_condition<T: fmt::Debug>(
+        condition: impl fmt::Display,
+        result: T,
+        e: &mut DiagnosticCollector,
+    ) -> T {
+        if let Some(trace) = e.trace.as_mut() {
+            trace.push(format!("{} => {:?}", condition, result));
+        }
+        result
+    }
+
+    #[allow(unused)]
+    /// Record the rule that was selected, e.g. `endpoint https://{Region}.example.com`
+    pub(crate) fn record_rule(&mut self, rule: impl fmt::Display) {
+        if let Some(trace) = self.trace.as_mut() {
+            trace.push(format!("selected {}", rule));
+        }
+    }
+
+    #[allow(unused)]
+    /// Emit the recorded trace as a `debug` event, if a trace is being recorded
+    pub(crate) fn emit_trace(&self) {
+        if let Some(events) = &self.trace {
+            tracing::debug!(trace = ?events, "evaluated endpoint rules");
+        }
+    }
+}
+
+/// Trace of the conditions and rules that were evaluated to resolve an endpoint
+///
+/// This is the source of endpoint resolution errors when tracing was enabled. Its own source is
+/// the last error that the endpoint functions reported.
+#[derive(Debug)]
+pub(crate) struct EvaluationTrace {
+    events: Vec<String>,
+    source: Option<Box<dyn Error + Send + Sync>>,
+}
+
+impl fmt::Display for EvaluationTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "endpoint rule evaluation trace:")?;
+        for event in &self.events {
+            write!(f, "\n    {}", event)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for EvaluationTrace {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_ref().map(|err| err.as_ref() as _)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DiagnosticCollector;
+
+    #[test]
+    fn only_the_last_error_without_trace() {
+        let mut e = DiagnosticCollector::new();
+        assert!(DiagnosticCollector::record_condition(
+            "isSet(Region)",
+            true,
+            &mut e
+        ));
+        e.record_rule("endpoint https://example.com");
+        e.report_error("first");
+        e.report_error("second");
+        assert_eq!("second", e.take_last_error().unwrap().to_string());
+        assert!(e.take_last_error().is_none());
+    }
+
+    #[test]
+    fn trace_is_attached_to_the_error() {
+        let mut e = DiagnosticCollector::with_trace();
+        assert_eq!(
+            Some("us-east-1"),
+            DiagnosticCollector::record_condition("isSet(Region)", Some("us-east-1"), &mut e)
+        );
+        DiagnosticCollector::record_condition("booleanEquals(UseFIPS, true)", false, &mut e);
+        e.record_rule("error \"Missing Region\"");
+        e.report_error("invalid region");
+
+        let err = e.take_last_error().unwrap();
+        assert_eq!(
+            "endpoint rule evaluation trace:\n    \
+            isSet(Region) => Some(\"us-east-1\")\n    \
+            booleanEquals(UseFIPS, true) => false\n    \
+            selected error \"Missing Region\"",
+            err.to_string()
+        );
+        assert_eq!("invalid region", err.source().unwrap().to_string());
+    }
+}
@@ -0,0 +1,87 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Runtime interpreter for Smithy endpoint rule sets.
+//!
+//! Generated clients compile the endpoint rules of a service into Rust code. This crate loads a
+//! rule set and the partitions it refers to from JSON instead, and evaluates them with the same
+//! standard library that the generated code uses. This makes it possible to test or hot-fix
+//! endpoint rules, e.g. for private partitions, without regenerating a client.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use aws_smithy_endpoint_rules::{EndpointRuleSet, Params, Partitions, RuleSetResolver};
+//! use aws_smithy_http::endpoint::ResolveEndpoint;
+//!
+//! let rule_set = EndpointRuleSet::from_json(br#"{
+//!     "version": "1.0",
+//!     "parameters": {
+//!         "Region": { "type": "String", "builtIn": "AWS::Region", "required": true }
+//!     },
+//!     "rules": [{
+//!         "conditions": [
+//!             { "fn": "aws.partition", "argv": [{ "ref": "Region" }], "assign": "partition" }
+//!         ],
+//!         "endpoint": { "url": "https://service.{Region}.{partition#dnsSuffix}" },
+//!         "type": "endpoint"
+//!     }]
+//! }"#).expect("valid rule set");
+//! let partitions = Partitions::from_json(br#"{
+//!     "version": "1.1",
+//!     "partitions": [{
+//!         "id": "aws",
+//!         "regionRegex": "^(us|eu)-\\w+-\\d+$",
+//!         "regions": {},
+//!         "outputs": {
+//!             "name": "aws",
+//!             "dnsSuffix": "amazonaws.com",
+//!             "dualStackDnsSuffix": "api.aws",
+//!             "supportsFIPS": true,
+//!             "supportsDualStack": true
+//!         }
+//!     }]
+//! }"#).expect("valid partitions");
+//!
+//! let resolver = RuleSetResolver::new(rule_set, partitions);
+//! let endpoint = resolver
+//!     .resolve_endpoint(&Params::builder().built_in("AWS::Region", "eu-west-1").build())
+//!     .expect("rules match");
+//! assert_eq!("https://service.eu-west-1.amazonaws.com", endpoint.url());
+//! ```
+
+#![allow(clippy::derive_partial_eq_without_eq)]
+#![warn(
+    missing_docs,
+    rustdoc::missing_crate_level_docs,
+    unreachable_pub,
+    rust_2018_idioms
+)]
+
+// Vendored copy of the standard library that generated clients inline from `rust-runtime/inlineable`.
+// The `endpoint_lib_is_in_sync` test fails if the two copies drift apart.
+#[allow(unused, rust_2018_idioms)]
+mod endpoint_lib {
+    pub(crate) mod arn;
+    pub(crate) mod diagnostic;
+    pub(crate) mod host;
+    pub(crate) mod parse_url;
+    pub(crate) mod partition;
+    pub(crate) mod s3;
+    pub(crate) mod substring;
+    pub(crate) mod uri_encode;
+}
+
+mod error;
+mod params;
+mod resolver;
+mod ruleset;
+mod value;
+
+pub use error::LoadError;
+pub use params::{Params, ParamsBuilder};
+pub use resolver::{Partitions, RuleSetResolver};
+pub use ruleset::EndpointRuleSet;
+pub use value::Value;
@@ -0,0 +1,64 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::value::Value;
+use std::collections::HashMap;
+
+/// Parameters for resolving an endpoint with a [`RuleSetResolver`](crate::RuleSetResolver)
+///
+/// Parameters are set either by the name the rule set gives them, e.g. `Region`, or by the
+/// built-in they are bound to, e.g. `AWS::Region`. A parameter that is set by name takes
+/// precedence over its built-in.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Params {
+    params: HashMap<String, Value>,
+    built_ins: HashMap<String, Value>,
+}
+
+impl Params {
+    /// Create a builder for [`Params`]
+    pub fn builder() -> ParamsBuilder {
+        ParamsBuilder::default()
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&Value> {
+        self.params.get(name)
+    }
+
+    pub(crate) fn get_built_in(&self, name: &str) -> Option<&Value> {
+        self.built_ins.get(name)
+    }
+
+    pub(crate) fn names(&self) -> impl Iterator<Item = &str> {
+        self.params.keys().map(String::as_str)
+    }
+}
+
+/// Builder for [`Params`]
+#[derive(Clone, Debug, Default)]
+pub struct ParamsBuilder {
+    params: Params,
+}
+
+impl ParamsBuilder {
+    /// Set the parameter with the given `name`
+    pub fn param(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.params.params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Set the value of a built-in, e.g. `AWS::Region`
+    ///
+    /// The value is used for every parameter of the rule set that is bound to this built-in.
+    pub fn built_in(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.params.built_ins.insert(name.into(), value.into());
+        self
+    }
+
+    /// Create the [`Params`]
+    pub fn build(self) -> Params {
+        self.params
+    }
+}
@@ -0,0 +1,632 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::endpoint_lib::arn::parse_arn;
+use crate::endpoint_lib::diagnostic::DiagnosticCollector;
+use crate::endpoint_lib::host::is_valid_host_label;
+use crate::endpoint_lib::parse_url::parse_url;
+use crate::endpoint_lib::partition::PartitionResolver;
+use crate::endpoint_lib::s3::is_virtual_hostable_s3_bucket;
+use crate::endpoint_lib::substring::substring;
+use crate::endpoint_lib::uri_encode::uri_encode;
+use crate::error::LoadError;
+use crate::params::Params;
+use crate::ruleset::{
+    EndpointRuleSet, EndpointTemplate, Expr, Function, PathPart, Rule, RuleKind, TemplatePart,
+};
+use crate::value::Value;
+use aws_smithy_http::endpoint::{ResolveEndpoint, ResolveEndpointError};
+use aws_smithy_types::endpoint::Endpoint;
+use std::collections::HashMap;
+
+/// Partitions that the `aws.partition` function resolves regions to
+#[derive(Debug)]
+pub struct Partitions {
+    resolver: PartitionResolver,
+}
+
+impl Partitions {
+    /// Load partitions from their JSON representation (`partitions.json`)
+    pub fn from_json(partitions_json: &[u8]) -> Result<Self, LoadError> {
+        Ok(Self {
+            resolver: PartitionResolver::new_from_json(partitions_json).map_err(LoadError::json)?,
+        })
+    }
+
+    /// Partitions that don't contain any partition
+    ///
+    /// This is useful for rule sets that don't use the `aws.partition` function.
+    pub fn empty() -> Self {
+        Self {
+            resolver: PartitionResolver::empty(),
+        }
+    }
+}
+
+/// Endpoint resolver that evaluates an [`EndpointRuleSet`] at runtime
+///
+/// The rules are evaluated with the same standard library as the resolvers that are generated
+/// for clients, so the resolved endpoints match those of a client that was generated with the
+/// same rule set.
+#[derive(Debug)]
+pub struct RuleSetResolver {
+    rule_set: EndpointRuleSet,
+    partitions: Partitions,
+    trace: bool,
+}
+
+impl RuleSetResolver {
+    /// Create a new resolver for the given rule set and partitions
+    pub fn new(rule_set: EndpointRuleSet, partitions: Partitions) -> Self {
+        Self {
+            rule_set,
+            partitions,
+            trace: false,
+        }
+    }
+
+    /// Record a trace of the conditions and rules that are evaluated to resolve an endpoint
+    ///
+    /// The trace is emitted as a `debug` event and attached to endpoint resolution errors as
+    /// their source.
+    pub fn with_trace(mut self, trace: bool) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    fn resolve(
+        &self,
+        params: &Params,
+        diagnostic_collector: &mut DiagnosticCollector,
+    ) -> Result<Endpoint, ResolveEndpointError> {
+        if let Some(unknown) = params.names().find(|name| {
+            !self
+                .rule_set
+                .parameters
+                .iter()
+                .any(|parameter| parameter.name == *name)
+        }) {
+            return Err(ResolveEndpointError::message(format!(
+                "`{}` is not a parameter of the rule set",
+                unknown
+            )));
+        }
+
+        let mut scope = Scope::new();
+        for parameter in &self.rule_set.parameters {
+            let value = params
+                .get(&parameter.name)
+                .or_else(|| {
+                    parameter
+                        .built_in
+                        .as_ref()
+                        .and_then(|built_in| params.get_built_in(built_in))
+                })
+                .or(parameter.default.as_ref());
+            match value {
+                Some(value) if parameter.kind.matches(value) => {
+                    scope.insert(parameter.name.clone(), value.clone());
+                }
+                Some(value) => {
+                    return Err(ResolveEndpointError::message(format!(
+                        "parameter `{}` must be a {:?} but was a {}",
+                        parameter.name,
+                        parameter.kind,
+                        value.type_name()
+                    )))
+                }
+                None if parameter.required => {
+                    return Err(ResolveEndpointError::message(format!(
+                        "a required field was missing: `{}`",
+                        parameter.name
+                    )))
+                }
+                None => {}
+            }
+        }
+
+        Evaluator {
+            params,
+            partitions: &self.partitions.resolver,
+            diagnostic_collector,
+        }
+        .eval_rules(&self.rule_set.rules, &scope)
+    }
+}
+
+impl ResolveEndpoint<Params> for RuleSetResolver {
+    fn resolve_endpoint(&self, params: &Params) -> aws_smithy_http::endpoint::Result {
+        let mut diagnostic_collector = if self.trace {
+            DiagnosticCollector::with_trace()
+        } else {
+            DiagnosticCollector::new()
+        };
+        let result = self.resolve(params, &mut diagnostic_collector);
+        diagnostic_collector.emit_trace();
+        result.map_err(|err| err.with_source(diagnostic_collector.take_last_error()))
+    }
+}
+
+type Scope = HashMap<String, Value>;
+
+struct Evaluator<'a> {
+    params: &'a Params,
+    partitions: &'a PartitionResolver,
+    diagnostic_collector: &'a mut DiagnosticCollector,
+}
+
+impl<'a> Evaluator<'a> {
+    /// Evaluates the first rule whose conditions match. Tree rules are terminal, so when the
+    /// conditions of a tree match, one of its rules must match as well.
+    fn eval_rules(
+        &mut self,
+        rules: &[Rule],
+        scope: &Scope,
+    ) -> Result<Endpoint, ResolveEndpointError> {
+        'rules: for rule in rules {
+            let mut scope = scope.clone();
+            for condition in &rule.conditions {
+                let value = self.eval(&condition.function, &scope)?;
+                let value = DiagnosticCollector::record_condition(
+                    condition,
+                    value,
+                    self.diagnostic_collector,
+                );
+                match (value, &condition.assign) {
+                    (None, _) | (Some(Value::Bool(false)), _) => continue 'rules,
+                    (Some(value), Some(name)) => {
+                        scope.insert(name.clone(), value);
+                    }
+                    (Some(_), None) => {}
+                }
+            }
+            return match &rule.kind {
+                RuleKind::Endpoint(endpoint) => {
+                    self.diagnostic_collector
+                        .record_rule(format_args!("endpoint {}", endpoint.url));
+                    self.eval_endpoint(endpoint, &scope)
+                }
+                RuleKind::Error(message) => {
+                    self.diagnostic_collector
+                        .record_rule(format_args!("error {}", message));
+                    Err(ResolveEndpointError::message(
+                        self.eval_string(message, &scope)?,
+                    ))
+                }
+                RuleKind::Tree(rules) => self.eval_rules(rules, &scope),
+            };
+        }
+        Err(ResolveEndpointError::message(format!(
+            "No rules matched these parameters. This is a bug. {:?}",
+            self.params
+        )))
+    }
+
+    fn eval_endpoint(
+        &mut self,
+        endpoint: &EndpointTemplate,
+        scope: &Scope,
+    ) -> Result<Endpoint, ResolveEndpointError> {
+        let mut builder = Endpoint::builder().url(self.eval_string(&endpoint.url, scope)?);
+        for (name, values) in &endpoint.headers {
+            for value in values {
+                builder = builder.header(name.clone(), self.eval_string(value, scope)?);
+            }
+        }
+        for (name, value) in &endpoint.properties {
+            let value = self.eval_set(value, scope)?;
+            builder = builder.property(name.clone(), value.into_document());
+        }
+        Ok(builder.build())
+    }
+
+    /// Evaluates an expression. `None` is returned for values that aren't set, e.g. optional
+    /// parameters, or when a function of the standard library failed.
+    fn eval(&mut self, expr: &Expr, scope: &Scope) -> Result<Option<Value>, ResolveEndpointError> {
+        Ok(Some(match expr {
+            Expr::Template(parts) => {
+                let mut out = String::new();
+                for part in parts {
+                    match part {
+                        TemplatePart::Static(value) => out.push_str(value),
+                        TemplatePart::Dynamic(expr) => {
+                            out.push_str(&self.eval_string(expr, scope)?)
+                        }
+                    }
+                }
+                Value::String(out)
+            }
+            Expr::Bool(value) => Value::Bool(*value),
+            Expr::Integer(value) => Value::Integer(*value),
+            Expr::Array(values) => Value::Array(
+                values
+                    .iter()
+                    .map(|value| self.eval_set(value, scope))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Expr::Object(values) => Value::Object(
+                values
+                    .iter()
+                    .map(|(key, value)| Ok((key.clone(), self.eval_set(value, scope)?)))
+                    .collect::<Result<_, ResolveEndpointError>>()?,
+            ),
+            Expr::Ref(name) => return Ok(scope.get(name).cloned()),
+            Expr::GetAttr(target, path) => match self.eval(target, scope)? {
+                Some(target) => return get_attr(target, path),
+                None => return Ok(None),
+            },
+            Expr::Function(Function::IsSet, args) => {
+                Value::Bool(self.eval(&args[0], scope)?.is_some())
+            }
+            Expr::Function(function, args) => {
+                let mut values = Vec::with_capacity(args.len());
+                for arg in args {
+                    match self.eval(arg, scope)? {
+                        Some(value) => values.push(value),
+                        None => return Ok(None),
+                    }
+                }
+                return self.call(*function, values);
+            }
+        }))
+    }
+
+    fn call(
+        &mut self,
+        function: Function,
+        args: Vec<Value>,
+    ) -> Result<Option<Value>, ResolveEndpointError> {
+        let mut args = Args {
+            function,
+            args: args.into_iter(),
+        };
+        let e = &mut *self.diagnostic_collector;
+        Ok(match function {
+            Function::IsSet => unreachable!("isSet is evaluated lazily"),
+            Function::Not => Some(Value::Bool(!args.bool()?)),
+            Function::BooleanEquals => Some(Value::Bool(args.bool()? == args.bool()?)),
+            Function::StringEquals => Some(Value::Bool(args.string()? == args.string()?)),
+            Function::Partition => {
+                let region = args.string()?;
+                self.partitions
+                    .resolve_partition(&region, e)
+                    .map(|partition| {
+                        object([
+                            ("name", partition.name().into()),
+                            ("dnsSuffix", partition.dns_suffix().into()),
+                            (
+                                "dualStackDnsSuffix",
+                                partition.dual_stack_dns_suffix().into(),
+                            ),
+                            ("supportsFIPS", partition.supports_fips().into()),
+                            ("supportsDualStack", partition.supports_dual_stack().into()),
+                        ])
+                    })
+            }
+            Function::ParseArn => parse_arn(&args.string()?, e).map(|arn| {
+                object([
+                    ("partition", arn.partition().into()),
+                    ("service", arn.service().into()),
+                    ("region", arn.region().into()),
+                    ("accountId", arn.account_id().into()),
+                    (
+                        "resourceId",
+                        Value::Array(arn.resource_id().iter().map(|&id| id.into()).collect()),
+                    ),
+                ])
+            }),
+            Function::ParseUrl => parse_url(&args.string()?, e).map(|url| {
+                object([
+                    ("scheme", url.scheme().into()),
+                    ("authority", url.authority().into()),
+                    ("path", url.path().into()),
+                    ("normalizedPath", url.normalized_path().into()),
+                    ("isIp", url.is_ip().into()),
+                ])
+            }),
+            Function::Substring => {
+                let (input, start, stop, reverse) =
+                    (args.string()?, args.index()?, args.index()?, args.bool()?);
+                substring(&input, start, stop, reverse, e).map(Value::from)
+            }
+            Function::UriEncode => Some(uri_encode(&args.string()?, e).into_owned().into()),
+            Function::IsValidHostLabel => {
+                let (label, allow_dots) = (args.string()?, args.bool()?);
+                Some(is_valid_host_label(&label, allow_dots, e).into())
+            }
+            Function::IsVirtualHostableS3Bucket => {
+                let (label, allow_subdomains) = (args.string()?, args.bool()?);
+                Some(is_virtual_hostable_s3_bucket(&label, allow_subdomains, e).into())
+            }
+        })
+    }
+
+    /// Evaluates an expression that must be set
+    fn eval_set(&mut self, expr: &Expr, scope: &Scope) -> Result<Value, ResolveEndpointError> {
+        self.eval(expr, scope)?.ok_or_else(|| {
+            ResolveEndpointError::message(format!("{:?} was used but isn't set", expr))
+        })
+    }
+
+    fn eval_string(&mut self, expr: &Expr, scope: &Scope) -> Result<String, ResolveEndpointError> {
+        match self.eval_set(expr, scope)? {
+            Value::String(value) => Ok(value),
+            other => Err(ResolveEndpointError::message(format!(
+                "expected {:?} to be a string but it was a {}",
+                expr,
+                other.type_name()
+            ))),
+        }
+    }
+}
+
+fn object<const N: usize>(fields: [(&str, Value); N]) -> Value {
+    Value::Object(
+        fields
+            .into_iter()
+            .map(|(key, value)| (key.to_string(), value))
+            .collect(),
+    )
+}
+
+fn get_attr(mut value: Value, path: &[PathPart]) -> Result<Option<Value>, ResolveEndpointError> {
+    for part in path {
+        value = match (part, value) {
+            (PathPart::Key(key), Value::Object(mut object)) => match object.remove(key) {
+                Some(value) => value,
+                None => return Ok(None),
+            },
+            (PathPart::Index(index), Value::Array(mut array)) if *index < array.len() => {
+                array.swap_remove(*index)
+            }
+            (PathPart::Index(_), Value::Array(_)) => return Ok(None),
+            (part, other) => {
+                return Err(ResolveEndpointError::message(format!(
+                    "can't get {:?} of a {}",
+                    part,
+                    other.type_name()
+                )))
+            }
+        };
+    }
+    Ok(Some(value))
+}
+
+/// The arguments of a function call, which are checked against their expected type
+struct Args {
+    function: Function,
+    args: std::vec::IntoIter<Value>,
+}
+
+impl Args {
+    fn next(&mut self, expected: &str) -> Result<Value, ResolveEndpointError> {
+        self.args.next().ok_or_else(|| {
+            ResolveEndpointError::message(format!(
+                "`{}` is missing a {} argument",
+                self.function.name(),
+                expected
+            ))
+        })
+    }
+
+    fn mismatch(&self, expected: &str, value: &Value) -> ResolveEndpointError {
+        ResolveEndpointError::message(format!(
+            "`{}` expected a {} argument but got a {}",
+            self.function.name(),
+            expected,
+            value.type_name()
+        ))
+    }
+
+    fn string(&mut self) -> Result<String, ResolveEndpointError> {
+        match self.next("string")? {
+            Value::String(value) => Ok(value),
+            other => Err(self.mismatch("string", &other)),
+        }
+    }
+
+    fn bool(&mut self) -> Result<bool, ResolveEndpointError> {
+        match self.next("boolean")? {
+            Value::Bool(value) => Ok(value),
+            other => Err(self.mismatch("boolean", &other)),
+        }
+    }
+
+    fn index(&mut self) -> Result<usize, ResolveEndpointError> {
+        match self.next("integer")? {
+            Value::Integer(value) if value >= 0 => Ok(value as usize),
+            other => Err(self.mismatch("non-negative integer", &other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{EndpointRuleSet, Params, Partitions, RuleSetResolver};
+    use aws_smithy_http::endpoint::ResolveEndpoint;
+    use aws_smithy_types::endpoint::Endpoint;
+    use aws_smithy_types::Document;
+    use std::collections::HashMap;
+    use std::error::Error;
+
+    fn resolver() -> RuleSetResolver {
+        RuleSetResolver::new(
+            EndpointRuleSet::from_json(include_bytes!("../test-data/rule-set.json")).unwrap(),
+            Partitions::from_json(include_bytes!("../test-data/partitions.json")).unwrap(),
+        )
+    }
+
+    fn resolve_err(params: Params) -> String {
+        resolver()
+            .resolve_endpoint(&params)
+            .expect_err("resolution should fail")
+            .to_string()
+    }
+
+    #[test]
+    fn resolves_endpoints() {
+        let resolver = resolver();
+        let endpoint = resolver
+            .resolve_endpoint(&Params::builder().param("Region", "us-west-2").build())
+            .unwrap();
+        assert_eq!(
+            Endpoint::builder()
+                .url("https://example.us-west-2.amazonaws.com")
+                .build(),
+            endpoint
+        );
+
+        let endpoint = resolver
+            .resolve_endpoint(
+                &Params::builder()
+                    .built_in("AWS::Region", "us-east-1")
+                    .param("UseFIPS", true)
+                    .build(),
+            )
+            .unwrap();
+        let auth_scheme = Document::Object(HashMap::from([
+            ("name".to_string(), Document::String("sigv4".into())),
+            (
+                "signingRegion".to_string(),
+                Document::String("us-east-1".into()),
+            ),
+        ]));
+        assert_eq!(
+            Endpoint::builder()
+                .url("https://example-fips.us-east-1.amazonaws.com")
+                .header("x-partition", "aws")
+                .property("authSchemes", vec![auth_scheme])
+                .build(),
+            endpoint
+        );
+    }
+
+    #[test]
+    fn private_partitions() {
+        let endpoint = resolver()
+            .resolve_endpoint(&Params::builder().param("Region", "private-east-1").build())
+            .unwrap();
+        assert_eq!(
+            "https://example.private-east-1.example.internal",
+            endpoint.url()
+        );
+
+        assert_eq!(
+            "FIPS is enabled but this partition does not support FIPS",
+            resolve_err(
+                Params::builder()
+                    .param("Region", "private-east-1")
+                    .param("UseFIPS", true)
+                    .build()
+            )
+        );
+    }
+
+    #[test]
+    fn custom_endpoints() {
+        let endpoint = resolver()
+            .resolve_endpoint(
+                &Params::builder()
+                    .built_in("SDK::Endpoint", "http://localhost:8080/base")
+                    .build(),
+            )
+            .unwrap();
+        assert_eq!("http://localhost:8080/base/custom", endpoint.url());
+
+        let err = resolver()
+            .resolve_endpoint(&Params::builder().param("Endpoint", "not a url").build())
+            .expect_err("the endpoint is invalid");
+        assert_eq!("Invalid endpoint URL: not a url", err.to_string());
+        assert!(err.source().is_some(), "the parse error is reported");
+    }
+
+    #[test]
+    fn arns() {
+        let endpoint = resolver()
+            .resolve_endpoint(
+                &Params::builder()
+                    .param("Region", "us-east-1")
+                    .param(
+                        "ResourceArn",
+                        "arn:aws:example:us-west-2:123456789012:bucket:my bucket",
+                    )
+                    .build(),
+            )
+            .unwrap();
+        assert_eq!(
+            "https://my%20bucket.1234.us-west-2.amazonaws.com",
+            endpoint.url()
+        );
+
+        let endpoint = resolver()
+            .resolve_endpoint(
+                &Params::builder()
+                    .param("Region", "us-east-1")
+                    .param(
+                        "ResourceArn",
+                        "arn:aws:example:us-west-2:123456789012:bucket",
+                    )
+                    .build(),
+            )
+            .unwrap();
+        assert_eq!(
+            "https://example.us-east-1.amazonaws.com",
+            endpoint.url(),
+            "a missing resource ID doesn't match"
+        );
+    }
+
+    #[test]
+    fn trace() {
+        let params = Params::builder()
+            .param("Region", "private-east-1")
+            .param("UseFIPS", true)
+            .build();
+        let err = resolver()
+            .with_trace(true)
+            .resolve_endpoint(&params)
+            .expect_err("the partition doesn't support FIPS");
+        let trace = err.source().expect("the trace is attached").to_string();
+        assert_eq!(
+            "endpoint rule evaluation trace:\n    \
+            isSet(Endpoint) => Some(Bool(false))\n    \
+            isSet(Region) => Some(Bool(true))\n    \
+            PartitionResult = aws.partition(Region) => Some(Object({...}))\n    \
+            isSet(ResourceArn) => Some(Bool(false))\n    \
+            booleanEquals(UseFIPS, true) => Some(Bool(true))\n    \
+            booleanEquals(true, PartitionResult#supportsFIPS) => Some(Bool(false))\n    \
+            selected error \"FIPS is enabled but this partition does not support FIPS\"",
+            trace.replace(
+                trace
+                    .split("Some(Object(")
+                    .nth(1)
+                    .and_then(|rest| rest.split("))\n").next())
+                    .unwrap(),
+                "{...}"
+            )
+        );
+
+        let err = resolver()
+            .resolve_endpoint(&params)
+            .expect_err("the partition doesn't support FIPS");
+        assert!(err.source().is_none(), "no trace without opting in");
+    }
+
+    #[test]
+    fn invalid_params() {
+        assert_eq!(
+            "Invalid Configuration: Missing Region",
+            resolve_err(Params::default())
+        );
+        assert_eq!(
+            "`Regoin` is not a parameter of the rule set",
+            resolve_err(Params::builder().param("Regoin", "us-east-1").build())
+        );
+        assert_eq!(
+            "parameter `UseFIPS` must be a Boolean but was a string",
+            resolve_err(Params::builder().param("UseFIPS", "true").build())
+        );
+    }
+}
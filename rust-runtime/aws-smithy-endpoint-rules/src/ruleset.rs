@@ -0,0 +1,630 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Loading of endpoint rule sets from JSON
+
+use crate::error::LoadError;
+use crate::value::Value;
+use aws_smithy_json::deserialize::json_token_iter;
+use aws_smithy_json::deserialize::token::expect_document;
+use aws_smithy_types::{Document, Number};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Endpoint rule set that was loaded from JSON
+///
+/// This is the `smithy.rules#endpointRuleSet` trait of a service, which contains the parameters
+/// of the rule set and its rules.
+#[derive(Debug)]
+pub struct EndpointRuleSet {
+    pub(crate) parameters: Vec<Parameter>,
+    pub(crate) rules: Vec<Rule>,
+}
+
+impl EndpointRuleSet {
+    /// Load a rule set from its JSON representation
+    ///
+    /// Every function that the rules call is checked against the standard library when the
+    /// rule set is loaded.
+    pub fn from_json(rule_set_json: &[u8]) -> Result<Self, LoadError> {
+        let mut tokens = json_token_iter(rule_set_json).peekable();
+        let document = expect_document(&mut tokens).map_err(LoadError::json)?;
+        if tokens.next().is_some() {
+            return Err(LoadError::new(
+                "found more JSON after the end of the rule set",
+            ));
+        }
+        parse_rule_set(document)
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Parameter {
+    pub(crate) name: String,
+    pub(crate) kind: ParameterKind,
+    pub(crate) required: bool,
+    pub(crate) default: Option<Value>,
+    pub(crate) built_in: Option<String>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ParameterKind {
+    String,
+    Boolean,
+}
+
+impl ParameterKind {
+    pub(crate) fn matches(self, value: &Value) -> bool {
+        matches!(
+            (self, value),
+            (ParameterKind::String, Value::String(_)) | (ParameterKind::Boolean, Value::Bool(_))
+        )
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct Rule {
+    pub(crate) conditions: Vec<Condition>,
+    pub(crate) kind: RuleKind,
+}
+
+#[derive(Debug)]
+pub(crate) enum RuleKind {
+    Endpoint(EndpointTemplate),
+    Error(Expr),
+    Tree(Vec<Rule>),
+}
+
+#[derive(Debug)]
+pub(crate) struct EndpointTemplate {
+    pub(crate) url: Expr,
+    pub(crate) headers: Vec<(String, Vec<Expr>)>,
+    pub(crate) properties: Vec<(String, Expr)>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Condition {
+    pub(crate) function: Expr,
+    pub(crate) assign: Option<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Expr {
+    Template(Vec<TemplatePart>),
+    Bool(bool),
+    Integer(i64),
+    Array(Vec<Expr>),
+    Object(Vec<(String, Expr)>),
+    Ref(String),
+    GetAttr(Box<Expr>, Vec<PathPart>),
+    Function(Function, Vec<Expr>),
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(assign) = &self.assign {
+            write!(f, "{} = ", assign)?;
+        }
+        write!(f, "{}", self.function)
+    }
+}
+
+/// Formats expressions the way they are written in the rule set language, e.g.
+/// `booleanEquals(UseFIPS, true)` or `"https://{Region}.{PartitionResult#dnsSuffix}"`
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn list<T>(
+            f: &mut fmt::Formatter<'_>,
+            items: &[T],
+            item: impl Fn(&mut fmt::Formatter<'_>, &T) -> fmt::Result,
+        ) -> fmt::Result {
+            for (index, value) in items.iter().enumerate() {
+                if index > 0 {
+                    write!(f, ", ")?;
+                }
+                item(f, value)?;
+            }
+            Ok(())
+        }
+
+        match self {
+            Expr::Template(parts) => {
+                write!(f, "\"")?;
+                for part in parts {
+                    match part {
+                        TemplatePart::Static(value) => {
+                            write!(f, "{}", value.replace('{', "{{").replace('}', "}}"))?
+                        }
+                        TemplatePart::Dynamic(expr) => write!(f, "{{{}}}", expr)?,
+                    }
+                }
+                write!(f, "\"")
+            }
+            Expr::Bool(value) => write!(f, "{}", value),
+            Expr::Integer(value) => write!(f, "{}", value),
+            Expr::Array(values) => {
+                write!(f, "[")?;
+                list(f, values, |f, value| write!(f, "{}", value))?;
+                write!(f, "]")
+            }
+            Expr::Object(values) => {
+                write!(f, "{{")?;
+                list(f, values, |f, (key, value)| {
+                    write!(f, "{:?}: {}", key, value)
+                })?;
+                write!(f, "}}")
+            }
+            Expr::Ref(name) => write!(f, "{}", name),
+            Expr::GetAttr(target, path) => {
+                write!(f, "{}#", target)?;
+                for (index, part) in path.iter().enumerate() {
+                    match part {
+                        PathPart::Key(key) if index > 0 => write!(f, ".{}", key)?,
+                        PathPart::Key(key) => write!(f, "{}", key)?,
+                        PathPart::Index(i) => write!(f, "[{}]", i)?,
+                    }
+                }
+                Ok(())
+            }
+            Expr::Function(function, args) => {
+                write!(f, "{}(", function.name())?;
+                list(f, args, |f, arg| write!(f, "{}", arg))?;
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum TemplatePart {
+    Static(String),
+    Dynamic(Expr),
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum PathPart {
+    Key(String),
+    Index(usize),
+}
+
+/// Functions of the rule set standard library
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Function {
+    IsSet,
+    Not,
+    BooleanEquals,
+    StringEquals,
+    Partition,
+    ParseArn,
+    ParseUrl,
+    Substring,
+    UriEncode,
+    IsValidHostLabel,
+    IsVirtualHostableS3Bucket,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "isSet" => Function::IsSet,
+            "not" => Function::Not,
+            "booleanEquals" => Function::BooleanEquals,
+            "stringEquals" => Function::StringEquals,
+            "aws.partition" => Function::Partition,
+            "aws.parseArn" => Function::ParseArn,
+            "parseURL" => Function::ParseUrl,
+            "substring" => Function::Substring,
+            "uriEncode" => Function::UriEncode,
+            "isValidHostLabel" => Function::IsValidHostLabel,
+            "aws.isVirtualHostableS3Bucket" => Function::IsVirtualHostableS3Bucket,
+            _ => return None,
+        })
+    }
+
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Function::IsSet => "isSet",
+            Function::Not => "not",
+            Function::BooleanEquals => "booleanEquals",
+            Function::StringEquals => "stringEquals",
+            Function::Partition => "aws.partition",
+            Function::ParseArn => "aws.parseArn",
+            Function::ParseUrl => "parseURL",
+            Function::Substring => "substring",
+            Function::UriEncode => "uriEncode",
+            Function::IsValidHostLabel => "isValidHostLabel",
+            Function::IsVirtualHostableS3Bucket => "aws.isVirtualHostableS3Bucket",
+        }
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Function::IsSet
+            | Function::Not
+            | Function::Partition
+            | Function::ParseArn
+            | Function::ParseUrl
+            | Function::UriEncode => 1,
+            Function::BooleanEquals
+            | Function::StringEquals
+            | Function::IsValidHostLabel
+            | Function::IsVirtualHostableS3Bucket => 2,
+            Function::Substring => 4,
+        }
+    }
+}
+
+type Object = HashMap<String, Document>;
+
+fn expect_object(document: Document, what: &str) -> Result<Object, LoadError> {
+    match document {
+        Document::Object(object) => Ok(object),
+        _ => Err(LoadError::new(format!("expected {} to be an object", what))),
+    }
+}
+
+fn expect_array(document: Document, what: &str) -> Result<Vec<Document>, LoadError> {
+    match document {
+        Document::Array(array) => Ok(array),
+        _ => Err(LoadError::new(format!("expected {} to be an array", what))),
+    }
+}
+
+fn expect_string(document: Document, what: &str) -> Result<String, LoadError> {
+    match document {
+        Document::String(string) => Ok(string),
+        _ => Err(LoadError::new(format!("expected {} to be a string", what))),
+    }
+}
+
+fn take(object: &mut Object, key: &str, what: &str) -> Result<Document, LoadError> {
+    object
+        .remove(key)
+        .ok_or_else(|| LoadError::new(format!("{} is missing `{}`", what, key)))
+}
+
+fn parse_rule_set(document: Document) -> Result<EndpointRuleSet, LoadError> {
+    let mut rule_set = expect_object(document, "the rule set")?;
+    let parameters = match rule_set.remove("parameters") {
+        Some(parameters) => expect_object(parameters, "`parameters`")?
+            .into_iter()
+            .map(|(name, parameter)| parse_parameter(name, parameter))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+    let rules = parse_rules(take(&mut rule_set, "rules", "the rule set")?)?;
+    if rules.is_empty() {
+        return Err(LoadError::new(
+            "the rule set must contain at least one rule",
+        ));
+    }
+    Ok(EndpointRuleSet { parameters, rules })
+}
+
+fn parse_parameter(name: String, document: Document) -> Result<Parameter, LoadError> {
+    let what = format!("parameter `{}`", name);
+    let mut parameter = expect_object(document, &what)?;
+    let kind = match expect_string(take(&mut parameter, "type", &what)?, "`type`")?
+        .to_ascii_lowercase()
+        .as_str()
+    {
+        "string" => ParameterKind::String,
+        "boolean" => ParameterKind::Boolean,
+        other => {
+            return Err(LoadError::new(format!(
+                "{} has an unsupported type `{}`",
+                what, other
+            )))
+        }
+    };
+    let required = match parameter.remove("required") {
+        Some(Document::Bool(required)) => required,
+        None => false,
+        Some(_) => return Err(LoadError::new(format!("`required` of {} is invalid", what))),
+    };
+    let default = match parameter.remove("default") {
+        Some(Document::String(value)) => Some(Value::String(value)),
+        Some(Document::Bool(value)) => Some(Value::Bool(value)),
+        None => None,
+        Some(_) => return Err(LoadError::new(format!("`default` of {} is invalid", what))),
+    };
+    if !default.iter().all(|default| kind.matches(default)) {
+        return Err(LoadError::new(format!(
+            "`default` of {} doesn't match its type",
+            what
+        )));
+    }
+    let built_in = parameter
+        .remove("builtIn")
+        .map(|built_in| expect_string(built_in, "`builtIn`"))
+        .transpose()?;
+    Ok(Parameter {
+        name,
+        kind,
+        required,
+        default,
+        built_in,
+    })
+}
+
+fn parse_rules(document: Document) -> Result<Vec<Rule>, LoadError> {
+    expect_array(document, "`rules`")?
+        .into_iter()
+        .map(parse_rule)
+        .collect()
+}
+
+fn parse_rule(document: Document) -> Result<Rule, LoadError> {
+    let mut rule = expect_object(document, "a rule")?;
+    let conditions = expect_array(take(&mut rule, "conditions", "a rule")?, "`conditions`")?
+        .into_iter()
+        .map(parse_condition)
+        .collect::<Result<Vec<_>, _>>()?;
+    let kind = match expect_string(take(&mut rule, "type", "a rule")?, "`type`")?.as_str() {
+        "endpoint" => RuleKind::Endpoint(parse_endpoint(take(
+            &mut rule,
+            "endpoint",
+            "an endpoint rule",
+        )?)?),
+        "error" => RuleKind::Error(parse_expr(take(&mut rule, "error", "an error rule")?)?),
+        "tree" => RuleKind::Tree(parse_rules(take(&mut rule, "rules", "a tree rule")?)?),
+        other => return Err(LoadError::new(format!("unknown rule type `{}`", other))),
+    };
+    Ok(Rule { conditions, kind })
+}
+
+fn parse_condition(document: Document) -> Result<Condition, LoadError> {
+    let mut condition = expect_object(document, "a condition")?;
+    let assign = condition
+        .remove("assign")
+        .map(|assign| expect_string(assign, "`assign`"))
+        .transpose()?;
+    let function = parse_function(condition)?;
+    Ok(Condition { function, assign })
+}
+
+fn parse_endpoint(document: Document) -> Result<EndpointTemplate, LoadError> {
+    let mut endpoint = expect_object(document, "`endpoint`")?;
+    let url = parse_expr(take(&mut endpoint, "url", "`endpoint`")?)?;
+    let headers = match endpoint.remove("headers") {
+        Some(headers) => expect_object(headers, "`headers`")?
+            .into_iter()
+            .map(|(name, values)| {
+                let values = expect_array(values, "a header")?
+                    .into_iter()
+                    .map(parse_expr)
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok((name, values))
+            })
+            .collect::<Result<Vec<_>, LoadError>>()?,
+        None => Vec::new(),
+    };
+    let properties = match endpoint.remove("properties") {
+        Some(properties) => expect_object(properties, "`properties`")?
+            .into_iter()
+            .map(|(name, value)| Ok((name, parse_expr(value)?)))
+            .collect::<Result<Vec<_>, LoadError>>()?,
+        None => Vec::new(),
+    };
+    Ok(EndpointTemplate {
+        url,
+        headers,
+        properties,
+    })
+}
+
+fn parse_expr(document: Document) -> Result<Expr, LoadError> {
+    match document {
+        Document::String(template) => parse_template(&template),
+        Document::Bool(value) => Ok(Expr::Bool(value)),
+        Document::Number(Number::PosInt(value)) if value <= i64::MAX as u64 => {
+            Ok(Expr::Integer(value as i64))
+        }
+        Document::Number(Number::NegInt(value)) => Ok(Expr::Integer(value)),
+        Document::Array(values) => Ok(Expr::Array(
+            values
+                .into_iter()
+                .map(parse_expr)
+                .collect::<Result<_, _>>()?,
+        )),
+        Document::Object(mut object) => {
+            if let Some(name) = object.remove("ref") {
+                Ok(Expr::Ref(expect_string(name, "`ref`")?))
+            } else if object.contains_key("fn") {
+                parse_function(object)
+            } else {
+                Ok(Expr::Object(
+                    object
+                        .into_iter()
+                        .map(|(key, value)| Ok((key, parse_expr(value)?)))
+                        .collect::<Result<_, LoadError>>()?,
+                ))
+            }
+        }
+        other => Err(LoadError::new(format!(
+            "unsupported expression: {:?}",
+            other
+        ))),
+    }
+}
+
+fn parse_function(mut object: Object) -> Result<Expr, LoadError> {
+    let name = expect_string(take(&mut object, "fn", "a function call")?, "`fn`")?;
+    let mut argv = expect_array(take(&mut object, "argv", "a function call")?, "`argv`")?;
+    if name == "getAttr" {
+        if argv.len() != 2 {
+            return Err(LoadError::new("`getAttr` expects 2 arguments"));
+        }
+        let path = expect_string(argv.pop().expect("checked above"), "the `getAttr` path")?;
+        let target = parse_expr(argv.pop().expect("checked above"))?;
+        return Ok(Expr::GetAttr(Box::new(target), parse_path(&path)?));
+    }
+
+    let function = Function::from_name(&name)
+        .ok_or_else(|| LoadError::new(format!("unknown function `{}`", name)))?;
+    if argv.len() != function.arity() {
+        return Err(LoadError::new(format!(
+            "`{}` expects {} arguments but was called with {}",
+            name,
+            function.arity(),
+            argv.len()
+        )));
+    }
+    let args = argv.into_iter().map(parse_expr).collect::<Result<_, _>>()?;
+    Ok(Expr::Function(function, args))
+}
+
+/// Parses the path of `getAttr`, e.g. `resourceId[1]` or `foo.bar`
+fn parse_path(path: &str) -> Result<Vec<PathPart>, LoadError> {
+    let invalid = || LoadError::new(format!("invalid `getAttr` path `{}`", path));
+    let mut parts = Vec::new();
+    for segment in path.split('.') {
+        let (key, indexes) = match segment.find('[') {
+            Some(start) => segment.split_at(start),
+            None => (segment, ""),
+        };
+        if !key.is_empty() {
+            parts.push(PathPart::Key(key.to_string()));
+        }
+        let mut indexes = indexes;
+        while !indexes.is_empty() {
+            let end = indexes.find(']').ok_or_else(invalid)?;
+            let index = indexes[1..end].parse().map_err(|_| invalid())?;
+            parts.push(PathPart::Index(index));
+            indexes = &indexes[end + 1..];
+            if !indexes.is_empty() && !indexes.starts_with('[') {
+                return Err(invalid());
+            }
+        }
+    }
+    if parts.is_empty() {
+        return Err(invalid());
+    }
+    Ok(parts)
+}
+
+/// Parses a string template, e.g. `https://{Region}.{PartitionResult#dnsSuffix}`
+///
+/// `{{` and `}}` escape literal braces.
+fn parse_template(template: &str) -> Result<Expr, LoadError> {
+    let unclosed = || LoadError::new(format!("unclosed `{{` in template `{}`", template));
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut rest = template;
+    while let Some(index) = rest.find(['{', '}']) {
+        literal.push_str(&rest[..index]);
+        let (brace, after) = (&rest[index..index + 1], &rest[index + 1..]);
+        if after.starts_with(brace) {
+            literal.push_str(brace);
+            rest = &after[1..];
+        } else if brace == "}" {
+            return Err(LoadError::new(format!(
+                "unmatched `}}` in template `{}`",
+                template
+            )));
+        } else {
+            let end = after.find('}').ok_or_else(unclosed)?;
+            if !literal.is_empty() {
+                parts.push(TemplatePart::Static(std::mem::take(&mut literal)));
+            }
+            let expr = match after[..end].split_once('#') {
+                Some((name, path)) => {
+                    Expr::GetAttr(Box::new(Expr::Ref(name.to_string())), parse_path(path)?)
+                }
+                None => Expr::Ref(after[..end].to_string()),
+            };
+            parts.push(TemplatePart::Dynamic(expr));
+            rest = &after[end + 1..];
+        }
+    }
+    literal.push_str(rest);
+    if !literal.is_empty() || parts.is_empty() {
+        parts.push(TemplatePart::Static(literal));
+    }
+    Ok(Expr::Template(parts))
+}
+
+#[cfg(test)]
+mod test {
+    use super::{parse_path, parse_template, EndpointRuleSet, Expr, PathPart, TemplatePart};
+
+    #[test]
+    fn templates() {
+        assert_eq!(
+            Expr::Template(vec![TemplatePart::Static("https://example.com".into())]),
+            parse_template("https://example.com").unwrap()
+        );
+        assert_eq!(
+            Expr::Template(vec![TemplatePart::Static("".into())]),
+            parse_template("").unwrap()
+        );
+        assert_eq!(
+            Expr::Template(vec![
+                TemplatePart::Static("https://".into()),
+                TemplatePart::Dynamic(Expr::Ref("Region".into())),
+                TemplatePart::Static(".".into()),
+                TemplatePart::Dynamic(Expr::GetAttr(
+                    Box::new(Expr::Ref("PartitionResult".into())),
+                    vec![PathPart::Key("dnsSuffix".into())]
+                )),
+                TemplatePart::Static("/{literal}".into()),
+            ]),
+            parse_template("https://{Region}.{PartitionResult#dnsSuffix}/{{literal}}").unwrap()
+        );
+        assert!(parse_template("https://{Region").is_err());
+        assert!(parse_template("https://Region}").is_err());
+    }
+
+    #[test]
+    fn display() {
+        for expr in [
+            "https://{Region}.{PartitionResult#dnsSuffix}/{{literal}}",
+            "{arn#resourceId[1]}",
+        ] {
+            assert_eq!(
+                format!("\"{}\"", expr),
+                parse_template(expr).unwrap().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            vec![PathPart::Key("resourceId".into()), PathPart::Index(2)],
+            parse_path("resourceId[2]").unwrap()
+        );
+        assert_eq!(
+            vec![
+                PathPart::Key("a".into()),
+                PathPart::Key("b".into()),
+                PathPart::Index(0),
+                PathPart::Index(1)
+            ],
+            parse_path("a.b[0][1]").unwrap()
+        );
+        assert!(parse_path("a[x]").is_err());
+        assert!(parse_path("a[0]b").is_err());
+        assert!(parse_path("").is_err());
+    }
+
+    #[test]
+    fn unknown_functions_are_rejected() {
+        let rule_set = br#"{
+            "version": "1.0",
+            "parameters": {},
+            "rules": [{
+                "type": "endpoint",
+                "conditions": [{ "fn": "aws.unknown", "argv": [] }],
+                "endpoint": { "url": "https://example.com" }
+            }]
+        }"#;
+        let err = EndpointRuleSet::from_json(rule_set).expect_err("function is unknown");
+        assert_eq!(
+            "failed to load endpoint rules: unknown function `aws.unknown`",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,81 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_types::{Document, Number};
+use std::collections::HashMap;
+
+/// A value that endpoint rules operate on
+///
+/// Parameters are either strings or booleans. The other variants are produced by the functions of
+/// the rule set standard library, e.g. `aws.partition` returns an object.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    /// String value
+    String(String),
+    /// Boolean value
+    Bool(bool),
+    /// Integer value
+    Integer(i64),
+    /// Array of values
+    Array(Vec<Value>),
+    /// Object with named values
+    Object(HashMap<String, Value>),
+}
+
+impl Value {
+    /// Returns the name of the type of this value, for error messages
+    pub(crate) fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Bool(_) => "boolean",
+            Value::Integer(_) => "integer",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+        }
+    }
+
+    pub(crate) fn into_document(self) -> Document {
+        match self {
+            Value::String(value) => Document::String(value),
+            Value::Bool(value) => Document::Bool(value),
+            Value::Integer(value) if value < 0 => Document::Number(Number::NegInt(value)),
+            Value::Integer(value) => Document::Number(Number::PosInt(value as u64)),
+            Value::Array(values) => {
+                Document::Array(values.into_iter().map(Value::into_document).collect())
+            }
+            Value::Object(values) => Document::Object(
+                values
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into_document()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::String(value.to_string())
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Integer(value)
+    }
+}
@@ -0,0 +1,41 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::fs;
+use std::path::Path;
+
+/// `src/endpoint_lib` is a vendored copy of the standard library in `rust-runtime/inlineable`.
+///
+/// The check is skipped outside of the smithy-rs repository, e.g. when building the published crate.
+#[test]
+fn endpoint_lib_is_in_sync() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let upstream = manifest_dir.join("../inlineable/src/endpoint_lib");
+    if !upstream.is_dir() {
+        return;
+    }
+    let vendored = manifest_dir.join("src/endpoint_lib");
+
+    let mut upstream_files: Vec<_> = fs::read_dir(&upstream)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    let mut vendored_files: Vec<_> = fs::read_dir(&vendored)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name())
+        .collect();
+    upstream_files.sort();
+    vendored_files.sort();
+    assert_eq!(upstream_files, vendored_files);
+
+    for file in upstream_files {
+        assert_eq!(
+            fs::read_to_string(upstream.join(&file)).unwrap(),
+            fs::read_to_string(vendored.join(&file)).unwrap(),
+            "{:?} differs from rust-runtime/inlineable/src/endpoint_lib; copy it over again",
+            file
+        );
+    }
+}
@@ -9,6 +9,7 @@ use std::fmt;
 
 #[derive(Debug)]
 pub(crate) enum ErrorKind {
+    BufferLimitExceeded(usize),
     HeadersTooLong,
     HeaderValueTooLong,
     InvalidHeaderNameLength,
@@ -65,6 +66,11 @@ impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use ErrorKind::*;
         match &self.kind {
+            BufferLimitExceeded(limit) => write!(
+                f,
+                "message headers exceed the buffer limit of {} bytes",
+                limit
+            ),
             HeadersTooLong => write!(f, "headers too long to fit in event stream frame"),
             HeaderValueTooLong => write!(f, "header value too long to fit in event stream frame"),
             InvalidHeaderNameLength => write!(f, "invalid header name length"),
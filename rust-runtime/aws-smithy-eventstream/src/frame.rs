@@ -140,6 +140,22 @@ impl SignMessage for NoOpSigner {
     }
 }
 
+pub type VerifyMessageError = Box<dyn StdError + Send + Sync + 'static>;
+
+/// Verifies the signature of a received Event Stream message.
+///
+/// This is the receiving counterpart of [`SignMessage`].
+pub trait VerifyMessage: fmt::Debug {
+    /// Verifies a signed message and returns the message it wraps.
+    ///
+    /// Returns `None` for the empty signed message that terminates the stream.
+    fn verify(&mut self, message: Message) -> Result<Option<Message>, VerifyMessageError>;
+
+    /// Returns true if the stream must end with an empty signed message. A stream that ends
+    /// without one was truncated.
+    fn requires_empty_end_message(&self) -> bool;
+}
+
 /// Converts a Smithy modeled Event Stream type into a [`Message`](Message).
 pub trait MarshallMessage: fmt::Debug {
     /// Smithy modeled input type to convert from.
@@ -518,13 +534,7 @@ impl Message {
         // Validate the prelude
         let header_len = crc_buffer.get_u32();
         let (expected_crc, prelude_crc) = (crc_buffer.into_crc(), buffer.get_u32());
-        if expected_crc != prelude_crc {
-            return Err(ErrorKind::PreludeChecksumMismatch(expected_crc, prelude_crc).into());
-        }
-        // The header length can be 0 or >= 2, but must fit within the frame size
-        if header_len == 1 || header_len > max_header_len(total_len)? {
-            return Err(ErrorKind::InvalidHeadersLength.into());
-        }
+        validate_prelude(total_len, header_len, expected_crc, prelude_crc)?;
         Ok((total_len, header_len))
     }
 
@@ -548,16 +558,7 @@ impl Message {
         }
 
         // Read headers
-        let mut header_bytes_read = 0;
-        let mut headers = Vec::new();
-        while header_bytes_read < header_len as usize {
-            let (header, bytes_read) = Header::read_from(&mut crc_buffer)?;
-            header_bytes_read += bytes_read;
-            if header_bytes_read > header_len as usize {
-                return Err(ErrorKind::InvalidHeaderValue.into());
-            }
-            headers.push(header);
-        }
+        let headers = read_headers_from(&mut crc_buffer, header_len)?;
 
         // Read payload
         let payload_len = payload_len(total_len, header_len)?;
@@ -619,6 +620,36 @@ fn checked<T: TryFrom<U>, U>(from: U, err: Error) -> Result<T, Error> {
     T::try_from(from).map_err(|_| err)
 }
 
+fn validate_prelude(
+    total_len: u32,
+    header_len: u32,
+    expected_crc: u32,
+    prelude_crc: u32,
+) -> Result<(), Error> {
+    if expected_crc != prelude_crc {
+        return Err(ErrorKind::PreludeChecksumMismatch(expected_crc, prelude_crc).into());
+    }
+    // The header length can be 0 or >= 2, but must fit within the frame size
+    if header_len == 1 || header_len > max_header_len(total_len)? {
+        return Err(ErrorKind::InvalidHeadersLength.into());
+    }
+    Ok(())
+}
+
+fn read_headers_from<B: Buf>(mut buffer: B, header_len: u32) -> Result<Vec<Header>, Error> {
+    let mut header_bytes_read = 0;
+    let mut headers = Vec::new();
+    while header_bytes_read < header_len as usize {
+        let (header, bytes_read) = Header::read_from(&mut buffer)?;
+        header_bytes_read += bytes_read;
+        if header_bytes_read > header_len as usize {
+            return Err(ErrorKind::InvalidHeaderValue.into());
+        }
+        headers.push(header);
+    }
+    Ok(headers)
+}
+
 fn max_header_len(total_len: u32) -> Result<u32, Error> {
     total_len
         .checked_sub(PRELUDE_LENGTH_BYTES + MESSAGE_CRC_LENGTH_BYTES)
@@ -944,6 +975,401 @@ mod message_frame_decoder_tests {
     }
 }
 
+/// Return value from [`StreamingFrameDecoder`].
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum DecodedFramePart {
+    /// There wasn't enough data in the buffer to decode the next part of a message.
+    Incomplete,
+    /// The headers of a new message.
+    Headers {
+        /// The message headers.
+        headers: Vec<Header>,
+        /// The length of the payload that follows the headers.
+        payload_len: u32,
+    },
+    /// The next chunk of the payload of the current message.
+    PayloadChunk(Bytes),
+    /// The end of the current message. The message checksum was valid.
+    End,
+}
+
+#[derive(Debug)]
+enum StreamingState {
+    Prelude,
+    Headers {
+        header_len: u32,
+        payload_len: u32,
+    },
+    Payload {
+        remaining: u32,
+    },
+    MessageCrc,
+    /// Discards the rest of a message whose headers exceeded the buffer limit
+    Skip {
+        remaining: u32,
+    },
+}
+
+/// Default limit for [`StreamingFrameDecoder::with_max_buffered_bytes`].
+pub const DEFAULT_MAX_BUFFERED_BYTES: usize = 128 * 1024;
+
+/// Streaming decoder that decodes a [`Message`] from a stream without reassembling its payload.
+///
+/// Unlike the [`MessageFrameDecoder`], which waits for the entire message to be in the buffer,
+/// this decoder returns the headers of a message as soon as they are available, followed by
+/// its payload in chunks as the data arrives. The message checksum is calculated incrementally,
+/// and a mismatch is only detected once the last chunk has been read. Consumers must discard
+/// the chunks they received for a message when decoding it fails before [`DecodedFramePart::End`].
+///
+/// Only the message headers need to be available in the buffer at once, and they are limited to
+/// [`DEFAULT_MAX_BUFFERED_BYTES`] unless configured otherwise. Payload chunks are never larger
+/// than that limit either.
+pub struct StreamingFrameDecoder {
+    state: StreamingState,
+    crc: crc32fast::Hasher,
+    max_buffered_bytes: usize,
+}
+
+impl fmt::Debug for StreamingFrameDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StreamingFrameDecoder")
+            .field("state", &self.state)
+            .field("max_buffered_bytes", &self.max_buffered_bytes)
+            .finish()
+    }
+}
+
+impl Default for StreamingFrameDecoder {
+    fn default() -> Self {
+        Self {
+            state: StreamingState::Prelude,
+            crc: crc32fast::Hasher::new(),
+            max_buffered_bytes: DEFAULT_MAX_BUFFERED_BYTES,
+        }
+    }
+}
+
+impl StreamingFrameDecoder {
+    /// Returns a new `StreamingFrameDecoder`.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the maximum number of bytes that the headers of a message may take up, which is
+    /// also the maximum size of a payload chunk.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_buffered_bytes` is zero.
+    pub fn with_max_buffered_bytes(mut self, max_buffered_bytes: usize) -> Self {
+        assert!(
+            max_buffered_bytes > 0,
+            "max_buffered_bytes must be greater than zero"
+        );
+        self.max_buffered_bytes = max_buffered_bytes;
+        self
+    }
+
+    /// Resets the decoder.
+    fn reset(&mut self) {
+        self.state = StreamingState::Prelude;
+        self.crc = crc32fast::Hasher::new();
+    }
+
+    /// Attempts to decode the next part of a [`Message`] from the given `buffer`.
+    ///
+    /// Every call returns at most one part, so this function should be called until it returns
+    /// [`DecodedFramePart::Incomplete`] before more data is added to the buffer. The parts that
+    /// were decoded are removed from the `Buf`. After an error, the decoder is reset and expects
+    /// the buffer to start with a new message. The exception is
+    /// [`BufferLimitExceeded`](crate::error::ErrorKind::BufferLimitExceeded): the decoder then
+    /// discards the rest of the oversized message before it decodes the next one.
+    pub fn decode_part<B: Buf>(&mut self, mut buffer: B) -> Result<DecodedFramePart, Error> {
+        let result = self.try_decode_part(&mut buffer);
+        if result.is_err() {
+            match self.state {
+                StreamingState::Skip { .. } => self.crc = crc32fast::Hasher::new(),
+                _ => self.reset(),
+            }
+        }
+        result
+    }
+
+    fn try_decode_part<B: Buf>(&mut self, buffer: &mut B) -> Result<DecodedFramePart, Error> {
+        loop {
+            match self.state {
+                StreamingState::Prelude => {
+                    if buffer.remaining() < PRELUDE_LENGTH_BYTES_USIZE {
+                        return Ok(DecodedFramePart::Incomplete);
+                    }
+                    let mut prelude = [0u8; PRELUDE_LENGTH_BYTES_USIZE];
+                    buffer.copy_to_slice(&mut prelude);
+
+                    let mut prelude_buf = &prelude[..];
+                    let mut crc_buffer = CrcBuf::new(&mut prelude_buf);
+                    let total_len = crc_buffer.get_u32();
+                    let header_len = crc_buffer.get_u32();
+                    let (expected_crc, prelude_crc) =
+                        (crc_buffer.into_crc(), prelude_buf.get_u32());
+                    validate_prelude(total_len, header_len, expected_crc, prelude_crc)?;
+                    if header_len as usize > self.max_buffered_bytes {
+                        // The prelude was already consumed, so the rest of the message needs to
+                        // be skipped to find the start of the next one
+                        self.state = StreamingState::Skip {
+                            remaining: total_len - PRELUDE_LENGTH_BYTES,
+                        };
+                        return Err(ErrorKind::BufferLimitExceeded(self.max_buffered_bytes).into());
+                    }
+
+                    self.crc.update(&prelude);
+                    self.state = StreamingState::Headers {
+                        header_len,
+                        payload_len: payload_len(total_len, header_len)?,
+                    };
+                }
+                StreamingState::Headers {
+                    header_len,
+                    payload_len,
+                } => {
+                    if buffer.remaining() < header_len as usize {
+                        return Ok(DecodedFramePart::Incomplete);
+                    }
+                    let header_bytes = buffer.copy_to_bytes(header_len as usize);
+                    self.crc.update(&header_bytes);
+                    let headers = read_headers_from(header_bytes, header_len)?;
+
+                    self.state = match payload_len {
+                        0 => StreamingState::MessageCrc,
+                        remaining => StreamingState::Payload { remaining },
+                    };
+                    return Ok(DecodedFramePart::Headers {
+                        headers,
+                        payload_len,
+                    });
+                }
+                StreamingState::Payload { remaining } => {
+                    let chunk_len = (remaining as usize)
+                        .min(buffer.remaining())
+                        .min(self.max_buffered_bytes);
+                    if chunk_len == 0 {
+                        return Ok(DecodedFramePart::Incomplete);
+                    }
+                    let chunk = buffer.copy_to_bytes(chunk_len);
+                    self.crc.update(&chunk);
+
+                    self.state = match remaining - chunk_len as u32 {
+                        0 => StreamingState::MessageCrc,
+                        remaining => StreamingState::Payload { remaining },
+                    };
+                    return Ok(DecodedFramePart::PayloadChunk(chunk));
+                }
+                StreamingState::MessageCrc => {
+                    if buffer.remaining() < MESSAGE_CRC_LENGTH_BYTES as usize {
+                        return Ok(DecodedFramePart::Incomplete);
+                    }
+                    let message_crc = buffer.get_u32();
+                    let expected_crc =
+                        std::mem::replace(&mut self.crc, crc32fast::Hasher::new()).finalize();
+                    self.state = StreamingState::Prelude;
+                    if expected_crc != message_crc {
+                        return Err(
+                            ErrorKind::MessageChecksumMismatch(expected_crc, message_crc).into(),
+                        );
+                    }
+                    return Ok(DecodedFramePart::End);
+                }
+                StreamingState::Skip { remaining } => {
+                    let skip_len = (remaining as usize).min(buffer.remaining());
+                    buffer.advance(skip_len);
+                    match remaining - skip_len as u32 {
+                        0 => self.state = StreamingState::Prelude,
+                        remaining => {
+                            self.state = StreamingState::Skip { remaining };
+                            return Ok(DecodedFramePart::Incomplete);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod streaming_frame_decoder_tests {
+    use super::{DecodedFramePart, StreamingFrameDecoder};
+    use crate::error::ErrorKind;
+    use crate::frame::Message;
+    use bytes::{Bytes, BytesMut};
+    use bytes_utils::SegmentedBuf;
+
+    // Decodes all parts of the given messages, reassembling them into messages
+    fn decode_all(
+        decoder: &mut StreamingFrameDecoder,
+        data: &[u8],
+        chunk_size: usize,
+    ) -> Vec<Message> {
+        let mut segmented = SegmentedBuf::new();
+        let mut decoded = Vec::new();
+        let mut current = None;
+        for window in data.chunks(chunk_size) {
+            segmented.push(Bytes::copy_from_slice(window));
+            loop {
+                match decoder.decode_part(&mut segmented).unwrap() {
+                    DecodedFramePart::Incomplete => break,
+                    DecodedFramePart::Headers {
+                        headers,
+                        payload_len,
+                    } => {
+                        assert!(current.is_none());
+                        current = Some((headers, BytesMut::with_capacity(payload_len as usize)));
+                    }
+                    DecodedFramePart::PayloadChunk(chunk) => {
+                        current.as_mut().unwrap().1.extend_from_slice(&chunk);
+                    }
+                    DecodedFramePart::End => {
+                        let (headers, payload) = current.take().unwrap();
+                        decoded.push(Message::new_from_parts(headers, payload.freeze()));
+                    }
+                }
+            }
+        }
+        assert!(current.is_none());
+        decoded
+    }
+
+    #[test]
+    fn multiple_streaming_messages() {
+        let message1 = include_bytes!("../test_data/valid_with_all_headers_and_payload");
+        let message2 = include_bytes!("../test_data/valid_empty_payload");
+        let message3 = include_bytes!("../test_data/valid_no_headers");
+        let mut repeated = message1.to_vec();
+        repeated.extend_from_slice(message2);
+        repeated.extend_from_slice(message3);
+
+        let expected = vec![
+            Message::read_from(&mut Bytes::from_static(message1)).unwrap(),
+            Message::read_from(&mut Bytes::from_static(message2)).unwrap(),
+            Message::read_from(&mut Bytes::from_static(message3)).unwrap(),
+        ];
+        for chunk_size in 1..=repeated.len() {
+            let mut decoder = StreamingFrameDecoder::new();
+            assert_eq!(expected, decode_all(&mut decoder, &repeated, chunk_size));
+        }
+    }
+
+    #[test]
+    fn payload_chunks_are_limited() {
+        let message = Message::new(Bytes::from(vec![7u8; 100]));
+        let mut data = Vec::new();
+        message.write_to(&mut data).unwrap();
+
+        let mut decoder = StreamingFrameDecoder::new().with_max_buffered_bytes(30);
+        let mut buffer = Bytes::from(data);
+        assert!(matches!(
+            decoder.decode_part(&mut buffer).unwrap(),
+            DecodedFramePart::Headers {
+                payload_len: 100,
+                ..
+            }
+        ));
+        let mut chunk_lens = Vec::new();
+        while let DecodedFramePart::PayloadChunk(chunk) = decoder.decode_part(&mut buffer).unwrap()
+        {
+            chunk_lens.push(chunk.len());
+        }
+        assert_eq!(vec![30, 30, 30, 10], chunk_lens);
+        assert_eq!(0, buffer.len());
+    }
+
+    #[test]
+    fn headers_over_the_limit() {
+        let message = include_bytes!("../test_data/valid_with_all_headers_and_payload");
+        let mut decoder = StreamingFrameDecoder::new().with_max_buffered_bytes(8);
+        let error = decoder
+            .decode_part(&mut Bytes::from_static(message))
+            .expect_err("the headers are too large");
+        assert!(matches!(error.kind(), ErrorKind::BufferLimitExceeded(8)));
+    }
+
+    #[test]
+    fn decoding_continues_after_headers_over_the_limit() {
+        let oversized = include_bytes!("../test_data/valid_with_all_headers_and_payload");
+        let next = Message::new(Bytes::from_static(b"next"));
+        let mut data = oversized.to_vec();
+        next.write_to(&mut data).unwrap();
+
+        for chunk_size in 1..=data.len() {
+            let mut decoder = StreamingFrameDecoder::new().with_max_buffered_bytes(8);
+            let mut segmented = SegmentedBuf::new();
+            let mut errors = 0;
+            let mut payload = BytesMut::new();
+            let mut ends = 0;
+            for window in data.chunks(chunk_size) {
+                segmented.push(Bytes::copy_from_slice(window));
+                loop {
+                    match decoder.decode_part(&mut segmented) {
+                        Ok(DecodedFramePart::Incomplete) => break,
+                        Ok(DecodedFramePart::Headers { payload_len, .. }) => {
+                            assert_eq!(4, payload_len)
+                        }
+                        Ok(DecodedFramePart::PayloadChunk(chunk)) => {
+                            payload.extend_from_slice(&chunk)
+                        }
+                        Ok(DecodedFramePart::End) => ends += 1,
+                        Err(err) => {
+                            assert!(matches!(err.kind(), ErrorKind::BufferLimitExceeded(8)));
+                            errors += 1;
+                        }
+                    }
+                }
+            }
+            assert_eq!(1, errors);
+            assert_eq!(1, ends);
+            assert_eq!(&b"next"[..], &payload[..]);
+        }
+    }
+
+    #[test]
+    fn invalid_message_checksum() {
+        let message = include_bytes!("../test_data/invalid_message_checksum");
+        let mut decoder = StreamingFrameDecoder::new();
+        let mut buffer = Bytes::from_static(message);
+        let error = loop {
+            match decoder.decode_part(&mut buffer) {
+                Ok(DecodedFramePart::Incomplete) => panic!("the message is complete"),
+                Ok(DecodedFramePart::End) => panic!("the message checksum is invalid"),
+                Ok(_) => {}
+                Err(err) => break err,
+            }
+        };
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::MessageChecksumMismatch(0x01a05860, 0xDEADBEEF)
+        ));
+
+        // The decoder can decode the next message after the error
+        let valid = include_bytes!("../test_data/valid_no_headers");
+        assert_eq!(
+            vec![Message::read_from(&mut Bytes::from_static(valid)).unwrap()],
+            decode_all(&mut decoder, valid, valid.len())
+        );
+    }
+
+    #[test]
+    fn invalid_prelude_checksum() {
+        let message = include_bytes!("../test_data/invalid_prelude_checksum");
+        let mut decoder = StreamingFrameDecoder::new();
+        let error = decoder
+            .decode_part(&mut Bytes::from_static(message))
+            .expect_err("the prelude checksum is invalid");
+        assert!(matches!(
+            error.kind(),
+            ErrorKind::PreludeChecksumMismatch(_, _)
+        ));
+    }
+}
+
 #[cfg(test)]
 mod deferred_signer_tests {
     use crate::frame::{DeferredSigner, Header, HeaderValue, Message, SignMessage};
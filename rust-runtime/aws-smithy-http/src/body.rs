@@ -32,9 +32,9 @@ pin_project! {
         inner: Inner,
         // An optional function to recreate the inner body
         //
-        // In the event of retry, this function will be called to generate a new body. See
-        // [`try_clone()`](SdkBody::try_clone)
-        rebuild: Option<Arc<dyn (Fn() -> Inner) + Send + Sync>>,
+        // In the event of retry, this function will be called to generate a new body. It returns
+        // `None` if the body can no longer be recreated. See [`try_clone()`](SdkBody::try_clone)
+        rebuild: Option<Arc<dyn (Fn() -> Option<Inner>) + Send + Sync>>,
     }
 }
 
@@ -109,7 +109,18 @@ impl SdkBody {
         let initial = f();
         SdkBody {
             inner: initial.inner,
-            rebuild: Some(Arc::new(move || f().inner)),
+            rebuild: Some(Arc::new(move || Some(f().inner))),
+        }
+    }
+
+    /// Construct an SDK body that is retryable for as long as `rebuild` returns `Some`
+    pub(crate) fn with_rebuild(
+        initial: SdkBody,
+        rebuild: impl Fn() -> Option<SdkBody> + Send + Sync + 'static,
+    ) -> Self {
+        SdkBody {
+            inner: initial.inner,
+            rebuild: Some(Arc::new(move || rebuild().map(|body| body.inner))),
         }
     }
 
@@ -126,7 +137,7 @@ impl SdkBody {
     pub fn empty() -> Self {
         Self {
             inner: Inner::Once { inner: None },
-            rebuild: Some(Arc::new(|| Inner::Once { inner: None })),
+            rebuild: Some(Arc::new(|| Some(Inner::Once { inner: None }))),
         }
     }
 
@@ -167,12 +178,12 @@ impl SdkBody {
     /// Attempt to clone this SdkBody. This will fail if the inner data is not cloneable, such as when
     /// it is a single-use stream that can't be recreated.
     pub fn try_clone(&self) -> Option<Self> {
-        self.rebuild.as_ref().map(|rebuild| {
-            let next = rebuild();
-            Self {
+        self.rebuild.as_ref().and_then(|rebuild| {
+            let next = rebuild()?;
+            Some(Self {
                 inner: next,
                 rebuild: self.rebuild.clone(),
-            }
+            })
         })
     }
 
@@ -185,10 +196,12 @@ impl SdkBody {
     /// Given a function to modify an `SdkBody`, run that function against this `SdkBody` before
     /// returning the result.
     pub fn map(self, f: impl Fn(SdkBody) -> SdkBody + Sync + Send + 'static) -> SdkBody {
-        if self.rebuild.is_some() {
-            SdkBody::retryable(move || f(self.try_clone().unwrap()))
-        } else {
-            f(self)
+        match self.try_clone() {
+            Some(initial) => {
+                let initial = f(initial);
+                SdkBody::with_rebuild(initial, move || self.try_clone().map(&f))
+            }
+            None => f(self),
         }
     }
 }
@@ -205,8 +218,10 @@ impl From<Bytes> for SdkBody {
             inner: Inner::Once {
                 inner: Some(bytes.clone()),
             },
-            rebuild: Some(Arc::new(move || Inner::Once {
-                inner: Some(bytes.clone()),
+            rebuild: Some(Arc::new(move || {
+                Some(Inner::Once {
+                    inner: Some(bytes.clone()),
+                })
             })),
         }
     }
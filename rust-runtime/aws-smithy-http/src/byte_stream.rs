@@ -122,7 +122,7 @@
 //! ```
 
 use crate::body::SdkBody;
-use crate::byte_stream::error::Error;
+use crate::byte_stream::error::{Error, ErrorKind};
 use bytes::Buf;
 use bytes::Bytes;
 use bytes_utils::SegmentedBuf;
@@ -138,9 +138,15 @@ mod bytestream_util;
 pub use bytestream_util::Length;
 
 pub mod error;
+#[cfg(feature = "rt-tokio")]
+mod spill;
+mod stream_builder;
 
 #[cfg(feature = "rt-tokio")]
 pub use self::bytestream_util::FsBuilder;
+#[cfg(feature = "rt-tokio")]
+pub use self::spill::SpilledBytes;
+pub use self::stream_builder::StreamBuilder;
 
 pin_project! {
     /// Stream of binary data
@@ -220,7 +226,7 @@ pin_project! {
     ///     ```
     ///
     /// ## Getting data into a ByteStream
-    /// ByteStreams can be created in one of three ways:
+    /// ByteStreams can be created in one of four ways:
     /// 1. **From in-memory binary data**: ByteStreams created from in-memory data are always retryable. Data
     /// will be converted into `Bytes` enabling a cheap clone during retries.
     ///     ```no_run
@@ -239,7 +245,12 @@ pin_project! {
     ///     # }
     ///     ```
     ///
-    /// 3. **From an `SdkBody` directly**: For more advanced / custom use cases, a ByteStream can be created directly
+    /// 3. **From a `Stream` or an `AsyncRead`**: ByteStreams created from a stream factory with
+    /// [`ByteStream::from_stream_fn`] are retryable: a new stream is created if a retry occurs. A ByteStream
+    /// created from a single stream with [`ByteStream::from_stream`] is only retryable when a
+    /// [replay buffer](StreamBuilder::replay_buffer_size) is configured. See [`StreamBuilder`] for an example.
+    ///
+    /// 4. **From an `SdkBody` directly**: For more advanced / custom use cases, a ByteStream can be created directly
     /// from an SdkBody. **When created from an SdkBody, care must be taken to ensure retriability.** An SdkBody is retryable
     /// when constructed from in-memory data or when using [`SdkBody::retryable`](crate::body::SdkBody::retryable).
     ///     ```no_run
@@ -304,6 +315,42 @@ impl ByteStream {
         self.inner.collect().await.map_err(Error::streaming)
     }
 
+    /// Read all the data from this `ByteStream` into memory, unless it's larger than `limit` bytes
+    ///
+    /// Unlike [`collect`](ByteStream::collect), this never buffers more than `limit` bytes. When the
+    /// size hint of the stream already exceeds `limit`, no data is read at all. Otherwise, reading
+    /// stops as soon as the limit is exceeded. In both cases, an error for which
+    /// [`is_limit_exceeded`](crate::byte_stream::error::Error::is_limit_exceeded) returns `true` is
+    /// returned.
+    /// ```no_run
+    /// use aws_smithy_http::byte_stream::ByteStream;
+    /// async fn get_data(stream: ByteStream) -> Option<bytes::Bytes> {
+    ///     match stream.collect_with_limit(10 * 1024 * 1024).await {
+    ///         Ok(data) => Some(data.into_bytes()),
+    ///         Err(err) if err.is_limit_exceeded() => None,
+    ///         Err(err) => panic!("failed to read the stream: {}", err),
+    ///     }
+    /// }
+    /// ```
+    pub async fn collect_with_limit(self, limit: u64) -> Result<AggregatedBytes, Error> {
+        if self.inner.body.size_hint().lower() > limit {
+            return Err(ErrorKind::LimitExceeded { limit }.into());
+        }
+        let mut output = SegmentedBuf::new();
+        let mut length = 0;
+        let body = self.inner.body;
+        pin_utils::pin_mut!(body);
+        while let Some(buf) = body.data().await {
+            let buf = buf.map_err(Error::streaming)?;
+            length += buf.len() as u64;
+            if length > limit {
+                return Err(ErrorKind::LimitExceeded { limit }.into());
+            }
+            output.push(buf);
+        }
+        Ok(AggregatedBytes(output))
+    }
+
     /// Returns a [`FsBuilder`](crate::byte_stream::FsBuilder), allowing you to build a `ByteStream` with
     /// full control over how the file is read (eg. specifying the length of the file or the size of the buffer used to read the file).
     /// ```no_run
@@ -658,4 +705,40 @@ mod tests {
         assert_eq!(lines.next_line().await.unwrap(), Some("data 3".to_owned()));
         assert_eq!(lines.next_line().await.unwrap(), None);
     }
+
+    #[tokio::test]
+    async fn collect_with_limit() {
+        use super::ByteStream;
+        use crate::body::SdkBody;
+
+        let data = ByteStream::from_static(b"0123456789")
+            .collect_with_limit(10)
+            .await
+            .unwrap();
+        assert_eq!(Bytes::from_static(b"0123456789"), data.into_bytes());
+
+        // The size hint already exceeds the limit
+        let err = ByteStream::from_static(b"0123456789")
+            .collect_with_limit(9)
+            .await
+            .unwrap_err();
+        assert!(err.is_limit_exceeded());
+        assert_eq!("the stream exceeded the limit of 9 bytes", err.to_string());
+
+        // Reading stops once the limit is exceeded, even though the stream continues
+        let (mut sender, body) = hyper::Body::channel();
+        let sent = tokio::spawn(async move {
+            let mut sent = 0;
+            while sender.send_data(Bytes::from_static(b"data")).await.is_ok() {
+                sent += 1;
+            }
+            sent
+        });
+        let err = ByteStream::new(SdkBody::from(body))
+            .collect_with_limit(10)
+            .await
+            .unwrap_err();
+        assert!(err.is_limit_exceeded());
+        assert!(sent.await.unwrap() <= 4);
+    }
 }
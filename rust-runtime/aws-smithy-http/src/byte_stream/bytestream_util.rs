@@ -18,7 +18,7 @@ use tokio::io::{self, AsyncReadExt, AsyncSeekExt};
 use tokio_util::io::ReaderStream;
 
 // 4KB corresponds to the default buffer size used by Tokio's ReaderStream
-const DEFAULT_BUFFER_SIZE: usize = 4096;
+pub(super) const DEFAULT_BUFFER_SIZE: usize = 4096;
 // By default, read files from their start
 const DEFAULT_OFFSET: u64 = 0;
 
@@ -28,7 +28,7 @@ const DEFAULT_OFFSET: u64 = 0;
 /// 1. The underlying file is wrapped with StreamReader to implement HTTP body
 /// 2. It can be constructed directly from a path so it's easy to use during retries
 /// 3. Provide size hint
-struct PathBody {
+pub(super) struct PathBody {
     state: State,
     // The number of bytes to read
     length: u64,
@@ -38,7 +38,12 @@ struct PathBody {
 }
 
 impl PathBody {
-    fn from_path(path_buf: PathBuf, length: u64, buffer_size: usize, offset: Option<u64>) -> Self {
+    pub(super) fn from_path(
+        path_buf: PathBuf,
+        length: u64,
+        buffer_size: usize,
+        offset: Option<u64>,
+    ) -> Self {
         PathBody {
             state: State::Unloaded(path_buf),
             length,
@@ -176,24 +181,7 @@ impl FsBuilder {
         };
 
         let buffer_size = self.buffer_size;
-        let offset = self.offset.unwrap_or(DEFAULT_OFFSET);
-        // Checking the file length like this does have a cost, but the benefit is that we can
-        // notify users when file/chunk is smaller than expected.
-        let file_length = self.get_file_size().await?;
-        if offset > file_length {
-            return Err(ErrorKind::OffsetLargerThanFileSize.into());
-        }
-
-        let length = match self.length {
-            Some(Length::Exact(length)) => {
-                if length > file_length - offset {
-                    return Err(ErrorKind::LengthLargerThanFileSizeMinusReadOffset.into());
-                }
-                length
-            }
-            Some(Length::UpTo(length)) => length,
-            None => file_length - offset,
-        };
+        let (offset, length, _) = self.get_range().await?;
 
         if let Some(path) = self.path {
             let body_loader = move || {
@@ -224,6 +212,96 @@ impl FsBuilder {
         }
     }
 
+    /// Returns [`ByteStreams`](ByteStream) for consecutive parts of the file, in order.
+    ///
+    /// The range selected with [`offset`](FsBuilder::offset) and [`length`](FsBuilder::length) is
+    /// split into parts of `part_size` bytes; only the last part may be smaller. Every part reads
+    /// its own range of the file, so parts can be read concurrently, e.g. to upload a large file
+    /// in parallel with a multipart upload. Each part is retryable and provides an exact size hint.
+    /// An empty range results in a single, empty part.
+    ///
+    /// NOTE: Parts can only be created from a [`path`](FsBuilder::path). An error is returned if
+    /// this builder was given a [`file`](FsBuilder::file) instead, or if `part_size` is zero.
+    ///
+    /// # Examples
+    /// ```no_run
+    /// # #[cfg(feature = "rt-tokio")]
+    /// # {
+    /// use aws_smithy_http::byte_stream::ByteStream;
+    ///
+    /// async fn upload_parts() {
+    ///     let parts = ByteStream::read_from()
+    ///         .path("docs/some-large-file.csv")
+    ///         .buffer_size(65_536)
+    ///         // Split the file into 8 MiB parts
+    ///         .build_parts(8 * 1024 * 1024)
+    ///         .await
+    ///         .expect("valid path");
+    ///     for (part_number, part) in parts.into_iter().enumerate() {
+    ///         // upload each part
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub async fn build_parts(self, part_size: u64) -> Result<Vec<ByteStream>, Error> {
+        if part_size == 0 {
+            return Err(ErrorKind::ZeroPartSize.into());
+        }
+        let path = match (self.path.as_ref(), self.file.as_ref()) {
+            (Some(path), None) => path.clone(),
+            _ => return Err(ErrorKind::PartsRequireOnlyPath.into()),
+        };
+
+        let buffer_size = self.buffer_size;
+        let (offset, length, file_length) = self.get_range().await?;
+        // A `Length::UpTo` can reach past the end of the file. Clamp the range to the file so
+        // that the size hint of every part is exact.
+        let end = offset + length.min(file_length - offset);
+
+        let mut parts = Vec::new();
+        let mut part_offset = offset;
+        loop {
+            let part_length = part_size.min(end - part_offset);
+            let path = path.clone();
+            parts.push(ByteStream::new(SdkBody::retryable(move || {
+                SdkBody::from_dyn(http_body::combinators::BoxBody::new(PathBody::from_path(
+                    path.clone(),
+                    part_length,
+                    buffer_size,
+                    Some(part_offset),
+                )))
+            })));
+            part_offset += part_length;
+            if part_offset >= end {
+                break;
+            }
+        }
+        Ok(parts)
+    }
+
+    /// Returns the offset and length to read, and the size of the file
+    async fn get_range(&self) -> Result<(u64, u64, u64), Error> {
+        let offset = self.offset.unwrap_or(DEFAULT_OFFSET);
+        // Checking the file length like this does have a cost, but the benefit is that we can
+        // notify users when file/chunk is smaller than expected.
+        let file_length = self.get_file_size().await?;
+        if offset > file_length {
+            return Err(ErrorKind::OffsetLargerThanFileSize.into());
+        }
+
+        let length = match self.length {
+            Some(Length::Exact(length)) => {
+                if length > file_length - offset {
+                    return Err(ErrorKind::LengthLargerThanFileSizeMinusReadOffset.into());
+                }
+                length
+            }
+            Some(Length::UpTo(length)) => length,
+            None => file_length - offset,
+        };
+        Ok((offset, length, file_length))
+    }
+
     async fn get_file_size(&self) -> Result<u64, Error> {
         Ok(match self.path.as_ref() {
             Some(path) => tokio::fs::metadata(path).await,
@@ -606,4 +684,125 @@ mod test {
 
         assert_eq!(data_str, in_memory_copy_of_file_contents);
     }
+
+    #[tokio::test]
+    async fn fsbuilder_build_parts() {
+        let mut file = NamedTempFile::new().unwrap();
+        let mut contents = Vec::new();
+        for i in 0..1000 {
+            writeln!(contents, "Line {:04}", i).unwrap();
+        }
+        file.write_all(&contents).unwrap();
+        file.flush().expect("flushing is OK");
+
+        // 10000 bytes, split into 2 parts of 4096 bytes and one of 1808 bytes
+        let parts = FsBuilder::new()
+            .path(&file)
+            .buffer_size(1000)
+            .build_parts(4096)
+            .await
+            .unwrap();
+        let sizes: Vec<_> = parts
+            .iter()
+            .map(|part| part.inner.body.size_hint().exact().unwrap())
+            .collect();
+        assert_eq!(vec![4096, 4096, 1808], sizes);
+
+        // Parts can be read concurrently, and every part can be re-read
+        let retries: Vec<_> = parts
+            .iter()
+            .map(|part| ByteStream::new(part.inner.body.try_clone().expect("parts are retryable")))
+            .collect();
+        let read_all = |parts: Vec<ByteStream>| async move {
+            let handles: Vec<_> = parts
+                .into_iter()
+                .map(|part| tokio::spawn(part.collect()))
+                .collect();
+            let mut collected = Vec::new();
+            for handle in handles {
+                collected.extend_from_slice(&handle.await.unwrap().unwrap().into_bytes());
+            }
+            collected
+        };
+        assert_eq!(contents, read_all(parts).await);
+        assert_eq!(contents, read_all(retries).await);
+    }
+
+    #[tokio::test]
+    async fn fsbuilder_build_parts_respects_offset_and_length() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "0123456789abcdefghij").unwrap();
+        file.flush().expect("flushing is OK");
+
+        let collect = |parts: Vec<ByteStream>| async move {
+            let mut collected = Vec::new();
+            for part in parts {
+                let size_hint = part.inner.body.size_hint().exact().unwrap();
+                let bytes = part.collect().await.unwrap().into_bytes();
+                assert_eq!(size_hint, bytes.len() as u64);
+                collected.push(String::from_utf8(bytes.to_vec()).unwrap());
+            }
+            collected
+        };
+
+        let parts = FsBuilder::new()
+            .path(&file)
+            .offset(5)
+            .length(Length::Exact(10))
+            .build_parts(4)
+            .await
+            .unwrap();
+        assert_eq!(vec!["5678", "9abc", "de"], collect(parts).await);
+
+        // Size hints are exact even when reading "up to" past the end of the file
+        let parts = FsBuilder::new()
+            .path(&file)
+            .offset(12)
+            .length(Length::UpTo(9000))
+            .build_parts(5)
+            .await
+            .unwrap();
+        assert_eq!(vec!["cdefg", "hij"], collect(parts).await);
+
+        let parts = FsBuilder::new()
+            .path(&file)
+            .offset(20)
+            .build_parts(5)
+            .await
+            .unwrap();
+        assert_eq!(vec![""], collect(parts).await);
+    }
+
+    #[tokio::test]
+    async fn fsbuilder_build_parts_rejects_invalid_input() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "0123456789").unwrap();
+        file.flush().expect("flushing is OK");
+
+        let err = FsBuilder::new()
+            .path(&file)
+            .build_parts(0)
+            .await
+            .expect_err("part size must be greater than zero");
+        assert_eq!("the part size must be greater than zero", err.to_string());
+
+        let err = FsBuilder::new()
+            .file(tokio::fs::File::open(file.path()).await.unwrap())
+            .build_parts(4)
+            .await
+            .expect_err("parts need a path");
+        assert_eq!(
+            "parts can only be built from a `path`, and no `file` may be set",
+            err.to_string()
+        );
+
+        let err = FsBuilder::new()
+            .build_parts(4)
+            .await
+            .expect_err("parts need a path");
+        assert_eq!(
+            "parts can only be built from a `path`, and no `file` may be set",
+            err.to_string()
+        );
+    }
 }
@@ -15,6 +15,17 @@ pub(super) enum ErrorKind {
     OffsetLargerThanFileSize,
     #[cfg(feature = "rt-tokio")]
     LengthLargerThanFileSizeMinusReadOffset,
+    #[cfg(feature = "rt-tokio")]
+    ZeroPartSize,
+    #[cfg(feature = "rt-tokio")]
+    PartsRequireOnlyPath,
+    StreamLengthMismatch {
+        expected: u64,
+        actual: u64,
+    },
+    LimitExceeded {
+        limit: u64,
+    },
     IoError(IoError),
     StreamingError(Box<dyn StdError + Send + Sync + 'static>),
 }
@@ -29,6 +40,13 @@ impl Error {
     pub(super) fn streaming(err: impl Into<Box<dyn StdError + Send + Sync + 'static>>) -> Self {
         ErrorKind::StreamingError(err.into()).into()
     }
+
+    /// Returns true if the stream was larger than the limit it was collected with
+    ///
+    /// See [`ByteStream::collect_with_limit`](crate::byte_stream::ByteStream::collect_with_limit).
+    pub fn is_limit_exceeded(&self) -> bool {
+        matches!(self.kind, ErrorKind::LimitExceeded { .. })
+    }
 }
 
 impl From<ErrorKind> for Error {
@@ -45,7 +63,7 @@ impl From<IoError> for Error {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self.kind {
+        match &self.kind {
             #[cfg(feature = "rt-tokio")]
             ErrorKind::OffsetLargerThanFileSize => write!(
                 f,
@@ -56,6 +74,21 @@ impl fmt::Display for Error {
                 f,
                 "`Length::Exact` was larger than file size minus read offset"
             ),
+            #[cfg(feature = "rt-tokio")]
+            ErrorKind::ZeroPartSize => write!(f, "the part size must be greater than zero"),
+            #[cfg(feature = "rt-tokio")]
+            ErrorKind::PartsRequireOnlyPath => write!(
+                f,
+                "parts can only be built from a `path`, and no `file` may be set"
+            ),
+            ErrorKind::StreamLengthMismatch { expected, actual } => write!(
+                f,
+                "expected the stream to contain {} bytes but read {} bytes",
+                expected, actual
+            ),
+            ErrorKind::LimitExceeded { limit } => {
+                write!(f, "the stream exceeded the limit of {} bytes", limit)
+            }
             ErrorKind::IoError(_) => write!(f, "IO error"),
             ErrorKind::StreamingError(_) => write!(f, "streaming error"),
         }
@@ -69,7 +102,10 @@ impl StdError for Error {
             ErrorKind::StreamingError(err) => Some(err.as_ref() as _),
             #[cfg(feature = "rt-tokio")]
             ErrorKind::OffsetLargerThanFileSize
-            | ErrorKind::LengthLargerThanFileSizeMinusReadOffset => None,
+            | ErrorKind::LengthLargerThanFileSizeMinusReadOffset
+            | ErrorKind::ZeroPartSize
+            | ErrorKind::PartsRequireOnlyPath => None,
+            ErrorKind::StreamLengthMismatch { .. } | ErrorKind::LimitExceeded { .. } => None,
         }
     }
 }
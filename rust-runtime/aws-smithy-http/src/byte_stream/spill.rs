@@ -0,0 +1,333 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::body::SdkBody;
+use crate::byte_stream::bytestream_util::{PathBody, DEFAULT_BUFFER_SIZE};
+use crate::byte_stream::error::Error;
+use crate::byte_stream::{AggregatedBytes, ByteStream};
+use bytes::{Buf, Bytes};
+use bytes_utils::SegmentedBuf;
+use http_body::Body;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// A file that is deleted when dropped
+#[derive(Debug)]
+struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    async fn create(dir: &Path) -> Result<(Self, File), Error> {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.subsec_nanos())
+            .unwrap_or_default();
+        let path = dir.join(format!(
+            ".aws-smithy-spill-{}-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed),
+            nanos
+        ));
+        // Never overwrite an existing file
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        // The temporary directory is usually shared, so keep other users from reading the data
+        #[cfg(unix)]
+        options.mode(0o600);
+        let file = options.open(&path).await?;
+        Ok((TempFile { path }, file))
+    }
+}
+
+impl Drop for TempFile {
+    // This blocks, even when dropped in an async context. Removing a file is a single unlink that
+    // doesn't touch its data, and deleting it right away means the data never outlives its last
+    // user, e.g. when the runtime shuts down before a background task could run.
+    fn drop(&mut self) {
+        if let Err(err) = std::fs::remove_file(&self.path) {
+            tracing::warn!(path = ?self.path, err = %err, "failed to remove spilled stream data");
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Storage {
+    Memory(AggregatedBytes),
+    File { file: Arc<TempFile>, length: u64 },
+}
+
+/// Binary data that was either kept in memory or spilled to a temporary file
+///
+/// Created by [`ByteStream::collect_or_spill`]. Small streams are kept in memory, like with
+/// [`ByteStream::collect`]. Streams that are larger than the spill threshold are written to a
+/// temporary file instead, which is deleted once the `SpilledBytes` and every `ByteStream` created
+/// from it are dropped.
+#[derive(Debug)]
+pub struct SpilledBytes {
+    storage: Storage,
+}
+
+impl SpilledBytes {
+    /// The number of bytes that were read from the stream
+    pub fn len(&self) -> u64 {
+        match &self.storage {
+            Storage::Memory(bytes) => bytes.remaining() as u64,
+            Storage::File { length, .. } => *length,
+        }
+    }
+
+    /// Returns true if the stream was empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true if the data was written to a temporary file
+    pub fn is_spilled(&self) -> bool {
+        matches!(self.storage, Storage::File { .. })
+    }
+
+    /// Path of the temporary file the data was written to, if it was spilled
+    pub fn path(&self) -> Option<&Path> {
+        match &self.storage {
+            Storage::Memory(_) => None,
+            Storage::File { file, .. } => Some(&file.path),
+        }
+    }
+
+    /// Convert this data into [`Bytes`], reading it into memory if it was spilled
+    pub async fn into_bytes(self) -> Result<Bytes, Error> {
+        Ok(Bytes::from(self.to_vec().await?))
+    }
+
+    /// Convert this data into a `Vec<u8>`, reading it into memory if it was spilled
+    pub async fn to_vec(self) -> Result<Vec<u8>, Error> {
+        match self.storage {
+            Storage::Memory(bytes) => Ok(bytes.to_vec()),
+            Storage::File { file, length } => {
+                let mut data = Vec::with_capacity(length as usize);
+                File::open(&file.path).await?.read_to_end(&mut data).await?;
+                Ok(data)
+            }
+        }
+    }
+
+    /// Convert this data into a retryable [`ByteStream`] with an exact size hint
+    ///
+    /// Spilled data is streamed from the temporary file, which is kept until the `ByteStream` and
+    /// all of its retries are dropped.
+    pub fn into_byte_stream(self) -> ByteStream {
+        match self.storage {
+            Storage::Memory(bytes) => ByteStream::from(bytes.into_bytes()),
+            Storage::File { file, length } => ByteStream::new(SdkBody::retryable(move || {
+                SdkBody::from_dyn(http_body::combinators::BoxBody::new(TempFileBody {
+                    body: PathBody::from_path(file.path.clone(), length, DEFAULT_BUFFER_SIZE, None),
+                    _file: file.clone(),
+                }))
+            })),
+        }
+    }
+}
+
+/// A [`PathBody`] that keeps its temporary file alive
+struct TempFileBody {
+    body: PathBody,
+    _file: Arc<TempFile>,
+}
+
+impl Body for TempFileBody {
+    type Data = Bytes;
+    type Error = <PathBody as Body>::Error;
+
+    fn poll_data(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Result<Self::Data, Self::Error>>> {
+        std::pin::Pin::new(&mut self.body).poll_data(cx)
+    }
+
+    fn poll_trailers(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        std::pin::Pin::new(&mut self.body).poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.body.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.body.size_hint()
+    }
+}
+
+impl ByteStream {
+    /// Read all the data from this `ByteStream`, writing it to a temporary file once more than
+    /// `threshold` bytes were read
+    ///
+    /// The temporary file is created in [`std::env::temp_dir`]. Use
+    /// [`collect_or_spill_in`](ByteStream::collect_or_spill_in) to choose another directory.
+    /// ```no_run
+    /// use aws_smithy_http::byte_stream::ByteStream;
+    /// async fn upload_again(stream: ByteStream) -> ByteStream {
+    ///     // Keep up to 8 MiB in memory
+    ///     let data = stream.collect_or_spill(8 * 1024 * 1024).await.expect("stream is readable");
+    ///     data.into_byte_stream()
+    /// }
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "rt-tokio")))]
+    pub async fn collect_or_spill(self, threshold: usize) -> Result<SpilledBytes, Error> {
+        self.collect_or_spill_in(threshold, std::env::temp_dir())
+            .await
+    }
+
+    /// Read all the data from this `ByteStream`, writing it to a temporary file in `dir` once more
+    /// than `threshold` bytes were read
+    ///
+    /// When the size hint of the stream already exceeds `threshold`, the data is written to the
+    /// file right away.
+    #[cfg_attr(docsrs, doc(cfg(feature = "rt-tokio")))]
+    pub async fn collect_or_spill_in(
+        self,
+        threshold: usize,
+        dir: impl AsRef<Path>,
+    ) -> Result<SpilledBytes, Error> {
+        let threshold = threshold as u64;
+        let body = self.inner.body;
+        pin_utils::pin_mut!(body);
+
+        let mut buffered = SegmentedBuf::new();
+        let mut length = 0;
+        if body.size_hint().lower() <= threshold {
+            while let Some(buf) = body.data().await {
+                let buf = buf.map_err(Error::streaming)?;
+                length += buf.len() as u64;
+                buffered.push(buf);
+                if length > threshold {
+                    break;
+                }
+            }
+            if length <= threshold {
+                return Ok(SpilledBytes {
+                    storage: Storage::Memory(AggregatedBytes(buffered)),
+                });
+            }
+        }
+
+        let (temp_file, mut file) = TempFile::create(dir.as_ref()).await?;
+        tracing::debug!(path = ?temp_file.path, threshold = threshold, "spilling stream to disk");
+        for segment in buffered.into_inner() {
+            file.write_all(&segment).await?;
+        }
+        while let Some(buf) = body.data().await {
+            let buf = buf.map_err(Error::streaming)?;
+            length += buf.len() as u64;
+            file.write_all(&buf).await?;
+        }
+        file.flush().await?;
+        Ok(SpilledBytes {
+            storage: Storage::File {
+                file: Arc::new(temp_file),
+                length,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::body::SdkBody;
+    use crate::byte_stream::ByteStream;
+    use bytes::Bytes;
+    use http_body::Body;
+
+    fn channel_stream(chunks: &'static [&'static str]) -> ByteStream {
+        let (mut sender, body) = hyper::Body::channel();
+        tokio::spawn(async move {
+            for chunk in chunks {
+                sender
+                    .send_data(Bytes::from_static(chunk.as_bytes()))
+                    .await
+                    .unwrap();
+            }
+        });
+        ByteStream::new(SdkBody::from(body))
+    }
+
+    #[tokio::test]
+    async fn small_streams_stay_in_memory() {
+        let data = channel_stream(&["hello", " world"])
+            .collect_or_spill(11)
+            .await
+            .unwrap();
+        assert!(!data.is_spilled());
+        assert_eq!(data.path(), None);
+        assert_eq!(11, data.len());
+        assert_eq!(
+            Bytes::from_static(b"hello world"),
+            data.into_bytes().await.unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn large_streams_are_spilled() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = channel_stream(&["hello", " world", "!"])
+            .collect_or_spill_in(8, dir.path())
+            .await
+            .unwrap();
+        assert!(data.is_spilled());
+        assert_eq!(12, data.len());
+        let path = data.path().unwrap().to_path_buf();
+        assert!(path.starts_with(dir.path()));
+
+        let stream = data.into_byte_stream();
+        let body = stream.into_inner();
+        assert_eq!(Some(12), body.size_hint().exact());
+        let retry = body.try_clone().expect("spilled data is retryable");
+        for body in [body, retry] {
+            assert_eq!(
+                Bytes::from_static(b"hello world!"),
+                ByteStream::new(body).collect().await.unwrap().into_bytes()
+            );
+        }
+        assert!(!path.exists(), "the temporary file is deleted once unused");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn spilled_files_are_only_readable_by_their_owner() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let data = ByteStream::from_static(b"0123456789")
+            .collect_or_spill_in(4, dir.path())
+            .await
+            .unwrap();
+        let mode = std::fs::metadata(data.path().unwrap())
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(0o600, mode & 0o777);
+    }
+
+    #[tokio::test]
+    async fn size_hint_over_threshold_spills_right_away() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = ByteStream::from_static(b"0123456789")
+            .collect_or_spill_in(4, dir.path())
+            .await
+            .unwrap();
+        assert!(data.is_spilled());
+        assert_eq!(b"0123456789".to_vec(), data.to_vec().await.unwrap());
+        assert_eq!(0, std::fs::read_dir(dir.path()).unwrap().count());
+    }
+}
@@ -0,0 +1,504 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::body::{BoxBody, SdkBody};
+use crate::byte_stream::{error::Error, error::ErrorKind, ByteStream};
+use bytes::Bytes;
+use futures_core::{ready, Stream};
+use http::HeaderMap;
+use http_body::{Body, SizeHint};
+use pin_project_lite::pin_project;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+enum Source {
+    /// Creates a new stream for every attempt
+    Factory(Arc<dyn Fn() -> BoxStream + Send + Sync>),
+    /// A stream that can only be read once
+    OneShot(BoxStream),
+}
+
+type BoxStream = Pin<Box<dyn Stream<Item = Result<Bytes, BoxError>> + Send + Sync>>;
+
+fn box_stream<S, E>(stream: S) -> BoxStream
+where
+    S: Stream<Item = Result<Bytes, E>> + Send + Sync + 'static,
+    E: Into<BoxError> + 'static,
+{
+    struct MapErr<S>(Pin<Box<S>>);
+
+    impl<S, E> Stream for MapErr<S>
+    where
+        S: Stream<Item = Result<Bytes, E>>,
+        E: Into<BoxError>,
+    {
+        type Item = Result<Bytes, BoxError>;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            self.0
+                .as_mut()
+                .poll_next(cx)
+                .map(|item| item.map(|result| result.map_err(Into::into)))
+        }
+    }
+
+    Box::pin(MapErr(Box::pin(stream)))
+}
+
+/// Builder for creating [`ByteStreams`](ByteStream) from a [`Stream`] or an `AsyncRead`.
+///
+/// A `ByteStream` created from a stream factory, with [`ByteStream::from_stream_fn`] or
+/// `ByteStream::from_async_read_fn`, is retryable: the factory is called again for every retry.
+/// A `ByteStream` created from a single stream, with [`ByteStream::from_stream`] or
+/// `ByteStream::from_async_read`, can only be read once unless a
+/// [replay buffer](StreamBuilder::replay_buffer_size) is configured.
+///
+/// Example usage:
+/// ```no_run
+/// use aws_smithy_http::byte_stream::ByteStream;
+/// use bytes::Bytes;
+///
+/// fn repeated_greeting(times: usize) -> ByteStream {
+///     // The factory is called again for every retry
+///     ByteStream::from_stream_fn(move || {
+///         let chunks = (0..times).map(|_| Ok::<_, std::io::Error>(Bytes::from_static(b"hello ")));
+///         futures_util::stream::iter(chunks)
+///     })
+///     .length(6 * times as u64)
+///     .build()
+/// }
+/// ```
+pub struct StreamBuilder {
+    source: Source,
+    length: Option<u64>,
+    replay_buffer_size: Option<usize>,
+}
+
+impl fmt::Debug for StreamBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let source = match self.source {
+            Source::Factory(_) => "Factory",
+            Source::OneShot(_) => "OneShot",
+        };
+        f.debug_struct("StreamBuilder")
+            .field("source", &source)
+            .field("length", &self.length)
+            .field("replay_buffer_size", &self.replay_buffer_size)
+            .finish()
+    }
+}
+
+impl StreamBuilder {
+    fn new(source: Source) -> Self {
+        Self {
+            source,
+            length: None,
+            replay_buffer_size: None,
+        }
+    }
+
+    /// Specify the exact length of the stream (in bytes).
+    ///
+    /// The resulting ByteStream will provide a size hint, allowing it to be sent with a
+    /// `Content-Length`. Reading the stream fails if it doesn't contain exactly `length` bytes.
+    /// When unset, the length of the stream is unknown.
+    pub fn length(mut self, length: u64) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// Make a stream that can only be read once retryable, by keeping up to `size` bytes of it in
+    /// memory.
+    ///
+    /// When a request is retried, the data that was already read is replayed from memory before the
+    /// rest of the stream is read. Once more than `size` bytes have been read, the buffer is
+    /// released and the ByteStream is no longer retryable. This has no effect on ByteStreams created
+    /// from a stream factory, which are always retryable.
+    pub fn replay_buffer_size(mut self, size: usize) -> Self {
+        self.replay_buffer_size = Some(size);
+        self
+    }
+
+    /// Returns a [`ByteStream`](ByteStream) from this builder.
+    pub fn build(self) -> ByteStream {
+        let length = self.length;
+        let body = match self.source {
+            Source::Factory(factory) => SdkBody::retryable(move || {
+                SdkBody::from_dyn(BoxBody::new(LengthCheckedBody::new(factory(), length)))
+            }),
+            Source::OneShot(stream) => {
+                let body = LengthCheckedBody::new(stream, length);
+                match self.replay_buffer_size {
+                    Some(size) => ReplayBody::new_retryable(body, size),
+                    None => SdkBody::from_dyn(BoxBody::new(body)),
+                }
+            }
+        };
+        ByteStream::new(body)
+    }
+}
+
+impl ByteStream {
+    /// Create a retryable ByteStream from a function that creates a [`Stream`] of [`Bytes`].
+    ///
+    /// `make_stream` is called once for the initial request and again for every retry. Every
+    /// stream it returns must contain the same data.
+    pub fn from_stream_fn<F, S, E>(make_stream: F) -> StreamBuilder
+    where
+        F: Fn() -> S + Send + Sync + 'static,
+        S: Stream<Item = Result<Bytes, E>> + Send + Sync + 'static,
+        E: Into<BoxError> + 'static,
+    {
+        StreamBuilder::new(Source::Factory(Arc::new(move || box_stream(make_stream()))))
+    }
+
+    /// Create a ByteStream from a [`Stream`] of [`Bytes`].
+    ///
+    /// The stream can only be read once, so the ByteStream isn't retryable unless a
+    /// [replay buffer](StreamBuilder::replay_buffer_size) is configured.
+    pub fn from_stream<S, E>(stream: S) -> StreamBuilder
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + Sync + 'static,
+        E: Into<BoxError> + 'static,
+    {
+        StreamBuilder::new(Source::OneShot(box_stream(stream)))
+    }
+
+    /// Create a retryable ByteStream from a function that creates an [`AsyncRead`](tokio::io::AsyncRead).
+    ///
+    /// `make_reader` is called once for the initial request and again for every retry. Every
+    /// reader it returns must produce the same data.
+    #[cfg(feature = "rt-tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rt-tokio")))]
+    pub fn from_async_read_fn<F, R>(make_reader: F) -> StreamBuilder
+    where
+        F: Fn() -> R + Send + Sync + 'static,
+        R: tokio::io::AsyncRead + Send + Sync + 'static,
+    {
+        Self::from_stream_fn(move || tokio_util::io::ReaderStream::new(make_reader()))
+    }
+
+    /// Create a ByteStream from an [`AsyncRead`](tokio::io::AsyncRead).
+    ///
+    /// The reader can only be read once, so the ByteStream isn't retryable unless a
+    /// [replay buffer](StreamBuilder::replay_buffer_size) is configured.
+    #[cfg(feature = "rt-tokio")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rt-tokio")))]
+    pub fn from_async_read<R>(reader: R) -> StreamBuilder
+    where
+        R: tokio::io::AsyncRead + Send + Sync + 'static,
+    {
+        Self::from_stream(tokio_util::io::ReaderStream::new(reader))
+    }
+}
+
+pin_project! {
+    /// An HTTP body that reads a stream and, if a length was given, checks that the stream
+    /// contains exactly that many bytes
+    struct LengthCheckedBody {
+        stream: BoxStream,
+        length: Option<u64>,
+        read: u64,
+    }
+}
+
+impl LengthCheckedBody {
+    fn new(stream: BoxStream, length: Option<u64>) -> Self {
+        Self {
+            stream,
+            length,
+            read: 0,
+        }
+    }
+
+    fn length_mismatch(&self) -> BoxError {
+        Box::new(Error::from(ErrorKind::StreamLengthMismatch {
+            expected: self.length.unwrap_or_default(),
+            actual: self.read,
+        }))
+    }
+}
+
+impl Body for LengthCheckedBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let next = ready!(self.stream.as_mut().poll_next(cx));
+        Poll::Ready(match next {
+            Some(Ok(data)) => {
+                self.read += data.len() as u64;
+                match self.length {
+                    Some(length) if self.read > length => Some(Err(self.length_mismatch())),
+                    _ => Some(Ok(data)),
+                }
+            }
+            Some(Err(err)) => Some(Err(err)),
+            None => match self.length {
+                Some(length) if self.read != length => Some(Err(self.length_mismatch())),
+                _ => None,
+            },
+        })
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        // fast path end-stream for empty streams
+        self.length == Some(0)
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        match self.length {
+            Some(length) => SizeHint::with_exact(length),
+            None => SizeHint::new(),
+        }
+    }
+}
+
+/// State shared by every attempt to read a one-shot stream with a replay buffer
+struct ReplayState {
+    source: LengthCheckedBody,
+    /// Every chunk read from `source` so far, unless `overflowed` is set
+    buffered: Vec<Bytes>,
+    buffered_len: usize,
+    capacity: usize,
+    /// Set once more than `capacity` bytes were read. The stream can't be replayed anymore.
+    overflowed: bool,
+    /// The ID of the attempt that is currently reading the stream. Only that attempt may read from
+    /// `source`; an attempt takes over when it's polled for the first time.
+    active: Option<u64>,
+    next_id: u64,
+}
+
+/// An HTTP body for a single attempt to read a one-shot stream with a replay buffer
+struct ReplayBody {
+    state: Arc<Mutex<ReplayState>>,
+    id: u64,
+    started: bool,
+    /// Index of the next buffered chunk to replay
+    position: usize,
+}
+
+impl ReplayBody {
+    fn new_retryable(source: LengthCheckedBody, capacity: usize) -> SdkBody {
+        let state = Arc::new(Mutex::new(ReplayState {
+            source,
+            buffered: Vec::new(),
+            buffered_len: 0,
+            capacity,
+            overflowed: false,
+            active: None,
+            next_id: 1,
+        }));
+        let initial = SdkBody::from_dyn(BoxBody::new(ReplayBody::new(state.clone(), 0)));
+        SdkBody::with_rebuild(initial, move || {
+            let mut locked = state.lock().unwrap();
+            if locked.overflowed {
+                tracing::debug!(
+                    capacity = locked.capacity,
+                    "stream exceeded its replay buffer and can't be retried"
+                );
+                return None;
+            }
+            let id = locked.next_id;
+            locked.next_id += 1;
+            Some(SdkBody::from_dyn(BoxBody::new(ReplayBody::new(
+                state.clone(),
+                id,
+            ))))
+        })
+    }
+
+    fn new(state: Arc<Mutex<ReplayState>>, id: u64) -> Self {
+        Self {
+            state,
+            id,
+            started: false,
+            position: 0,
+        }
+    }
+}
+
+impl Body for ReplayBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = &mut *self;
+        let mut state = this.state.lock().unwrap();
+        if state.active != Some(this.id) {
+            if this.started {
+                return Poll::Ready(Some(Err(Error::streaming(
+                    "this stream was replayed for a retry and can no longer be read",
+                )
+                .into())));
+            }
+            if state.active.is_some() && state.overflowed {
+                return Poll::Ready(Some(Err(Error::streaming(
+                    "this stream exceeded its replay buffer and can't be replayed",
+                )
+                .into())));
+            }
+            state.active = Some(this.id);
+            this.started = true;
+        }
+        if let Some(chunk) = state.buffered.get(this.position) {
+            this.position += 1;
+            return Poll::Ready(Some(Ok(chunk.clone())));
+        }
+        let next = ready!(Pin::new(&mut state.source).poll_data(cx));
+        if let Some(Ok(data)) = &next {
+            if !state.overflowed {
+                if state.buffered_len + data.len() <= state.capacity {
+                    state.buffered_len += data.len();
+                    state.buffered.push(data.clone());
+                    this.position += 1;
+                } else {
+                    state.overflowed = true;
+                    state.buffered = Vec::new();
+                }
+            }
+        }
+        Poll::Ready(next)
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.state.lock().unwrap().source.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::byte_stream::ByteStream;
+    use aws_smithy_types::error::display::DisplayErrorContext;
+    use bytes::Bytes;
+    use http_body::Body;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn chunks(chunks: &[&'static str]) -> Vec<Result<Bytes, std::io::Error>> {
+        chunks
+            .iter()
+            .map(|chunk| Ok(Bytes::from_static(chunk.as_bytes())))
+            .collect()
+    }
+
+    async fn read(stream: ByteStream) -> Result<String, crate::byte_stream::error::Error> {
+        let data = stream.collect().await?.into_bytes();
+        Ok(String::from_utf8(data.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn stream_factory_is_retryable() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let stream = ByteStream::from_stream_fn({
+            let calls = calls.clone();
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                tokio_stream::iter(chunks(&["hello", " ", "world"]))
+            }
+        })
+        .length(11)
+        .build();
+        let body = stream.into_inner();
+        assert_eq!(Some(11), body.content_length());
+
+        let retry = body.try_clone().expect("retryable");
+        assert_eq!("hello world", read(ByteStream::new(body)).await.unwrap());
+        assert_eq!("hello world", read(ByteStream::new(retry)).await.unwrap());
+        assert_eq!(2, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn unknown_length() {
+        let stream = ByteStream::from_stream(tokio_stream::iter(chunks(&["abc", "def"]))).build();
+        assert_eq!(None, stream.inner.body.content_length());
+        assert_eq!("abcdef", read(stream).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn length_mismatch_is_an_error() {
+        for length in [5, 7] {
+            let stream = ByteStream::from_stream(tokio_stream::iter(chunks(&["abc", "def"])))
+                .length(length)
+                .build();
+            let err = read(stream).await.expect_err("length doesn't match");
+            assert!(
+                DisplayErrorContext(&err)
+                    .to_string()
+                    .contains("expected the stream to contain"),
+                "length {}",
+                length
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn one_shot_stream_is_not_retryable() {
+        let stream = ByteStream::from_stream(tokio_stream::iter(chunks(&["abc"]))).build();
+        assert!(stream.into_inner().try_clone().is_none());
+    }
+
+    #[tokio::test]
+    async fn replay_buffer_replays_data_already_read() {
+        let stream = ByteStream::from_stream(tokio_stream::iter(chunks(&["abc", "def", "ghi"])))
+            .replay_buffer_size(6)
+            .build();
+        let mut first = stream.into_inner();
+        // Like the orchestrator, keep a checkpoint to create the body of each retry from
+        let checkpoint = first.try_clone().expect("nothing was read yet");
+        assert_eq!(
+            Bytes::from_static(b"abc"),
+            first.data().await.unwrap().unwrap()
+        );
+
+        let mut second = checkpoint.try_clone().expect("3 bytes fit in the buffer");
+        assert_eq!(
+            Bytes::from_static(b"abc"),
+            second.data().await.unwrap().unwrap()
+        );
+        assert!(
+            first.data().await.unwrap().is_err(),
+            "the first attempt was replaced by the retry"
+        );
+        assert_eq!("defghi", read(ByteStream::new(second)).await.unwrap());
+        assert!(
+            checkpoint.try_clone().is_none(),
+            "9 bytes don't fit in the buffer"
+        );
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test]
+    async fn async_read_factory() {
+        let stream = ByteStream::from_async_read_fn(|| &b"some data"[..]).build();
+        let retry = ByteStream::new(stream.inner.body.try_clone().unwrap());
+        assert_eq!("some data", read(stream).await.unwrap());
+        assert_eq!("some data", read(retry).await.unwrap());
+    }
+}
@@ -8,6 +8,7 @@
 use std::fmt::{Debug, Formatter};
 use std::net::SocketAddr;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 /// Metadata that tracks the state of an active connection.
 #[derive(Clone)]
@@ -89,6 +90,278 @@ impl CaptureSmithyConnection {
     }
 }
 
+/// What happened to a connection, see [`ConnectionEvent`]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionEventKind {
+    /// A new connection was established to send a request
+    Opened,
+    /// A pooled connection was reused to send a request
+    Reused,
+    /// The connection was poisoned, so that it won't be reused
+    Poisoned,
+    /// The connection was closed
+    Closed,
+}
+
+/// Event that connectors emit to [`ConnectionEventListener`]s over the lifetime of a connection
+#[derive(Clone, Debug)]
+pub struct ConnectionEvent {
+    kind: ConnectionEventKind,
+    connection_id: u64,
+    remote_addr: Option<SocketAddr>,
+    is_proxied: bool,
+    request_count: u64,
+    age: Duration,
+    connect_duration: Option<Duration>,
+    dns_duration: Option<Duration>,
+    tls_handshake_duration: Option<Duration>,
+    idle_duration: Option<Duration>,
+}
+
+impl ConnectionEvent {
+    /// Returns a builder for an event of `kind` about the connection with `connection_id`.
+    pub fn builder(kind: ConnectionEventKind, connection_id: u64) -> ConnectionEventBuilder {
+        ConnectionEventBuilder {
+            event: ConnectionEvent {
+                kind,
+                connection_id,
+                remote_addr: None,
+                is_proxied: false,
+                request_count: 0,
+                age: Duration::ZERO,
+                connect_duration: None,
+                dns_duration: None,
+                tls_handshake_duration: None,
+                idle_duration: None,
+            },
+        }
+    }
+
+    /// What happened to the connection
+    pub fn kind(&self) -> ConnectionEventKind {
+        self.kind
+    }
+
+    /// Identifier of the connection, which is unique within the process
+    pub fn connection_id(&self) -> u64 {
+        self.connection_id
+    }
+
+    /// The remote address of the connection, if known
+    pub fn remote_addr(&self) -> Option<SocketAddr> {
+        self.remote_addr
+    }
+
+    /// Whether the connection goes through a proxy
+    pub fn is_proxied(&self) -> bool {
+        self.is_proxied
+    }
+
+    /// The number of requests sent over the connection so far, including the current one
+    pub fn request_count(&self) -> u64 {
+        self.request_count
+    }
+
+    /// The time since the connection was opened
+    pub fn age(&self) -> Duration {
+        self.age
+    }
+
+    /// The time it took to establish the connection, including DNS resolution, connecting to the
+    /// server or proxy, and the TLS handshake
+    ///
+    /// This is set for [`Opened`](ConnectionEventKind::Opened) events.
+    pub fn connect_duration(&self) -> Option<Duration> {
+        self.connect_duration
+    }
+
+    /// The time that resolving the host name took, if the connector resolved it
+    ///
+    /// This is set for [`Opened`](ConnectionEventKind::Opened) events.
+    pub fn dns_duration(&self) -> Option<Duration> {
+        self.dns_duration
+    }
+
+    /// The time that the TLS handshake took, if the connector could measure it
+    ///
+    /// This is set for [`Opened`](ConnectionEventKind::Opened) events of TLS connections.
+    pub fn tls_handshake_duration(&self) -> Option<Duration> {
+        self.tls_handshake_duration
+    }
+
+    /// The time that the connection was idle in the pool
+    ///
+    /// This is set for [`Reused`](ConnectionEventKind::Reused) events if the connector knows
+    /// when the previous response was done.
+    pub fn idle_duration(&self) -> Option<Duration> {
+        self.idle_duration
+    }
+}
+
+/// Builder for [`ConnectionEvent`]
+#[derive(Clone, Debug)]
+pub struct ConnectionEventBuilder {
+    event: ConnectionEvent,
+}
+
+impl ConnectionEventBuilder {
+    /// Sets the remote address of the connection.
+    pub fn remote_addr(mut self, remote_addr: SocketAddr) -> Self {
+        self.event.remote_addr = Some(remote_addr);
+        self
+    }
+
+    /// Sets the remote address of the connection.
+    pub fn set_remote_addr(&mut self, remote_addr: Option<SocketAddr>) -> &mut Self {
+        self.event.remote_addr = remote_addr;
+        self
+    }
+
+    /// Sets whether the connection goes through a proxy.
+    pub fn is_proxied(mut self, is_proxied: bool) -> Self {
+        self.event.is_proxied = is_proxied;
+        self
+    }
+
+    /// Sets the number of requests sent over the connection so far.
+    pub fn request_count(mut self, request_count: u64) -> Self {
+        self.event.request_count = request_count;
+        self
+    }
+
+    /// Sets the time since the connection was opened.
+    pub fn age(mut self, age: Duration) -> Self {
+        self.event.age = age;
+        self
+    }
+
+    /// Sets the time it took to establish the connection.
+    pub fn connect_duration(mut self, connect_duration: Duration) -> Self {
+        self.event.connect_duration = Some(connect_duration);
+        self
+    }
+
+    /// Sets the time it took to establish the connection.
+    pub fn set_connect_duration(&mut self, connect_duration: Option<Duration>) -> &mut Self {
+        self.event.connect_duration = connect_duration;
+        self
+    }
+
+    /// Sets the time that resolving the host name took.
+    pub fn dns_duration(mut self, dns_duration: Duration) -> Self {
+        self.event.dns_duration = Some(dns_duration);
+        self
+    }
+
+    /// Sets the time that resolving the host name took.
+    pub fn set_dns_duration(&mut self, dns_duration: Option<Duration>) -> &mut Self {
+        self.event.dns_duration = dns_duration;
+        self
+    }
+
+    /// Sets the time that the TLS handshake took.
+    pub fn tls_handshake_duration(mut self, tls_handshake_duration: Duration) -> Self {
+        self.event.tls_handshake_duration = Some(tls_handshake_duration);
+        self
+    }
+
+    /// Sets the time that the TLS handshake took.
+    pub fn set_tls_handshake_duration(
+        &mut self,
+        tls_handshake_duration: Option<Duration>,
+    ) -> &mut Self {
+        self.event.tls_handshake_duration = tls_handshake_duration;
+        self
+    }
+
+    /// Sets the time that the connection was idle in the pool.
+    pub fn idle_duration(mut self, idle_duration: Duration) -> Self {
+        self.event.idle_duration = Some(idle_duration);
+        self
+    }
+
+    /// Sets the time that the connection was idle in the pool.
+    pub fn set_idle_duration(&mut self, idle_duration: Option<Duration>) -> &mut Self {
+        self.event.idle_duration = idle_duration;
+        self
+    }
+
+    /// Builds the [`ConnectionEvent`].
+    pub fn build(self) -> ConnectionEvent {
+        self.event
+    }
+}
+
+/// Listener for the [`ConnectionEvent`]s of the connections that requests are sent over
+///
+/// Listeners are called synchronously by the connector, so they should return quickly.
+pub trait ConnectionEventListener: Send + Sync + Debug {
+    /// Called when something happens to a connection.
+    fn on_event(&self, event: &ConnectionEvent);
+}
+
+/// A [`ConnectionEventListener`] that can be shared
+#[derive(Clone, Debug)]
+pub struct SharedConnectionEventListener(Arc<dyn ConnectionEventListener>);
+
+impl SharedConnectionEventListener {
+    /// Creates a new shared listener.
+    pub fn new(listener: impl ConnectionEventListener + 'static) -> Self {
+        Self(Arc::new(listener))
+    }
+}
+
+impl ConnectionEventListener for SharedConnectionEventListener {
+    fn on_event(&self, event: &ConnectionEvent) {
+        self.0.on_event(event)
+    }
+}
+
+/// The [`ConnectionEventListener`]s of a request
+///
+/// Connectors that support connection events look for this in the extensions of the requests
+/// they send, and notify the listeners about the connections that the requests are sent over.
+/// Events that happen after the request, like the connection being closed, are sent to the
+/// listeners of the last request that was sent over the connection.
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionEventListeners {
+    listeners: Vec<SharedConnectionEventListener>,
+}
+
+impl ConnectionEventListeners {
+    /// Creates an empty set of listeners.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a listener.
+    pub fn push(&mut self, listener: SharedConnectionEventListener) -> &mut Self {
+        self.listeners.push(listener);
+        self
+    }
+
+    /// Returns `true` if there are no listeners.
+    pub fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+
+    /// Notifies every listener of `event`.
+    pub fn emit(&self, event: &ConnectionEvent) {
+        for listener in &self.listeners {
+            listener.on_event(event);
+        }
+    }
+}
+
+impl FromIterator<SharedConnectionEventListener> for ConnectionEventListeners {
+    fn from_iter<T: IntoIterator<Item = SharedConnectionEventListener>>(iter: T) -> Self {
+        Self {
+            listeners: iter.into_iter().collect(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::connection::{CaptureSmithyConnection, ConnectionMetadata};
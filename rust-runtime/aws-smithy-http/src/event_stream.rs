@@ -14,7 +14,9 @@ mod sender;
 pub type BoxError = Box<dyn StdError + Send + Sync + 'static>;
 
 #[doc(inline)]
-pub use sender::{EventStreamSender, MessageStreamAdapter, MessageStreamError};
+pub use sender::{
+    CloseHandle, Closed, EventStreamSender, MessageStreamAdapter, MessageStreamError,
+};
 
 #[doc(inline)]
 pub use receiver::{RawMessage, Receiver, ReceiverError};
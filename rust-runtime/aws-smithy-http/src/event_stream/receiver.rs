@@ -5,8 +5,11 @@
 
 use crate::body::SdkBody;
 use crate::result::{ConnectorError, SdkError};
+use aws_smithy_async::future::timeout::Timeout;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
 use aws_smithy_eventstream::frame::{
     DecodedFrame, Message, MessageFrameDecoder, UnmarshallMessage, UnmarshalledMessage,
+    VerifyMessage,
 };
 use bytes::Buf;
 use bytes::Bytes;
@@ -16,6 +19,7 @@ use std::error::Error as StdError;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem;
+use std::time::Duration;
 use tracing::trace;
 
 /// Wrapper around SegmentedBuf that tracks the state of the stream.
@@ -107,6 +111,12 @@ impl RawMessage {
 enum ReceiverErrorKind {
     /// The stream ended before a complete message frame was received.
     UnexpectedEndOfStream,
+    /// The stream ended without the empty signed message that terminates a signed stream.
+    MissingEndMessage,
+    /// A message was received after the empty signed message that terminates a signed stream.
+    MessageAfterEndMessage,
+    /// No data was received within the idle timeout.
+    IdleTimeout(Duration),
 }
 
 /// An error that occurs within an event stream receiver.
@@ -119,12 +129,29 @@ impl fmt::Display for ReceiverError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.kind {
             ReceiverErrorKind::UnexpectedEndOfStream => write!(f, "unexpected end of stream"),
+            ReceiverErrorKind::MissingEndMessage => {
+                write!(f, "signed stream ended without an empty signed message")
+            }
+            ReceiverErrorKind::MessageAfterEndMessage => {
+                write!(f, "received a message after the empty signed message")
+            }
+            ReceiverErrorKind::IdleTimeout(timeout) => {
+                write!(f, "no data was received for {:?}", timeout)
+            }
         }
     }
 }
 
 impl StdError for ReceiverError {}
 
+/// Returns true for the empty messages that an `EventStreamSender` sends as keepalives.
+///
+/// Smithy-modeled messages always have a `:message-type` header, so these can't be confused with
+/// modeled events.
+fn is_keepalive(message: &Message) -> bool {
+    message.headers().is_empty() && message.payload().is_empty()
+}
+
 /// Receives Smithy-modeled messages out of an Event Stream.
 #[derive(Debug)]
 pub struct Receiver<T, E> {
@@ -137,6 +164,13 @@ pub struct Receiver<T, E> {
     /// initial response, then the message will be stored in `buffered_message` so that it can
     /// be returned with the next call of `recv()`.
     buffered_message: Option<Message>,
+    /// Verifies the signature of every message before it's unmarshalled, if set.
+    verifier: Option<Box<dyn VerifyMessage + Send + Sync>>,
+    /// Whether the empty signed message that terminates a signed stream was received.
+    received_end_message: bool,
+    idle_timeout: Option<(Duration, SharedAsyncSleep)>,
+    /// Whether empty keepalive messages are skipped instead of unmarshalled.
+    skip_keepalives: bool,
     _phantom: PhantomData<E>,
 }
 
@@ -152,10 +186,70 @@ impl<T, E> Receiver<T, E> {
             buffer: RecvBuf::Empty,
             body,
             buffered_message: None,
+            verifier: None,
+            received_end_message: false,
+            idle_timeout: None,
+            skip_keepalives: false,
             _phantom: Default::default(),
         }
     }
 
+    /// Fails with a [`SdkError::TimeoutError`] when no data is received for `timeout`.
+    ///
+    /// Any data resets the timeout, including keepalive messages sent by an
+    /// [`EventStreamSender`](crate::event_stream::EventStreamSender). The stream is terminated
+    /// once it timed out.
+    pub fn with_idle_timeout(mut self, timeout: Duration, sleep_impl: SharedAsyncSleep) -> Self {
+        self.idle_timeout = Some((timeout, sleep_impl));
+        self
+    }
+
+    /// Skips the empty keepalive messages sent by an
+    /// [`EventStreamSender`](crate::event_stream::EventStreamSender) with keepalive enabled.
+    ///
+    /// Without this, messages without headers and payload are passed to the unmarshaller.
+    pub fn with_keepalive(mut self) -> Self {
+        self.skip_keepalives = true;
+        self
+    }
+
+    /// Verifies the signature of every received message with the given `verifier`.
+    ///
+    /// Each signed message is unwrapped before it's unmarshalled. A message that fails
+    /// verification is returned as a [`SdkError::ResponseError`] and terminates the stream.
+    pub fn with_verifier(mut self, verifier: impl VerifyMessage + Send + Sync + 'static) -> Self {
+        self.verifier = Some(Box::new(verifier));
+        self
+    }
+
+    /// Verifies a signed message and unwraps it. Returns `None` for the empty end message.
+    fn verify(&mut self, message: Message) -> Result<Option<Message>, SdkError<E, RawMessage>> {
+        let verifier = match self.verifier.as_mut() {
+            Some(verifier) => verifier,
+            None => return Ok(Some(message)),
+        };
+        let result = if self.received_end_message {
+            Err(ReceiverError {
+                kind: ReceiverErrorKind::MessageAfterEndMessage,
+            }
+            .into())
+        } else {
+            verifier.verify(message.clone())
+        };
+        match result {
+            Ok(Some(verified)) => Ok(Some(verified)),
+            Ok(None) => {
+                trace!("received the empty signed message that terminates the stream");
+                self.received_end_message = true;
+                Ok(None)
+            }
+            Err(err) => {
+                self.buffer = RecvBuf::Terminated;
+                Err(SdkError::response_error(err, RawMessage::Decoded(message)))
+            }
+        }
+    }
+
     fn unmarshall(&self, message: Message) -> Result<Option<T>, SdkError<E, RawMessage>> {
         match self.unmarshaller.unmarshall(&message) {
             Ok(unmarshalled) => match unmarshalled {
@@ -170,10 +264,24 @@ impl<T, E> Receiver<T, E> {
 
     async fn buffer_next_chunk(&mut self) -> Result<(), SdkError<E, RawMessage>> {
         if !self.buffer.is_eos() {
-            let next_chunk = self
-                .body
-                .data()
-                .await
+            let next_chunk = match &self.idle_timeout {
+                Some((timeout, sleep_impl)) => {
+                    let sleep = sleep_impl.sleep(*timeout);
+                    match Timeout::new(self.body.data(), sleep).await {
+                        Ok(next_chunk) => next_chunk,
+                        Err(_) => {
+                            let timeout = *timeout;
+                            trace!(timeout = ?timeout, "event stream idle timeout elapsed");
+                            self.buffer = RecvBuf::Terminated;
+                            return Err(SdkError::timeout_error(ReceiverError {
+                                kind: ReceiverErrorKind::IdleTimeout(timeout),
+                            }));
+                        }
+                    }
+                }
+                None => self.body.data().await,
+            };
+            let next_chunk = next_chunk
                 .transpose()
                 .map_err(|err| SdkError::dispatch_failure(ConnectorError::io(err)))?;
             let buffer = mem::replace(&mut self.buffer, RecvBuf::Empty);
@@ -201,7 +309,14 @@ impl<T, E> Receiver<T, E> {
                     })?
                 {
                     trace!(message = ?message, "received complete event stream message");
-                    return Ok(Some(message));
+                    match self.verify(message)? {
+                        Some(message) if self.skip_keepalives && is_keepalive(&message) => {
+                            trace!("skipping keepalive message");
+                        }
+                        Some(message) => return Ok(Some(message)),
+                        None => {}
+                    }
+                    continue;
                 }
             }
 
@@ -216,6 +331,23 @@ impl<T, E> Receiver<T, E> {
                 RawMessage::invalid(self.buffer.buffered()),
             ));
         }
+        let requires_end_message = self
+            .verifier
+            .as_ref()
+            .map(|verifier| verifier.requires_empty_end_message())
+            .unwrap_or_default();
+        if requires_end_message
+            && !self.received_end_message
+            && !matches!(self.buffer, RecvBuf::Terminated)
+        {
+            self.buffer = RecvBuf::Terminated;
+            return Err(SdkError::response_error(
+                ReceiverError {
+                    kind: ReceiverErrorKind::MissingEndMessage,
+                },
+                RawMessage::Invalid(None),
+            ));
+        }
         Ok(None)
     }
 
@@ -278,12 +410,17 @@ mod tests {
     use super::{Receiver, UnmarshallMessage};
     use crate::body::SdkBody;
     use crate::result::SdkError;
+    use aws_smithy_async::rt::sleep::SharedAsyncSleep;
+    use aws_smithy_async::test_util::InstantSleep;
     use aws_smithy_eventstream::error::Error as EventStreamError;
-    use aws_smithy_eventstream::frame::{Header, HeaderValue, Message, UnmarshalledMessage};
+    use aws_smithy_eventstream::frame::{
+        Header, HeaderValue, Message, UnmarshalledMessage, VerifyMessage, VerifyMessageError,
+    };
     use bytes::Bytes;
     use hyper::body::Body;
     use std::error::Error as StdError;
     use std::io::{Error as IOError, ErrorKind};
+    use std::time::Duration;
 
     fn encode_initial_response() -> Bytes {
         let mut buffer = Vec::new();
@@ -554,4 +691,163 @@ mod tests {
     async fn receiver_is_send_and_sync() {
         assert_send_and_sync::<Receiver<(), ()>>();
     }
+
+    fn encode_signed(message: Option<&str>) -> Bytes {
+        let mut payload = Vec::new();
+        if let Some(message) = message {
+            Message::new(Bytes::copy_from_slice(message.as_bytes()))
+                .write_to(&mut payload)
+                .unwrap();
+        }
+        let mut buffer = Vec::new();
+        Message::new(payload)
+            .add_header(Header::new("signed", HeaderValue::Bool(true)))
+            .write_to(&mut buffer)
+            .unwrap();
+        buffer.into()
+    }
+
+    #[derive(Debug)]
+    struct TestVerifier;
+    impl VerifyMessage for TestVerifier {
+        fn verify(&mut self, message: Message) -> Result<Option<Message>, VerifyMessageError> {
+            if !message
+                .headers()
+                .iter()
+                .any(|h| h.name().as_str() == "signed")
+            {
+                return Err("message is not signed".into());
+            }
+            if message.payload().is_empty() {
+                return Ok(None);
+            }
+            Ok(Some(Message::read_from(&message.payload()[..])?))
+        }
+
+        fn requires_empty_end_message(&self) -> bool {
+            true
+        }
+    }
+
+    fn verifying_receiver(chunks: Vec<Bytes>) -> Receiver<TestMessage, EventStreamError> {
+        let chunks: Vec<Result<_, IOError>> = chunks.into_iter().map(Ok).collect();
+        let body = SdkBody::from(Body::wrap_stream(futures_util::stream::iter(chunks)));
+        Receiver::new(Unmarshaller, body).with_verifier(TestVerifier)
+    }
+
+    #[tokio::test]
+    async fn receive_verified_messages() {
+        let mut receiver = verifying_receiver(vec![
+            encode_signed(Some("one")),
+            encode_signed(Some("two")),
+            encode_signed(None),
+        ]);
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(
+            TestMessage("two".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(None, receiver.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn receive_unverified_message() {
+        let mut receiver =
+            verifying_receiver(vec![encode_signed(Some("one")), encode_message("two")]);
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        let err = receiver.recv().await.unwrap_err();
+        assert!(matches!(err, SdkError::ResponseError(_)));
+        assert_eq!("message is not signed", err.source().unwrap().to_string());
+        // The stream was terminated
+        assert_eq!(None, receiver.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn receive_verified_stream_without_end_message() {
+        let mut receiver = verifying_receiver(vec![encode_signed(Some("one"))]);
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        let err = receiver.recv().await.unwrap_err();
+        assert!(matches!(err, SdkError::ResponseError(_)));
+        assert_eq!(
+            "signed stream ended without an empty signed message",
+            err.source().unwrap().to_string()
+        );
+
+        let mut receiver =
+            verifying_receiver(vec![encode_signed(None), encode_signed(Some("one"))]);
+        assert!(matches!(
+            receiver.recv().await,
+            Err(SdkError::ResponseError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn receive_skips_keepalive_messages() {
+        let mut keepalive = Vec::new();
+        Message::new(Bytes::new()).write_to(&mut keepalive).unwrap();
+        let chunks: Vec<Result<_, IOError>> = vec![
+            Ok(keepalive.clone().into()),
+            Ok(encode_message("one")),
+            Ok(keepalive.into()),
+        ];
+        let body = SdkBody::from(Body::wrap_stream(futures_util::stream::iter(chunks)));
+        let mut receiver =
+            Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body).with_keepalive();
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(None, receiver.recv().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn receive_passes_empty_messages_without_keepalive() {
+        let mut empty = Vec::new();
+        Message::new(Bytes::new()).write_to(&mut empty).unwrap();
+        let chunks: Vec<Result<_, IOError>> = vec![Ok(empty.into()), Ok(encode_message("one"))];
+        let body = SdkBody::from(Body::wrap_stream(futures_util::stream::iter(chunks)));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+        assert_eq!(
+            TestMessage("".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn receive_idle_timeout() {
+        let sleep = InstantSleep::new(Default::default());
+        let (mut sender, body) = Body::channel();
+        sender.send_data(encode_message("one")).await.unwrap();
+        let mut receiver =
+            Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, SdkBody::from(body))
+                .with_idle_timeout(Duration::from_secs(5), SharedAsyncSleep::new(sleep.clone()));
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        // The sender is still connected, but doesn't send anything
+        let err = receiver.recv().await.unwrap_err();
+        assert!(matches!(err, SdkError::TimeoutError(_)));
+        assert_eq!(
+            "no data was received for 5s",
+            err.source().unwrap().to_string()
+        );
+        assert_eq!(Some(&Duration::from_secs(5)), sleep.logs().last());
+        // The stream was terminated
+        assert_eq!(None, receiver.recv().await.unwrap());
+        drop(sender);
+    }
 }
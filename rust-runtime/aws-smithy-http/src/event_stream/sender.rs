@@ -4,20 +4,26 @@
  */
 
 use crate::result::SdkError;
-use aws_smithy_eventstream::frame::{MarshallMessage, SignMessage};
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, Sleep};
+use aws_smithy_eventstream::frame::{MarshallMessage, Message, SignMessage};
 use bytes::Bytes;
 use futures_core::Stream;
 use std::error::Error as StdError;
 use std::fmt;
 use std::fmt::Debug;
+use std::future::Future;
 use std::marker::PhantomData;
 use std::pin::Pin;
-use std::task::{Context, Poll};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
 use tracing::trace;
 
 /// Input type for Event Streams.
 pub struct EventStreamSender<T, E> {
     input_stream: Pin<Box<dyn Stream<Item = Result<T, E>> + Send + Sync>>,
+    keepalive: Option<KeepaliveConfig>,
+    close_handle: CloseHandle,
 }
 
 impl<T, E> Debug for EventStreamSender<T, E> {
@@ -28,6 +34,31 @@ impl<T, E> Debug for EventStreamSender<T, E> {
     }
 }
 
+impl<T, E> EventStreamSender<T, E> {
+    /// Sends a keepalive message whenever no message was sent for `interval`.
+    ///
+    /// The keepalive is an empty message (without headers or payload) that is signed like any
+    /// other message. A [`Receiver`](crate::event_stream::Receiver) only skips these messages when
+    /// [`Receiver::with_keepalive`](crate::event_stream::Receiver::with_keepalive) is set.
+    ///
+    /// AWS services may reject empty event messages, so only enable this for peers that are known
+    /// to accept them.
+    pub fn keepalive(mut self, interval: Duration, sleep_impl: SharedAsyncSleep) -> Self {
+        self.keepalive = Some(KeepaliveConfig {
+            interval,
+            sleep_impl,
+        });
+        self
+    }
+
+    /// Returns a handle to gracefully close this stream.
+    ///
+    /// See [`CloseHandle`] for details.
+    pub fn close_handle(&self) -> CloseHandle {
+        self.close_handle.clone()
+    }
+}
+
 impl<T, E: StdError + Send + Sync + 'static> EventStreamSender<T, E> {
     #[doc(hidden)]
     pub fn into_body_stream(
@@ -36,7 +67,13 @@ impl<T, E: StdError + Send + Sync + 'static> EventStreamSender<T, E> {
         error_marshaller: impl MarshallMessage<Input = E> + Send + Sync + 'static,
         signer: impl SignMessage + Send + Sync + 'static,
     ) -> MessageStreamAdapter<T, E> {
-        MessageStreamAdapter::new(marshaller, error_marshaller, signer, self.input_stream)
+        let mut adapter =
+            MessageStreamAdapter::new(marshaller, error_marshaller, signer, self.input_stream)
+                .with_close_handle(self.close_handle);
+        if let Some(keepalive) = self.keepalive {
+            adapter = adapter.with_keepalive(keepalive.interval, keepalive.sleep_impl);
+        }
+        adapter
     }
 }
 
@@ -47,6 +84,103 @@ where
     fn from(stream: S) -> Self {
         EventStreamSender {
             input_stream: Box::pin(stream),
+            keepalive: None,
+            close_handle: CloseHandle::new(),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct KeepaliveConfig {
+    interval: Duration,
+    sleep_impl: SharedAsyncSleep,
+}
+
+#[derive(Debug, Default)]
+struct CloseState {
+    /// Set when [`CloseHandle::close`] was called
+    requested: bool,
+    /// Set to `Some(graceful)` once the stream ended
+    ended: Option<bool>,
+    /// Wakes the [`MessageStreamAdapter`] once a close is requested
+    adapter_waker: Option<Waker>,
+    /// Wake the [`Closed`] futures once the stream ended
+    closed_wakers: Vec<Waker>,
+}
+
+/// Handle to gracefully close an event stream.
+///
+/// Closing stops reading the input stream of the [`EventStreamSender`]. Messages that the input
+/// stream has yet to produce are never sent. The stream is then terminated like it is at the end
+/// of the input stream: with the final signed empty message when the signer requires one.
+#[derive(Clone, Debug)]
+pub struct CloseHandle {
+    state: Arc<Mutex<CloseState>>,
+}
+
+impl CloseHandle {
+    fn new() -> Self {
+        Self {
+            state: Default::default(),
+        }
+    }
+
+    /// Requests the stream to be closed.
+    ///
+    /// The returned future completes once the stream ended, which also happens when the input
+    /// stream ends on its own. It resolves to `true` if the stream ended gracefully, including
+    /// sending the final signed empty message, and to `false` if the stream was dropped before.
+    pub fn close(&self) -> Closed {
+        let mut state = self.state.lock().unwrap();
+        if !state.requested {
+            trace!("requested to close the event stream");
+            state.requested = true;
+            if let Some(waker) = state.adapter_waker.take() {
+                waker.wake();
+            }
+        }
+        Closed {
+            state: self.state.clone(),
+        }
+    }
+
+    fn is_requested(&self, cx: &mut Context<'_>) -> bool {
+        let mut state = self.state.lock().unwrap();
+        if !state.requested {
+            state.adapter_waker = Some(cx.waker().clone());
+        }
+        state.requested
+    }
+
+    fn ended(&self, graceful: bool) {
+        let mut state = self.state.lock().unwrap();
+        if state.ended.is_none() {
+            state.ended = Some(graceful);
+            for waker in state.closed_wakers.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+/// Future returned by [`CloseHandle::close`].
+#[derive(Debug)]
+#[must_use]
+pub struct Closed {
+    state: Arc<Mutex<CloseState>>,
+}
+
+impl Future for Closed {
+    type Output = bool;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+        match state.ended {
+            Some(graceful) => Poll::Ready(graceful),
+            None => {
+                state.closed_wakers.push(cx.waker().clone());
+                Poll::Pending
+            }
         }
     }
 }
@@ -114,10 +248,25 @@ pub struct MessageStreamAdapter<T, E: StdError + Send + Sync + 'static> {
     error_marshaller: Box<dyn MarshallMessage<Input = E> + Send + Sync>,
     signer: Box<dyn SignMessage + Send + Sync>,
     stream: Pin<Box<dyn Stream<Item = Result<T, E>> + Send>>,
+    keepalive: Option<Keepalive>,
+    close_handle: Option<CloseHandle>,
     end_signal_sent: bool,
+    ended: bool,
     _phantom: PhantomData<E>,
 }
 
+struct Keepalive {
+    interval: Duration,
+    sleep_impl: SharedAsyncSleep,
+    sleep: Sleep,
+}
+
+impl Keepalive {
+    fn reset(&mut self) {
+        self.sleep = self.sleep_impl.sleep(self.interval);
+    }
+}
+
 impl<T, E: StdError + Send + Sync + 'static> Unpin for MessageStreamAdapter<T, E> {}
 
 impl<T, E: StdError + Send + Sync + 'static> MessageStreamAdapter<T, E> {
@@ -133,60 +282,133 @@ impl<T, E: StdError + Send + Sync + 'static> MessageStreamAdapter<T, E> {
             error_marshaller: Box::new(error_marshaller),
             signer: Box::new(signer),
             stream,
+            keepalive: None,
+            close_handle: None,
             end_signal_sent: false,
+            ended: false,
             _phantom: Default::default(),
         }
     }
+
+    /// Sends a signed, empty keepalive message whenever no message was sent for `interval`.
+    pub fn with_keepalive(mut self, interval: Duration, sleep_impl: SharedAsyncSleep) -> Self {
+        self.keepalive = Some(Keepalive {
+            interval,
+            sleep: sleep_impl.sleep(interval),
+            sleep_impl,
+        });
+        self
+    }
+
+    /// Stops sending messages from the input stream once `close_handle` requests it.
+    pub fn with_close_handle(mut self, close_handle: CloseHandle) -> Self {
+        self.close_handle = Some(close_handle);
+        self
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn sign_and_write(&mut self, message: Message) -> Result<Bytes, SdkError<E>> {
+        trace!(unsigned_message = ?message, "signing event stream message");
+        let message = self
+            .signer
+            .sign(message)
+            .map_err(SdkError::construction_failure)?;
+
+        let mut buffer = Vec::new();
+        message
+            .write_to(&mut buffer)
+            .map_err(SdkError::construction_failure)?;
+        trace!(signed_message = ?buffer, "sending signed event stream message");
+        if let Some(keepalive) = self.keepalive.as_mut() {
+            keepalive.reset();
+        }
+        Ok(Bytes::from(buffer))
+    }
+
+    /// Ends the stream, sending the signed empty message if the signer requires one
+    #[allow(clippy::result_large_err)]
+    fn end(&mut self) -> Option<Result<Bytes, SdkError<E>>> {
+        if !self.end_signal_sent {
+            self.end_signal_sent = true;
+            if let Some(sign) = self.signer.sign_empty() {
+                let mut buffer = Vec::new();
+                let result = sign
+                    .map_err(SdkError::construction_failure)
+                    .and_then(|message| {
+                        message
+                            .write_to(&mut buffer)
+                            .map_err(SdkError::construction_failure)
+                    });
+                return match result {
+                    Ok(_) => {
+                        trace!(signed_message = ?buffer, "sending signed empty message to terminate the event stream");
+                        Some(Ok(Bytes::from(buffer)))
+                    }
+                    Err(err) => Some(Err(err)),
+                };
+            }
+        }
+        // The final message (if any) was taken by the transport
+        self.ended = true;
+        if let Some(close_handle) = &self.close_handle {
+            close_handle.ended(true);
+        }
+        None
+    }
 }
 
 impl<T, E: StdError + Send + Sync + 'static> Stream for MessageStreamAdapter<T, E> {
     type Item = Result<Bytes, SdkError<E>>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.end_signal_sent {
+            return Poll::Ready(self.end());
+        }
+        let close_requested = self
+            .close_handle
+            .as_ref()
+            .map(|close_handle| close_handle.is_requested(cx))
+            .unwrap_or_default();
+        if close_requested {
+            return Poll::Ready(self.end());
+        }
         match self.stream.as_mut().poll_next(cx) {
-            Poll::Ready(message_option) => {
-                if let Some(message_result) = message_option {
-                    let message = match message_result {
-                        Ok(message) => self
-                            .marshaller
-                            .marshall(message)
-                            .map_err(SdkError::construction_failure)?,
-                        Err(message) => self
-                            .error_marshaller
-                            .marshall(message)
-                            .map_err(SdkError::construction_failure)?,
-                    };
-
-                    trace!(unsigned_message = ?message, "signing event stream message");
-                    let message = self
-                        .signer
-                        .sign(message)
-                        .map_err(SdkError::construction_failure)?;
-
-                    let mut buffer = Vec::new();
-                    message
-                        .write_to(&mut buffer)
-                        .map_err(SdkError::construction_failure)?;
-                    trace!(signed_message = ?buffer, "sending signed event stream message");
-                    Poll::Ready(Some(Ok(Bytes::from(buffer))))
-                } else if !self.end_signal_sent {
-                    self.end_signal_sent = true;
-                    let mut buffer = Vec::new();
-                    match self.signer.sign_empty() {
-                        Some(sign) => {
-                            sign.map_err(SdkError::construction_failure)?
-                                .write_to(&mut buffer)
-                                .map_err(SdkError::construction_failure)?;
-                            trace!(signed_message = ?buffer, "sending signed empty message to terminate the event stream");
-                            Poll::Ready(Some(Ok(Bytes::from(buffer))))
-                        }
-                        None => Poll::Ready(None),
-                    }
+            Poll::Ready(Some(message_result)) => {
+                let message = match message_result {
+                    Ok(message) => self
+                        .marshaller
+                        .marshall(message)
+                        .map_err(SdkError::construction_failure)?,
+                    Err(message) => self
+                        .error_marshaller
+                        .marshall(message)
+                        .map_err(SdkError::construction_failure)?,
+                };
+                Poll::Ready(Some(self.sign_and_write(message)))
+            }
+            Poll::Ready(None) => Poll::Ready(self.end()),
+            Poll::Pending => {
+                let keepalive_due = match self.keepalive.as_mut() {
+                    Some(keepalive) => Pin::new(&mut keepalive.sleep).poll(cx).is_ready(),
+                    None => false,
+                };
+                if keepalive_due {
+                    trace!("sending keepalive message");
+                    Poll::Ready(Some(self.sign_and_write(Message::new(Bytes::new()))))
                 } else {
-                    Poll::Ready(None)
+                    Poll::Pending
                 }
             }
-            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<T, E: StdError + Send + Sync + 'static> Drop for MessageStreamAdapter<T, E> {
+    fn drop(&mut self) {
+        if !self.ended {
+            if let Some(close_handle) = &self.close_handle {
+                close_handle.ended(false);
+            }
         }
     }
 }
@@ -197,6 +419,8 @@ mod tests {
     use crate::event_stream::{EventStreamSender, MessageStreamAdapter};
     use crate::result::SdkError;
     use async_stream::stream;
+    use aws_smithy_async::rt::sleep::SharedAsyncSleep;
+    use aws_smithy_async::test_util::InstantSleep;
     use aws_smithy_eventstream::error::Error as EventStreamError;
     use aws_smithy_eventstream::frame::{
         Header, HeaderValue, Message, NoOpSigner, SignMessage, SignMessageError,
@@ -205,6 +429,7 @@ mod tests {
     use futures_core::Stream;
     use futures_util::stream::StreamExt;
     use std::error::Error as StdError;
+    use std::time::Duration;
 
     #[derive(Debug)]
     struct FakeError;
@@ -347,4 +572,82 @@ mod tests {
             yield Err(TestServiceError);
         });
     }
+
+    fn unwrap_signed(bytes: Bytes) -> Message {
+        let sent = Message::read_from(&mut &bytes[..]).unwrap();
+        assert_eq!("signed", sent.headers()[0].name().as_str());
+        if sent.payload().is_empty() {
+            sent
+        } else {
+            Message::read_from(&mut (&sent.payload()[..])).unwrap()
+        }
+    }
+
+    #[tokio::test]
+    async fn message_stream_adapter_keepalive() {
+        let (tx, rx) = channel();
+        let sleep = InstantSleep::new(Default::default());
+        let sender = EventStreamSender::<TestMessage, TestServiceError>::from(rx).keepalive(
+            Duration::from_secs(30),
+            SharedAsyncSleep::new(sleep.clone()),
+        );
+        let mut adapter = check_compatible_with_hyper_wrap_stream(sender.into_body_stream(
+            Marshaller,
+            ErrorMarshaller,
+            TestSigner,
+        ));
+
+        // Nothing is sent, so the keepalive is due
+        let keepalive = unwrap_signed(adapter.next().await.unwrap().unwrap());
+        assert!(keepalive.headers().is_empty());
+        assert!(keepalive.payload().is_empty());
+        assert_eq!(vec![Duration::from_secs(30)], sleep.logs());
+
+        tx.send(TestMessage("test".into())).unwrap();
+        let sent = unwrap_signed(adapter.next().await.unwrap().unwrap());
+        assert_eq!(&b"test"[..], &sent.payload()[..]);
+    }
+
+    #[tokio::test]
+    async fn message_stream_adapter_close() {
+        let (tx, rx) = channel();
+        let sender = EventStreamSender::<TestMessage, TestServiceError>::from(rx);
+        let close_handle = sender.close_handle();
+        let mut adapter = sender.into_body_stream(Marshaller, ErrorMarshaller, TestSigner);
+
+        tx.send(TestMessage("first".into())).unwrap();
+        let sent = unwrap_signed(adapter.next().await.unwrap().unwrap());
+        assert_eq!(&b"first"[..], &sent.payload()[..]);
+
+        let closed = tokio::spawn(close_handle.close());
+        // Messages that weren't sent yet are dropped
+        tx.send(TestMessage("second".into())).unwrap();
+        let end_signal = unwrap_signed(adapter.next().await.unwrap().unwrap());
+        assert!(end_signal.payload().is_empty());
+        assert!(adapter.next().await.is_none());
+        assert!(closed.await.unwrap(), "the stream was closed gracefully");
+    }
+
+    #[tokio::test]
+    async fn message_stream_adapter_dropped_before_close() {
+        let (_tx, rx) = channel();
+        let sender = EventStreamSender::<TestMessage, TestServiceError>::from(rx);
+        let close_handle = sender.close_handle();
+        let adapter = sender.into_body_stream(Marshaller, ErrorMarshaller, TestSigner);
+        let closed = close_handle.close();
+        drop(adapter);
+        assert!(!closed.await);
+    }
+
+    /// An unbounded channel whose receiver is a `Stream` of `Ok` messages
+    fn channel() -> (
+        tokio::sync::mpsc::UnboundedSender<TestMessage>,
+        impl Stream<Item = Result<TestMessage, TestServiceError>> + Send + Sync + 'static,
+    ) {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+        (
+            tx,
+            tokio_stream::wrappers::UnboundedReceiverStream::new(rx).map(Ok),
+        )
+    }
 }
@@ -33,5 +33,8 @@ pub mod auth;
 /// A type to track the number of requests sent by the orchestrator for a given operation.
 pub mod request_attempts;
 
+/// Cancellation of in-flight operations.
+pub mod cancellation;
+
 /// Smithy connectors and related code.
 pub mod connectors;
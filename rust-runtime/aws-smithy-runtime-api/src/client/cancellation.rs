@@ -0,0 +1,106 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+#[derive(Debug, Default)]
+struct Inner {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+/// A token used to cancel an in-flight operation.
+///
+/// When a token is placed in the config bag, the orchestrator stops sending retries once it is
+/// cancelled and aborts the current attempt or backoff delay. The operation then completes with
+/// a [`Cancelled`] error, so interceptors still get to run their completion hooks.
+///
+/// Clones of a token share the same state, so a clone can be kept as an abort handle for an
+/// operation running elsewhere.
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken {
+    inner: Arc<Inner>,
+}
+
+impl CancellationToken {
+    /// Creates a new token that hasn't been cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels every operation that uses this token.
+    ///
+    /// Cancelling a token more than once has no further effect.
+    pub fn cancel(&self) {
+        if !self.inner.cancelled.swap(true, Ordering::SeqCst) {
+            self.inner.notify.notify_waiters();
+        }
+    }
+
+    /// Returns true if this token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Waits until this token is cancelled.
+    pub async fn cancelled(&self) {
+        // The `Notified` future must be created before checking the flag so that a cancellation
+        // that happens in between isn't missed.
+        let notified = self.inner.notify.notified();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await
+    }
+}
+
+impl Storable for CancellationToken {
+    type Storer = StoreReplace<Self>;
+}
+
+/// The error an operation fails with when its [`CancellationToken`] is cancelled.
+#[non_exhaustive]
+#[derive(Debug, Default)]
+pub struct Cancelled;
+
+impl Cancelled {
+    /// Creates a new `Cancelled` error.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the operation was cancelled")
+    }
+}
+
+impl StdError for Cancelled {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clones_share_cancellation() {
+        let token = CancellationToken::new();
+        let handle = token.clone();
+        assert!(!token.is_cancelled());
+
+        handle.cancel();
+        assert!(token.is_cancelled());
+        assert!(handle.is_cancelled());
+
+        // Cancelling twice is harmless
+        handle.cancel();
+        assert!(token.is_cancelled());
+    }
+}
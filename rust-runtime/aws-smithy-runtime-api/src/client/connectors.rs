@@ -4,8 +4,11 @@
  */
 
 use crate::client::orchestrator::{BoxFuture, HttpRequest, HttpResponse};
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use aws_smithy_types::timeout::TimeoutConfig;
 use std::fmt;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub trait Connector: Send + Sync + fmt::Debug {
     fn call(&self, request: HttpRequest) -> BoxFuture<HttpResponse>;
@@ -25,3 +28,155 @@ impl Connector for SharedConnector {
         (*self.0).call(request)
     }
 }
+
+/// Builder for [`ConnectorSettings`].
+#[non_exhaustive]
+#[derive(Default, Debug)]
+pub struct ConnectorSettingsBuilder {
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+}
+
+impl ConnectorSettingsBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets the connect timeout that should be used.
+    ///
+    /// The connect timeout is a limit on the amount of time it takes to initiate a socket connection.
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Sets the connect timeout that should be used.
+    ///
+    /// The connect timeout is a limit on the amount of time it takes to initiate a socket connection.
+    pub fn set_connect_timeout(&mut self, connect_timeout: Option<Duration>) -> &mut Self {
+        self.connect_timeout = connect_timeout;
+        self
+    }
+
+    /// Sets the read timeout that should be used.
+    ///
+    /// The read timeout is the limit on the amount of time it takes to read the first byte of a response
+    /// from the time the request is initiated.
+    pub fn read_timeout(mut self, read_timeout: Duration) -> Self {
+        self.read_timeout = Some(read_timeout);
+        self
+    }
+
+    /// Sets the read timeout that should be used.
+    ///
+    /// The read timeout is the limit on the amount of time it takes to read the first byte of a response
+    /// from the time the request is initiated.
+    pub fn set_read_timeout(&mut self, read_timeout: Option<Duration>) -> &mut Self {
+        self.read_timeout = read_timeout;
+        self
+    }
+
+    /// Sets the maximum number of idle connections that the connection pool keeps per host.
+    ///
+    /// Set it to `0` to disable connection reuse.
+    pub fn pool_max_idle_per_host(mut self, pool_max_idle_per_host: usize) -> Self {
+        self.pool_max_idle_per_host = Some(pool_max_idle_per_host);
+        self
+    }
+
+    /// Sets the maximum number of idle connections that the connection pool keeps per host.
+    ///
+    /// Set it to `0` to disable connection reuse.
+    pub fn set_pool_max_idle_per_host(
+        &mut self,
+        pool_max_idle_per_host: Option<usize>,
+    ) -> &mut Self {
+        self.pool_max_idle_per_host = pool_max_idle_per_host;
+        self
+    }
+
+    /// Sets how long idle connections are kept in the connection pool before they are closed.
+    pub fn pool_idle_timeout(mut self, pool_idle_timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(pool_idle_timeout);
+        self
+    }
+
+    /// Sets how long idle connections are kept in the connection pool before they are closed.
+    pub fn set_pool_idle_timeout(&mut self, pool_idle_timeout: Option<Duration>) -> &mut Self {
+        self.pool_idle_timeout = pool_idle_timeout;
+        self
+    }
+
+    /// Builds the [`ConnectorSettings`].
+    pub fn build(self) -> ConnectorSettings {
+        ConnectorSettings {
+            connect_timeout: self.connect_timeout,
+            read_timeout: self.read_timeout,
+            pool_max_idle_per_host: self.pool_max_idle_per_host,
+            pool_idle_timeout: self.pool_idle_timeout,
+        }
+    }
+}
+
+/// Settings for connectors
+#[non_exhaustive]
+#[derive(Clone, Default, Debug)]
+pub struct ConnectorSettings {
+    connect_timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    pool_idle_timeout: Option<Duration>,
+}
+
+impl Storable for ConnectorSettings {
+    type Storer = StoreReplace<Self>;
+}
+
+impl ConnectorSettings {
+    /// Returns a builder for `ConnectorSettings`.
+    pub fn builder() -> ConnectorSettingsBuilder {
+        Default::default()
+    }
+
+    /// Creates settings with the connect and read timeouts of the given timeout config.
+    pub fn from_timeout_config(timeout_config: &TimeoutConfig) -> Self {
+        Self {
+            connect_timeout: timeout_config.connect_timeout(),
+            read_timeout: timeout_config.read_timeout(),
+            pool_max_idle_per_host: None,
+            pool_idle_timeout: None,
+        }
+    }
+
+    /// Returns the connect timeout that should be used.
+    ///
+    /// The connect timeout is a limit on the amount of time it takes to initiate a socket connection.
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.connect_timeout
+    }
+
+    /// Returns the read timeout that should be used.
+    ///
+    /// The read timeout is the limit on the amount of time it takes to read the first byte of a response
+    /// from the time the request is initiated.
+    pub fn read_timeout(&self) -> Option<Duration> {
+        self.read_timeout
+    }
+
+    /// Returns the maximum number of idle connections that the connection pool keeps per host.
+    ///
+    /// Connectors use their default when this is `None`.
+    pub fn pool_max_idle_per_host(&self) -> Option<usize> {
+        self.pool_max_idle_per_host
+    }
+
+    /// Returns how long idle connections are kept in the connection pool before they are closed.
+    ///
+    /// Connectors use their default when this is `None`.
+    pub fn pool_idle_timeout(&self) -> Option<Duration> {
+        self.pool_idle_timeout
+    }
+}
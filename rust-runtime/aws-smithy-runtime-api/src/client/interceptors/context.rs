@@ -302,13 +302,14 @@ impl<I, O, E: Debug> InterceptorContext<I, O, E> {
             return RewindResult::Unnecessary;
         }
 
-        // Otherwise, rewind to the saved request checkpoint
+        // Otherwise, rewind to the saved request checkpoint. Cloning the checkpoint can still fail
+        // when its body can only be replayed a limited number of bytes, e.g. from a replay buffer.
+        let request = match try_clone(self.request_checkpoint.as_ref().expect("checked above")) {
+            Some(request) => request,
+            None => return RewindResult::Impossible,
+        };
         self.phase = Phase::BeforeTransmit;
-        self.request = try_clone(self.request_checkpoint.as_ref().expect("checked above"));
-        assert!(
-            self.request.is_some(),
-            "if the request wasn't cloneable, then we should have already return from this method."
-        );
+        self.request = Some(request);
         self.response = None;
         self.output_or_error = None;
         RewindResult::Occurred
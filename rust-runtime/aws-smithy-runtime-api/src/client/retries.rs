@@ -30,6 +30,12 @@ impl ShouldAttempt {
 }
 
 pub trait RetryStrategy: Send + Sync + Debug {
+    /// Called once per operation, before [`should_attempt_initial_request`](RetryStrategy::should_attempt_initial_request).
+    ///
+    /// A retry strategy is shared by all operations of a client, so any state it keeps on behalf
+    /// of a single operation, such as retry permits, should be stored in that operation's `cfg`.
+    fn on_operation_start(&self, _runtime_components: &RuntimeComponents, _cfg: &mut ConfigBag) {}
+
     fn should_attempt_initial_request(
         &self,
         runtime_components: &RuntimeComponents,
@@ -42,6 +48,10 @@ pub trait RetryStrategy: Send + Sync + Debug {
         runtime_components: &RuntimeComponents,
         cfg: &ConfigBag,
     ) -> Result<ShouldAttempt, BoxError>;
+
+    /// Called when an operation is cancelled so that any retry permits held on its behalf can
+    /// be released.
+    fn on_cancellation(&self, _runtime_components: &RuntimeComponents, _cfg: &ConfigBag) {}
 }
 
 #[derive(Clone, Debug)]
@@ -54,6 +64,10 @@ impl SharedRetryStrategy {
 }
 
 impl RetryStrategy for SharedRetryStrategy {
+    fn on_operation_start(&self, runtime_components: &RuntimeComponents, cfg: &mut ConfigBag) {
+        self.0.on_operation_start(runtime_components, cfg)
+    }
+
     fn should_attempt_initial_request(
         &self,
         runtime_components: &RuntimeComponents,
@@ -72,6 +86,10 @@ impl RetryStrategy for SharedRetryStrategy {
         self.0
             .should_attempt_retry(context, runtime_components, cfg)
     }
+
+    fn on_cancellation(&self, runtime_components: &RuntimeComponents, cfg: &ConfigBag) {
+        self.0.on_cancellation(runtime_components, cfg)
+    }
 }
 
 #[non_exhaustive]
@@ -13,6 +13,7 @@ use crate::client::orchestrator::SharedEndpointResolver;
 use crate::client::retries::{RetryClassifiers, SharedRetryStrategy};
 use aws_smithy_async::rt::sleep::SharedAsyncSleep;
 use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_http::connection::SharedConnectionEventListener;
 use std::fmt;
 
 pub(crate) static EMPTY_RUNTIME_COMPONENTS_BUILDER: RuntimeComponentsBuilder =
@@ -176,6 +177,8 @@ declare_runtime_components! {
         // A connector is not required since a client could technically only be used for presigning
         connector: Option<SharedConnector>,
 
+        connection_event_listeners: Vec<SharedConnectionEventListener>,
+
         #[required]
         endpoint_resolver: Option<SharedEndpointResolver>,
 
@@ -214,6 +217,15 @@ impl RuntimeComponents {
         self.connector.as_ref().map(|s| s.value.clone())
     }
 
+    /// Returns an iterator over the connection event listeners.
+    pub fn connection_event_listeners(
+        &self,
+    ) -> impl Iterator<Item = SharedConnectionEventListener> + '_ {
+        self.connection_event_listeners
+            .iter()
+            .map(|s| s.value.clone())
+    }
+
     /// Returns the endpoint resolver.
     pub fn endpoint_resolver(&self) -> SharedEndpointResolver {
         self.endpoint_resolver.value.clone()
@@ -295,6 +307,40 @@ impl RuntimeComponentsBuilder {
         self
     }
 
+    /// Returns the connection event listeners.
+    pub fn connection_event_listeners(
+        &self,
+    ) -> impl Iterator<Item = SharedConnectionEventListener> + '_ {
+        self.connection_event_listeners
+            .iter()
+            .map(|s| s.value.clone())
+    }
+
+    /// Adds a connection event listener.
+    ///
+    /// Connectors that support connection events notify the listeners about the connections
+    /// that requests are sent over, see [`ConnectionEventListeners`](aws_smithy_http::connection::ConnectionEventListeners).
+    pub fn push_connection_event_listener(
+        &mut self,
+        listener: SharedConnectionEventListener,
+    ) -> &mut Self {
+        self.connection_event_listeners
+            .push(Tracked::new(self.builder_name, listener));
+        self
+    }
+
+    /// Adds a connection event listener.
+    ///
+    /// Connectors that support connection events notify the listeners about the connections
+    /// that requests are sent over, see [`ConnectionEventListeners`](aws_smithy_http::connection::ConnectionEventListeners).
+    pub fn with_connection_event_listener(
+        mut self,
+        listener: SharedConnectionEventListener,
+    ) -> Self {
+        self.push_connection_event_listener(listener);
+        self
+    }
+
     /// Returns the endpoint resolver.
     pub fn endpoint_resolver(&self) -> Option<SharedEndpointResolver> {
         self.endpoint_resolver.as_ref().map(|s| s.value.clone())
@@ -32,6 +32,9 @@ pub mod retries;
 #[cfg(feature = "test-util")]
 pub mod test_util;
 
+/// Stalled stream protection for request and response bodies.
+pub mod stalled_stream_protection;
+
 mod timeout;
 
 /// Smithy identity used by auth and signing.
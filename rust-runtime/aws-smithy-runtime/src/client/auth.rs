@@ -3,7 +3,21 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+
 pub mod no_auth;
 
 #[cfg(feature = "http-auth")]
 pub mod http;
+
+/// Stored by an auth scheme's interceptor when the service answered an attempt with an
+/// authentication challenge that the next attempt can answer, e.g. for HTTP digest auth.
+///
+/// The orchestrator makes one more attempt for the challenge without consulting the retry strategy.
+#[cfg_attr(not(feature = "http-auth"), allow(dead_code))]
+#[derive(Debug)]
+pub(crate) struct RetryForAuthChallenge;
+
+impl Storable for RetryForAuthChallenge {
+    type Storer = StoreReplace<Self>;
+}
@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::client::auth::RetryForAuthChallenge;
 use aws_smithy_http::query_writer::QueryWriter;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::auth::http::{
@@ -14,12 +15,21 @@ use aws_smithy_runtime_api::client::auth::{
 };
 use aws_smithy_runtime_api::client::identity::http::{Login, Token};
 use aws_smithy_runtime_api::client::identity::{Identity, SharedIdentityResolver};
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeDeserializationInterceptorContextRef, BeforeTransmitInterceptorContextRef,
+};
+use aws_smithy_runtime_api::client::interceptors::Interceptor;
 use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
 use aws_smithy_runtime_api::client::runtime_components::{GetIdentityResolver, RuntimeComponents};
 use aws_smithy_types::base64::encode;
-use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
 use http::header::HeaderName;
 use http::HeaderValue;
+use md5::{Digest, Md5};
+use sha2::Sha256;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use tracing::debug;
 
 /// Destination for the API key
 #[derive(Copy, Clone, Debug)]
@@ -224,6 +234,17 @@ impl HttpRequestSigner for BearerAuthSigner {
 }
 
 /// Auth implementation for Smithy's `@httpDigestAuth` auth scheme
+///
+/// Digest auth needs a challenge from the service before a request can be signed. Requests are
+/// sent without credentials until the service has answered one with a `401` response and a
+/// `WWW-Authenticate: Digest ...` challenge. The [`DigestAuthInterceptor`] returned by
+/// [`DigestAuthScheme::interceptor`] must be registered alongside this scheme to pick up that
+/// challenge and send the request once more. The challenge is kept per host so that later
+/// requests can answer it right away, with an incrementing nonce count. Challenges are kept for up
+/// to [`MAX_DIGEST_CHALLENGES`] hosts, after which the oldest challenge is dropped.
+///
+/// The `MD5`, `MD5-sess`, `SHA-256` and `SHA-256-sess` algorithms are supported with either
+/// `qop=auth` or no `qop` at all.
 #[derive(Debug, Default)]
 pub struct DigestAuthScheme {
     signer: DigestAuthSigner,
@@ -233,7 +254,14 @@ impl DigestAuthScheme {
     /// Creates a new `DigestAuthScheme`.
     pub fn new() -> Self {
         Self {
-            signer: DigestAuthSigner,
+            signer: DigestAuthSigner::default(),
+        }
+    }
+
+    /// Returns the interceptor that answers digest challenges for this auth scheme.
+    pub fn interceptor(&self) -> DigestAuthInterceptor {
+        DigestAuthInterceptor {
+            challenges: self.signer.challenges.clone(),
         }
     }
 }
@@ -255,24 +283,415 @@ impl HttpAuthScheme for DigestAuthScheme {
     }
 }
 
+/// Maximum number of hosts whose digest challenge a [`DigestAuthScheme`] remembers
+pub const MAX_DIGEST_CHALLENGES: usize = 64;
+
+type DigestChallenges = Arc<Mutex<ChallengeCache>>;
+
+/// The latest digest challenge of each host, by authority
+///
+/// Holds at most [`MAX_DIGEST_CHALLENGES`] challenges and evicts the oldest one first.
+#[derive(Debug, Default)]
+struct ChallengeCache {
+    by_authority: HashMap<String, ChallengeState>,
+    /// Authorities in the order in which their challenge was received, oldest first
+    order: VecDeque<String>,
+}
+
+impl ChallengeCache {
+    fn get_mut(&mut self, authority: &str) -> Option<&mut ChallengeState> {
+        self.by_authority.get_mut(authority)
+    }
+
+    fn insert(&mut self, authority: String, state: ChallengeState) {
+        if self.by_authority.insert(authority.clone(), state).is_some() {
+            self.order.retain(|existing| existing != &authority);
+        } else if self.by_authority.len() > MAX_DIGEST_CHALLENGES {
+            if let Some(oldest) = self.order.pop_front() {
+                self.by_authority.remove(&oldest);
+            }
+        }
+        self.order.push_back(authority);
+    }
+}
+
+#[derive(Debug)]
+struct ChallengeState {
+    challenge: DigestChallenge,
+    nonce_count: u32,
+}
+
+/// Recorded on requests by the signer so that the interceptor knows how the request was signed
+#[derive(Clone, Debug)]
+struct DigestAuthAttempt {
+    authority: String,
+    answered_challenge: bool,
+}
+
+impl Storable for DigestAuthAttempt {
+    type Storer = StoreReplace<Self>;
+}
+
 #[derive(Debug, Default)]
-struct DigestAuthSigner;
+struct DigestAuthSigner {
+    challenges: DigestChallenges,
+}
 
 impl HttpRequestSigner for DigestAuthSigner {
     fn sign_request(
         &self,
-        _request: &mut HttpRequest,
-        _identity: &Identity,
+        request: &mut HttpRequest,
+        identity: &Identity,
         _auth_scheme_endpoint_config: AuthSchemeEndpointConfig<'_>,
         _runtime_components: &RuntimeComponents,
         _config_bag: &ConfigBag,
     ) -> Result<(), BoxError> {
-        unimplemented!(
-            "support for signing with Smithy's `@httpDigestAuth` auth scheme is not implemented yet"
-        )
+        let login = identity
+            .data::<Login>()
+            .ok_or("HTTP digest auth requires a `Login` identity")?;
+        let authority = request
+            .uri()
+            .authority()
+            .map(|authority| authority.as_str().to_ascii_lowercase())
+            .unwrap_or_default();
+
+        let authorization = {
+            let mut challenges = self.challenges.lock().expect("lock is never poisoned");
+            challenges.get_mut(&authority).map(|state| {
+                state.nonce_count += 1;
+                let uri = request
+                    .uri()
+                    .path_and_query()
+                    .map(|path| path.as_str())
+                    .unwrap_or("/");
+                state.challenge.authorization(
+                    login,
+                    request.method().as_str(),
+                    uri,
+                    state.nonce_count,
+                    &new_cnonce(),
+                )
+            })
+        };
+        let answered_challenge = authorization.is_some();
+        if let Some(authorization) = authorization {
+            request.headers_mut().insert(
+                http::header::AUTHORIZATION,
+                HeaderValue::try_from(authorization).map_err(|_| {
+                    "Digest credentials contain characters that can't be included in a HTTP header"
+                })?,
+            );
+        }
+        request.extensions_mut().insert(DigestAuthAttempt {
+            authority,
+            answered_challenge,
+        });
+        Ok(())
+    }
+}
+
+fn new_cnonce() -> String {
+    hex::encode((0..16).map(|_| fastrand::u8(..)).collect::<Vec<_>>())
+}
+
+/// Interceptor that answers the digest challenges of a [`DigestAuthScheme`]
+///
+/// When a request that was signed with digest auth receives a `401` response with a digest
+/// challenge, the challenge is remembered and the request is sent again to answer it. This only
+/// happens once per operation, and only when the request didn't already answer a challenge that
+/// is still valid, so that wrong credentials don't cause a loop.
+#[derive(Debug)]
+pub struct DigestAuthInterceptor {
+    challenges: DigestChallenges,
+}
+
+impl Interceptor for DigestAuthInterceptor {
+    fn read_before_transmit(
+        &self,
+        context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        // The request is consumed by the connector, so remember how it was signed
+        let attempt = context.request().extensions().get::<DigestAuthAttempt>();
+        cfg.interceptor_state().store_or_unset(attempt.cloned());
+        Ok(())
+    }
+
+    fn read_after_transmit(
+        &self,
+        context: &BeforeDeserializationInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let response = context.response();
+        if response.status() != http::StatusCode::UNAUTHORIZED {
+            return Ok(());
+        }
+        let attempt = match cfg.load::<DigestAuthAttempt>() {
+            Some(attempt) => attempt.clone(),
+            None => return Ok(()),
+        };
+        let challenge = match DigestChallenge::from_headers(
+            response
+                .headers()
+                .get_all(http::header::WWW_AUTHENTICATE)
+                .iter(),
+        ) {
+            Some(challenge) => challenge,
+            None => {
+                debug!("the service didn't send a supported digest challenge");
+                return Ok(());
+            }
+        };
+
+        let retry = !attempt.answered_challenge || challenge.stale;
+        self.challenges
+            .lock()
+            .expect("lock is never poisoned")
+            .insert(
+                attempt.authority,
+                ChallengeState {
+                    challenge,
+                    nonce_count: 0,
+                },
+            );
+        if retry {
+            cfg.interceptor_state().store_put(RetryForAuthChallenge);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum DigestAlgorithm {
+    Md5,
+    Md5Sess,
+    Sha256,
+    Sha256Sess,
+}
+
+impl DigestAlgorithm {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "MD5" => Some(Self::Md5),
+            "MD5-SESS" => Some(Self::Md5Sess),
+            "SHA-256" => Some(Self::Sha256),
+            "SHA-256-SESS" => Some(Self::Sha256Sess),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Md5 => "MD5",
+            Self::Md5Sess => "MD5-sess",
+            Self::Sha256 => "SHA-256",
+            Self::Sha256Sess => "SHA-256-sess",
+        }
+    }
+
+    fn is_session(self) -> bool {
+        matches!(self, Self::Md5Sess | Self::Sha256Sess)
+    }
+
+    /// Preference when the service offers several challenges
+    fn strength(self) -> u8 {
+        match self {
+            Self::Md5 | Self::Md5Sess => 0,
+            Self::Sha256 | Self::Sha256Sess => 1,
+        }
+    }
+
+    fn hash(self, data: &str) -> String {
+        match self {
+            Self::Md5 | Self::Md5Sess => hex::encode(Md5::digest(data)),
+            Self::Sha256 | Self::Sha256Sess => hex::encode(Sha256::digest(data)),
+        }
     }
 }
 
+/// A digest challenge from a `WWW-Authenticate` header, as defined in RFC 7616
+#[derive(Clone, Debug, PartialEq)]
+struct DigestChallenge {
+    realm: String,
+    nonce: String,
+    opaque: Option<String>,
+    algorithm: DigestAlgorithm,
+    /// Whether `qop=auth` is used. Without a `qop`, responses are computed as in RFC 2069.
+    qop_auth: bool,
+    stale: bool,
+}
+
+impl DigestChallenge {
+    /// Returns the strongest supported digest challenge of the given `WWW-Authenticate` headers
+    fn from_headers<'a>(headers: impl Iterator<Item = &'a HeaderValue>) -> Option<Self> {
+        headers
+            .filter_map(|header| header.to_str().ok())
+            .flat_map(parse_challenges)
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("digest"))
+            .filter_map(|(_, params)| Self::from_params(&params))
+            .fold(None, |best: Option<Self>, challenge| match best {
+                Some(best) if best.algorithm.strength() >= challenge.algorithm.strength() => {
+                    Some(best)
+                }
+                _ => Some(challenge),
+            })
+    }
+
+    fn from_params(params: &[(String, String)]) -> Option<Self> {
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        };
+        let algorithm = match param("algorithm") {
+            Some(algorithm) => DigestAlgorithm::from_name(algorithm)?,
+            None => DigestAlgorithm::Md5,
+        };
+        let qop_auth = match param("qop") {
+            Some(qop) => {
+                if !qop
+                    .split(',')
+                    .any(|qop| qop.trim().eq_ignore_ascii_case("auth"))
+                {
+                    // Only `auth-int` is offered, which requires hashing the request body
+                    return None;
+                }
+                true
+            }
+            None => false,
+        };
+        if algorithm.is_session() && !qop_auth {
+            return None;
+        }
+        Some(Self {
+            realm: param("realm")?.to_string(),
+            nonce: param("nonce")?.to_string(),
+            opaque: param("opaque").map(str::to_string),
+            algorithm,
+            qop_auth,
+            stale: matches!(param("stale"), Some(stale) if stale.eq_ignore_ascii_case("true")),
+        })
+    }
+
+    /// Returns the value of the `Authorization` header that answers this challenge
+    fn authorization(
+        &self,
+        login: &Login,
+        method: &str,
+        uri: &str,
+        nonce_count: u32,
+        cnonce: &str,
+    ) -> String {
+        let algorithm = self.algorithm;
+        let nc = format!("{:08x}", nonce_count);
+        let mut ha1 = algorithm.hash(&format!(
+            "{}:{}:{}",
+            login.user(),
+            self.realm,
+            login.password()
+        ));
+        if algorithm.is_session() {
+            ha1 = algorithm.hash(&format!("{}:{}:{}", ha1, self.nonce, cnonce));
+        }
+        let ha2 = algorithm.hash(&format!("{}:{}", method, uri));
+        let response = if self.qop_auth {
+            algorithm.hash(&format!(
+                "{}:{}:{}:{}:auth:{}",
+                ha1, self.nonce, nc, cnonce, ha2
+            ))
+        } else {
+            algorithm.hash(&format!("{}:{}:{}", ha1, self.nonce, ha2))
+        };
+
+        let mut authorization = format!(
+            "Digest username={}, realm={}, uri={}, algorithm={}, nonce={}",
+            quote(login.user()),
+            quote(&self.realm),
+            quote(uri),
+            algorithm.name(),
+            quote(&self.nonce),
+        );
+        if self.qop_auth {
+            authorization.push_str(&format!(", nc={}, cnonce={}, qop=auth", nc, quote(cnonce)));
+        }
+        authorization.push_str(&format!(", response={}", quote(&response)));
+        if let Some(opaque) = &self.opaque {
+            authorization.push_str(&format!(", opaque={}", quote(opaque)));
+        }
+        authorization
+    }
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Parses the challenges of a `WWW-Authenticate` header into their scheme and parameters
+///
+/// The `token68` credentials of other schemes are skipped.
+fn parse_challenges(header: &str) -> Vec<(String, Vec<(String, String)>)> {
+    let mut challenges: Vec<(String, Vec<(String, String)>)> = Vec::new();
+    let mut rest = header;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c == ',' || c.is_ascii_whitespace());
+        if rest.is_empty() {
+            return challenges;
+        }
+        let token_end = rest
+            .find(|c: char| c == '=' || c == ',' || c.is_ascii_whitespace())
+            .unwrap_or(rest.len());
+        let (token, after_token) = rest.split_at(token_end);
+        let after_equals = match after_token.trim_start().strip_prefix('=') {
+            Some(after_equals) => after_equals.trim_start(),
+            // A token that isn't followed by `=` starts a new challenge
+            None => {
+                challenges.push((token.to_string(), Vec::new()));
+                rest = after_token;
+                continue;
+            }
+        };
+        if token.is_empty() || after_equals.is_empty() || after_equals.starts_with(['=', ',']) {
+            // The padding of a `token68`
+            rest = after_equals.trim_start_matches('=');
+            continue;
+        }
+        let (value, after_value) = match after_equals.strip_prefix('"') {
+            Some(quoted) => parse_quoted(quoted),
+            None => {
+                let end = after_equals.find(',').unwrap_or(after_equals.len());
+                let (value, after_value) = after_equals.split_at(end);
+                (value.trim_end().to_string(), after_value)
+            }
+        };
+        if let Some((_, params)) = challenges.last_mut() {
+            params.push((token.to_string(), value));
+        }
+        rest = after_value;
+    }
+}
+
+/// Parses a quoted string whose opening quote was already consumed, returning the unescaped value
+/// and the rest of the input
+fn parse_quoted(input: &str) -> (String, &str) {
+    let mut value = String::new();
+    let mut chars = input.char_indices();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '"' => return (value, &input[index + 1..]),
+            '\\' => {
+                if let Some((_, escaped)) = chars.next() {
+                    value.push(escaped);
+                }
+            }
+            c => value.push(c),
+        }
+    }
+    (value, "")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -385,4 +804,408 @@ mod tests {
             request.headers().get("Authorization").unwrap()
         );
     }
+
+    // Examples from section 3.9.1 of RFC 7616
+    const RFC_7616_CHALLENGE: &str =
+        "Digest realm=\"http-auth@example.org\", qop=\"auth, auth-int\", \
+        algorithm=ALGORITHM, nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", \
+        opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\"";
+    const RFC_7616_CNONCE: &str = "f2/wE4q74E6zIJEtWaHKaf5wv/H5QzzpXusqGemxURZJ";
+
+    fn challenge(header: &str) -> Option<DigestChallenge> {
+        DigestChallenge::from_headers([HeaderValue::from_str(header).unwrap()].iter())
+    }
+
+    #[test]
+    fn test_digest_auth_rfc_7616_examples() {
+        let login = Login::new("Mufasa", "Circle of Life", None);
+        for (algorithm, response) in [
+            ("MD5", "8ca523f5e9506fed4657c9700eebdbec"),
+            (
+                "SHA-256",
+                "753927fa0e85d155564e2e272a28d1802ca10daf4496794697cf8db5856cb6c1",
+            ),
+        ] {
+            let challenge = challenge(&RFC_7616_CHALLENGE.replace("ALGORITHM", algorithm))
+                .expect("supported challenge");
+            assert_eq!(
+                format!(
+                    "Digest username=\"Mufasa\", realm=\"http-auth@example.org\", \
+                    uri=\"/dir/index.html\", algorithm={algorithm}, \
+                    nonce=\"7ypf/xlj9XXwfDPEoM4URrv/xwf94BcCAzFZH4GiTo0v\", nc=00000001, \
+                    cnonce=\"{RFC_7616_CNONCE}\", qop=auth, response=\"{response}\", \
+                    opaque=\"FQhe/qaU925kfnzjCev0ciny7QMkPqMAFRtzCUYo5tdS\""
+                ),
+                challenge.authorization(&login, "GET", "/dir/index.html", 1, RFC_7616_CNONCE)
+            );
+        }
+    }
+
+    #[test]
+    fn test_digest_auth_session_and_legacy_responses() {
+        let login = Login::new("Mufasa", "Circle of Life", None);
+        let sess = challenge(&RFC_7616_CHALLENGE.replace("ALGORITHM", "MD5-sess")).unwrap();
+        let authorization =
+            sess.authorization(&login, "GET", "/dir/index.html", 2, RFC_7616_CNONCE);
+        // MD5(MD5(Mufasa:http-auth@example.org:Circle of Life):nonce:cnonce) is used as HA1
+        assert!(authorization.contains("algorithm=MD5-sess"));
+        assert!(authorization.contains("nc=00000002"));
+        assert!(authorization.contains("response=\"6914b51e16f9459d9abc967ad41c4599\""));
+
+        // Example from section 2.1.2 of RFC 2069, which doesn't use qop
+        let legacy = challenge(
+            "Digest realm=\"testrealm@host.com\", \
+            nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", \
+            opaque=\"5ccc069c403ebaf9f0171e9517f40e41\"",
+        )
+        .unwrap();
+        assert_eq!(
+            "Digest username=\"Mufasa\", realm=\"testrealm@host.com\", uri=\"/dir/index.html\", \
+            algorithm=MD5, nonce=\"dcd98b7102dd2f0e8b11d0f600bfb0c093\", \
+            response=\"1949323746fe6a43ef61f9606e7febea\", \
+            opaque=\"5ccc069c403ebaf9f0171e9517f40e41\"",
+            legacy.authorization(
+                &Login::new("Mufasa", "CircleOfLife", None),
+                "GET",
+                "/dir/index.html",
+                1,
+                "unused"
+            )
+        );
+    }
+
+    #[test]
+    fn test_digest_challenge_parsing() {
+        let header = "Basic realm=\"simple\", Newauth realm=\"apps\", type=1, title=\"Login to \\\"apps\\\"\", \
+            Other dG9rZW42OA==, Digest realm=\"md5\", nonce=abc, \
+            Digest realm = \"sha\" , nonce=\"def\", algorithm=SHA-256, qop=auth, stale=TRUE";
+        assert_eq!(
+            vec![
+                ("Basic".to_string(), vec![("realm".into(), "simple".into())]),
+                (
+                    "Newauth".to_string(),
+                    vec![
+                        ("realm".into(), "apps".into()),
+                        ("type".into(), "1".into()),
+                        ("title".into(), "Login to \"apps\"".into()),
+                    ]
+                ),
+                ("Other".to_string(), vec![]),
+                (
+                    "Digest".to_string(),
+                    vec![
+                        ("realm".into(), "md5".into()),
+                        ("nonce".into(), "abc".into())
+                    ]
+                ),
+                (
+                    "Digest".to_string(),
+                    vec![
+                        ("realm".into(), "sha".into()),
+                        ("nonce".into(), "def".into()),
+                        ("algorithm".into(), "SHA-256".into()),
+                        ("qop".into(), "auth".into()),
+                        ("stale".into(), "TRUE".into()),
+                    ]
+                ),
+            ],
+            parse_challenges(header)
+        );
+
+        // The strongest challenge is used
+        assert_eq!(
+            Some(DigestChallenge {
+                realm: "sha".into(),
+                nonce: "def".into(),
+                opaque: None,
+                algorithm: DigestAlgorithm::Sha256,
+                qop_auth: true,
+                stale: true,
+            }),
+            challenge(header)
+        );
+
+        // Unsupported challenges
+        assert_eq!(None, challenge("Basic realm=\"simple\""));
+        assert_eq!(
+            None,
+            challenge("Digest realm=\"a\", nonce=\"b\", algorithm=SHA-512-256")
+        );
+        assert_eq!(
+            None,
+            challenge("Digest realm=\"a\", nonce=\"b\", qop=auth-int")
+        );
+        assert_eq!(
+            None,
+            challenge("Digest realm=\"a\", algorithm=MD5-sess, nonce=\"b\"")
+        );
+        assert_eq!(None, challenge("Digest realm=\"a\""));
+    }
+
+    #[test]
+    fn challenge_cache_evicts_the_oldest_challenge() {
+        let state = || ChallengeState {
+            challenge: challenge("Digest realm=\"a\", nonce=\"b\"").unwrap(),
+            nonce_count: 0,
+        };
+        let mut cache = ChallengeCache::default();
+        for host in 0..MAX_DIGEST_CHALLENGES {
+            cache.insert(format!("host-{host}"), state());
+        }
+        // Refreshing a challenge makes it the most recent one
+        cache.insert("host-0".into(), state());
+        assert_eq!(MAX_DIGEST_CHALLENGES, cache.by_authority.len());
+
+        cache.insert("another-host".into(), state());
+        assert_eq!(MAX_DIGEST_CHALLENGES, cache.by_authority.len());
+        assert!(cache.get_mut("host-0").is_some());
+        assert!(cache.get_mut("host-1").is_none());
+        assert!(cache.get_mut("another-host").is_some());
+    }
+
+    #[cfg(feature = "test-util")]
+    mod digest_auth_flow {
+        use super::*;
+        use crate::client::orchestrator::endpoints::StaticUriEndpointResolver;
+        use crate::client::orchestrator::invoke;
+        use crate::client::retries::strategy::NeverRetryStrategy;
+        use crate::client::test_util::serializer::CannedRequestSerializer;
+        use aws_smithy_runtime_api::client::auth::option_resolver::StaticAuthOptionResolver;
+        use aws_smithy_runtime_api::client::auth::{
+            AuthOptionResolverParams, SharedAuthOptionResolver, SharedHttpAuthScheme,
+        };
+        use aws_smithy_runtime_api::client::connectors::{Connector, SharedConnector};
+        use aws_smithy_runtime_api::client::interceptors::context::{Error, Output};
+        use aws_smithy_runtime_api::client::interceptors::SharedInterceptor;
+        use aws_smithy_runtime_api::client::orchestrator::{
+            BoxFuture, DynResponseDeserializer, EndpointResolverParams, Future, HttpResponse,
+            OrchestratorError, ResponseDeserializer, SharedEndpointResolver,
+            SharedRequestSerializer,
+        };
+        use aws_smithy_runtime_api::client::request_attempts::RequestAttempts;
+        use aws_smithy_runtime_api::client::retries::SharedRetryStrategy;
+        use aws_smithy_runtime_api::client::runtime_plugin::{
+            RuntimePlugin, RuntimePlugins, StaticRuntimePlugin,
+        };
+        use aws_smithy_types::config_bag::{FrozenLayer, Layer};
+        use aws_smithy_types::type_erasure::TypeErasedBox;
+        use std::borrow::Cow;
+
+        /// Responds with a challenge until the request carries a response for the latest nonce
+        #[derive(Clone, Debug, Default)]
+        struct DigestService {
+            nonce: Arc<Mutex<u32>>,
+            requests: Arc<Mutex<Vec<Option<String>>>>,
+        }
+
+        impl DigestService {
+            fn next_nonce(&self) {
+                *self.nonce.lock().unwrap() += 1;
+            }
+
+            fn authorizations(&self) -> Vec<Option<String>> {
+                self.requests.lock().unwrap().clone()
+            }
+        }
+
+        impl Connector for DigestService {
+            fn call(&self, request: HttpRequest) -> BoxFuture<HttpResponse> {
+                let authorization = request
+                    .headers()
+                    .get(http::header::AUTHORIZATION)
+                    .map(|value| value.to_str().unwrap().to_string());
+                self.requests.lock().unwrap().push(authorization.clone());
+                let nonce = format!("nonce-{}", self.nonce.lock().unwrap());
+                let (valid_user, valid_nonce) = authorization.map_or((false, false), |auth| {
+                    (
+                        auth.contains("username=\"Mufasa\""),
+                        auth.contains(&format!("nonce=\"{nonce}\"")),
+                    )
+                });
+                let response = match (valid_user, valid_nonce) {
+                    (true, true) => http::Response::builder().status(200),
+                    (stale, _) => http::Response::builder().status(401).header(
+                        http::header::WWW_AUTHENTICATE,
+                        format!(
+                            "Digest realm=\"test\", qop=\"auth\", nonce=\"{nonce}\", stale={stale}"
+                        ),
+                    ),
+                };
+                Box::pin(Future::ready(Ok(response.body(SdkBody::empty()).unwrap())))
+            }
+        }
+
+        #[derive(Debug)]
+        struct StatusDeserializer;
+
+        impl ResponseDeserializer for StatusDeserializer {
+            fn deserialize_nonstreaming(
+                &self,
+                response: &HttpResponse,
+            ) -> Result<Output, OrchestratorError<Error>> {
+                Ok(Output::new(response.status().as_u16()))
+            }
+        }
+
+        #[derive(Debug)]
+        struct TestPlugin(RuntimeComponentsBuilder);
+
+        impl RuntimePlugin for TestPlugin {
+            fn config(&self) -> Option<FrozenLayer> {
+                let mut layer = Layer::new("test");
+                layer.store_put(AuthOptionResolverParams::new("unused"));
+                layer.store_put(EndpointResolverParams::new("unused"));
+                layer.store_put(SharedRequestSerializer::new(
+                    CannedRequestSerializer::success(
+                        http::Request::builder()
+                            .uri("/dir/index.html")
+                            .body(SdkBody::empty())
+                            .unwrap(),
+                    ),
+                ));
+                layer.store_put(DynResponseDeserializer::new(StatusDeserializer));
+                Some(layer.freeze())
+            }
+
+            fn runtime_components(&self) -> Cow<'_, RuntimeComponentsBuilder> {
+                Cow::Borrowed(&self.0)
+            }
+        }
+
+        fn plugins(
+            scheme: &Arc<DigestAuthScheme>,
+            service: &DigestService,
+            login: Login,
+        ) -> RuntimePlugins {
+            RuntimePlugins::new().with_operation_plugin(TestPlugin(
+                RuntimeComponentsBuilder::new("test")
+                    .with_retry_strategy(Some(SharedRetryStrategy::new(NeverRetryStrategy::new())))
+                    .with_endpoint_resolver(Some(SharedEndpointResolver::new(
+                        StaticUriEndpointResolver::http_localhost(8080),
+                    )))
+                    .with_connector(Some(SharedConnector::new(service.clone())))
+                    .with_auth_option_resolver(Some(SharedAuthOptionResolver::new(
+                        StaticAuthOptionResolver::new(vec![HTTP_DIGEST_AUTH_SCHEME_ID]),
+                    )))
+                    .with_http_auth_scheme(SharedHttpAuthScheme::new(SharedScheme(scheme.clone())))
+                    .with_identity_resolver(
+                        HTTP_DIGEST_AUTH_SCHEME_ID,
+                        SharedIdentityResolver::new(login),
+                    )
+                    .with_interceptor(SharedInterceptor::new(scheme.interceptor())),
+            ))
+        }
+
+        async fn status(plugins: &RuntimePlugins) -> u16 {
+            let output = invoke("test", "test", TypeErasedBox::new(()), plugins)
+                .await
+                .expect("success");
+            *output.downcast::<u16>().unwrap()
+        }
+
+        /// Lets tests keep a handle on the scheme that the client uses
+        #[derive(Debug)]
+        struct SharedScheme(Arc<DigestAuthScheme>);
+
+        impl HttpAuthScheme for SharedScheme {
+            fn scheme_id(&self) -> AuthSchemeId {
+                self.0.scheme_id()
+            }
+
+            fn identity_resolver(
+                &self,
+                identity_resolvers: &dyn GetIdentityResolver,
+            ) -> Option<SharedIdentityResolver> {
+                self.0.identity_resolver(identity_resolvers)
+            }
+
+            fn request_signer(&self) -> &dyn HttpRequestSigner {
+                self.0.request_signer()
+            }
+        }
+
+        #[tokio::test]
+        async fn answers_challenges_and_reuses_them() {
+            let scheme = Arc::new(DigestAuthScheme::new());
+            let service = DigestService::default();
+            let plugins = plugins(&scheme, &service, Login::new("Mufasa", "secret", None));
+
+            assert_eq!(200, status(&plugins).await);
+            assert_eq!(200, status(&plugins).await);
+            // The service changes its nonce, so the next request is challenged once more with a
+            // stale nonce
+            service.next_nonce();
+            assert_eq!(200, status(&plugins).await);
+
+            let authorizations = service.authorizations();
+            assert_eq!(5, authorizations.len(), "{authorizations:?}");
+            assert_eq!(None, authorizations[0]);
+            for (index, nc) in [(1, 1), (2, 2), (3, 3), (4, 1)] {
+                let authorization = authorizations[index].as_ref().unwrap();
+                assert!(authorization.starts_with("Digest username=\"Mufasa\""));
+                assert!(
+                    authorization.contains(&format!("nc={nc:08x}")),
+                    "{authorization}"
+                );
+            }
+            assert!(authorizations[3]
+                .as_ref()
+                .unwrap()
+                .contains("nonce=\"nonce-0\""));
+            assert!(authorizations[4]
+                .as_ref()
+                .unwrap()
+                .contains("nonce=\"nonce-1\""));
+        }
+
+        #[tokio::test]
+        async fn retries_only_once() {
+            let scheme = Arc::new(DigestAuthScheme::new());
+            let service = DigestService::default();
+            // The service only accepts Mufasa
+            let plugins = plugins(&scheme, &service, Login::new("Scar", "secret", None));
+
+            assert_eq!(401, status(&plugins).await);
+            assert_eq!(401, status(&plugins).await);
+            let authorizations = service.authorizations();
+            // The second operation answers the cached challenge right away and isn't retried
+            assert_eq!(3, authorizations.len(), "{authorizations:?}");
+            assert_eq!(None, authorizations[0]);
+            assert!(authorizations[1].is_some() && authorizations[2].is_some());
+        }
+
+        #[derive(Debug, Default)]
+        struct RecordAttempts(Arc<Mutex<Vec<u32>>>);
+
+        impl Interceptor for RecordAttempts {
+            fn read_before_transmit(
+                &self,
+                _context: &BeforeTransmitInterceptorContextRef<'_>,
+                _runtime_components: &RuntimeComponents,
+                cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                let attempts = cfg.load::<RequestAttempts>().expect("set").attempts();
+                self.0.lock().unwrap().push(attempts);
+                Ok(())
+            }
+        }
+
+        #[tokio::test]
+        async fn answering_a_challenge_does_not_count_as_an_attempt() {
+            let scheme = Arc::new(DigestAuthScheme::new());
+            let service = DigestService::default();
+            let attempts = Arc::new(Mutex::new(Vec::new()));
+            let plugins =
+                plugins(&scheme, &service, Login::new("Mufasa", "secret", None))
+                    .with_operation_plugin(StaticRuntimePlugin::new().with_runtime_components(
+                        RuntimeComponentsBuilder::new("record_attempts").with_interceptor(
+                            SharedInterceptor::new(RecordAttempts(attempts.clone())),
+                        ),
+                    ));
+
+            assert_eq!(200, status(&plugins).await);
+            assert_eq!(2, service.authorizations().len());
+            assert_eq!(vec![1, 1], *attempts.lock().unwrap());
+        }
+    }
 }
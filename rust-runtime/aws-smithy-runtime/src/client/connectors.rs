@@ -3,34 +3,46 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::client::config_override::Resolver;
+use aws_smithy_runtime_api::client::connectors::SharedConnector;
+
 pub mod connection_poisoning;
+#[cfg(feature = "connector-hyper")]
+pub mod hyper_connector;
 #[cfg(feature = "test-util")]
 pub mod test_util;
 
-pub mod adapter {
-    use aws_smithy_client::erase::DynConnector;
-    use aws_smithy_runtime_api::client::connectors::Connector;
-    use aws_smithy_runtime_api::client::orchestrator::{BoxFuture, HttpRequest, HttpResponse};
-    use std::sync::{Arc, Mutex};
-
-    #[derive(Debug)]
-    pub struct DynConnectorAdapter {
-        // `DynConnector` requires `&mut self`, so we need interior mutability to adapt to it
-        dyn_connector: Arc<Mutex<DynConnector>>,
-    }
+/// Creates the default connector of a client, which is a rustls [`HyperConnector`](hyper_connector::HyperConnector)
+///
+/// The connector is configured from the client's config and runtime components, see
+/// [`HyperConnectorBuilder::client_config`](hyper_connector::HyperConnectorBuilder::client_config).
+/// Unless the config has a [`ProxyConfig`](hyper_connector::proxy::ProxyConfig), requests are
+/// sent through the proxy configured in the environment. Returns `None` if the `tls-rustls`
+/// feature isn't enabled.
+#[cfg_attr(not(feature = "tls-rustls"), allow(unused_variables))]
+pub fn default_connector(resolver: &Resolver<'_>) -> Option<SharedConnector> {
+    #[cfg(feature = "tls-rustls")]
+    {
+        use aws_smithy_types::error::display::DisplayErrorContext;
+        use hyper_connector::proxy::ProxyConfig;
+        use hyper_connector::HyperConnector;
 
-    impl DynConnectorAdapter {
-        pub fn new(dyn_connector: DynConnector) -> Self {
-            Self {
-                dyn_connector: Arc::new(Mutex::new(dyn_connector)),
-            }
-        }
+        let proxy = match resolver.resolve_config::<ProxyConfig>() {
+            Some(proxy) => Some(proxy.clone()),
+            None => ProxyConfig::from_env().unwrap_or_else(|err| {
+                tracing::warn!(err = %DisplayErrorContext(&err), "ignoring the proxy configured in the environment");
+                None
+            }),
+        };
+        let mut builder = HyperConnector::builder();
+        builder.set_proxy(proxy);
+        let builder = builder.client_config(resolver);
+        tracing::trace!(builder = ?builder, "creating a new default connector");
+        Some(SharedConnector::new(builder.build_https()))
     }
-
-    impl Connector for DynConnectorAdapter {
-        fn call(&self, request: HttpRequest) -> BoxFuture<HttpResponse> {
-            let future = self.dyn_connector.lock().unwrap().call_lite(request);
-            future
-        }
+    #[cfg(not(feature = "tls-rustls"))]
+    {
+        tracing::trace!("no default connector is available without the `tls-rustls` feature");
+        None
     }
 }
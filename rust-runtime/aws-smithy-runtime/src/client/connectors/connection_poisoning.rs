@@ -25,7 +25,7 @@ use tracing::{debug, error};
 ///
 /// **In order for this interceptor to work,** the configured connection must interact with the
 /// "connection retriever" stored in an HTTP request's `extensions` map. For an example of this,
-/// see [aws_smithy_client::hyper_ext::Adapter](https://github.com/awslabs/smithy-rs/blob/47b3d23ff3cabd67e797af616101f5a4ea6be5e8/rust-runtime/aws-smithy-client/src/hyper_ext.rs#L155).
+/// see the `HyperConnector` in the `hyper_connector` module, which requires the `connector-hyper` feature.
 /// When a connection is made available to the retriever, this interceptor will call a `.poison`
 /// method on it, signalling that the connection should be dropped. It is up to the connection
 /// implementer to handle this.
@@ -0,0 +1,619 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Connection events for hyper connectors
+//!
+//! [`TrackConnections`] wraps a hyper connector so that the connections it opens report
+//! [`ConnectionEvent`]s to the [`ConnectionEventListeners`] found in the extensions of the requests
+//! sent over them. [`HyperConnector`](super::HyperConnector)s track their connections this way, so
+//! listeners are notified when a connection is opened, reused, poisoned or closed.
+
+use aws_smithy_http::body::SdkBody;
+use aws_smithy_http::connection::{ConnectionEvent, ConnectionEventKind, ConnectionEventListeners};
+use aws_smithy_runtime_api::box_error::BoxError;
+use bytes::Bytes;
+use http::uri::Scheme;
+use http::{Extensions, HeaderMap, Uri};
+use hyper::client::connect::{CaptureConnection, Connected, Connection, HttpInfo};
+use hyper::service::Service;
+use std::cell::Cell;
+use std::fmt;
+use std::future::Future;
+use std::io::IoSlice;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+
+tokio::task_local! {
+    static CONNECTING: Cell<Progress>;
+}
+
+/// Progress of the connection being established
+#[derive(Clone, Copy, Debug, Default)]
+struct Progress {
+    dns_duration: Option<Duration>,
+    // When the transport, i.e. the TCP connection or proxy tunnel, was ready. The TLS handshake
+    // happens after that.
+    transport_connected_at: Option<Instant>,
+}
+
+fn update_progress(update: impl FnOnce(&mut Progress)) {
+    let _ = CONNECTING.try_with(|progress| {
+        let mut value = progress.get();
+        update(&mut value);
+        progress.set(value);
+    });
+}
+
+/// Records that the transport of the connection being established is ready
+///
+/// Connectors that wrap the transport in TLS call this before the TLS handshake, so that the
+/// handshake can be timed.
+pub(crate) fn mark_transport_connected() {
+    update_progress(|progress| progress.transport_connected_at = Some(Instant::now()));
+}
+
+/// Records how long resolving the host name of the connection being established took
+pub(crate) fn record_dns_duration(duration: Duration) {
+    update_progress(|progress| progress.dns_duration = Some(duration));
+}
+
+/// Hyper connector that tracks the connections that `C` opens
+#[derive(Clone, Debug)]
+pub struct TrackConnections<C> {
+    inner: C,
+}
+
+impl<C> TrackConnections<C> {
+    /// Tracks the connections that `inner` opens.
+    pub fn new(inner: C) -> Self {
+        Self { inner }
+    }
+}
+
+impl<C> Service<Uri> for TrackConnections<C>
+where
+    C: Service<Uri>,
+    C::Response: Connection + Send + 'static,
+    C::Future: Send + 'static,
+    C::Error: Into<BoxError>,
+{
+    type Response = TrackedStream<C::Response>;
+    type Error = BoxError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, BoxError>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let started_at = Instant::now();
+        let is_https = uri.scheme() == Some(&Scheme::HTTPS);
+        let connect = self.inner.call(uri);
+        Box::pin(CONNECTING.scope(Cell::default(), async move {
+            let stream = connect.await.map_err(Into::into)?;
+            let opened_at = Instant::now();
+            let progress = CONNECTING.with(Cell::get);
+            let connected = stream.connected();
+            let mut extras = Extensions::new();
+            connected.get_extras(&mut extras);
+            let state = ConnectionState {
+                id: NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed),
+                remote_addr: extras.get::<HttpInfo>().map(HttpInfo::remote_addr),
+                is_proxied: connected.is_proxied(),
+                opened_at,
+                connect_duration: opened_at - started_at,
+                dns_duration: progress.dns_duration,
+                tls_handshake_duration: progress
+                    .transport_connected_at
+                    .filter(|_| is_https)
+                    .map(|at| opened_at - at),
+                usage: Mutex::new(Usage::default()),
+            };
+            Ok(TrackedStream {
+                inner: stream,
+                state: Arc::new(state),
+            })
+        }))
+    }
+}
+
+struct ConnectionState {
+    id: u64,
+    remote_addr: Option<SocketAddr>,
+    is_proxied: bool,
+    opened_at: Instant,
+    connect_duration: Duration,
+    dns_duration: Option<Duration>,
+    tls_handshake_duration: Option<Duration>,
+    usage: Mutex<Usage>,
+}
+
+#[derive(Default)]
+struct Usage {
+    request_count: u64,
+    released_at: Option<Instant>,
+    listeners: ConnectionEventListeners,
+}
+
+impl ConnectionState {
+    fn event(&self, kind: ConnectionEventKind, request_count: u64) -> ConnectionEvent {
+        let mut builder = ConnectionEvent::builder(kind, self.id)
+            .is_proxied(self.is_proxied)
+            .request_count(request_count)
+            .age(self.opened_at.elapsed());
+        builder.set_remote_addr(self.remote_addr);
+        builder.build()
+    }
+
+    fn emit(&self, kind: ConnectionEventKind) {
+        let usage = self.usage.lock().unwrap();
+        let event = self.event(kind, usage.request_count);
+        let listeners = usage.listeners.clone();
+        drop(usage);
+        listeners.emit(&event);
+    }
+}
+
+/// Stream of a connection opened by [`TrackConnections`]
+///
+/// The listeners of the last request sent over the connection are notified when it is closed.
+pub struct TrackedStream<T> {
+    inner: T,
+    state: Arc<ConnectionState>,
+}
+
+impl<T> fmt::Debug for TrackedStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackedStream")
+            .field("connection_id", &self.state.id)
+            .finish()
+    }
+}
+
+impl<T> Drop for TrackedStream<T> {
+    fn drop(&mut self) {
+        self.state.emit(ConnectionEventKind::Closed);
+    }
+}
+
+impl<T: Connection> Connection for TrackedStream<T> {
+    fn connected(&self) -> Connected {
+        self.inner.connected().extra(ConnectionHandle {
+            state: self.state.clone(),
+        })
+    }
+}
+
+impl<T: AsyncRead + Unpin> AsyncRead for TrackedStream<T> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_read(cx, buf)
+    }
+}
+
+impl<T: AsyncWrite + Unpin> AsyncWrite for TrackedStream<T> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write(cx, buf)
+    }
+
+    fn poll_write_vectored(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        bufs: &[IoSlice<'_>],
+    ) -> Poll<std::io::Result<usize>> {
+        Pin::new(&mut self.inner).poll_write_vectored(cx, bufs)
+    }
+
+    fn is_write_vectored(&self) -> bool {
+        self.inner.is_write_vectored()
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        Pin::new(&mut self.inner).poll_shutdown(cx)
+    }
+}
+
+/// Handle to a connection opened by [`TrackConnections`], found in the extras of its [`Connected`]
+#[derive(Clone)]
+struct ConnectionHandle {
+    state: Arc<ConnectionState>,
+}
+
+impl ConnectionHandle {
+    fn from_connected(connected: &Connected) -> Option<Self> {
+        let mut extras = Extensions::new();
+        connected.get_extras(&mut extras);
+        extras.remove::<ConnectionHandle>()
+    }
+
+    /// Records that a request was sent over the connection, and notifies its listeners
+    fn on_request(&self, listeners: ConnectionEventListeners) {
+        let mut usage = self.state.usage.lock().unwrap();
+        usage.request_count += 1;
+        let request_count = usage.request_count;
+        let released_at = usage.released_at.take();
+        usage.listeners = listeners.clone();
+        drop(usage);
+
+        let event = if request_count == 1 {
+            let mut builder = ConnectionEvent::builder(ConnectionEventKind::Opened, self.state.id)
+                .connect_duration(self.state.connect_duration);
+            builder
+                .set_dns_duration(self.state.dns_duration)
+                .set_tls_handshake_duration(self.state.tls_handshake_duration);
+            builder
+        } else {
+            let mut builder = ConnectionEvent::builder(ConnectionEventKind::Reused, self.state.id);
+            builder.set_idle_duration(released_at.map(|at| at.elapsed()));
+            builder
+        };
+        let mut builder = event
+            .is_proxied(self.state.is_proxied)
+            .request_count(request_count)
+            .age(self.state.opened_at.elapsed());
+        builder.set_remote_addr(self.state.remote_addr);
+        listeners.emit(&builder.build());
+    }
+
+    /// Records that the response was done, so that the connection went back to the pool
+    fn on_response_done(&self) {
+        self.state.usage.lock().unwrap().released_at = Some(Instant::now());
+    }
+}
+
+/// Notifies the listeners of the connection that `connected` describes that it was poisoned
+#[doc(hidden)]
+pub fn on_poisoned(connected: &Connected) {
+    if let Some(handle) = ConnectionHandle::from_connected(connected) {
+        handle.state.emit(ConnectionEventKind::Poisoned);
+    }
+}
+
+/// Notifies the listeners of a request about the connection that it was sent over, and converts
+/// the response body to an [`SdkBody`]
+///
+/// `capture_connection` captures the connection of the request. Requests without listeners aren't
+/// tracked beyond counting them.
+#[doc(hidden)]
+pub fn track_response(
+    capture_connection: &CaptureConnection,
+    listeners: Option<ConnectionEventListeners>,
+    response: http::Response<hyper::Body>,
+) -> http::Response<SdkBody> {
+    let handle = capture_connection
+        .connection_metadata()
+        .as_ref()
+        .and_then(ConnectionHandle::from_connected);
+    let handle = match handle {
+        Some(handle) => handle,
+        None => return response.map(SdkBody::from),
+    };
+    match listeners.filter(|listeners| !listeners.is_empty()) {
+        Some(listeners) => {
+            handle.on_request(listeners);
+            response.map(|body| {
+                SdkBody::from_dyn(http_body::combinators::BoxBody::new(ReleaseOnDone {
+                    inner: body,
+                    handle: Some(handle),
+                }))
+            })
+        }
+        None => {
+            handle.on_request(ConnectionEventListeners::new());
+            response.map(SdkBody::from)
+        }
+    }
+}
+
+/// Response body that records when the response is done
+struct ReleaseOnDone {
+    inner: hyper::Body,
+    handle: Option<ConnectionHandle>,
+}
+
+impl ReleaseOnDone {
+    fn release(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.on_response_done();
+        }
+    }
+}
+
+impl Drop for ReleaseOnDone {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl http_body::Body for ReleaseOnDone {
+    type Data = Bytes;
+    type Error = aws_smithy_http::body::Error;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let data = Pin::new(&mut self.inner).poll_data(cx);
+        if let Poll::Ready(None) = data {
+            if self.inner.is_end_stream() {
+                self.release();
+            }
+        }
+        data.map_err(Into::into)
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        let trailers = Pin::new(&mut self.inner).poll_trailers(cx);
+        if trailers.is_ready() {
+            self.release();
+        }
+        trailers.map_err(Into::into)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::client::connectors::hyper_connector::dns::{DnsAnswer, ResolveDns};
+    use crate::client::connectors::hyper_connector::happy_eyeballs::HappyEyeballsConnector;
+    use crate::client::connectors::hyper_connector::HyperConnector;
+    use aws_smithy_http::body::SdkBody;
+    use aws_smithy_http::connection::{
+        CaptureSmithyConnection, ConnectionEvent, ConnectionEventKind, ConnectionEventListener,
+        ConnectionEventListeners, SharedConnectionEventListener,
+    };
+    use aws_smithy_runtime_api::client::connectors::{Connector, ConnectorSettings};
+    use aws_smithy_runtime_api::client::orchestrator::BoxFuture;
+    use std::net::{Ipv4Addr, SocketAddr};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[derive(Clone, Debug, Default)]
+    struct RecordEvents(Arc<Mutex<Vec<ConnectionEvent>>>);
+
+    impl RecordEvents {
+        fn kinds(&self) -> Vec<(ConnectionEventKind, u64)> {
+            self.0
+                .lock()
+                .unwrap()
+                .iter()
+                .map(|event| (event.kind(), event.connection_id()))
+                .collect()
+        }
+
+        fn events(&self) -> Vec<ConnectionEvent> {
+            self.0.lock().unwrap().clone()
+        }
+
+        async fn wait_for(&self, count: usize) {
+            for _ in 0..100 {
+                if self.0.lock().unwrap().len() >= count {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+            panic!("expected {} events, got {:?}", count, self.kinds());
+        }
+    }
+
+    impl ConnectionEventListener for RecordEvents {
+        fn on_event(&self, event: &ConnectionEvent) {
+            self.0.lock().unwrap().push(event.clone());
+        }
+    }
+
+    /// Serves `200 OK` to every request over keep-alive connections until the client hangs up
+    async fn keep_alive_server() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0; 1024];
+                    loop {
+                        let read = stream.read(&mut buf).await.unwrap_or(0);
+                        if read == 0 {
+                            return;
+                        }
+                        request.extend_from_slice(&buf[..read]);
+                        if request.windows(4).any(|w| w == b"\r\n\r\n") {
+                            request.clear();
+                            stream
+                                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 2\r\n\r\nok")
+                                .await
+                                .unwrap();
+                        }
+                    }
+                });
+            }
+        });
+        addr
+    }
+
+    async fn send(connector: &HyperConnector, addr: SocketAddr, listeners: &RecordEvents) {
+        send_with(connector, addr, listeners, |_| {}).await
+    }
+
+    async fn send_with(
+        connector: &HyperConnector,
+        addr: SocketAddr,
+        listeners: &RecordEvents,
+        customize: impl FnOnce(&mut http::Request<SdkBody>),
+    ) {
+        let mut request = http::Request::builder()
+            .uri(format!("http://{}/", addr))
+            .body(SdkBody::empty())
+            .unwrap();
+        request.extensions_mut().insert(
+            std::iter::once(SharedConnectionEventListener::new(listeners.clone()))
+                .collect::<ConnectionEventListeners>(),
+        );
+        customize(&mut request);
+        let response = connector.call(request).await.expect("success");
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!("ok", body);
+    }
+
+    #[tokio::test]
+    async fn connections_are_opened_reused_and_closed() {
+        let addr = keep_alive_server().await;
+        let events = RecordEvents::default();
+        let connector = HyperConnector::builder().build(hyper::client::HttpConnector::new());
+
+        send(&connector, addr, &events).await;
+        send(&connector, addr, &events).await;
+        let recorded = events.events();
+        assert_eq!(2, recorded.len(), "{:?}", recorded);
+        let (opened, reused) = (&recorded[0], &recorded[1]);
+        assert_eq!(ConnectionEventKind::Opened, opened.kind());
+        assert_eq!(Some(addr), opened.remote_addr());
+        assert!(!opened.is_proxied());
+        assert_eq!(1, opened.request_count());
+        assert!(opened.connect_duration().is_some());
+        assert_eq!(None, opened.tls_handshake_duration());
+        assert_eq!(None, opened.idle_duration());
+
+        assert_eq!(ConnectionEventKind::Reused, reused.kind());
+        assert_eq!(opened.connection_id(), reused.connection_id());
+        assert_eq!(2, reused.request_count());
+        assert_eq!(None, reused.connect_duration());
+        assert!(reused.idle_duration().is_some());
+
+        // Dropping the connector drops its pool, which closes the idle connection
+        drop(connector);
+        events.wait_for(3).await;
+        let closed = &events.events()[2];
+        assert_eq!(ConnectionEventKind::Closed, closed.kind());
+        assert_eq!(opened.connection_id(), closed.connection_id());
+        assert_eq!(2, closed.request_count());
+    }
+
+    #[derive(Debug)]
+    struct Localhost;
+
+    impl ResolveDns for Localhost {
+        fn resolve_dns(&self, _name: &str) -> BoxFuture<DnsAnswer> {
+            Box::pin(async { Ok(DnsAnswer::new(vec![Ipv4Addr::LOCALHOST.into()])) })
+        }
+    }
+
+    #[tokio::test]
+    async fn dns_resolution_is_timed() {
+        let addr = keep_alive_server().await;
+        let events = RecordEvents::default();
+        let connector = HyperConnector::builder().build(
+            HappyEyeballsConnector::builder()
+                .dns_resolver(Localhost)
+                .build(),
+        );
+
+        let mut request = http::Request::builder()
+            .uri(format!("http://localhost.test:{}/", addr.port()))
+            .body(SdkBody::empty())
+            .unwrap();
+        request.extensions_mut().insert(
+            std::iter::once(SharedConnectionEventListener::new(events.clone()))
+                .collect::<ConnectionEventListeners>(),
+        );
+        connector.call(request).await.expect("success");
+        let opened = &events.events()[0];
+        assert_eq!(ConnectionEventKind::Opened, opened.kind());
+        assert_eq!(Some(addr), opened.remote_addr());
+        assert!(opened.dns_duration().is_some());
+        assert_eq!(None, opened.tls_handshake_duration());
+    }
+
+    #[tokio::test]
+    async fn pool_settings_are_applied() {
+        let addr = keep_alive_server().await;
+        let events = RecordEvents::default();
+        let connector = HyperConnector::builder()
+            .connector_settings(
+                ConnectorSettings::builder()
+                    .pool_max_idle_per_host(0)
+                    .build(),
+            )
+            .build(hyper::client::HttpConnector::new());
+
+        send(&connector, addr, &events).await;
+        send(&connector, addr, &events).await;
+        events.wait_for(4).await;
+        let kinds = events.kinds();
+        let opened: Vec<_> = kinds
+            .iter()
+            .filter(|(kind, _)| *kind == ConnectionEventKind::Opened)
+            .map(|(_, id)| *id)
+            .collect();
+        assert_eq!(2, opened.len(), "{:?}", kinds);
+        assert_ne!(opened[0], opened[1]);
+        assert!(!kinds
+            .iter()
+            .any(|(kind, _)| *kind == ConnectionEventKind::Reused));
+    }
+
+    #[tokio::test]
+    async fn poisoned_connections_are_reported() {
+        let addr = keep_alive_server().await;
+        let events = RecordEvents::default();
+        let connector = HyperConnector::builder().build(hyper::client::HttpConnector::new());
+
+        let capture = CaptureSmithyConnection::new();
+        send_with(&connector, addr, &events, |request| {
+            request.extensions_mut().insert(capture.clone());
+        })
+        .await;
+        capture.get().expect("connection captured").poison();
+        send(&connector, addr, &events).await;
+
+        let kinds = events.kinds();
+        let first = kinds[0].1;
+        assert_eq!(
+            vec![
+                (ConnectionEventKind::Opened, first),
+                (ConnectionEventKind::Poisoned, first)
+            ],
+            kinds[..2]
+        );
+        assert!(
+            kinds[2..]
+                .iter()
+                .any(|(kind, id)| *kind == ConnectionEventKind::Opened && *id != first),
+            "{:?}",
+            kinds
+        );
+    }
+}
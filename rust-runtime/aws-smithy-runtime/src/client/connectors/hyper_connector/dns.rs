@@ -0,0 +1,447 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Pluggable DNS resolution for hyper connectors
+//!
+//! Implement [`ResolveDns`] to control how host names are resolved, and plug the resolver into a
+//! [`HyperConnector`](super::HyperConnector) or a
+//! [`HappyEyeballsConnector`](super::happy_eyeballs::HappyEyeballsConnector). By default, names are
+//! resolved by the operating system with [`SystemDnsResolver`]. Wrap a resolver in a
+//! [`CachingDnsResolver`] to cache its answers for as long as their TTL allows.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use aws_smithy_runtime::client::connectors::hyper_connector::dns::{CachingDnsResolver, SystemDnsResolver};
+//! use aws_smithy_runtime::client::connectors::hyper_connector::HyperConnector;
+//! use std::time::Duration;
+//!
+//! let resolver = CachingDnsResolver::builder()
+//!     .default_ttl(Duration::from_secs(10))
+//!     .build(SystemDnsResolver::new());
+//! let connector = HyperConnector::builder()
+//!     .dns_resolver(resolver)
+//!     .build_http();
+//! ```
+
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::orchestrator::BoxFuture;
+use hyper::client::connect::dns::{GaiResolver, Name};
+use hyper::service::Service;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
+
+/// Resolves host names to IP addresses
+pub trait ResolveDns: Send + Sync + Debug {
+    /// Resolve `name` to the IP addresses of its hosts, in order of preference
+    fn resolve_dns(&self, name: &str) -> BoxFuture<DnsAnswer>;
+}
+
+/// IP addresses that a host name resolved to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DnsAnswer {
+    addrs: Vec<IpAddr>,
+    ttl: Option<Duration>,
+}
+
+impl DnsAnswer {
+    /// Creates an answer with `addrs`, in order of preference, and no TTL.
+    pub fn new(addrs: Vec<IpAddr>) -> Self {
+        Self { addrs, ttl: None }
+    }
+
+    /// Sets how long the answer may be cached.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Returns the IP addresses, in order of preference.
+    pub fn addrs(&self) -> &[IpAddr] {
+        &self.addrs
+    }
+
+    /// Returns how long the answer may be cached, if known.
+    pub fn ttl(&self) -> Option<Duration> {
+        self.ttl
+    }
+}
+
+/// [`ResolveDns`] implementation that can be shared and cloned
+///
+/// It also implements hyper's resolver interface, so it can be plugged into a
+/// [`hyper::client::HttpConnector`] with `HttpConnector::new_with_resolver`.
+#[derive(Clone, Debug)]
+pub struct SharedDnsResolver(Arc<dyn ResolveDns>);
+
+impl SharedDnsResolver {
+    /// Creates a new [`SharedDnsResolver`].
+    pub fn new(resolver: impl ResolveDns + 'static) -> Self {
+        Self(Arc::new(resolver))
+    }
+}
+
+impl Default for SharedDnsResolver {
+    fn default() -> Self {
+        Self::new(SystemDnsResolver::new())
+    }
+}
+
+impl ResolveDns for SharedDnsResolver {
+    fn resolve_dns(&self, name: &str) -> BoxFuture<DnsAnswer> {
+        self.0.resolve_dns(name)
+    }
+}
+
+impl Service<Name> for SharedDnsResolver {
+    type Response = std::vec::IntoIter<SocketAddr>;
+    type Error = BoxError;
+    type Future = BoxFuture<Self::Response>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, name: Name) -> Self::Future {
+        let answer = self.resolve_dns(name.as_str());
+        Box::pin(async move {
+            // hyper sets the port of the addresses
+            let addrs: Vec<_> = answer
+                .await?
+                .addrs
+                .into_iter()
+                .map(|addr| SocketAddr::new(addr, 0))
+                .collect();
+            Ok(addrs.into_iter())
+        })
+    }
+}
+
+/// Resolves names with the operating system's resolver, i.e. `getaddrinfo`
+///
+/// Lookups run on a blocking thread. The operating system doesn't report TTLs, so the answers
+/// have none.
+#[derive(Clone, Debug, Default)]
+pub struct SystemDnsResolver {
+    _private: (),
+}
+
+impl SystemDnsResolver {
+    /// Creates a new [`SystemDnsResolver`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ResolveDns for SystemDnsResolver {
+    fn resolve_dns(&self, name: &str) -> BoxFuture<DnsAnswer> {
+        let name = match Name::from_str(name) {
+            Ok(name) => name,
+            Err(err) => return Box::pin(async move { Err(err.into()) }),
+        };
+        let lookup = GaiResolver::new().call(name);
+        Box::pin(async move {
+            let addrs = lookup.await?.map(|addr| addr.ip()).collect();
+            Ok(DnsAnswer::new(addrs))
+        })
+    }
+}
+
+/// Caches the answers of another resolver for as long as their TTL allows
+///
+/// Answers without a TTL are cached for the [default TTL](CachingDnsResolverBuilder::default_ttl).
+/// Failed lookups aren't cached.
+#[derive(Clone, Debug)]
+pub struct CachingDnsResolver {
+    inner: SharedDnsResolver,
+    default_ttl: Duration,
+    max_ttl: Duration,
+    time_source: SharedTimeSource,
+    cache: Arc<Mutex<HashMap<String, CachedAnswer>>>,
+}
+
+#[derive(Debug)]
+struct CachedAnswer {
+    answer: DnsAnswer,
+    expires_at: SystemTime,
+}
+
+const DEFAULT_TTL: Duration = Duration::from_secs(30);
+const DEFAULT_MAX_TTL: Duration = Duration::from_secs(300);
+
+impl CachingDnsResolver {
+    /// Returns a builder for a [`CachingDnsResolver`].
+    pub fn builder() -> CachingDnsResolverBuilder {
+        CachingDnsResolverBuilder::default()
+    }
+
+    /// Caches the answers of `inner` with the default settings.
+    pub fn new(inner: impl ResolveDns + 'static) -> Self {
+        Self::builder().build(inner)
+    }
+
+    fn cached(&self, name: &str) -> Option<DnsAnswer> {
+        let now = self.time_source.now();
+        let mut cache = self.cache.lock().unwrap();
+        match cache.get(name) {
+            Some(cached) if cached.expires_at > now => Some(cached.answer.clone()),
+            Some(_) => {
+                cache.remove(name);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn store(&self, name: String, answer: &DnsAnswer) {
+        let ttl = answer.ttl.unwrap_or(self.default_ttl).min(self.max_ttl);
+        if ttl.is_zero() {
+            return;
+        }
+        let now = self.time_source.now();
+        let mut cache = self.cache.lock().unwrap();
+        cache.retain(|_, cached| cached.expires_at > now);
+        cache.insert(
+            name,
+            CachedAnswer {
+                answer: answer.clone(),
+                expires_at: now + ttl,
+            },
+        );
+    }
+}
+
+impl ResolveDns for CachingDnsResolver {
+    fn resolve_dns(&self, name: &str) -> BoxFuture<DnsAnswer> {
+        if let Some(answer) = self.cached(name) {
+            tracing::trace!(name = %name, answer = ?answer, "using the cached DNS answer");
+            return Box::pin(async move { Ok(answer) });
+        }
+        let this = self.clone();
+        let name = name.to_owned();
+        let lookup = self.inner.resolve_dns(&name);
+        Box::pin(async move {
+            let answer = lookup.await?;
+            this.store(name, &answer);
+            Ok(answer)
+        })
+    }
+}
+
+/// Builder for [`CachingDnsResolver`]
+#[derive(Debug, Default)]
+pub struct CachingDnsResolverBuilder {
+    default_ttl: Option<Duration>,
+    max_ttl: Option<Duration>,
+    time_source: Option<SharedTimeSource>,
+}
+
+impl CachingDnsResolverBuilder {
+    /// How long to cache answers that don't have a TTL, like the ones of [`SystemDnsResolver`]
+    ///
+    /// Defaults to 30 seconds. A zero duration disables caching of these answers.
+    pub fn default_ttl(mut self, default_ttl: Duration) -> Self {
+        self.default_ttl = Some(default_ttl);
+        self
+    }
+
+    /// How long to cache answers that don't have a TTL, like the ones of [`SystemDnsResolver`]
+    ///
+    /// Defaults to 30 seconds. A zero duration disables caching of these answers.
+    pub fn set_default_ttl(&mut self, default_ttl: Option<Duration>) -> &mut Self {
+        self.default_ttl = default_ttl;
+        self
+    }
+
+    /// The longest time to cache any answer for, regardless of its TTL
+    ///
+    /// Defaults to 5 minutes.
+    pub fn max_ttl(mut self, max_ttl: Duration) -> Self {
+        self.max_ttl = Some(max_ttl);
+        self
+    }
+
+    /// The longest time to cache any answer for, regardless of its TTL
+    ///
+    /// Defaults to 5 minutes.
+    pub fn set_max_ttl(&mut self, max_ttl: Option<Duration>) -> &mut Self {
+        self.max_ttl = max_ttl;
+        self
+    }
+
+    /// Set the time source used to expire answers
+    ///
+    /// Calling this is only necessary for testing.
+    pub fn time_source(mut self, time_source: SharedTimeSource) -> Self {
+        self.time_source = Some(time_source);
+        self
+    }
+
+    /// Set the time source used to expire answers
+    ///
+    /// Calling this is only necessary for testing.
+    pub fn set_time_source(&mut self, time_source: Option<SharedTimeSource>) -> &mut Self {
+        self.time_source = time_source;
+        self
+    }
+
+    /// Creates a [`CachingDnsResolver`] that caches the answers of `inner`.
+    pub fn build(self, inner: impl ResolveDns + 'static) -> CachingDnsResolver {
+        CachingDnsResolver {
+            inner: SharedDnsResolver::new(inner),
+            default_ttl: self.default_ttl.unwrap_or(DEFAULT_TTL),
+            max_ttl: self.max_ttl.unwrap_or(DEFAULT_MAX_TTL),
+            time_source: self.time_source.unwrap_or_default(),
+            cache: Default::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CachingDnsResolver, DnsAnswer, ResolveDns, SharedDnsResolver};
+    use aws_smithy_async::time::{SharedTimeSource, TimeSource};
+    use aws_smithy_runtime_api::client::orchestrator::BoxFuture;
+    use hyper::client::connect::dns::Name;
+    use hyper::service::Service;
+    use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+    use std::str::FromStr;
+    use std::sync::{Arc, Mutex};
+    use std::time::{Duration, SystemTime};
+
+    #[derive(Clone, Debug, Default)]
+    struct CountingResolver {
+        lookups: Arc<Mutex<Vec<String>>>,
+        ttl: Option<Duration>,
+    }
+
+    impl CountingResolver {
+        fn lookups(&self) -> Vec<String> {
+            self.lookups.lock().unwrap().clone()
+        }
+    }
+
+    impl ResolveDns for CountingResolver {
+        fn resolve_dns(&self, name: &str) -> BoxFuture<DnsAnswer> {
+            let mut lookups = self.lookups.lock().unwrap();
+            lookups.push(name.to_owned());
+            let mut answer = DnsAnswer::new(vec![IpAddr::V4(Ipv4Addr::new(
+                127,
+                0,
+                0,
+                lookups.len() as u8,
+            ))]);
+            if let Some(ttl) = self.ttl {
+                answer = answer.with_ttl(ttl);
+            }
+            Box::pin(async move { Ok(answer) })
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    struct TestTime(Arc<Mutex<SystemTime>>);
+
+    impl TestTime {
+        fn new() -> Self {
+            Self(Arc::new(Mutex::new(SystemTime::UNIX_EPOCH)))
+        }
+
+        fn advance(&self, duration: Duration) {
+            *self.0.lock().unwrap() += duration;
+        }
+    }
+
+    impl TimeSource for TestTime {
+        fn now(&self) -> SystemTime {
+            *self.0.lock().unwrap()
+        }
+    }
+
+    async fn resolve(resolver: &impl ResolveDns, name: &str) -> String {
+        resolver.resolve_dns(name).await.unwrap().addrs()[0].to_string()
+    }
+
+    #[tokio::test]
+    async fn answers_are_cached_for_their_ttl() {
+        let inner = CountingResolver {
+            ttl: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        let time = TestTime::new();
+        let resolver = CachingDnsResolver::builder()
+            .time_source(SharedTimeSource::new(time.clone()))
+            .build(inner.clone());
+
+        assert_eq!("127.0.0.1", resolve(&resolver, "example.com").await);
+        assert_eq!("127.0.0.2", resolve(&resolver, "amazon.com").await);
+        time.advance(Duration::from_secs(59));
+        assert_eq!("127.0.0.1", resolve(&resolver, "example.com").await);
+        time.advance(Duration::from_secs(1));
+        assert_eq!("127.0.0.3", resolve(&resolver, "example.com").await);
+        assert_eq!(
+            vec!["example.com", "amazon.com", "example.com"],
+            inner.lookups()
+        );
+    }
+
+    #[tokio::test]
+    async fn default_and_max_ttl() {
+        let time = TestTime::new();
+        let inner = CountingResolver::default();
+        let resolver = CachingDnsResolver::builder()
+            .default_ttl(Duration::from_secs(10))
+            .time_source(SharedTimeSource::new(time.clone()))
+            .build(inner.clone());
+        resolve(&resolver, "example.com").await;
+        time.advance(Duration::from_secs(9));
+        resolve(&resolver, "example.com").await;
+        assert_eq!(1, inner.lookups().len());
+        time.advance(Duration::from_secs(1));
+        resolve(&resolver, "example.com").await;
+        assert_eq!(2, inner.lookups().len());
+
+        let inner = CountingResolver {
+            ttl: Some(Duration::from_secs(3600)),
+            ..Default::default()
+        };
+        let resolver = CachingDnsResolver::builder()
+            .max_ttl(Duration::from_secs(60))
+            .time_source(SharedTimeSource::new(time.clone()))
+            .build(inner.clone());
+        resolve(&resolver, "example.com").await;
+        time.advance(Duration::from_secs(60));
+        resolve(&resolver, "example.com").await;
+        assert_eq!(2, inner.lookups().len());
+    }
+
+    #[tokio::test]
+    async fn zero_ttl_disables_caching() {
+        let inner = CountingResolver {
+            ttl: Some(Duration::ZERO),
+            ..Default::default()
+        };
+        let resolver = CachingDnsResolver::new(inner.clone());
+        resolve(&resolver, "example.com").await;
+        resolve(&resolver, "example.com").await;
+        assert_eq!(2, inner.lookups().len());
+    }
+
+    #[tokio::test]
+    async fn shared_resolvers_can_be_used_by_hyper() {
+        let mut resolver = SharedDnsResolver::new(CountingResolver::default());
+        let addrs: Vec<_> = resolver
+            .call(Name::from_str("example.com").unwrap())
+            .await
+            .unwrap()
+            .collect();
+        assert_eq!(vec![SocketAddr::from(([127, 0, 0, 1], 0))], addrs);
+    }
+}
@@ -0,0 +1,449 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! TCP connector that races connections to dual-stack hosts ("Happy Eyeballs")
+//!
+//! [`HappyEyeballsConnector`] resolves host names with a pluggable [resolver](super::dns), then
+//! connects as described in [RFC 8305](https://www.rfc-editor.org/rfc/rfc8305):
+//! - the addresses are interleaved by family, starting with the family of the most preferred
+//!   address, so that IPv6 and IPv4 addresses alternate (section 4);
+//! - a connection attempt is started every [connection attempt delay](HappyEyeballsConnectorBuilder::connection_attempt_delay),
+//!   or as soon as the previous attempt fails, without cancelling the attempts in flight (section 5);
+//! - the first connection to be established wins, and the other attempts are cancelled.
+//!
+//! This way, a host with a broken IPv6 route is reached over IPv4 after a short delay, rather
+//! than after the IPv6 connection attempt times out.
+
+use super::dns::{ResolveDns, SharedDnsResolver};
+use aws_smithy_async::rt::sleep::{default_async_sleep, AsyncSleep, SharedAsyncSleep, Sleep};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::orchestrator::BoxFuture;
+use http::uri::Scheme;
+use http::Uri;
+use hyper::client::HttpConnector;
+use hyper::service::Service;
+use std::future::Future;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::net::TcpStream;
+
+const DEFAULT_CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+// RFC 8305 section 5: "Connection Attempt Delay" must not be lower than 10 milliseconds
+const MIN_CONNECTION_ATTEMPT_DELAY: Duration = Duration::from_millis(10);
+
+/// Hyper connector that resolves names with a [`ResolveDns`] and connects to dual-stack hosts
+/// with Happy Eyeballs
+///
+/// It makes plain TCP connections, so wrap it in a TLS connector for HTTPS. See the
+/// [module documentation](self) for details.
+#[derive(Clone, Debug)]
+pub struct HappyEyeballsConnector {
+    resolver: SharedDnsResolver,
+    http: HttpConnector,
+    connection_attempt_delay: Duration,
+    sleep_impl: Option<SharedAsyncSleep>,
+}
+
+impl HappyEyeballsConnector {
+    /// Returns a builder for a [`HappyEyeballsConnector`].
+    pub fn builder() -> HappyEyeballsConnectorBuilder {
+        HappyEyeballsConnectorBuilder::default()
+    }
+
+    /// Creates a connector that resolves names with the operating system's resolver.
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl Default for HappyEyeballsConnector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Service<Uri> for HappyEyeballsConnector {
+    type Response = TcpStream;
+    type Error = BoxError;
+    type Future = BoxFuture<Self::Response>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let this = self.clone();
+        Box::pin(async move {
+            let host = uri
+                .host()
+                .ok_or_else(|| format!("URI `{}` has no host", uri))?
+                .trim_start_matches('[')
+                .trim_end_matches(']');
+            let port = uri
+                .port_u16()
+                .unwrap_or(if uri.scheme() == Some(&Scheme::HTTPS) {
+                    443
+                } else {
+                    80
+                });
+            let addrs = match host.parse::<IpAddr>() {
+                Ok(addr) => vec![addr],
+                Err(_) => {
+                    let started_at = Instant::now();
+                    let answer = this.resolver.resolve_dns(host).await?;
+                    super::connection_events::record_dns_duration(started_at.elapsed());
+                    tracing::trace!(host = %host, answer = ?answer, "resolved DNS name");
+                    answer.addrs().to_vec()
+                }
+            };
+            if addrs.is_empty() {
+                return Err(format!("no addresses were found for `{}`", host).into());
+            }
+            let addrs = interleave_families(addrs)
+                .into_iter()
+                .map(|addr| SocketAddr::new(addr, port));
+            let http = this.http;
+            race(
+                addrs,
+                this.connection_attempt_delay,
+                this.sleep_impl,
+                move |addr| {
+                    let mut http = http.clone();
+                    let uri = Uri::builder()
+                        .scheme(Scheme::HTTP)
+                        .authority(addr.to_string())
+                        .path_and_query("/")
+                        .build()
+                        .expect("valid URI");
+                    Box::pin(async move { http.call(uri).await.map_err(Into::into) })
+                },
+            )
+            .await
+        })
+    }
+}
+
+/// Orders addresses so that their families alternate, starting with the family of the first one
+///
+/// The order of the addresses within a family is kept.
+fn interleave_families(addrs: Vec<IpAddr>) -> Vec<IpAddr> {
+    let first_is_ipv6 = matches!(addrs.first(), Some(IpAddr::V6(_)));
+    let (preferred, fallback): (Vec<_>, Vec<_>) = addrs
+        .into_iter()
+        .partition(|addr| addr.is_ipv6() == first_is_ipv6);
+    let mut interleaved = Vec::with_capacity(preferred.len() + fallback.len());
+    let (mut preferred, mut fallback) = (preferred.into_iter(), fallback.into_iter());
+    loop {
+        match (preferred.next(), fallback.next()) {
+            (None, None) => return interleaved,
+            (first, second) => interleaved.extend(first.into_iter().chain(second)),
+        }
+    }
+}
+
+/// Connects to `addrs` in order, starting an attempt every `delay` or when the previous attempt
+/// fails, and returns the first connection that is established
+///
+/// Without a sleep implementation, attempts are only started when the previous one fails.
+async fn race<T, C>(
+    addrs: impl Iterator<Item = SocketAddr>,
+    delay: Duration,
+    sleep_impl: Option<SharedAsyncSleep>,
+    connect: C,
+) -> Result<T, BoxError>
+where
+    C: Fn(SocketAddr) -> BoxFuture<T>,
+{
+    let mut addrs = addrs.peekable();
+    let mut attempts: Vec<(SocketAddr, BoxFuture<T>)> = Vec::new();
+    let mut next_attempt: Option<Sleep> = None;
+    let mut last_error: Option<BoxError> = None;
+    std::future::poll_fn(move |cx| loop {
+        let mut failed = false;
+        let mut index = 0;
+        while index < attempts.len() {
+            match attempts[index].1.as_mut().poll(cx) {
+                Poll::Ready(Ok(stream)) => {
+                    tracing::debug!(addr = %attempts[index].0, "connected");
+                    return Poll::Ready(Ok(stream));
+                }
+                Poll::Ready(Err(err)) => {
+                    let (addr, _) = attempts.remove(index);
+                    tracing::debug!(addr = %addr, err = %err, "connection attempt failed");
+                    last_error = Some(err);
+                    failed = true;
+                }
+                Poll::Pending => index += 1,
+            }
+        }
+        let delay_elapsed = match next_attempt.as_mut() {
+            Some(sleep) => Pin::new(sleep).poll(cx).is_ready(),
+            None => false,
+        };
+        if failed || delay_elapsed || attempts.is_empty() {
+            match addrs.next() {
+                Some(addr) => {
+                    tracing::debug!(addr = %addr, "starting a connection attempt");
+                    attempts.push((addr, connect(addr)));
+                    next_attempt = match (&sleep_impl, addrs.peek()) {
+                        (Some(sleep_impl), Some(_)) => Some(sleep_impl.sleep(delay)),
+                        _ => None,
+                    };
+                    continue;
+                }
+                None if attempts.is_empty() => {
+                    return Poll::Ready(Err(last_error
+                        .take()
+                        .unwrap_or_else(|| "no addresses to connect to".into())))
+                }
+                None => next_attempt = None,
+            }
+        }
+        return Poll::Pending;
+    })
+    .await
+}
+
+/// Builder for [`HappyEyeballsConnector`]
+#[derive(Debug, Default)]
+pub struct HappyEyeballsConnectorBuilder {
+    resolver: Option<SharedDnsResolver>,
+    connection_attempt_delay: Option<Duration>,
+    sleep_impl: Option<SharedAsyncSleep>,
+}
+
+impl HappyEyeballsConnectorBuilder {
+    /// Resolve names with `resolver` instead of the operating system's resolver
+    pub fn dns_resolver(mut self, resolver: impl ResolveDns + 'static) -> Self {
+        self.resolver = Some(SharedDnsResolver::new(resolver));
+        self
+    }
+
+    /// Resolve names with `resolver` instead of the operating system's resolver
+    pub fn set_dns_resolver(&mut self, resolver: Option<SharedDnsResolver>) -> &mut Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// How long to wait for a connection attempt before starting the next one in parallel
+    ///
+    /// Defaults to 250 milliseconds, as recommended by RFC 8305. Delays below 10 milliseconds are
+    /// raised to 10 milliseconds.
+    pub fn connection_attempt_delay(mut self, delay: Duration) -> Self {
+        self.connection_attempt_delay = Some(delay);
+        self
+    }
+
+    /// How long to wait for a connection attempt before starting the next one in parallel
+    ///
+    /// Defaults to 250 milliseconds, as recommended by RFC 8305. Delays below 10 milliseconds are
+    /// raised to 10 milliseconds.
+    pub fn set_connection_attempt_delay(&mut self, delay: Option<Duration>) -> &mut Self {
+        self.connection_attempt_delay = delay;
+        self
+    }
+
+    /// Set the async sleep implementation used to delay connection attempts
+    ///
+    /// Calling this is only necessary for testing or to use something other than
+    /// [`default_async_sleep`].
+    pub fn sleep_impl(mut self, sleep_impl: SharedAsyncSleep) -> Self {
+        self.sleep_impl = Some(sleep_impl);
+        self
+    }
+
+    /// Set the async sleep implementation used to delay connection attempts
+    ///
+    /// Calling this is only necessary for testing or to use something other than
+    /// [`default_async_sleep`].
+    pub fn set_sleep_impl(&mut self, sleep_impl: Option<SharedAsyncSleep>) -> &mut Self {
+        self.sleep_impl = sleep_impl;
+        self
+    }
+
+    /// Creates a [`HappyEyeballsConnector`].
+    pub fn build(self) -> HappyEyeballsConnector {
+        let mut http = HttpConnector::new();
+        // Each attempt connects to a single address, and the TLS connector takes care of the scheme
+        http.set_happy_eyeballs_timeout(None);
+        http.enforce_http(false);
+        HappyEyeballsConnector {
+            resolver: self.resolver.unwrap_or_default(),
+            http,
+            connection_attempt_delay: self
+                .connection_attempt_delay
+                .unwrap_or(DEFAULT_CONNECTION_ATTEMPT_DELAY)
+                .max(MIN_CONNECTION_ATTEMPT_DELAY),
+            sleep_impl: self.sleep_impl.or_else(default_async_sleep),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{interleave_families, race, HappyEyeballsConnector};
+    use crate::client::connectors::hyper_connector::dns::{DnsAnswer, ResolveDns};
+    use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
+    use aws_smithy_runtime_api::client::orchestrator::BoxFuture;
+    use hyper::service::Service;
+    use std::net::{IpAddr, SocketAddr};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::net::TcpListener;
+
+    fn ips(addrs: &[&str]) -> Vec<IpAddr> {
+        addrs.iter().map(|addr| addr.parse().unwrap()).collect()
+    }
+
+    fn socket_addrs(addrs: &[&str]) -> impl Iterator<Item = SocketAddr> {
+        ips(addrs)
+            .into_iter()
+            .map(|addr| SocketAddr::new(addr, 443))
+    }
+
+    #[test]
+    fn families_are_interleaved() {
+        assert_eq!(
+            ips(&["::1", "10.0.0.1", "::2", "10.0.0.2", "10.0.0.3"]),
+            interleave_families(ips(&["::1", "::2", "10.0.0.1", "10.0.0.2", "10.0.0.3"]))
+        );
+        assert_eq!(
+            ips(&["10.0.0.1", "::1", "10.0.0.2"]),
+            interleave_families(ips(&["10.0.0.1", "10.0.0.2", "::1"]))
+        );
+        assert_eq!(
+            ips(&["::1", "::2"]),
+            interleave_families(ips(&["::1", "::2"]))
+        );
+        assert_eq!(Vec::<IpAddr>::new(), interleave_families(vec![]));
+    }
+
+    /// Connection attempts that hang for `::1`, fail for `::2`, and otherwise succeed after 100ms
+    fn connect(
+        started: Arc<Mutex<Vec<(SocketAddr, tokio::time::Instant)>>>,
+    ) -> impl Fn(SocketAddr) -> BoxFuture<SocketAddr> {
+        move |addr| {
+            started
+                .lock()
+                .unwrap()
+                .push((addr, tokio::time::Instant::now()));
+            Box::pin(async move {
+                match addr.ip().to_string().as_str() {
+                    "::1" => std::future::pending().await,
+                    "::2" => Err("connection refused".into()),
+                    _ => {
+                        tokio::time::sleep(Duration::from_millis(100)).await;
+                        Ok(addr)
+                    }
+                }
+            })
+        }
+    }
+
+    fn sleep_impl() -> Option<SharedAsyncSleep> {
+        Some(SharedAsyncSleep::new(TokioSleep::new()))
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stalled_attempts_fall_back_after_the_delay() {
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let start = tokio::time::Instant::now();
+        let connected = race(
+            socket_addrs(&["::1", "10.0.0.1"]),
+            Duration::from_millis(250),
+            sleep_impl(),
+            connect(started.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!("10.0.0.1:443", connected.to_string());
+        let started = started.lock().unwrap();
+        assert_eq!(2, started.len());
+        assert_eq!(Duration::from_millis(250), started[1].1 - start);
+        assert_eq!(Duration::from_millis(350), start.elapsed());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn failed_attempts_start_the_next_one_immediately() {
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let start = tokio::time::Instant::now();
+        let connected = race(
+            socket_addrs(&["::2", "10.0.0.1"]),
+            Duration::from_millis(250),
+            sleep_impl(),
+            connect(started.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!("10.0.0.1:443", connected.to_string());
+        assert_eq!(Duration::from_millis(100), start.elapsed());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn the_last_error_is_returned_when_all_attempts_fail() {
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let err = race(
+            socket_addrs(&["::2", "::2"]),
+            Duration::from_millis(250),
+            sleep_impl(),
+            connect(started.clone()),
+        )
+        .await
+        .expect_err("all attempts fail");
+        assert_eq!("connection refused", err.to_string());
+        assert_eq!(2, started.lock().unwrap().len());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn attempts_are_sequential_without_a_sleep_impl() {
+        let started = Arc::new(Mutex::new(Vec::new()));
+        let result = tokio::time::timeout(
+            Duration::from_secs(10),
+            race(
+                socket_addrs(&["::1", "10.0.0.1"]),
+                Duration::from_millis(250),
+                None,
+                connect(started.clone()),
+            ),
+        )
+        .await;
+        assert!(result.is_err(), "the stalled attempt blocks the next one");
+        assert_eq!(1, started.lock().unwrap().len());
+    }
+
+    #[derive(Debug)]
+    struct StaticResolver(Vec<IpAddr>);
+
+    impl ResolveDns for StaticResolver {
+        fn resolve_dns(&self, _name: &str) -> BoxFuture<DnsAnswer> {
+            let answer = DnsAnswer::new(self.0.clone());
+            Box::pin(async move { Ok(answer) })
+        }
+    }
+
+    #[tokio::test]
+    async fn connects_to_resolved_addresses() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mut connector = HappyEyeballsConnector::builder()
+            .dns_resolver(StaticResolver(ips(&["127.0.0.1"])))
+            .build();
+        let stream = connector
+            .call(format!("http://example.com:{}", port).parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(listener.local_addr().unwrap(), stream.peer_addr().unwrap());
+
+        let err = HappyEyeballsConnector::builder()
+            .dns_resolver(StaticResolver(vec![]))
+            .build()
+            .call("https://example.com".parse().unwrap())
+            .await
+            .expect_err("no addresses");
+        assert_eq!("no addresses were found for `example.com`", err.to_string());
+    }
+}
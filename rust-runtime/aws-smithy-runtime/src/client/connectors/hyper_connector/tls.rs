@@ -0,0 +1,619 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! TLS options for the `rustls` connectors
+//!
+//! By default, connectors trust the native root certificates of the platform. [`TlsOptions`] can
+//! add private certificate authorities, present a client certificate for mutual TLS, and pin the
+//! certificates that servers must present.
+//!
+//! # Examples
+//!
+//! ```no_run
+//! use aws_smithy_runtime::client::connectors::hyper_connector::tls::TlsOptions;
+//! use aws_smithy_runtime::client::connectors::hyper_connector::HyperConnector;
+//!
+//! # fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let tls_options = TlsOptions::builder()
+//!     .ca_bundle(std::fs::read("/etc/pki/private-ca.pem")?)
+//!     .client_certificate(
+//!         std::fs::read("/etc/pki/client.pem")?,
+//!         std::fs::read("/etc/pki/client-key.pem")?,
+//!     )
+//!     .build()?;
+//! let connector = HyperConnector::builder()
+//!     .tls_options(tls_options)
+//!     .build_https();
+//! # Ok(())
+//! # }
+//! ```
+
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use once_cell::sync::Lazy;
+use rustls::client::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier, WebPkiVerifier,
+};
+use rustls::{
+    Certificate, CertificateError, ClientConfig, ConfigBuilder, DigitallySignedStruct, PrivateKey,
+    RootCertStore, ServerName, SignatureScheme, WantsVerifier,
+};
+use sha2::{Digest, Sha256};
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// Returns a builder for client configurations with the cipher suites and protocol versions
+/// that the connectors support, which require TLS 1.2 or later
+fn config_builder() -> ConfigBuilder<ClientConfig, WantsVerifier> {
+    ClientConfig::builder()
+        .with_cipher_suites(&[
+            // TLS1.3 suites
+            rustls::cipher_suite::TLS13_AES_256_GCM_SHA384,
+            rustls::cipher_suite::TLS13_AES_128_GCM_SHA256,
+            // TLS1.2 suites
+            rustls::cipher_suite::TLS_ECDHE_ECDSA_WITH_AES_256_GCM_SHA384,
+            rustls::cipher_suite::TLS_ECDHE_ECDSA_WITH_AES_128_GCM_SHA256,
+            rustls::cipher_suite::TLS_ECDHE_RSA_WITH_AES_256_GCM_SHA384,
+            rustls::cipher_suite::TLS_ECDHE_RSA_WITH_AES_128_GCM_SHA256,
+            rustls::cipher_suite::TLS_ECDHE_RSA_WITH_CHACHA20_POLY1305_SHA256,
+        ])
+        .with_safe_default_kx_groups()
+        .with_safe_default_protocol_versions()
+        .expect("Error with the TLS configuration. Please file a bug report under https://github.com/awslabs/smithy-rs/issues.")
+}
+
+// Loading the native root certificates takes hundreds of milliseconds on some platforms, so this
+// is only done once.
+static NATIVE_ROOTS_CLIENT_CONFIG: Lazy<ClientConfig> = Lazy::new(|| {
+    use hyper_rustls::ConfigBuilderExt;
+    config_builder().with_native_roots().with_no_client_auth()
+});
+
+/// Returns the client configuration of connectors without [`TlsOptions`], which trusts the
+/// native root certificates and requires TLS 1.2 or later
+pub fn native_roots_client_config() -> &'static ClientConfig {
+    &NATIVE_ROOTS_CLIENT_CONFIG
+}
+
+/// TLS configuration of a `rustls` connector
+///
+/// Create one with [`TlsOptions::builder`], and a connector that uses it with
+/// [`HyperConnectorBuilder::tls_options`](super::HyperConnectorBuilder::tls_options).
+#[derive(Clone)]
+pub struct TlsOptions {
+    config: Arc<ClientConfig>,
+    summary: Summary,
+}
+
+impl Storable for TlsOptions {
+    type Storer = StoreReplace<Self>;
+}
+
+#[derive(Clone, Debug)]
+struct Summary {
+    native_roots: bool,
+    ca_certificates: usize,
+    client_certificate: bool,
+    pinned_certificates: usize,
+}
+
+impl fmt::Debug for TlsOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TlsOptions")
+            .field("native_roots", &self.summary.native_roots)
+            .field("ca_certificates", &self.summary.ca_certificates)
+            .field("client_certificate", &self.summary.client_certificate)
+            .field("pinned_certificates", &self.summary.pinned_certificates)
+            .finish()
+    }
+}
+
+impl TlsOptions {
+    /// Returns a builder for `TlsOptions`.
+    pub fn builder() -> TlsOptionsBuilder {
+        TlsOptionsBuilder::new()
+    }
+
+    /// Returns the `rustls` client configuration.
+    pub fn client_config(&self) -> &ClientConfig {
+        &self.config
+    }
+}
+
+/// Builder for [`TlsOptions`]
+#[derive(Clone, Debug)]
+pub struct TlsOptionsBuilder {
+    native_roots: bool,
+    ca_bundles: Vec<Vec<u8>>,
+    client_certificate: Option<(Vec<u8>, Vec<u8>)>,
+    pinned_certificates: Vec<String>,
+}
+
+impl Default for TlsOptionsBuilder {
+    fn default() -> Self {
+        Self {
+            native_roots: true,
+            ca_bundles: Vec::new(),
+            client_certificate: None,
+            pinned_certificates: Vec::new(),
+        }
+    }
+}
+
+impl TlsOptionsBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether to trust the native root certificates of the platform, which defaults to `true`
+    ///
+    /// Disable them to only trust the certificate authorities added with [`ca_bundle`](Self::ca_bundle).
+    pub fn native_roots(mut self, native_roots: bool) -> Self {
+        self.native_roots = native_roots;
+        self
+    }
+
+    /// Trust the certificate authorities in `pem`, a bundle of PEM encoded certificates
+    ///
+    /// This can be called multiple times to add several bundles.
+    pub fn ca_bundle(mut self, pem: impl Into<Vec<u8>>) -> Self {
+        self.ca_bundles.push(pem.into());
+        self
+    }
+
+    /// Present a client certificate to servers that ask for one
+    ///
+    /// `certificate_chain` holds the PEM encoded certificate, followed by the intermediate
+    /// certificates that servers need to verify it. `private_key` holds the PEM encoded
+    /// PKCS#8, PKCS#1 (RSA) or SEC1 (EC) private key of the certificate.
+    pub fn client_certificate(
+        mut self,
+        certificate_chain: impl Into<Vec<u8>>,
+        private_key: impl Into<Vec<u8>>,
+    ) -> Self {
+        self.client_certificate = Some((certificate_chain.into(), private_key.into()));
+        self
+    }
+
+    /// Only accept servers that present the certificate with this SHA-256 fingerprint
+    ///
+    /// The fingerprint is hex encoded, optionally with `:` separators, as printed by
+    /// `openssl x509 -noout -fingerprint -sha256`. It may be the fingerprint of the server's own
+    /// certificate or of an intermediate certificate that the server presents. Certificates are
+    /// still verified against the trusted roots.
+    ///
+    /// This can be called multiple times, e.g. to accept the next certificate before a rotation.
+    /// Servers must present at least one of the pinned certificates.
+    pub fn pinned_certificate_sha256(mut self, fingerprint: impl Into<String>) -> Self {
+        self.pinned_certificates.push(fingerprint.into());
+        self
+    }
+
+    /// Creates the [`TlsOptions`]
+    ///
+    /// This fails if a certificate, private key or fingerprint is invalid, or if no certificate
+    /// authority is trusted.
+    pub fn build(self) -> Result<TlsOptions, TlsOptionsError> {
+        let mut roots = RootCertStore::empty();
+        if self.native_roots {
+            let certificates = rustls_native_certs::load_native_certs()
+                .map_err(|err| TlsOptionsError::new(ErrorKind::NativeRoots(err)))?;
+            let certificates: Vec<_> = certificates.into_iter().map(|cert| cert.0).collect();
+            let (_, invalid) = roots.add_parsable_certificates(&certificates);
+            if invalid > 0 {
+                tracing::debug!(invalid, "ignored invalid native root certificates");
+            }
+        }
+        let mut ca_certificates = 0;
+        for bundle in &self.ca_bundles {
+            let certificates = read_certificates(bundle, "CA bundle")?;
+            for certificate in &certificates {
+                roots.add(certificate).map_err(|err| {
+                    TlsOptionsError::new(ErrorKind::InvalidCertificate {
+                        what: "CA bundle",
+                        source: err,
+                    })
+                })?;
+            }
+            ca_certificates += certificates.len();
+        }
+        if roots.is_empty() {
+            return Err(TlsOptionsError::new(ErrorKind::NoRoots));
+        }
+
+        let pins = self
+            .pinned_certificates
+            .iter()
+            .map(|fingerprint| parse_fingerprint(fingerprint))
+            .collect::<Result<Vec<_>, _>>()?;
+        let verifier = WebPkiVerifier::new(roots, None);
+        let verifier: Arc<dyn ServerCertVerifier> = if pins.is_empty() {
+            Arc::new(verifier)
+        } else {
+            Arc::new(PinnedCertificateVerifier {
+                inner: verifier,
+                pins,
+            })
+        };
+        let builder = config_builder().with_custom_certificate_verifier(verifier);
+        let config = match &self.client_certificate {
+            Some((certificate_chain, private_key)) => {
+                let certificate_chain = read_certificates(certificate_chain, "client certificate")?;
+                builder
+                    .with_client_auth_cert(certificate_chain, read_private_key(private_key)?)
+                    .map_err(|err| {
+                        TlsOptionsError::new(ErrorKind::InvalidCertificate {
+                            what: "client certificate",
+                            source: err,
+                        })
+                    })?
+            }
+            None => builder.with_no_client_auth(),
+        };
+        Ok(TlsOptions {
+            config: Arc::new(config),
+            summary: Summary {
+                native_roots: self.native_roots,
+                ca_certificates,
+                client_certificate: self.client_certificate.is_some(),
+                pinned_certificates: self.pinned_certificates.len(),
+            },
+        })
+    }
+}
+
+fn read_certificates(pem: &[u8], what: &'static str) -> Result<Vec<Certificate>, TlsOptionsError> {
+    let certificates = rustls_pemfile::certs(&mut &pem[..])
+        .map_err(|err| TlsOptionsError::new(ErrorKind::InvalidPem { what, source: err }))?;
+    if certificates.is_empty() {
+        return Err(TlsOptionsError::new(ErrorKind::NoCertificates { what }));
+    }
+    Ok(certificates.into_iter().map(Certificate).collect())
+}
+
+fn read_private_key(pem: &[u8]) -> Result<PrivateKey, TlsOptionsError> {
+    let items = rustls_pemfile::read_all(&mut &pem[..]).map_err(|err| {
+        TlsOptionsError::new(ErrorKind::InvalidPem {
+            what: "private key",
+            source: err,
+        })
+    })?;
+    items
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| TlsOptionsError::new(ErrorKind::NoPrivateKey))
+}
+
+fn parse_fingerprint(fingerprint: &str) -> Result<[u8; 32], TlsOptionsError> {
+    let invalid = || {
+        TlsOptionsError::new(ErrorKind::InvalidFingerprint {
+            fingerprint: fingerprint.into(),
+        })
+    };
+    let digits: Vec<u8> = fingerprint.bytes().filter(|byte| *byte != b':').collect();
+    if digits.len() != 64 {
+        return Err(invalid());
+    }
+    let mut result = [0; 32];
+    for (byte, pair) in result.iter_mut().zip(digits.chunks(2)) {
+        let pair = std::str::from_utf8(pair).map_err(|_| invalid())?;
+        *byte = u8::from_str_radix(pair, 16).map_err(|_| invalid())?;
+    }
+    Ok(result)
+}
+
+/// Verifier that requires one of the certificates that the server presents to be pinned
+struct PinnedCertificateVerifier {
+    inner: WebPkiVerifier,
+    pins: Vec<[u8; 32]>,
+}
+
+impl ServerCertVerifier for PinnedCertificateVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            scts,
+            ocsp_response,
+            now,
+        )?;
+        let pinned = std::iter::once(end_entity)
+            .chain(intermediates)
+            .any(|certificate| {
+                let fingerprint: [u8; 32] = Sha256::digest(&certificate.0).into();
+                self.pins.contains(&fingerprint)
+            });
+        if pinned {
+            Ok(verified)
+        } else {
+            tracing::debug!(server_name = ?server_name, "the server didn't present a pinned certificate");
+            Err(rustls::Error::InvalidCertificate(
+                CertificateError::ApplicationVerificationFailure,
+            ))
+        }
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &Certificate,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &Certificate,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+
+    fn request_scts(&self) -> bool {
+        self.inner.request_scts()
+    }
+}
+
+/// Error returned when [`TlsOptions`] can't be built
+#[derive(Debug)]
+pub struct TlsOptionsError {
+    kind: ErrorKind,
+}
+
+#[derive(Debug)]
+enum ErrorKind {
+    NativeRoots(io::Error),
+    InvalidPem {
+        what: &'static str,
+        source: io::Error,
+    },
+    NoCertificates {
+        what: &'static str,
+    },
+    InvalidCertificate {
+        what: &'static str,
+        source: rustls::Error,
+    },
+    NoRoots,
+    NoPrivateKey,
+    InvalidFingerprint {
+        fingerprint: String,
+    },
+}
+
+impl TlsOptionsError {
+    fn new(kind: ErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for TlsOptionsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ErrorKind::NativeRoots(_) => write!(f, "failed to load the native root certificates"),
+            ErrorKind::InvalidPem { what, .. } => write!(f, "the {} is not valid PEM", what),
+            ErrorKind::NoCertificates { what } => {
+                write!(f, "the {} doesn't contain any certificates", what)
+            }
+            ErrorKind::InvalidCertificate { what, .. } => {
+                write!(f, "the {} contains an invalid certificate", what)
+            }
+            ErrorKind::NoRoots => write!(f, "no certificate authorities are trusted"),
+            ErrorKind::NoPrivateKey => write!(f, "the private key doesn't contain a private key"),
+            ErrorKind::InvalidFingerprint { fingerprint } => write!(
+                f,
+                "`{}` is not a hex encoded SHA-256 fingerprint",
+                fingerprint
+            ),
+        }
+    }
+}
+
+impl Error for TlsOptionsError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            ErrorKind::NativeRoots(err) | ErrorKind::InvalidPem { source: err, .. } => Some(err),
+            ErrorKind::InvalidCertificate { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_certificates, read_private_key, TlsOptions};
+    use rustls::server::AllowAnyAuthenticatedClient;
+    use rustls::{ClientConnection, Connection, RootCertStore, ServerConfig, ServerConnection};
+    use std::error::Error;
+    use std::sync::Arc;
+
+    const CA: &[u8] = include_bytes!("../../../../test-data/tls/ca.pem");
+    const SERVER: &[u8] = include_bytes!("../../../../test-data/tls/server.pem");
+    const SERVER_KEY: &[u8] = include_bytes!("../../../../test-data/tls/server-key.pem");
+    const CLIENT: &[u8] = include_bytes!("../../../../test-data/tls/client.pem");
+    const CLIENT_KEY: &[u8] = include_bytes!("../../../../test-data/tls/client-key.pem");
+    const SERVER_FINGERPRINT: &str =
+        "93:32:AE:9B:83:3A:9D:76:D4:9C:C4:09:D7:24:E1:2C:55:AA:33:20:5D:E7:B5:F1:D0:DC:EE:D1:41:DF:01:30";
+
+    fn server(client_auth: bool) -> ServerConnection {
+        let builder = ServerConfig::builder().with_safe_defaults();
+        let builder = if client_auth {
+            let mut roots = RootCertStore::empty();
+            roots.add(&read_certificates(CA, "CA").unwrap()[0]).unwrap();
+            builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+        } else {
+            builder.with_no_client_auth()
+        };
+        let config = builder
+            .with_single_cert(
+                read_certificates(SERVER, "server").unwrap(),
+                read_private_key(SERVER_KEY).unwrap(),
+            )
+            .unwrap();
+        ServerConnection::new(Arc::new(config)).unwrap()
+    }
+
+    /// Sends the pending TLS records of `from` to `to` and returns whether there were any
+    fn transfer(from: &mut Connection, to: &mut Connection) -> Result<bool, rustls::Error> {
+        let mut buf = Vec::new();
+        while from.wants_write() {
+            from.write_tls(&mut buf).unwrap();
+        }
+        to.read_tls(&mut &buf[..]).unwrap();
+        to.process_new_packets()?;
+        Ok(!buf.is_empty())
+    }
+
+    /// Runs a handshake between `options` and the test server, and returns the client's error
+    fn handshake(options: &TlsOptions, client_auth: bool) -> Result<(), rustls::Error> {
+        let mut client: Connection = ClientConnection::new(
+            Arc::new(options.client_config().clone()),
+            "localhost".try_into().unwrap(),
+        )
+        .unwrap()
+        .into();
+        let mut server: Connection = server(client_auth).into();
+        while client.is_handshaking() || server.is_handshaking() {
+            // A server that rejects the client sends an alert to the client
+            let sent = transfer(&mut client, &mut server)
+                .or_else(|_| transfer(&mut server, &mut client))?;
+            let received = transfer(&mut server, &mut client)?;
+            assert!(sent || received, "the handshake is stuck");
+        }
+        Ok(())
+    }
+
+    fn private_ca() -> super::TlsOptionsBuilder {
+        TlsOptions::builder().native_roots(false).ca_bundle(CA)
+    }
+
+    #[test]
+    fn servers_are_verified_with_the_ca_bundle() {
+        handshake(&private_ca().build().unwrap(), false).unwrap();
+
+        let untrusted = TlsOptions::builder()
+            .native_roots(false)
+            .ca_bundle(CLIENT)
+            .build()
+            .unwrap();
+        assert!(matches!(
+            handshake(&untrusted, false),
+            Err(rustls::Error::InvalidCertificate(_))
+        ));
+    }
+
+    #[test]
+    fn client_certificates_are_presented() {
+        let options = private_ca()
+            .client_certificate(CLIENT, CLIENT_KEY)
+            .build()
+            .unwrap();
+        handshake(&options, true).unwrap();
+
+        let err = handshake(&private_ca().build().unwrap(), true).unwrap_err();
+        assert!(matches!(err, rustls::Error::AlertReceived(_)), "{:?}", err);
+    }
+
+    #[test]
+    fn pinned_certificates() {
+        let pinned = private_ca()
+            .pinned_certificate_sha256(SERVER_FINGERPRINT.to_lowercase().replace(':', ""))
+            .build()
+            .unwrap();
+        handshake(&pinned, false).unwrap();
+
+        let rotated = private_ca()
+            .pinned_certificate_sha256("00".repeat(32))
+            .pinned_certificate_sha256(SERVER_FINGERPRINT)
+            .build()
+            .unwrap();
+        handshake(&rotated, false).unwrap();
+
+        let other = private_ca()
+            .pinned_certificate_sha256("00".repeat(32))
+            .build()
+            .unwrap();
+        assert_eq!(
+            handshake(&other, false),
+            Err(rustls::Error::InvalidCertificate(
+                rustls::CertificateError::ApplicationVerificationFailure
+            ))
+        );
+    }
+
+    #[test]
+    fn invalid_options() {
+        fn error(builder: super::TlsOptionsBuilder) -> String {
+            builder.build().unwrap_err().to_string()
+        }
+        assert_eq!(
+            "no certificate authorities are trusted",
+            error(TlsOptions::builder().native_roots(false))
+        );
+        assert_eq!(
+            "the CA bundle doesn't contain any certificates",
+            error(private_ca().ca_bundle("not a certificate"))
+        );
+        assert_eq!(
+            "the CA bundle contains an invalid certificate",
+            error(
+                private_ca()
+                    .ca_bundle("-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n")
+            )
+        );
+        assert_eq!(
+            "the private key doesn't contain a private key",
+            error(private_ca().client_certificate(CLIENT, CLIENT))
+        );
+        assert_eq!(
+            "the client certificate doesn't contain any certificates",
+            error(private_ca().client_certificate(CLIENT_KEY, CLIENT_KEY))
+        );
+        let err = private_ca()
+            .pinned_certificate_sha256("93:32")
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            "`93:32` is not a hex encoded SHA-256 fingerprint",
+            err.to_string()
+        );
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn debug_omits_secrets() {
+        let options = private_ca()
+            .client_certificate(CLIENT, CLIENT_KEY)
+            .build()
+            .unwrap();
+        assert_eq!(
+            "TlsOptions { native_roots: false, ca_certificates: 1, client_certificate: true, pinned_certificates: 0 }",
+            format!("{:?}", options)
+        );
+    }
+}
@@ -7,14 +7,18 @@
 #![allow(unknown_lints)]
 
 use self::auth::orchestrate_auth;
+use crate::client::auth::RetryForAuthChallenge;
 use crate::client::orchestrator::endpoints::orchestrate_endpoint;
 use crate::client::orchestrator::http::read_body;
+use crate::client::stalled_stream_protection::StalledStreamProtection;
 use crate::client::timeout::{MaybeTimeout, MaybeTimeoutConfig, TimeoutKind};
 use aws_smithy_async::rt::sleep::AsyncSleep;
 use aws_smithy_http::body::SdkBody;
 use aws_smithy_http::byte_stream::ByteStream;
+use aws_smithy_http::connection::ConnectionEventListeners;
 use aws_smithy_http::result::SdkError;
 use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::cancellation::{CancellationToken, Cancelled};
 use aws_smithy_runtime_api::client::connectors::Connector;
 use aws_smithy_runtime_api::client::interceptors::context::{
     Error, Input, InterceptorContext, Output, RewindResult,
@@ -29,7 +33,9 @@ use aws_smithy_runtime_api::client::retries::{RetryStrategy, ShouldAttempt};
 use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
 use aws_smithy_runtime_api::client::runtime_plugin::RuntimePlugins;
 use aws_smithy_types::config_bag::ConfigBag;
+use std::future::{poll_fn, Future};
 use std::mem;
+use std::task::Poll;
 use tracing::{debug, debug_span, instrument, trace, Instrument};
 
 mod auth;
@@ -175,6 +181,16 @@ async fn try_op(
     runtime_components: &RuntimeComponents,
     stop_point: StopPoint,
 ) {
+    let cancellation = cfg.load::<CancellationToken>().cloned();
+    if cancellation
+        .as_ref()
+        .map(CancellationToken::is_cancelled)
+        .unwrap_or_default()
+    {
+        debug!("operation was cancelled before it started");
+        return cancel(ctx, cfg, runtime_components);
+    }
+
     // Before serialization
     run_interceptors!(halt_on_err: {
         read_before_serialization(ctx, runtime_components, cfg);
@@ -213,6 +229,11 @@ async fn try_op(
         modify_before_retry_loop(ctx, runtime_components, cfg);
     });
 
+    // Let the retry strategy set up any state it keeps for this operation
+    runtime_components
+        .retry_strategy()
+        .on_operation_start(runtime_components, cfg);
+
     // If we got a retry strategy from the bag, ask it what to do.
     // Otherwise, assume we should attempt the initial request.
     let should_attempt = runtime_components
@@ -233,7 +254,13 @@ async fn try_op(
                 "the retry strategy requested a delay before sending the initial request, but no 'async sleep' implementation was set"
             )));
             debug!("retry strategy has OKed initial request after a {delay:?} delay");
-            sleep_impl.sleep(delay).await;
+            if until_cancelled(cancellation.as_ref(), sleep_impl.sleep(delay))
+                .await
+                .is_err()
+            {
+                debug!("operation was cancelled while delaying the initial request");
+                return cancel(ctx, cfg, runtime_components);
+            }
         }
     }
 
@@ -241,6 +268,7 @@ async fn try_op(
     // the request in the case of retry attempts.
     ctx.save_checkpoint();
     let mut retry_delay = None;
+    let mut answered_auth_challenge = false;
     for i in 1u32.. {
         // Break from the loop if we can't rewind the request's state. This will always succeed the
         // first time, but will fail on subsequent iterations if the request body wasn't retryable.
@@ -249,30 +277,57 @@ async fn try_op(
             debug!("request cannot be retried since the request body cannot be cloned");
             break;
         }
-        // Track which attempt we're currently on.
+        // Track which attempt we're currently on. The attempt that answers an authentication
+        // challenge doesn't count towards the attempts of the retry strategy.
+        let attempt = i - u32::from(answered_auth_challenge);
         cfg.interceptor_state()
-            .store_put::<RequestAttempts>(i.into());
+            .store_put::<RequestAttempts>(attempt.into());
         // Backoff time should not be included in the attempt timeout
         if let Some((delay, sleep)) = retry_delay.take() {
             debug!("delaying for {delay:?}");
-            sleep.await;
+            if until_cancelled(cancellation.as_ref(), sleep).await.is_err() {
+                debug!("operation was cancelled while backing off before attempt #{i}");
+                return cancel(ctx, cfg, runtime_components);
+            }
         }
         let attempt_timeout_config =
             MaybeTimeoutConfig::new(runtime_components, cfg, TimeoutKind::OperationAttempt);
         trace!(attempt_timeout_config = ?attempt_timeout_config);
-        let maybe_timeout = async {
+        let attempt = async {
             debug!("beginning attempt #{i}");
             try_attempt(ctx, cfg, runtime_components, stop_point).await;
             finally_attempt(ctx, cfg, runtime_components).await;
             Result::<_, SdkError<Error, HttpResponse>>::Ok(())
         }
-        .maybe_timeout(attempt_timeout_config)
-        .await
-        .map_err(|err| OrchestratorError::timeout(err.into_source().unwrap()));
+        .maybe_timeout(attempt_timeout_config);
+        let maybe_timeout = match until_cancelled(cancellation.as_ref(), attempt).await {
+            Ok(result) => {
+                result.map_err(|err| OrchestratorError::timeout(err.into_source().unwrap()))
+            }
+            // The aborted attempt never got to its completion hooks, so run them now.
+            Err(_) => {
+                debug!("operation was cancelled during attempt #{i}");
+                cancel(ctx, cfg, runtime_components);
+                finally_attempt(ctx, cfg, runtime_components).await;
+                return;
+            }
+        };
 
         // We continue when encountering a timeout error. The retry classifier will decide what to do with it.
         continue_on_err!([ctx] => maybe_timeout);
 
+        // An auth scheme may ask to answer an authentication challenge with another attempt.
+        // This happens at most once per operation, isn't subject to the retry strategy, and
+        // doesn't use up one of its attempts.
+        if cfg.load::<RetryForAuthChallenge>().is_some() {
+            cfg.interceptor_state().unset::<RetryForAuthChallenge>();
+            if !answered_auth_challenge {
+                debug!("retrying to answer an authentication challenge");
+                answered_auth_challenge = true;
+                continue;
+            }
+        }
+
         // If we got a retry strategy from the bag, ask it what to do.
         // If no strategy was set, we won't retry.
         let should_attempt = halt_on_err!([ctx] => runtime_components
@@ -298,6 +353,36 @@ async fn try_op(
     }
 }
 
+/// Runs `future` to completion unless `cancellation` is cancelled first.
+async fn until_cancelled<F: Future>(
+    cancellation: Option<&CancellationToken>,
+    future: F,
+) -> Result<F::Output, Cancelled> {
+    let cancellation = match cancellation {
+        Some(cancellation) => cancellation,
+        None => return Ok(future.await),
+    };
+    let cancelled = cancellation.cancelled();
+    pin_utils::pin_mut!(future);
+    pin_utils::pin_mut!(cancelled);
+    poll_fn(|cx| {
+        if cancelled.as_mut().poll(cx).is_ready() {
+            return Poll::Ready(Err(Cancelled::new()));
+        }
+        future.as_mut().poll(cx).map(Ok)
+    })
+    .await
+}
+
+/// Fails the operation with a [`Cancelled`] error and lets the retry strategy release any
+/// permits it is holding for the operation.
+fn cancel(ctx: &mut InterceptorContext, cfg: &ConfigBag, runtime_components: &RuntimeComponents) {
+    ctx.fail(OrchestratorError::other(Cancelled::new()));
+    runtime_components
+        .retry_strategy()
+        .on_cancellation(runtime_components, cfg);
+}
+
 #[instrument(skip_all)]
 async fn try_attempt(
     ctx: &mut InterceptorContext,
@@ -332,12 +417,30 @@ async fn try_attempt(
     // within the interceptor context, so we clone it here.
     ctx.enter_transmit_phase();
     let response = halt_on_err!([ctx] => {
-        let request = ctx.take_request().expect("set during serialization");
+        let mut request = ctx.take_request().expect("set during serialization");
+        let listeners: ConnectionEventListeners =
+            runtime_components.connection_event_listeners().collect();
+        if !listeners.is_empty() {
+            request.extensions_mut().insert(listeners);
+        }
         trace!(request = ?request, "transmitting request");
         let connector = halt_on_err!([ctx] => runtime_components.connector().ok_or_else(||
             OrchestratorError::other("a connector is required to send requests")
         ));
-        connector.call(request).await.map_err(|err| {
+        let stalled_stream_protection = StalledStreamProtection::new(runtime_components, cfg);
+        let upload_monitor = stalled_stream_protection
+            .as_ref()
+            .and_then(|protection| protection.protect_request(&mut request));
+        let response = match upload_monitor {
+            Some(upload_monitor) => upload_monitor.send(connector.call(request)).await,
+            None => connector.call(request).await,
+        };
+        response.map(|mut response| {
+            if let Some(protection) = &stalled_stream_protection {
+                protection.protect_response(&mut response);
+            }
+            response
+        }).map_err(|err| {
             match err.downcast() {
                 Ok(connector_error) => OrchestratorError::connector(*connector_error),
                 Err(box_err) => OrchestratorError::other(box_err)
@@ -419,6 +522,7 @@ mod tests {
         deserializer::CannedResponseDeserializer, serializer::CannedRequestSerializer,
     };
     use ::http::{Request, Response, StatusCode};
+    use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
     use aws_smithy_runtime_api::client::auth::option_resolver::StaticAuthOptionResolver;
     use aws_smithy_runtime_api::client::auth::{
         AuthOptionResolverParams, SharedAuthOptionResolver,
@@ -1249,4 +1353,275 @@ mod tests {
             .read_after_execution_called
             .load(Ordering::Relaxed));
     }
+
+    #[derive(Debug, Clone, Default)]
+    struct HookRecorder {
+        hooks: Arc<std::sync::Mutex<Vec<&'static str>>>,
+    }
+
+    impl HookRecorder {
+        fn record(&self, hook: &'static str) {
+            self.hooks.lock().unwrap().push(hook);
+        }
+
+        fn hooks(&self) -> Vec<&'static str> {
+            self.hooks.lock().unwrap().clone()
+        }
+    }
+
+    impl Interceptor for HookRecorder {
+        fn read_before_attempt(
+            &self,
+            _context: &BeforeTransmitInterceptorContextRef<'_>,
+            _rc: &RuntimeComponents,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), BoxError> {
+            self.record("read_before_attempt");
+            Ok(())
+        }
+
+        fn modify_before_attempt_completion(
+            &self,
+            _context: &mut FinalizerInterceptorContextMut<'_>,
+            _rc: &RuntimeComponents,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), BoxError> {
+            self.record("modify_before_attempt_completion");
+            Ok(())
+        }
+
+        fn modify_before_completion(
+            &self,
+            _context: &mut FinalizerInterceptorContextMut<'_>,
+            _rc: &RuntimeComponents,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), BoxError> {
+            self.record("modify_before_completion");
+            Ok(())
+        }
+
+        fn read_after_execution(
+            &self,
+            context: &FinalizerInterceptorContextRef<'_>,
+            _rc: &RuntimeComponents,
+            _cfg: &mut ConfigBag,
+        ) -> Result<(), BoxError> {
+            match context.output_or_error() {
+                Some(Err(OrchestratorError::Other { err })) if err.is::<Cancelled>() => {
+                    self.record("read_after_execution(cancelled)")
+                }
+                _ => self.record("read_after_execution"),
+            }
+            Ok(())
+        }
+    }
+
+    /// Responds with a 200 OK, except that call number `cancel_on_call` cancels the token first.
+    /// If `hang_after_cancel` is set, that call never completes.
+    #[derive(Debug)]
+    struct CancellingConnector {
+        token: CancellationToken,
+        cancel_on_call: usize,
+        hang_after_cancel: bool,
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl Connector for CancellingConnector {
+        fn call(&self, request: HttpRequest) -> BoxFuture<HttpResponse> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if call == self.cancel_on_call {
+                self.token.cancel();
+                if self.hang_after_cancel {
+                    return Box::pin(std::future::pending());
+                }
+            }
+            OkConnector::new().call(request)
+        }
+    }
+
+    #[derive(Clone, Debug, Default)]
+    struct AlwaysRetryStrategy {
+        cancellations: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl RetryStrategy for AlwaysRetryStrategy {
+        fn should_attempt_initial_request(
+            &self,
+            _runtime_components: &RuntimeComponents,
+            _cfg: &ConfigBag,
+        ) -> Result<ShouldAttempt, BoxError> {
+            Ok(ShouldAttempt::Yes)
+        }
+
+        fn should_attempt_retry(
+            &self,
+            _context: &InterceptorContext,
+            _runtime_components: &RuntimeComponents,
+            _cfg: &ConfigBag,
+        ) -> Result<ShouldAttempt, BoxError> {
+            Ok(ShouldAttempt::YesAfterDelay(
+                std::time::Duration::from_secs(3600),
+            ))
+        }
+
+        fn on_cancellation(&self, _runtime_components: &RuntimeComponents, _cfg: &ConfigBag) {
+            self.cancellations.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Unlike the canned deserializer, this one can deserialize more than one response.
+    #[derive(Debug)]
+    struct UnitResponseDeserializer;
+
+    impl ResponseDeserializer for UnitResponseDeserializer {
+        fn deserialize_nonstreaming(
+            &self,
+            _response: &HttpResponse,
+        ) -> Result<Output, OrchestratorError<Error>> {
+            Ok(Output::new(Box::new(())))
+        }
+    }
+
+    #[derive(Debug)]
+    struct CancellationTestRuntimePlugin {
+        token: CancellationToken,
+        builder: RuntimeComponentsBuilder,
+    }
+
+    impl RuntimePlugin for CancellationTestRuntimePlugin {
+        fn config(&self) -> Option<FrozenLayer> {
+            let mut layer = Layer::new("CancellationTestRuntimePlugin");
+            layer.store_put(self.token.clone());
+            layer.store_put(DynResponseDeserializer::new(UnitResponseDeserializer));
+            Some(layer.freeze())
+        }
+
+        fn runtime_components(&self) -> Cow<'_, RuntimeComponentsBuilder> {
+            Cow::Borrowed(&self.builder)
+        }
+    }
+
+    struct CancellationTest {
+        token: CancellationToken,
+        recorder: HookRecorder,
+        retry_strategy: AlwaysRetryStrategy,
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+        cancel_on_call: usize,
+        hang_after_cancel: bool,
+    }
+
+    impl CancellationTest {
+        fn new(cancel_on_call: usize, hang_after_cancel: bool) -> Self {
+            Self {
+                token: CancellationToken::new(),
+                recorder: HookRecorder::default(),
+                retry_strategy: Default::default(),
+                calls: Default::default(),
+                cancel_on_call,
+                hang_after_cancel,
+            }
+        }
+
+        async fn invoke(&self) -> Result<Output, SdkError<Error, HttpResponse>> {
+            let connector = CancellingConnector {
+                token: self.token.clone(),
+                cancel_on_call: self.cancel_on_call,
+                hang_after_cancel: self.hang_after_cancel,
+                calls: self.calls.clone(),
+            };
+            let runtime_plugins = RuntimePlugins::new()
+                .with_operation_plugin(TestOperationRuntimePlugin::new())
+                .with_operation_plugin(NoAuthRuntimePlugin::new())
+                .with_operation_plugin(CancellationTestRuntimePlugin {
+                    token: self.token.clone(),
+                    builder: RuntimeComponentsBuilder::new("test")
+                        .with_connector(Some(SharedConnector::new(connector)))
+                        .with_retry_strategy(Some(SharedRetryStrategy::new(
+                            self.retry_strategy.clone(),
+                        )))
+                        .with_sleep_impl(Some(SharedAsyncSleep::new(TokioSleep::new())))
+                        .with_interceptor(SharedInterceptor::new(self.recorder.clone())),
+                });
+            invoke("test", "test", TypedBox::new(()).erase(), &runtime_plugins).await
+        }
+
+        fn calls(&self) -> usize {
+            self.calls.load(Ordering::SeqCst)
+        }
+
+        fn cancellations(&self) -> usize {
+            self.retry_strategy.cancellations.load(Ordering::SeqCst)
+        }
+    }
+
+    fn caused_by_cancellation(err: &(dyn std::error::Error + 'static)) -> bool {
+        let mut source = Some(err);
+        while let Some(err) = source {
+            if err.is::<Cancelled>() {
+                return true;
+            }
+            source = err.source();
+        }
+        false
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_before_invoke() {
+        let test = CancellationTest::new(usize::MAX, false);
+        test.token.cancel();
+
+        let err = test.invoke().await.expect_err("cancelled");
+        assert!(caused_by_cancellation(&err), "{err:?}");
+        assert_eq!(0, test.calls());
+        assert_eq!(1, test.cancellations());
+        assert_eq!(
+            vec![
+                "modify_before_completion",
+                "read_after_execution(cancelled)"
+            ],
+            test.recorder.hooks()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_aborts_in_flight_attempt() {
+        let test = CancellationTest::new(1, true);
+
+        let err = test.invoke().await.expect_err("cancelled");
+        assert!(caused_by_cancellation(&err), "{err:?}");
+        assert_eq!(1, test.calls());
+        assert_eq!(1, test.cancellations());
+        assert_eq!(
+            vec![
+                "read_before_attempt",
+                "modify_before_attempt_completion",
+                "modify_before_completion",
+                "read_after_execution(cancelled)",
+            ],
+            test.recorder.hooks()
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_cancellation_stops_retries() {
+        // The second attempt completes, but the token is cancelled while it is in flight,
+        // so the hour-long backoff before the third attempt is aborted.
+        let test = CancellationTest::new(2, false);
+
+        let err = test.invoke().await.expect_err("cancelled");
+        assert!(caused_by_cancellation(&err), "{err:?}");
+        assert_eq!(2, test.calls());
+        assert_eq!(1, test.cancellations());
+        assert_eq!(
+            vec![
+                "read_before_attempt",
+                "modify_before_attempt_completion",
+                "read_before_attempt",
+                "modify_before_attempt_completion",
+                "modify_before_completion",
+                "read_after_execution(cancelled)",
+            ],
+            test.recorder.hooks()
+        );
+    }
 }
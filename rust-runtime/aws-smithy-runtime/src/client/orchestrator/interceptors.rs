@@ -3,6 +3,15 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+#[cfg(feature = "request-compression")]
+mod request_compression;
 mod service_clock_skew;
 
+#[cfg(feature = "request-compression")]
+pub use request_compression::{
+    CompressionAlgorithm, DisableRequestCompression, InvalidMinCompressionSizeError,
+    RequestCompressionInterceptor, RequestMinCompressionSizeBytes,
+    UnknownCompressionAlgorithmError, DEFAULT_MIN_COMPRESSION_SIZE_BYTES,
+    MAX_MIN_COMPRESSION_SIZE_BYTES,
+};
 pub use service_clock_skew::{ServiceClockSkew, ServiceClockSkewInterceptor};
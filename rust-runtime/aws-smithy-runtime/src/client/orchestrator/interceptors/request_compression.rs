@@ -0,0 +1,501 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_http::body::{BoxBody, Error as BodyError, SdkBody};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::interceptors::context::{
+    BeforeSerializationInterceptorContextRef, BeforeTransmitInterceptorContextMut,
+};
+use aws_smithy_runtime_api::client::interceptors::Interceptor;
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::{ConfigBag, Storable, StoreReplace};
+use bytes::Bytes;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use http::header::{HeaderValue, CONTENT_ENCODING, CONTENT_LENGTH};
+use http::HeaderMap;
+use http_body::{Body, SizeHint};
+use pin_project_lite::pin_project;
+use std::io::Write;
+use std::pin::Pin;
+use std::str::FromStr;
+use std::task::{Context, Poll};
+use std::{fmt, mem};
+
+pub use aws_smithy_types::request_compression::{
+    DEFAULT_MIN_COMPRESSION_SIZE_BYTES, MAX_MIN_COMPRESSION_SIZE_BYTES,
+};
+
+/// When `true`, request bodies are never compressed.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DisableRequestCompression(pub bool);
+
+impl Storable for DisableRequestCompression {
+    type Storer = StoreReplace<Self>;
+}
+
+/// The minimum size, in bytes, that a request body must have to be compressed.
+///
+/// Streaming request bodies have no known size up front, so they are always compressed.
+/// Defaults to [`DEFAULT_MIN_COMPRESSION_SIZE_BYTES`], and must be at most
+/// [`MAX_MIN_COMPRESSION_SIZE_BYTES`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RequestMinCompressionSizeBytes(pub u32);
+
+impl Default for RequestMinCompressionSizeBytes {
+    fn default() -> Self {
+        Self(DEFAULT_MIN_COMPRESSION_SIZE_BYTES)
+    }
+}
+
+impl Storable for RequestMinCompressionSizeBytes {
+    type Storer = StoreReplace<Self>;
+}
+
+/// A content encoding that request bodies can be compressed with.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CompressionAlgorithm {
+    /// `gzip`
+    Gzip,
+}
+
+impl CompressionAlgorithm {
+    /// Returns the value of this algorithm for the `Content-Encoding` header.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CompressionAlgorithm::Gzip => "gzip",
+        }
+    }
+}
+
+impl fmt::Display for CompressionAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Error returned when [`RequestMinCompressionSizeBytes`] is larger than
+/// [`MAX_MIN_COMPRESSION_SIZE_BYTES`].
+#[derive(Debug)]
+pub struct InvalidMinCompressionSizeError {
+    value: u32,
+}
+
+impl fmt::Display for InvalidMinCompressionSizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid request minimum compression size {}: it must be between 0 and {}",
+            self.value, MAX_MIN_COMPRESSION_SIZE_BYTES
+        )
+    }
+}
+
+impl std::error::Error for InvalidMinCompressionSizeError {}
+
+/// Error returned when parsing an unsupported [`CompressionAlgorithm`].
+#[derive(Debug)]
+pub struct UnknownCompressionAlgorithmError {
+    algorithm: String,
+}
+
+impl fmt::Display for UnknownCompressionAlgorithmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unsupported compression algorithm `{}`", self.algorithm)
+    }
+}
+
+impl std::error::Error for UnknownCompressionAlgorithmError {}
+
+impl FromStr for CompressionAlgorithm {
+    type Err = UnknownCompressionAlgorithmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("gzip") {
+            Ok(CompressionAlgorithm::Gzip)
+        } else {
+            Err(UnknownCompressionAlgorithmError {
+                algorithm: s.to_owned(),
+            })
+        }
+    }
+}
+
+/// Interceptor that compresses request bodies for operations modeled with the
+/// `@requestCompression` trait.
+///
+/// The body is compressed with the first of the operation's supported encodings that this
+/// interceptor also supports. In-memory bodies are only compressed if they are at least
+/// [`RequestMinCompressionSizeBytes`] long. Streaming bodies are compressed as they are sent.
+/// Compression can be turned off entirely with [`DisableRequestCompression`].
+///
+/// Requests fail with an [`InvalidMinCompressionSizeError`] if the configured
+/// [`RequestMinCompressionSizeBytes`] is larger than [`MAX_MIN_COMPRESSION_SIZE_BYTES`].
+#[derive(Debug)]
+pub struct RequestCompressionInterceptor {
+    algorithm: Option<CompressionAlgorithm>,
+}
+
+impl RequestCompressionInterceptor {
+    /// Creates a new `RequestCompressionInterceptor` for an operation that supports the given
+    /// encodings, in order of preference.
+    pub fn new(encodings: &[&str]) -> Self {
+        Self {
+            algorithm: encodings.iter().find_map(|encoding| encoding.parse().ok()),
+        }
+    }
+}
+
+impl Interceptor for RequestCompressionInterceptor {
+    fn read_before_execution(
+        &self,
+        _context: &BeforeSerializationInterceptorContextRef<'_>,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        match cfg.load::<RequestMinCompressionSizeBytes>() {
+            Some(min_size) if min_size.0 > MAX_MIN_COMPRESSION_SIZE_BYTES => {
+                Err(InvalidMinCompressionSizeError { value: min_size.0 }.into())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn modify_before_signing(
+        &self,
+        context: &mut BeforeTransmitInterceptorContextMut<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        let algorithm = match self.algorithm {
+            Some(algorithm) => algorithm,
+            None => return Ok(()),
+        };
+        if cfg.load::<DisableRequestCompression>().copied() == Some(DisableRequestCompression(true))
+        {
+            return Ok(());
+        }
+        let min_size = cfg
+            .load::<RequestMinCompressionSizeBytes>()
+            .copied()
+            .unwrap_or_default();
+
+        let request = context.request_mut();
+        let body = mem::replace(request.body_mut(), SdkBody::taken());
+        let body = match body.bytes() {
+            Some(bytes) if bytes.len() < min_size.0 as usize => {
+                *request.body_mut() = body;
+                return Ok(());
+            }
+            Some(bytes) => {
+                let compressed = compress(algorithm, bytes)?;
+                request
+                    .headers_mut()
+                    .insert(CONTENT_LENGTH, HeaderValue::from(compressed.len()));
+                SdkBody::from(compressed)
+            }
+            None => {
+                request.headers_mut().remove(CONTENT_LENGTH);
+                body.map(move |body| {
+                    SdkBody::from_dyn(BoxBody::new(CompressedBody::new(body, algorithm)))
+                })
+            }
+        };
+        *request.body_mut() = body;
+        append_content_encoding(request.headers_mut(), algorithm);
+        Ok(())
+    }
+}
+
+fn compress(algorithm: CompressionAlgorithm, bytes: &[u8]) -> Result<Vec<u8>, BoxError> {
+    match algorithm {
+        CompressionAlgorithm::Gzip => {
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(bytes)?;
+            Ok(encoder.finish()?)
+        }
+    }
+}
+
+fn append_content_encoding(headers: &mut HeaderMap, algorithm: CompressionAlgorithm) {
+    let encoding = match headers
+        .get(CONTENT_ENCODING)
+        .and_then(|value| value.to_str().ok())
+    {
+        Some(existing) if !existing.is_empty() => {
+            HeaderValue::try_from(format!("{}, {}", existing, algorithm))
+                .expect("both encodings are valid header values")
+        }
+        _ => HeaderValue::from_static(algorithm.as_str()),
+    };
+    headers.insert(CONTENT_ENCODING, encoding);
+}
+
+pin_project! {
+    /// Body wrapper that compresses the inner body as it is read.
+    struct CompressedBody<B> {
+        #[pin]
+        inner: B,
+        encoder: Option<GzEncoder<Vec<u8>>>,
+    }
+}
+
+impl<B> CompressedBody<B> {
+    fn new(inner: B, algorithm: CompressionAlgorithm) -> Self {
+        let encoder = match algorithm {
+            CompressionAlgorithm::Gzip => GzEncoder::new(Vec::new(), Compression::default()),
+        };
+        Self {
+            inner,
+            encoder: Some(encoder),
+        }
+    }
+}
+
+impl<B> Body for CompressedBody<B>
+where
+    B: Body<Data = Bytes, Error = BodyError>,
+{
+    type Data = Bytes;
+    type Error = BodyError;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let mut this = self.project();
+        loop {
+            let encoder = match this.encoder.as_mut() {
+                Some(encoder) => encoder,
+                None => return Poll::Ready(None),
+            };
+            match this.inner.as_mut().poll_data(cx) {
+                Poll::Ready(Some(Ok(data))) => {
+                    encoder.write_all(&data)?;
+                    let compressed = mem::take(encoder.get_mut());
+                    // The encoder buffers small writes, so there may be nothing to send yet
+                    if !compressed.is_empty() {
+                        return Poll::Ready(Some(Ok(compressed.into())));
+                    }
+                }
+                Poll::Ready(None) => {
+                    let compressed = this.encoder.take().expect("checked above").finish()?;
+                    return Poll::Ready(Some(Ok(compressed.into())));
+                }
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        self.project().inner.poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.encoder.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        // The compressed size isn't known until the body has been read
+        SizeHint::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_http::byte_stream::ByteStream;
+    use aws_smithy_runtime_api::client::interceptors::context::{Input, InterceptorContext};
+    use aws_smithy_runtime_api::client::orchestrator::HttpRequest;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::config_bag::Layer;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    /// Streaming body that yields the given chunks.
+    struct Chunks(Vec<Bytes>);
+
+    impl Body for Chunks {
+        type Data = Bytes;
+        type Error = BodyError;
+
+        fn poll_data(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            if self.0.is_empty() {
+                Poll::Ready(None)
+            } else {
+                Poll::Ready(Some(Ok(self.0.remove(0))))
+            }
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    fn decompress(bytes: &[u8]) -> Vec<u8> {
+        let mut decompressed = Vec::new();
+        GzDecoder::new(bytes)
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        decompressed
+    }
+
+    fn run(
+        interceptor: &RequestCompressionInterceptor,
+        request: HttpRequest,
+        layer: Layer,
+    ) -> HttpRequest {
+        let mut context = InterceptorContext::new(Input::doesnt_matter());
+        context.enter_serialization_phase();
+        context.set_request(request);
+        let _ = context.take_input();
+        context.enter_before_transmit_phase();
+
+        let runtime_components = RuntimeComponentsBuilder::for_tests().build().unwrap();
+        let mut cfg = ConfigBag::of_layers(vec![layer]);
+        let mut context = BeforeTransmitInterceptorContextMut::from(&mut context);
+        interceptor
+            .modify_before_signing(&mut context, &runtime_components, &mut cfg)
+            .unwrap();
+        mem::replace(context.request_mut(), HttpRequest::new(SdkBody::empty()))
+    }
+
+    fn request(body: SdkBody) -> HttpRequest {
+        let mut request = HttpRequest::new(body);
+        if let Some(len) = request.body().content_length() {
+            request
+                .headers_mut()
+                .insert(CONTENT_LENGTH, HeaderValue::from(len));
+        }
+        request
+    }
+
+    fn payload() -> Vec<u8> {
+        "{\"hello\":\"world\"}".repeat(1_000).into_bytes()
+    }
+
+    #[test]
+    fn compresses_large_bodies() {
+        let interceptor = RequestCompressionInterceptor::new(&["br", "gzip"]);
+        let request = run(
+            &interceptor,
+            request(SdkBody::from(payload())),
+            Layer::new("test"),
+        );
+
+        assert_eq!("gzip", request.headers()[CONTENT_ENCODING]);
+        let body = request.body().bytes().unwrap();
+        assert!(body.len() < payload().len());
+        assert_eq!(body.len().to_string(), request.headers()[CONTENT_LENGTH]);
+        assert_eq!(payload(), decompress(body));
+    }
+
+    #[test]
+    fn small_bodies_are_not_compressed() {
+        let interceptor = RequestCompressionInterceptor::new(&["gzip"]);
+        let mut layer = Layer::new("test");
+        layer.store_put(RequestMinCompressionSizeBytes(payload().len() as u32 + 1));
+        let request = run(&interceptor, request(SdkBody::from(payload())), layer);
+
+        assert!(request.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(Some(&payload()[..]), request.body().bytes());
+    }
+
+    #[test]
+    fn min_compression_sizes_over_the_limit_are_rejected() {
+        let interceptor = RequestCompressionInterceptor::new(&["gzip"]);
+        let context = InterceptorContext::new(Input::doesnt_matter());
+        let check = |min_size: u32| {
+            let mut layer = Layer::new("test");
+            layer.store_put(RequestMinCompressionSizeBytes(min_size));
+            let mut cfg = ConfigBag::of_layers(vec![layer]);
+            interceptor.read_before_execution(&(&context).into(), &mut cfg)
+        };
+
+        check(MAX_MIN_COMPRESSION_SIZE_BYTES).expect("the limit itself is valid");
+        let err = check(MAX_MIN_COMPRESSION_SIZE_BYTES + 1).expect_err("over the limit");
+        assert_eq!(
+            "invalid request minimum compression size 10485761: it must be between 0 and 10485760",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn compression_can_be_disabled() {
+        let interceptor = RequestCompressionInterceptor::new(&["gzip"]);
+        let mut layer = Layer::new("test");
+        layer.store_put(DisableRequestCompression(true));
+        let request = run(&interceptor, request(SdkBody::from(payload())), layer);
+
+        assert!(request.headers().get(CONTENT_ENCODING).is_none());
+        assert_eq!(Some(&payload()[..]), request.body().bytes());
+    }
+
+    #[test]
+    fn unsupported_encodings_are_ignored() {
+        let interceptor = RequestCompressionInterceptor::new(&["br"]);
+        let request = run(
+            &interceptor,
+            request(SdkBody::from(payload())),
+            Layer::new("test"),
+        );
+        assert!(request.headers().get(CONTENT_ENCODING).is_none());
+    }
+
+    #[test]
+    fn existing_content_encodings_are_preserved() {
+        let interceptor = RequestCompressionInterceptor::new(&["gzip"]);
+        let mut request = request(SdkBody::from(payload()));
+        request
+            .headers_mut()
+            .insert(CONTENT_ENCODING, HeaderValue::from_static("custom"));
+        let request = run(&interceptor, request, Layer::new("test"));
+        assert_eq!("custom, gzip", request.headers()[CONTENT_ENCODING]);
+    }
+
+    #[tokio::test]
+    async fn streaming_bodies_are_compressed_regardless_of_size() {
+        let interceptor = RequestCompressionInterceptor::new(&["gzip"]);
+        let body = SdkBody::from_dyn(BoxBody::new(Chunks(vec![
+            Bytes::from_static(b"hello "),
+            Bytes::from_static(b"streaming "),
+            Bytes::from_static(b"world"),
+        ])));
+        let request = run(&interceptor, request(body), Layer::new("test"));
+
+        assert_eq!("gzip", request.headers()[CONTENT_ENCODING]);
+        assert!(request.headers().get(CONTENT_LENGTH).is_none());
+        let body = ByteStream::new(request.into_body())
+            .collect()
+            .await
+            .unwrap()
+            .into_bytes();
+        assert_eq!(&b"hello streaming world"[..], &decompress(&body)[..]);
+    }
+
+    #[tokio::test]
+    async fn compressed_streaming_bodies_can_be_retried() {
+        let interceptor = RequestCompressionInterceptor::new(&["gzip"]);
+        let body =
+            SdkBody::retryable(|| SdkBody::from_dyn(BoxBody::new(SdkBody::from("retry me"))));
+        let request = run(&interceptor, request(body), Layer::new("test"));
+
+        let retry = request.body().try_clone().expect("retryable");
+        for body in [request.into_body(), retry] {
+            let body = ByteStream::new(body).collect().await.unwrap().into_bytes();
+            assert_eq!(&b"retry me"[..], &decompress(&body)[..]);
+        }
+    }
+}
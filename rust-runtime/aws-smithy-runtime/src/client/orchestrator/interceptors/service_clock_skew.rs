@@ -69,10 +69,15 @@ impl Interceptor for ServiceClockSkewInterceptor {
     fn modify_before_deserialization(
         &self,
         ctx: &mut BeforeDeserializationInterceptorContextMut<'_>,
-        _runtime_components: &RuntimeComponents,
+        runtime_components: &RuntimeComponents,
         cfg: &mut ConfigBag,
     ) -> Result<(), BoxError> {
-        let time_received = DateTime::from(SystemTime::now());
+        let time_received = DateTime::from(
+            runtime_components
+                .time_source()
+                .map(|time_source| time_source.now())
+                .unwrap_or_else(SystemTime::now),
+        );
         let time_sent = match extract_time_sent_from_response(ctx) {
             Ok(time_sent) => time_sent,
             Err(e) => {
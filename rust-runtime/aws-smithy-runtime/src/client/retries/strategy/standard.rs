@@ -32,13 +32,35 @@ pub struct StandardRetryStrategy {
     initial_backoff: Duration,
     max_attempts: u32,
     max_backoff: Duration,
-    retry_permit: Mutex<Option<OwnedSemaphorePermit>>,
 }
 
 impl Storable for StandardRetryStrategy {
     type Storer = StoreReplace<Self>;
 }
 
+/// The retry permit that a single operation is holding, if any
+///
+/// An empty `RetryPermit` is put into the config bag of every operation when it starts. Since a
+/// retry strategy is shared by all operations of a client, the permit has to live here, so that
+/// finishing or cancelling an operation only ever releases the permit that operation acquired.
+/// A permit that is still held when the operation ends is forgotten.
+#[derive(Debug, Default)]
+pub(crate) struct RetryPermit(Mutex<Option<OwnedSemaphorePermit>>);
+
+impl Storable for RetryPermit {
+    type Storer = StoreReplace<Self>;
+}
+
+impl Drop for RetryPermit {
+    fn drop(&mut self) {
+        // An operation that ends without releasing its permit failed, so the retries it paid
+        // for aren't refunded.
+        if let Some(p) = self.0.get_mut().ok().and_then(Option::take) {
+            p.forget()
+        }
+    }
+}
+
 impl StandardRetryStrategy {
     pub fn new(retry_config: &RetryConfig) -> Self {
         let base = if retry_config.use_static_exponential_base() {
@@ -74,9 +96,11 @@ impl StandardRetryStrategy {
         self
     }
 
-    fn release_retry_permit(&self) -> ReleaseResult {
-        let mut retry_permit = self.retry_permit.lock().unwrap();
-        match retry_permit.take() {
+    fn release_retry_permit(&self, cfg: &ConfigBag) -> ReleaseResult {
+        let retry_permit = cfg
+            .load::<RetryPermit>()
+            .and_then(|retry_permit| retry_permit.0.lock().unwrap().take());
+        match retry_permit {
             Some(p) => {
                 drop(p);
                 APermitWasReleased
@@ -85,9 +109,13 @@ impl StandardRetryStrategy {
         }
     }
 
-    fn set_retry_permit(&self, new_retry_permit: OwnedSemaphorePermit) {
-        let mut old_retry_permit = self.retry_permit.lock().unwrap();
-        if let Some(p) = old_retry_permit.replace(new_retry_permit) {
+    fn set_retry_permit(&self, cfg: &ConfigBag, new_retry_permit: OwnedSemaphorePermit) {
+        let old_retry_permit = match cfg.load::<RetryPermit>() {
+            Some(retry_permit) => retry_permit.0.lock().unwrap().replace(new_retry_permit),
+            // There's nowhere to hold on to the permit for this operation, so treat it as replaced
+            None => Some(new_retry_permit),
+        };
+        if let Some(p) = old_retry_permit {
             // Whenever we set a new retry permit and it replaces the old one, we need to "forget"
             // the old permit, removing it from the bucket forever.
             p.forget()
@@ -121,7 +149,7 @@ impl StandardRetryStrategy {
                 } else {
                     if let Some(tb) = token_bucket {
                         match tb.acquire(kind) {
-                            Some(permit) => self.set_retry_permit(permit),
+                            Some(permit) => self.set_retry_permit(cfg, permit),
                             None => {
                                 debug!("attempt #{request_attempts} failed with {kind:?}; However, no retry permits are available, so no retry will be attempted.");
                                 return Err(ShouldAttempt::No);
@@ -168,12 +196,15 @@ impl Default for StandardRetryStrategy {
             // by default, use a random base for exponential backoff
             base: fastrand::f64,
             initial_backoff: Duration::from_secs(1),
-            retry_permit: Mutex::new(None),
         }
     }
 }
 
 impl RetryStrategy for StandardRetryStrategy {
+    fn on_operation_start(&self, _runtime_components: &RuntimeComponents, cfg: &mut ConfigBag) {
+        cfg.interceptor_state().store_put(RetryPermit::default());
+    }
+
     fn should_attempt_initial_request(
         &self,
         runtime_components: &RuntimeComponents,
@@ -209,7 +240,7 @@ impl RetryStrategy for StandardRetryStrategy {
             debug!("request succeeded, no retry necessary");
             if let Some(tb) = token_bucket {
                 // If this retry strategy is holding any permits, release them back to the bucket.
-                if let NoPermitWasReleased = self.release_retry_permit() {
+                if let NoPermitWasReleased = self.release_retry_permit(cfg) {
                     // In the event that there was no retry permit to release, we generate new
                     // permits from nothing. We do this to make up for permits we had to "forget".
                     // Otherwise, repeated retries would empty the bucket and nothing could fill it
@@ -258,6 +289,13 @@ impl RetryStrategy for StandardRetryStrategy {
 
         Ok(ShouldAttempt::YesAfterDelay(backoff))
     }
+
+    fn on_cancellation(&self, _runtime_components: &RuntimeComponents, cfg: &ConfigBag) {
+        // The retry that the operation's permit paid for is never going to complete, so return it to the bucket.
+        if let APermitWasReleased = self.release_retry_permit(cfg) {
+            debug!("operation was cancelled; released the retry permit back to the token bucket");
+        }
+    }
 }
 
 fn update_rate_limiter_if_exists(
@@ -469,7 +507,8 @@ mod tests {
             ))
             .build()
             .unwrap();
-        let cfg = ConfigBag::base();
+        let mut cfg = ConfigBag::base();
+        StandardRetryStrategy::default().on_operation_start(&rc, &mut cfg);
         let mut ctx = InterceptorContext::new(TypeErasedBox::doesnt_matter());
         // This type doesn't matter b/c the classifier will just return whatever we tell it to.
         ctx.set_output_or_error(Err(OrchestratorError::other("doesn't matter")));
@@ -557,6 +596,71 @@ mod tests {
         assert_eq!(token_bucket.available_permits(), 0);
     }
 
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn cancellation_releases_retry_permit() {
+        let (mut cfg, rc, ctx) = setup_test(vec![RetryReason::Error(ErrorKind::ServerError)]);
+        let strategy = StandardRetryStrategy::default()
+            .with_base(|| 1.0)
+            .with_max_attempts(5);
+        cfg.interceptor_state().store_put(TokenBucket::default());
+        let token_bucket = cfg.load::<TokenBucket>().unwrap().clone();
+
+        cfg.interceptor_state().store_put(RequestAttempts::new(1));
+        let should_retry = strategy.should_attempt_retry(&ctx, &rc, &cfg).unwrap();
+        should_retry.expect_delay();
+        assert_eq!(token_bucket.available_permits(), 495);
+
+        strategy.on_cancellation(&rc, &cfg);
+        assert_eq!(token_bucket.available_permits(), 500);
+
+        // Nothing is left to release the second time around
+        strategy.on_cancellation(&rc, &cfg);
+        assert_eq!(token_bucket.available_permits(), 500);
+    }
+
+    #[cfg(feature = "test-util")]
+    #[test]
+    fn cancellation_only_releases_the_permit_of_the_cancelled_operation() {
+        let (mut first, rc, ctx) = setup_test(vec![RetryReason::Error(ErrorKind::ServerError)]);
+        let (mut second, _, _) = setup_test(vec![]);
+        let strategy = StandardRetryStrategy::default()
+            .with_base(|| 1.0)
+            .with_max_attempts(5);
+        let token_bucket = TokenBucket::default();
+        for cfg in [&mut first, &mut second] {
+            cfg.interceptor_state().store_put(token_bucket.clone());
+            cfg.interceptor_state().store_put(RequestAttempts::new(1));
+        }
+
+        // Both operations are retrying at the same time, each holding a permit
+        strategy
+            .should_attempt_retry(&ctx, &rc, &first)
+            .unwrap()
+            .expect_delay();
+        strategy
+            .should_attempt_retry(&ctx, &rc, &second)
+            .unwrap()
+            .expect_delay();
+        assert_eq!(token_bucket.available_permits(), 490);
+
+        // Cancelling the first operation doesn't touch the permit of the second
+        strategy.on_cancellation(&rc, &first);
+        assert_eq!(token_bucket.available_permits(), 495);
+        strategy.on_cancellation(&rc, &first);
+        assert_eq!(token_bucket.available_permits(), 495);
+        assert!(second
+            .load::<RetryPermit>()
+            .unwrap()
+            .0
+            .lock()
+            .unwrap()
+            .is_some());
+
+        strategy.on_cancellation(&rc, &second);
+        assert_eq!(token_bucket.available_permits(), 500);
+    }
+
     #[cfg(feature = "test-util")]
     #[test]
     fn quota_replenishes_on_success() {
@@ -621,7 +725,14 @@ mod tests {
         }
 
         // Forget the permit so that we can only refill by "success on first try".
-        let permit = strategy.retry_permit.lock().unwrap().take().unwrap();
+        let permit = cfg
+            .load::<RetryPermit>()
+            .unwrap()
+            .0
+            .lock()
+            .unwrap()
+            .take()
+            .unwrap();
         permit.forget();
 
         ctx.set_output_or_error(Ok(TypeErasedBox::doesnt_matter()));
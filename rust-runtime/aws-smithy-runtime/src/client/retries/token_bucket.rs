@@ -106,7 +106,7 @@ impl TokenBucket {
         }
     }
 
-    #[cfg(all(test, feature = "test-util"))]
+    #[cfg(feature = "test-util")]
     pub(crate) fn available_permits(&self) -> usize {
         self.semaphore.available_permits()
     }
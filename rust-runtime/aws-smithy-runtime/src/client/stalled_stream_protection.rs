@@ -0,0 +1,678 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Stalled stream protection.
+//!
+//! Operation and attempt timeouts put an upper bound on how long a request can take, but they
+//! can't tell a large transfer that is making steady progress apart from one that has stopped
+//! making progress. Stalled stream protection monitors the throughput of request and response
+//! bodies, and fails the attempt with a retryable error when that throughput drops below the
+//! minimum set in [`StalledStreamProtectionConfig`] for longer than its grace period.
+//!
+//! Only time spent waiting on the other side of the connection counts towards a stall. For
+//! downloads, that is the time spent waiting for the response body to produce data. For uploads,
+//! it is the time spent waiting for the connection to accept more data from the request body.
+//! A slow consumer of a response body, or a slow producer of a request body, will never cause a
+//! stream to be considered stalled.
+//!
+//! Stalled stream protection is only enabled when a [`StalledStreamProtectionConfig`] is placed
+//! in the config bag and the runtime components include a sleep implementation.
+
+use aws_smithy_async::future::timeout::TimedOutError;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, Sleep};
+use aws_smithy_async::time::SharedTimeSource;
+use aws_smithy_http::body::{BoxBody, Error as BodyError, SdkBody};
+use aws_smithy_http::result::ConnectorError;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::orchestrator::{HttpRequest, HttpResponse};
+use aws_smithy_runtime_api::client::runtime_components::RuntimeComponents;
+use aws_smithy_types::config_bag::ConfigBag;
+use aws_smithy_types::stalled_stream_protection::StalledStreamProtectionConfig;
+use bytes::Bytes;
+use http::HeaderMap;
+use http_body::{Body, SizeHint};
+use pin_project_lite::pin_project;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime};
+
+/// The direction of a transfer monitored by stalled stream protection.
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TransferDirection {
+    /// The request body being sent to the service.
+    Upload,
+    /// The response body being received from the service.
+    Download,
+}
+
+impl fmt::Display for TransferDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransferDirection::Upload => write!(f, "upload"),
+            TransferDirection::Download => write!(f, "download"),
+        }
+    }
+}
+
+/// Error returned when the throughput of a request or response body drops below the configured
+/// minimum for longer than the grace period.
+///
+/// The [source](std::error::Error::source) of this error is a [`TimedOutError`] so that it is
+/// classified as a retryable timeout.
+#[derive(Debug)]
+pub struct StalledStreamError {
+    direction: TransferDirection,
+    bytes_transferred: u64,
+    window: Duration,
+    minimum_throughput: u64,
+    source: TimedOutError,
+}
+
+impl StalledStreamError {
+    /// Returns the direction of the transfer that stalled.
+    pub fn direction(&self) -> TransferDirection {
+        self.direction
+    }
+
+    /// Returns the number of bytes transferred during the window in which the stall was detected.
+    pub fn bytes_transferred(&self) -> u64 {
+        self.bytes_transferred
+    }
+
+    /// Returns the length of the window in which the stall was detected.
+    pub fn window(&self) -> Duration {
+        self.window
+    }
+
+    /// Returns the configured minimum throughput in bytes per second.
+    pub fn minimum_throughput(&self) -> u64 {
+        self.minimum_throughput
+    }
+}
+
+impl fmt::Display for StalledStreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} stalled: {} bytes were transferred in {:?}, which is below the minimum throughput of {} bytes per second",
+            self.direction, self.bytes_transferred, self.window, self.minimum_throughput
+        )
+    }
+}
+
+impl std::error::Error for StalledStreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[derive(Debug, Default)]
+struct Progress {
+    /// Set while time counts towards a stall.
+    counting_since: Option<SystemTime>,
+    /// Time counted towards a stall in the current window.
+    window_elapsed: Duration,
+    /// Bytes transferred in the current window.
+    window_bytes: u64,
+    /// Set once the stream has been fully transferred.
+    complete: bool,
+}
+
+impl Progress {
+    fn accrue(&mut self, now: SystemTime) {
+        if let Some(since) = self.counting_since {
+            self.window_elapsed += now.duration_since(since).unwrap_or_default();
+            self.counting_since = Some(now);
+        }
+    }
+}
+
+/// Tracks the throughput of a single body.
+///
+/// Throughput is evaluated in windows that last for the grace period. At the end of each window,
+/// the stream is considered stalled if the bytes transferred in that window fall short of the
+/// minimum throughput.
+#[derive(Clone, Debug)]
+struct ThroughputMonitor {
+    direction: TransferDirection,
+    grace_period: Duration,
+    minimum_throughput: u64,
+    time_source: SharedTimeSource,
+    progress: Arc<Mutex<Progress>>,
+}
+
+impl ThroughputMonitor {
+    fn new(
+        direction: TransferDirection,
+        config: &StalledStreamProtectionConfig,
+        time_source: SharedTimeSource,
+    ) -> Self {
+        Self {
+            direction,
+            grace_period: config.grace_period(),
+            minimum_throughput: config.minimum_throughput(),
+            time_source,
+            progress: Default::default(),
+        }
+    }
+
+    fn set_counting(&self, counting: bool) {
+        let now = self.time_source.now();
+        let mut progress = self.progress.lock().unwrap();
+        progress.accrue(now);
+        progress.counting_since = if counting { Some(now) } else { None };
+    }
+
+    fn record(&self, bytes: usize) {
+        self.progress.lock().unwrap().window_bytes += bytes as u64;
+    }
+
+    fn finish(&self) {
+        let mut progress = self.progress.lock().unwrap();
+        progress.counting_since = None;
+        progress.complete = true;
+    }
+
+    /// Returns an error if the stream has stalled. Otherwise, returns how long to wait before
+    /// checking again, or `None` if the stream is complete.
+    fn check(&self) -> Result<Option<Duration>, StalledStreamError> {
+        let now = self.time_source.now();
+        let mut progress = self.progress.lock().unwrap();
+        if progress.complete {
+            return Ok(None);
+        }
+        progress.accrue(now);
+        if progress.window_elapsed >= self.grace_period {
+            let transferred = u128::from(progress.window_bytes) * 1_000_000_000;
+            let required = u128::from(self.minimum_throughput) * progress.window_elapsed.as_nanos();
+            if transferred < required {
+                return Err(StalledStreamError {
+                    direction: self.direction,
+                    bytes_transferred: progress.window_bytes,
+                    window: progress.window_elapsed,
+                    minimum_throughput: self.minimum_throughput,
+                    source: TimedOutError,
+                });
+            }
+            progress.window_elapsed = Duration::ZERO;
+            progress.window_bytes = 0;
+        }
+        Ok(Some(self.grace_period - progress.window_elapsed))
+    }
+
+    /// Resolves with an error once the stream stalls. Never resolves if it completes.
+    async fn stalled(self, sleep_impl: SharedAsyncSleep) -> StalledStreamError {
+        loop {
+            match self.check() {
+                Ok(Some(next_check)) => sleep_impl.sleep(next_check).await,
+                Ok(None) => std::future::pending::<()>().await,
+                Err(err) => return err,
+            }
+        }
+    }
+}
+
+pin_project! {
+    /// Body wrapper that reports progress to a [`ThroughputMonitor`].
+    ///
+    /// Downloads are checked by the body itself since the consumer is waiting on it. Uploads are
+    /// checked by [`UploadMonitor`] since the connection stops polling the body when it stalls.
+    struct MinimumThroughputBody<B> {
+        #[pin]
+        inner: B,
+        monitor: ThroughputMonitor,
+        sleep_impl: SharedAsyncSleep,
+        // `BoxBody` requires bodies to be `Sync`, which `Sleep` isn't
+        sleep: Mutex<Option<Sleep>>,
+        remaining_len: Option<u64>,
+    }
+}
+
+impl<B> MinimumThroughputBody<B>
+where
+    B: Body,
+{
+    fn new(inner: B, monitor: ThroughputMonitor, sleep_impl: SharedAsyncSleep) -> Self {
+        let remaining_len = inner.size_hint().exact();
+        Self {
+            inner,
+            monitor,
+            sleep_impl,
+            sleep: Mutex::new(None),
+            remaining_len,
+        }
+    }
+}
+
+impl<B> Body for MinimumThroughputBody<B>
+where
+    B: Body<Data = Bytes, Error = BodyError>,
+{
+    type Data = Bytes;
+    type Error = BodyError;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let mut this = self.project();
+        let sleep = this.sleep.get_mut().unwrap();
+        let direction = this.monitor.direction;
+        if direction == TransferDirection::Upload {
+            this.monitor.set_counting(false);
+        }
+        match this.inner.as_mut().poll_data(cx) {
+            Poll::Ready(Some(Ok(data))) => {
+                this.monitor.record(data.len());
+                if let Some(remaining_len) = this.remaining_len.as_mut() {
+                    *remaining_len = remaining_len.saturating_sub(data.len() as u64);
+                }
+                if this.inner.is_end_stream() || *this.remaining_len == Some(0) {
+                    this.monitor.finish();
+                } else if direction == TransferDirection::Upload {
+                    // Until the connection polls for more data, we're waiting on the service
+                    this.monitor.set_counting(true);
+                } else {
+                    this.monitor.set_counting(false);
+                    *sleep = None;
+                    if let Err(err) = this.monitor.check() {
+                        return Poll::Ready(Some(Err(err.into())));
+                    }
+                }
+                Poll::Ready(Some(Ok(data)))
+            }
+            Poll::Ready(None) => {
+                this.monitor.finish();
+                Poll::Ready(None)
+            }
+            Poll::Ready(Some(Err(err))) => {
+                this.monitor.finish();
+                Poll::Ready(Some(Err(err)))
+            }
+            Poll::Pending if direction == TransferDirection::Upload => Poll::Pending,
+            Poll::Pending => {
+                this.monitor.set_counting(true);
+                loop {
+                    let next_check = match this.monitor.check() {
+                        Ok(Some(next_check)) => next_check,
+                        Ok(None) => return Poll::Pending,
+                        Err(err) => return Poll::Ready(Some(Err(err.into()))),
+                    };
+                    let pending_sleep =
+                        sleep.get_or_insert_with(|| this.sleep_impl.sleep(next_check));
+                    match Pin::new(pending_sleep).poll(cx) {
+                        Poll::Ready(()) => *sleep = None,
+                        Poll::Pending => return Poll::Pending,
+                    }
+                }
+            }
+        }
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+        self.project().inner.poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+/// Watches a request body for stalls while the request is being sent.
+#[derive(Debug)]
+pub(crate) struct UploadMonitor {
+    monitor: ThroughputMonitor,
+    sleep_impl: SharedAsyncSleep,
+}
+
+impl UploadMonitor {
+    /// Runs `send_request` until it completes or the request body stalls, whichever is first.
+    ///
+    /// A stall is reported as a [`ConnectorError::timeout`].
+    pub(crate) async fn send<F>(self, send_request: F) -> Result<HttpResponse, BoxError>
+    where
+        F: Future<Output = Result<HttpResponse, BoxError>>,
+    {
+        let stalled = self.monitor.stalled(self.sleep_impl);
+        pin_utils::pin_mut!(send_request);
+        pin_utils::pin_mut!(stalled);
+        std::future::poll_fn(|cx| {
+            if let Poll::Ready(result) = send_request.as_mut().poll(cx) {
+                return Poll::Ready(result);
+            }
+            stalled
+                .as_mut()
+                .poll(cx)
+                .map(|err| Err(ConnectorError::timeout(err.into()).into()))
+        })
+        .await
+    }
+}
+
+/// Applies stalled stream protection to the bodies of a request attempt.
+#[derive(Clone, Debug)]
+pub(crate) struct StalledStreamProtection {
+    config: StalledStreamProtectionConfig,
+    sleep_impl: SharedAsyncSleep,
+    time_source: SharedTimeSource,
+}
+
+impl StalledStreamProtection {
+    /// Returns `None` if stalled stream protection isn't configured, or there is no sleep
+    /// implementation to run it with.
+    pub(crate) fn new(runtime_components: &RuntimeComponents, cfg: &ConfigBag) -> Option<Self> {
+        let config = cfg
+            .load::<StalledStreamProtectionConfig>()
+            .filter(|config| config.is_enabled())?;
+        let sleep_impl = runtime_components.sleep_impl()?;
+        Some(Self {
+            config: config.clone(),
+            sleep_impl,
+            time_source: runtime_components.time_source().unwrap_or_default(),
+        })
+    }
+
+    /// Wraps the request body, returning a monitor that must race the request for stalls to be
+    /// detected. Returns `None` if there is no body to protect.
+    pub(crate) fn protect_request(&self, request: &mut HttpRequest) -> Option<UploadMonitor> {
+        if !self.config.upload_enabled() || request.body().content_length() == Some(0) {
+            return None;
+        }
+        let monitor = ThroughputMonitor::new(
+            TransferDirection::Upload,
+            &self.config,
+            self.time_source.clone(),
+        );
+        self.wrap(request.body_mut(), monitor.clone());
+        Some(UploadMonitor {
+            monitor,
+            sleep_impl: self.sleep_impl.clone(),
+        })
+    }
+
+    /// Wraps the response body so that reading from it fails if it stalls.
+    pub(crate) fn protect_response(&self, response: &mut HttpResponse) {
+        // Bodies that are already in memory can't stall
+        if !self.config.download_enabled() || response.body().bytes().is_some() {
+            return;
+        }
+        let monitor = ThroughputMonitor::new(
+            TransferDirection::Download,
+            &self.config,
+            self.time_source.clone(),
+        );
+        self.wrap(response.body_mut(), monitor);
+    }
+
+    fn wrap(&self, body: &mut SdkBody, monitor: ThroughputMonitor) {
+        let inner = std::mem::replace(body, SdkBody::taken());
+        *body = SdkBody::from_dyn(BoxBody::new(MinimumThroughputBody::new(
+            inner,
+            monitor,
+            self.sleep_impl.clone(),
+        )));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_smithy_async::rt::sleep::TokioSleep;
+    use aws_smithy_async::time::TimeSource;
+    use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+    use aws_smithy_types::config_bag::Layer;
+    use std::collections::VecDeque;
+    use std::time::UNIX_EPOCH;
+
+    /// Time source that follows tokio's (pausable) clock.
+    #[derive(Debug)]
+    struct TokioTime(tokio::time::Instant);
+
+    impl TimeSource for TokioTime {
+        fn now(&self) -> SystemTime {
+            UNIX_EPOCH + self.0.elapsed()
+        }
+    }
+
+    /// Body that yields each chunk after the given delay.
+    struct DelayedChunks {
+        chunks: VecDeque<(Duration, &'static [u8])>,
+        sleep: Option<Pin<Box<tokio::time::Sleep>>>,
+    }
+
+    impl DelayedChunks {
+        fn body(chunks: impl IntoIterator<Item = (Duration, &'static [u8])>) -> SdkBody {
+            SdkBody::from_dyn(BoxBody::new(Self {
+                chunks: chunks.into_iter().collect(),
+                sleep: None,
+            }))
+        }
+    }
+
+    impl Body for DelayedChunks {
+        type Data = Bytes;
+        type Error = BodyError;
+
+        fn poll_data(
+            mut self: Pin<&mut Self>,
+            cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            let (delay, data) = match self.chunks.front() {
+                Some(chunk) => *chunk,
+                None => return Poll::Ready(None),
+            };
+            let sleep = self
+                .sleep
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep(delay)));
+            match sleep.as_mut().poll(cx) {
+                Poll::Ready(()) => {
+                    self.sleep = None;
+                    self.chunks.pop_front();
+                    Poll::Ready(Some(Ok(Bytes::from_static(data))))
+                }
+                Poll::Pending => Poll::Pending,
+            }
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    fn protection(config: StalledStreamProtectionConfig) -> StalledStreamProtection {
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_sleep_impl(Some(SharedAsyncSleep::new(TokioSleep::new())))
+            .with_time_source(Some(SharedTimeSource::new(TokioTime(
+                tokio::time::Instant::now(),
+            ))))
+            .build()
+            .unwrap();
+        let mut layer = Layer::new("test");
+        layer.store_put(config);
+        StalledStreamProtection::new(&runtime_components, &ConfigBag::of_layers(vec![layer]))
+            .expect("protection is configured")
+    }
+
+    fn config() -> StalledStreamProtectionConfig {
+        StalledStreamProtectionConfig::builder()
+            .grace_period(Duration::from_secs(5))
+            .minimum_throughput(4)
+            .build()
+    }
+
+    async fn read_to_end(body: SdkBody) -> Result<usize, BodyError> {
+        pin_utils::pin_mut!(body);
+        let mut len = 0;
+        while let Some(data) = body.data().await {
+            len += data?.len();
+        }
+        Ok(len)
+    }
+
+    fn response(body: SdkBody) -> HttpResponse {
+        http::Response::builder().body(body).unwrap()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn trickling_download_fails() {
+        let protection = protection(config());
+        let mut response = response(DelayedChunks::body(vec![
+            (Duration::from_secs(1), &b"a"[..]);
+            100
+        ]));
+        protection.protect_response(&mut response);
+
+        let start = tokio::time::Instant::now();
+        let err = read_to_end(response.into_body()).await.unwrap_err();
+        let err = err.downcast_ref::<StalledStreamError>().expect("stalled");
+        assert_eq!(TransferDirection::Download, err.direction());
+        assert_eq!(5, err.bytes_transferred());
+        assert_eq!(Duration::from_secs(5), start.elapsed());
+        assert!(std::error::Error::source(err)
+            .unwrap()
+            .is::<TimedOutError>());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn download_that_stops_fails_after_the_grace_period() {
+        let protection = protection(config());
+        let mut response = response(DelayedChunks::body([
+            (
+                Duration::from_millis(100),
+                &b"0123456789abcdefghijklmnopqrstuvwxyz"[..],
+            ),
+            (Duration::from_secs(3600), &b"never"[..]),
+        ]));
+        protection.protect_response(&mut response);
+
+        let start = tokio::time::Instant::now();
+        let err = read_to_end(response.into_body()).await.unwrap_err();
+        let err = err.downcast_ref::<StalledStreamError>().expect("stalled");
+        assert_eq!(0, err.bytes_transferred());
+        // The first window saw enough throughput, the second saw none
+        assert_eq!(Duration::from_millis(10_000), start.elapsed());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn steady_download_succeeds() {
+        let protection = protection(config());
+        let mut response = response(DelayedChunks::body(vec![
+            (
+                Duration::from_secs(1),
+                &b"abcdef"[..]
+            );
+            20
+        ]));
+        protection.protect_response(&mut response);
+
+        assert_eq!(120, read_to_end(response.into_body()).await.unwrap());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn slow_consumers_do_not_stall_downloads() {
+        let protection = protection(config());
+        let mut response = response(DelayedChunks::body(vec![(Duration::ZERO, &b"a"[..]); 3]));
+        protection.protect_response(&mut response);
+
+        let body = response.into_body();
+        pin_utils::pin_mut!(body);
+        while let Some(data) = body.data().await {
+            data.unwrap();
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn in_memory_responses_are_not_wrapped() {
+        let protection = protection(config());
+        let mut response = response(SdkBody::from("hello"));
+        protection.protect_response(&mut response);
+        assert_eq!(Some(&b"hello"[..]), response.body().bytes());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stalled_upload_fails_with_a_timeout() {
+        let protection = protection(config());
+        let mut request = http::Request::builder()
+            .body(DelayedChunks::body(vec![(Duration::ZERO, &b"a"[..]); 10]))
+            .unwrap();
+        let monitor = protection
+            .protect_request(&mut request)
+            .expect("has a body");
+
+        let start = tokio::time::Instant::now();
+        let err = monitor
+            .send(async move {
+                // Send one chunk, then stop polling the body as if the connection stopped
+                // accepting data.
+                let body = request.body_mut();
+                body.data().await.unwrap().unwrap();
+                std::future::pending::<()>().await;
+                unreachable!()
+            })
+            .await
+            .unwrap_err();
+        let err = err
+            .downcast_ref::<ConnectorError>()
+            .expect("connector error");
+        assert!(err.is_timeout());
+        assert_eq!(Duration::from_secs(5), start.elapsed());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn completed_uploads_can_wait_for_a_response() {
+        let protection = protection(config());
+        let mut request = http::Request::builder()
+            .body(DelayedChunks::body([
+                (Duration::ZERO, &b"abc"[..]),
+                (Duration::from_secs(30), &b"def"[..]),
+            ]))
+            .unwrap();
+        let monitor = protection
+            .protect_request(&mut request)
+            .expect("has a body");
+
+        let result = monitor
+            .send(async move {
+                // A slow request body doesn't count towards a stall, and neither does waiting for
+                // the response once the body has been sent.
+                while let Some(data) = request.body_mut().data().await {
+                    data.unwrap();
+                }
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok(response(SdkBody::empty()))
+            })
+            .await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn protection_requires_config() {
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_sleep_impl(Some(SharedAsyncSleep::new(TokioSleep::new())))
+            .build()
+            .unwrap();
+        assert!(StalledStreamProtection::new(&runtime_components, &ConfigBag::base()).is_none());
+
+        let mut layer = Layer::new("test");
+        layer.store_put(StalledStreamProtectionConfig::disabled());
+        let cfg = ConfigBag::of_layers(vec![layer]);
+        assert!(StalledStreamProtection::new(&runtime_components, &cfg).is_none());
+    }
+}
@@ -5,3 +5,4 @@
 
 pub mod deserializer;
 pub mod serializer;
+pub mod simulation;
@@ -0,0 +1,999 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A deterministic simulation harness for the orchestrator.
+//!
+//! A [`Simulation`] runs a single operation against a scripted sequence of responses, latencies
+//! and connection failures. Time is virtual: the harness only advances its clock when every
+//! part of the orchestrator is waiting on a sleep, so backoff delays and timeouts play out
+//! instantly and in the same order on every run. Afterwards, the [`SimulationReport`] describes
+//! the attempt timeline so that tests can assert on backoff delays, the token bucket balance,
+//! the clock skew of the service, and the order in which interceptor hooks fired.
+//!
+//! # Example
+//!
+//! ```rust
+//! use aws_smithy_runtime::client::test_util::simulation::{ScriptedExchange, Simulation};
+//! use aws_smithy_types::retry::RetryConfig;
+//! use std::time::Duration;
+//!
+//! let report = Simulation::builder()
+//!     .retry_config(
+//!         RetryConfig::standard()
+//!             .with_max_attempts(3)
+//!             .with_use_static_exponential_base(true),
+//!     )
+//!     .exchange(ScriptedExchange::status(503).with_latency(Duration::from_millis(100)))
+//!     .exchange(ScriptedExchange::io_error())
+//!     .exchange(ScriptedExchange::status(200))
+//!     .build()
+//!     .run();
+//!
+//! assert!(report.result.is_ok());
+//! assert_eq!(report.attempts.len(), 3);
+//! assert_eq!(
+//!     report.backoff_delays(),
+//!     vec![Duration::from_secs(1), Duration::from_secs(2)]
+//! );
+//! // Each retry after a transient error took 10 permits, and the success returned the last retry's permits.
+//! assert_eq!(report.token_bucket_permits, 490);
+//! ```
+
+use crate::client::auth::no_auth::{NoAuthRuntimePlugin, NO_AUTH_SCHEME_ID};
+use crate::client::orchestrator::endpoints::StaticUriEndpointResolver;
+use crate::client::orchestrator::interceptors::{ServiceClockSkew, ServiceClockSkewInterceptor};
+use crate::client::orchestrator::invoke;
+use crate::client::retries::classifier::{
+    HttpStatusCodeClassifier, ModeledAsRetryableClassifier, SmithyErrorClassifier,
+};
+use crate::client::retries::strategy::{NeverRetryStrategy, StandardRetryStrategy};
+use crate::client::retries::TokenBucket;
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::test_util::{tick_advance_time_and_sleep, TickAdvanceSleep, TickAdvanceTime};
+use aws_smithy_async::time::{SharedTimeSource, TimeSource};
+use aws_smithy_http::body::SdkBody;
+use aws_smithy_http::result::{ConnectorError, SdkError};
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::auth::option_resolver::StaticAuthOptionResolver;
+use aws_smithy_runtime_api::client::auth::{AuthOptionResolverParams, SharedAuthOptionResolver};
+use aws_smithy_runtime_api::client::connectors::{Connector, SharedConnector};
+use aws_smithy_runtime_api::client::interceptors::context::{
+    AfterDeserializationInterceptorContextRef, BeforeDeserializationInterceptorContextMut,
+    BeforeDeserializationInterceptorContextRef, BeforeSerializationInterceptorContextMut,
+    BeforeSerializationInterceptorContextRef, BeforeTransmitInterceptorContextMut,
+    BeforeTransmitInterceptorContextRef, Error, FinalizerInterceptorContextMut,
+    FinalizerInterceptorContextRef, Input, InterceptorContext, Output,
+};
+use aws_smithy_runtime_api::client::interceptors::{Interceptor, SharedInterceptor};
+use aws_smithy_runtime_api::client::orchestrator::{
+    BoxFuture, DynResponseDeserializer, EndpointResolverParams, HttpRequest, HttpResponse,
+    OrchestratorError, RequestSerializer, ResponseDeserializer, SharedEndpointResolver,
+    SharedRequestSerializer,
+};
+use aws_smithy_runtime_api::client::request_attempts::RequestAttempts;
+use aws_smithy_runtime_api::client::retries::{
+    RetryClassifiers, RetryStrategy, SharedRetryStrategy, ShouldAttempt,
+};
+use aws_smithy_runtime_api::client::runtime_components::{
+    RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_runtime_api::client::runtime_plugin::{
+    RuntimePlugin, RuntimePlugins, SharedRuntimePlugin, StaticRuntimePlugin,
+};
+use aws_smithy_types::config_bag::{ConfigBag, Layer};
+use aws_smithy_types::date_time::{DateTime, Format};
+use aws_smithy_types::retry::{ErrorKind, ProvideErrorKind, RetryConfig};
+use aws_smithy_types::timeout::TimeoutConfig;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Wake, Waker};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// One scripted exchange between the connector and the simulated service.
+///
+/// Each attempt that reaches the transmit phase consumes the next exchange in the script.
+#[derive(Debug)]
+pub struct ScriptedExchange {
+    latency: Duration,
+    outcome: Result<HttpResponse, ConnectorError>,
+}
+
+impl ScriptedExchange {
+    /// The service replies with `response`.
+    pub fn response(response: HttpResponse) -> Self {
+        Self {
+            latency: Duration::ZERO,
+            outcome: Ok(response),
+        }
+    }
+
+    /// The service replies with an empty response with the given status code.
+    pub fn status(status: u16) -> Self {
+        Self::response(
+            http::Response::builder()
+                .status(status)
+                .body(SdkBody::empty())
+                .expect("valid response"),
+        )
+    }
+
+    /// The connector fails with `error` instead of returning a response.
+    pub fn connector_error(error: ConnectorError) -> Self {
+        Self {
+            latency: Duration::ZERO,
+            outcome: Err(error),
+        }
+    }
+
+    /// The connection fails with an IO error instead of returning a response.
+    pub fn io_error() -> Self {
+        Self::connector_error(ConnectorError::io("simulated connection failure".into()))
+    }
+
+    /// Sets how long the exchange takes on the virtual clock before it completes.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Sets the `Date` header of the response to `server_time`, so that the operation can
+    /// work out how far the service's clock is ahead of the virtual clock.
+    ///
+    /// Has no effect if the exchange fails instead of returning a response.
+    pub fn with_server_time(mut self, server_time: SystemTime) -> Self {
+        if let Ok(response) = &mut self.outcome {
+            let date = DateTime::from(server_time)
+                .fmt(Format::HttpDate)
+                .expect("server time is a valid HTTP date");
+            response.headers_mut().insert(
+                "date",
+                date.parse().expect("HTTP dates are valid header values"),
+            );
+        }
+        self
+    }
+}
+
+/// The error that the simulated operation fails with when the service replies with a
+/// non-success status code.
+///
+/// A `429 Too Many Requests` status is modeled as a throttling error.
+#[derive(Debug)]
+pub struct SimulatedServiceError {
+    status: u16,
+}
+
+impl SimulatedServiceError {
+    /// Returns the status code the service replied with.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+}
+
+impl fmt::Display for SimulatedServiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the simulated service replied with status {}",
+            self.status
+        )
+    }
+}
+
+impl std::error::Error for SimulatedServiceError {}
+
+impl ProvideErrorKind for SimulatedServiceError {
+    fn retryable_error_kind(&self) -> Option<ErrorKind> {
+        (self.status == 429).then_some(ErrorKind::ThrottlingError)
+    }
+
+    fn code(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// What happened during a single request attempt.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AttemptRecord {
+    /// The attempt number, starting at 1.
+    pub attempt: u32,
+    /// When the attempt started, relative to the start of the simulation.
+    pub started_at: Duration,
+    /// When the retry strategy was asked whether to retry this attempt, relative to the start
+    /// of the simulation. `None` if the operation ended before the retry strategy was consulted.
+    pub ended_at: Option<Duration>,
+    /// The status code of the response, if one was received.
+    pub response_status: Option<u16>,
+    /// What the retry strategy decided after this attempt.
+    pub retry_decision: Option<ShouldAttempt>,
+    /// The number of permits left in the token bucket once the retry strategy made its decision.
+    pub token_bucket_permits: Option<usize>,
+    /// The clock skew of the service known once the retry strategy made its decision, based on
+    /// the `Date` header of the latest response that had one.
+    pub clock_skew: Option<Duration>,
+}
+
+/// The outcome of a [`Simulation`] run.
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct SimulationReport {
+    /// The result of the simulated operation.
+    pub result: Result<Output, SdkError<Error, HttpResponse>>,
+    /// The request attempts that were made, in order.
+    pub attempts: Vec<AttemptRecord>,
+    /// The interceptor hooks that fired, in order.
+    pub hooks: Vec<&'static str>,
+    /// How much virtual time the operation took.
+    pub elapsed: Duration,
+    /// The number of permits left in the token bucket when the operation completed.
+    pub token_bucket_permits: usize,
+    /// The number of scripted exchanges that were never used.
+    pub unused_exchanges: usize,
+}
+
+impl SimulationReport {
+    /// Returns the time that passed between the end of each attempt and the start of the next.
+    pub fn backoff_delays(&self) -> Vec<Duration> {
+        self.attempts
+            .windows(2)
+            .map(|pair| {
+                let ended_at = pair[0]
+                    .ended_at
+                    .expect("the retry strategy is consulted before every retry");
+                pair[1].started_at - ended_at
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Default)]
+struct RecordedTimeline {
+    attempts: Vec<AttemptRecord>,
+    hooks: Vec<&'static str>,
+}
+
+#[derive(Clone, Debug)]
+struct TimelineRecorder {
+    start_time: SystemTime,
+    time_source: TickAdvanceTime,
+    timeline: Arc<Mutex<RecordedTimeline>>,
+}
+
+impl TimelineRecorder {
+    fn elapsed(&self) -> Duration {
+        self.time_source
+            .now()
+            .duration_since(self.start_time)
+            .expect("the virtual clock never goes backwards")
+    }
+
+    fn record_hook(&self, hook: &'static str) {
+        self.timeline.lock().unwrap().hooks.push(hook);
+    }
+
+    fn start_attempt(&self, cfg: &ConfigBag) {
+        let attempt = current_attempt(cfg);
+        let started_at = self.elapsed();
+        self.timeline.lock().unwrap().attempts.push(AttemptRecord {
+            attempt,
+            started_at,
+            ended_at: None,
+            response_status: None,
+            retry_decision: None,
+            token_bucket_permits: None,
+            clock_skew: None,
+        });
+    }
+
+    fn end_attempt(
+        &self,
+        ctx: &InterceptorContext,
+        cfg: &ConfigBag,
+        retry_decision: Option<ShouldAttempt>,
+    ) {
+        let attempt = current_attempt(cfg);
+        let ended_at = self.elapsed();
+        let mut timeline = self.timeline.lock().unwrap();
+        if let Some(record) = timeline
+            .attempts
+            .iter_mut()
+            .rev()
+            .find(|record| record.attempt == attempt)
+        {
+            record.ended_at = Some(ended_at);
+            record.response_status = ctx.response().map(|response| response.status().as_u16());
+            record.retry_decision = retry_decision;
+            record.token_bucket_permits = token_bucket_permits(cfg);
+            record.clock_skew = cfg.load::<ServiceClockSkew>().map(ServiceClockSkew::skew);
+        }
+    }
+}
+
+fn current_attempt(cfg: &ConfigBag) -> u32 {
+    cfg.load::<RequestAttempts>()
+        .map(RequestAttempts::attempts)
+        .unwrap_or_default()
+}
+
+fn token_bucket_permits(cfg: &ConfigBag) -> Option<usize> {
+    cfg.load::<TokenBucket>()
+        .map(TokenBucket::available_permits)
+}
+
+/// Records every interceptor hook, as well as when each attempt starts.
+#[derive(Debug)]
+struct TimelineInterceptor {
+    recorder: TimelineRecorder,
+}
+
+macro_rules! record_hooks {
+    ($($hook:ident($ctx:ty);)+) => {
+        $(
+            fn $hook(
+                &self,
+                _context: $ctx,
+                _runtime_components: &RuntimeComponents,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                self.recorder.record_hook(stringify!($hook));
+                Ok(())
+            }
+        )+
+    };
+}
+
+impl Interceptor for TimelineInterceptor {
+    fn read_before_execution(
+        &self,
+        _context: &BeforeSerializationInterceptorContextRef<'_>,
+        _cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        self.recorder.record_hook("read_before_execution");
+        Ok(())
+    }
+
+    fn read_before_attempt(
+        &self,
+        _context: &BeforeTransmitInterceptorContextRef<'_>,
+        _runtime_components: &RuntimeComponents,
+        cfg: &mut ConfigBag,
+    ) -> Result<(), BoxError> {
+        self.recorder.start_attempt(cfg);
+        self.recorder.record_hook("read_before_attempt");
+        Ok(())
+    }
+
+    record_hooks! {
+        modify_before_serialization(&mut BeforeSerializationInterceptorContextMut<'_>);
+        read_before_serialization(&BeforeSerializationInterceptorContextRef<'_>);
+        read_after_serialization(&BeforeTransmitInterceptorContextRef<'_>);
+        modify_before_retry_loop(&mut BeforeTransmitInterceptorContextMut<'_>);
+        modify_before_signing(&mut BeforeTransmitInterceptorContextMut<'_>);
+        read_before_signing(&BeforeTransmitInterceptorContextRef<'_>);
+        read_after_signing(&BeforeTransmitInterceptorContextRef<'_>);
+        modify_before_transmit(&mut BeforeTransmitInterceptorContextMut<'_>);
+        read_before_transmit(&BeforeTransmitInterceptorContextRef<'_>);
+        read_after_transmit(&BeforeDeserializationInterceptorContextRef<'_>);
+        modify_before_deserialization(&mut BeforeDeserializationInterceptorContextMut<'_>);
+        read_before_deserialization(&BeforeDeserializationInterceptorContextRef<'_>);
+        read_after_deserialization(&AfterDeserializationInterceptorContextRef<'_>);
+        modify_before_attempt_completion(&mut FinalizerInterceptorContextMut<'_>);
+        read_after_attempt(&FinalizerInterceptorContextRef<'_>);
+        modify_before_completion(&mut FinalizerInterceptorContextMut<'_>);
+        read_after_execution(&FinalizerInterceptorContextRef<'_>);
+    }
+}
+
+/// Delegates to the configured retry strategy, recording each of its decisions.
+#[derive(Debug)]
+struct RecordingRetryStrategy {
+    inner: SharedRetryStrategy,
+    recorder: TimelineRecorder,
+}
+
+impl RetryStrategy for RecordingRetryStrategy {
+    fn on_operation_start(&self, runtime_components: &RuntimeComponents, cfg: &mut ConfigBag) {
+        self.inner.on_operation_start(runtime_components, cfg)
+    }
+
+    fn should_attempt_initial_request(
+        &self,
+        runtime_components: &RuntimeComponents,
+        cfg: &ConfigBag,
+    ) -> Result<ShouldAttempt, BoxError> {
+        self.inner
+            .should_attempt_initial_request(runtime_components, cfg)
+    }
+
+    fn should_attempt_retry(
+        &self,
+        context: &InterceptorContext,
+        runtime_components: &RuntimeComponents,
+        cfg: &ConfigBag,
+    ) -> Result<ShouldAttempt, BoxError> {
+        let decision = self
+            .inner
+            .should_attempt_retry(context, runtime_components, cfg);
+        self.recorder
+            .end_attempt(context, cfg, decision.as_ref().ok().cloned());
+        decision
+    }
+
+    fn on_cancellation(&self, runtime_components: &RuntimeComponents, cfg: &ConfigBag) {
+        self.inner.on_cancellation(runtime_components, cfg)
+    }
+}
+
+/// Replies to each request with the next scripted exchange, after its latency has passed on
+/// the virtual clock.
+#[derive(Debug)]
+struct ScriptedConnector {
+    exchanges: Arc<Mutex<VecDeque<ScriptedExchange>>>,
+    sleep_impl: TickAdvanceSleep,
+}
+
+impl Connector for ScriptedConnector {
+    fn call(&self, _request: HttpRequest) -> BoxFuture<HttpResponse> {
+        let exchange = self.exchanges.lock().unwrap().pop_front();
+        let sleep_impl = self.sleep_impl.clone();
+        Box::pin(async move {
+            let exchange = exchange.ok_or_else(|| {
+                ConnectorError::other("the simulation ran out of scripted exchanges".into(), None)
+            })?;
+            if !exchange.latency.is_zero() {
+                sleep_impl.sleep(exchange.latency).await;
+            }
+            exchange.outcome.map_err(BoxError::from)
+        })
+    }
+}
+
+#[derive(Debug)]
+struct SimulatedRequestSerializer;
+
+impl RequestSerializer for SimulatedRequestSerializer {
+    fn serialize_input(
+        &self,
+        _input: Input,
+        _cfg: &mut ConfigBag,
+    ) -> Result<HttpRequest, BoxError> {
+        Ok(http::Request::builder()
+            .body(SdkBody::empty())
+            .expect("valid request"))
+    }
+}
+
+#[derive(Debug)]
+struct SimulatedResponseDeserializer;
+
+impl ResponseDeserializer for SimulatedResponseDeserializer {
+    fn deserialize_nonstreaming(
+        &self,
+        response: &HttpResponse,
+    ) -> Result<Output, OrchestratorError<Error>> {
+        if response.status().is_success() {
+            Ok(Output::new(()))
+        } else {
+            Err(OrchestratorError::operation(Error::new(
+                SimulatedServiceError {
+                    status: response.status().as_u16(),
+                },
+            )))
+        }
+    }
+}
+
+/// Builder for [`Simulation`].
+#[derive(Debug)]
+pub struct SimulationBuilder {
+    start_time: SystemTime,
+    exchanges: VecDeque<ScriptedExchange>,
+    retry_config: RetryConfig,
+    timeout_config: Option<TimeoutConfig>,
+    token_bucket: TokenBucket,
+    interceptors: Vec<SharedInterceptor>,
+    runtime_plugins: Vec<SharedRuntimePlugin>,
+}
+
+impl Default for SimulationBuilder {
+    fn default() -> Self {
+        Self {
+            start_time: UNIX_EPOCH,
+            exchanges: VecDeque::new(),
+            retry_config: RetryConfig::standard().with_use_static_exponential_base(true),
+            timeout_config: None,
+            token_bucket: TokenBucket::default(),
+            interceptors: Vec::new(),
+            runtime_plugins: Vec::new(),
+        }
+    }
+}
+
+impl SimulationBuilder {
+    /// Creates a new builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the time that the virtual clock starts at.
+    ///
+    /// Defaults to the UNIX epoch.
+    pub fn start_time(mut self, start_time: SystemTime) -> Self {
+        self.start_time = start_time;
+        self
+    }
+
+    /// Adds an exchange to the end of the script.
+    pub fn exchange(mut self, exchange: ScriptedExchange) -> Self {
+        self.exchanges.push_back(exchange);
+        self
+    }
+
+    /// Adds several exchanges to the end of the script.
+    pub fn exchanges(mut self, exchanges: impl IntoIterator<Item = ScriptedExchange>) -> Self {
+        self.exchanges.extend(exchanges);
+        self
+    }
+
+    /// Sets the retry config used to create a standard retry strategy.
+    ///
+    /// Defaults to the standard retry config with a static exponential base. Backoff delays
+    /// are only deterministic when the static exponential base is used.
+    pub fn retry_config(mut self, retry_config: RetryConfig) -> Self {
+        self.retry_config = retry_config;
+        self
+    }
+
+    /// Sets the timeout config for the operation.
+    pub fn timeout_config(mut self, timeout_config: TimeoutConfig) -> Self {
+        self.timeout_config = Some(timeout_config);
+        self
+    }
+
+    /// Sets the token bucket that retries draw from.
+    ///
+    /// Defaults to a token bucket with the default capacity.
+    pub fn token_bucket(mut self, token_bucket: TokenBucket) -> Self {
+        self.token_bucket = token_bucket;
+        self
+    }
+
+    /// Adds an interceptor that runs after the interceptor that records the timeline.
+    pub fn interceptor(mut self, interceptor: impl Interceptor + Send + Sync + 'static) -> Self {
+        self.interceptors.push(SharedInterceptor::new(interceptor));
+        self
+    }
+
+    /// Adds an operation runtime plugin that is applied after the simulation's own plugins,
+    /// so it can override any of their config or components.
+    pub fn runtime_plugin(mut self, runtime_plugin: impl RuntimePlugin + 'static) -> Self {
+        self.runtime_plugins
+            .push(SharedRuntimePlugin::new(runtime_plugin));
+        self
+    }
+
+    /// Builds the simulation.
+    pub fn build(self) -> Simulation {
+        Simulation { builder: self }
+    }
+}
+
+/// A deterministic simulation of a single operation.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug)]
+pub struct Simulation {
+    builder: SimulationBuilder,
+}
+
+impl Simulation {
+    /// Returns a builder for a simulation.
+    pub fn builder() -> SimulationBuilder {
+        SimulationBuilder::new()
+    }
+
+    /// Runs the operation to completion on the virtual clock.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the operation gets stuck waiting on something other than the virtual clock,
+    /// since nothing else can make progress during a simulation.
+    pub fn run(self) -> SimulationReport {
+        let builder = self.builder;
+        let (time_source, sleep_impl) = tick_advance_time_and_sleep(builder.start_time);
+        let recorder = TimelineRecorder {
+            start_time: builder.start_time,
+            time_source: time_source.clone(),
+            timeline: Default::default(),
+        };
+        let exchanges = Arc::new(Mutex::new(builder.exchanges));
+
+        let retry_strategy = if builder.retry_config.has_retry() {
+            SharedRetryStrategy::new(StandardRetryStrategy::new(&builder.retry_config))
+        } else {
+            SharedRetryStrategy::new(NeverRetryStrategy::new())
+        };
+        let mut runtime_components = RuntimeComponentsBuilder::new("Simulation")
+            .with_connector(Some(SharedConnector::new(ScriptedConnector {
+                exchanges: exchanges.clone(),
+                sleep_impl: sleep_impl.clone(),
+            })))
+            .with_endpoint_resolver(Some(SharedEndpointResolver::new(
+                StaticUriEndpointResolver::http_localhost(8080),
+            )))
+            .with_auth_option_resolver(Some(SharedAuthOptionResolver::new(
+                StaticAuthOptionResolver::new(vec![NO_AUTH_SCHEME_ID]),
+            )))
+            .with_retry_strategy(Some(SharedRetryStrategy::new(RecordingRetryStrategy {
+                inner: retry_strategy,
+                recorder: recorder.clone(),
+            })))
+            .with_retry_classifiers(Some(
+                RetryClassifiers::new()
+                    .with_classifier(SmithyErrorClassifier::<SimulatedServiceError>::new())
+                    .with_classifier(ModeledAsRetryableClassifier::<SimulatedServiceError>::new())
+                    .with_classifier(HttpStatusCodeClassifier::default()),
+            ))
+            .with_time_source(Some(SharedTimeSource::new(time_source.clone())))
+            .with_sleep_impl(Some(SharedAsyncSleep::new(sleep_impl)))
+            .with_interceptor(SharedInterceptor::new(TimelineInterceptor {
+                recorder: recorder.clone(),
+            }))
+            .with_interceptor(SharedInterceptor::new(ServiceClockSkewInterceptor::new()));
+        for interceptor in builder.interceptors {
+            runtime_components.push_interceptor(interceptor);
+        }
+
+        let mut config = Layer::new("Simulation");
+        config.store_put(AuthOptionResolverParams::new("simulation"));
+        config.store_put(EndpointResolverParams::new("simulation"));
+        config.store_put(SharedRequestSerializer::new(SimulatedRequestSerializer));
+        config.store_put(DynResponseDeserializer::new(SimulatedResponseDeserializer));
+        config.store_put(builder.token_bucket.clone());
+        if let Some(timeout_config) = builder.timeout_config {
+            config.store_put(timeout_config);
+        }
+
+        let mut runtime_plugins = RuntimePlugins::new()
+            .with_operation_plugin(
+                StaticRuntimePlugin::new()
+                    .with_config(config.freeze())
+                    .with_runtime_components(runtime_components),
+            )
+            .with_operation_plugin(NoAuthRuntimePlugin::new());
+        for runtime_plugin in builder.runtime_plugins {
+            runtime_plugins = runtime_plugins.with_operation_plugin(runtime_plugin);
+        }
+
+        let result = run_on_virtual_clock(
+            &time_source,
+            invoke("simulation", "simulation", Input::new(()), &runtime_plugins),
+        );
+
+        let timeline = std::mem::take(&mut *recorder.timeline.lock().unwrap());
+        let unused_exchanges = exchanges.lock().unwrap().len();
+        SimulationReport {
+            result,
+            attempts: timeline.attempts,
+            hooks: timeline.hooks,
+            elapsed: recorder.elapsed(),
+            token_bucket_permits: builder.token_bucket.available_permits(),
+            unused_exchanges,
+        }
+    }
+}
+
+/// Wakes the simulation driver by setting a flag.
+#[derive(Debug, Default)]
+struct FlagWaker {
+    woken: AtomicBool,
+}
+
+impl Wake for FlagWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.woken.store(true, Ordering::SeqCst);
+    }
+}
+
+/// Polls `future` to completion, advancing the virtual clock to the next pending sleep whenever
+/// nothing else can make progress.
+fn run_on_virtual_clock<F: Future>(time_source: &TickAdvanceTime, future: F) -> F::Output {
+    let flag = Arc::new(FlagWaker::default());
+    let waker = Waker::from(flag.clone());
+    let mut cx = Context::from_waker(&waker);
+    pin_utils::pin_mut!(future);
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        if flag.woken.swap(false, Ordering::SeqCst) {
+            continue;
+        }
+        if time_source.advance_to_next_sleep().is_none() {
+            panic!("the simulation stalled: the operation is waiting on something other than the virtual clock");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn standard_retries(max_attempts: u32) -> RetryConfig {
+        RetryConfig::standard()
+            .with_max_attempts(max_attempts)
+            .with_use_static_exponential_base(true)
+    }
+
+    #[test]
+    fn success_on_first_attempt() {
+        let report = Simulation::builder()
+            .exchange(ScriptedExchange::status(200).with_latency(Duration::from_millis(250)))
+            .build()
+            .run();
+        assert!(report.result.is_ok());
+        assert_eq!(report.elapsed, Duration::from_millis(250));
+        assert_eq!(report.attempts.len(), 1);
+        assert_eq!(report.attempts[0].response_status, Some(200));
+        assert_eq!(report.attempts[0].retry_decision, Some(ShouldAttempt::No));
+        assert_eq!(report.unused_exchanges, 0);
+        assert_eq!(
+            report.hooks,
+            vec![
+                "read_before_execution",
+                "read_before_serialization",
+                "modify_before_serialization",
+                "read_after_serialization",
+                "modify_before_retry_loop",
+                "read_before_attempt",
+                "modify_before_signing",
+                "read_before_signing",
+                "read_after_signing",
+                "modify_before_transmit",
+                "read_before_transmit",
+                "read_after_transmit",
+                "modify_before_deserialization",
+                "read_before_deserialization",
+                "read_after_deserialization",
+                "modify_before_attempt_completion",
+                "read_after_attempt",
+                "modify_before_completion",
+                "read_after_execution",
+            ]
+        );
+    }
+
+    #[test]
+    fn retries_back_off_exponentially() {
+        let report = Simulation::builder()
+            .retry_config(standard_retries(4))
+            .exchanges([
+                ScriptedExchange::status(500),
+                ScriptedExchange::io_error().with_latency(Duration::from_millis(10)),
+                ScriptedExchange::status(503),
+                ScriptedExchange::status(200),
+            ])
+            .build()
+            .run();
+
+        assert!(report.result.is_ok());
+        assert_eq!(
+            report.backoff_delays(),
+            vec![
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+                Duration::from_secs(4)
+            ]
+        );
+        let started_at: Vec<_> = report.attempts.iter().map(|a| a.started_at).collect();
+        assert_eq!(
+            started_at,
+            vec![
+                Duration::ZERO,
+                Duration::from_secs(1),
+                Duration::from_millis(3010),
+                Duration::from_millis(7010),
+            ]
+        );
+        // Transient errors cost 10 permits each, and the final success returns one retry's worth
+        let permits: Vec<_> = report
+            .attempts
+            .iter()
+            .map(|a| a.token_bucket_permits)
+            .collect();
+        assert_eq!(permits, vec![Some(490), Some(480), Some(470), Some(480)]);
+        assert_eq!(report.token_bucket_permits, 480);
+    }
+
+    #[test]
+    fn clock_skew_is_picked_up_across_retries() {
+        let start_time = UNIX_EPOCH + Duration::from_secs(1_600_000_000);
+        let report = Simulation::builder()
+            .start_time(start_time)
+            .retry_config(standard_retries(3))
+            .exchanges([
+                ScriptedExchange::status(503)
+                    .with_server_time(start_time + Duration::from_secs(300)),
+                ScriptedExchange::io_error(),
+                ScriptedExchange::status(200)
+                    .with_server_time(start_time + Duration::from_secs(3 + 600)),
+            ])
+            .build()
+            .run();
+
+        assert!(report.result.is_ok());
+        assert_eq!(
+            report.backoff_delays(),
+            vec![Duration::from_secs(1), Duration::from_secs(2)]
+        );
+        // The failed connection has no `Date` header, so the skew of the first response is kept
+        let skews: Vec<_> = report.attempts.iter().map(|a| a.clock_skew).collect();
+        assert_eq!(
+            skews,
+            vec![
+                Some(Duration::from_secs(300)),
+                Some(Duration::from_secs(300)),
+                Some(Duration::from_secs(600)),
+            ]
+        );
+    }
+
+    #[test]
+    fn throttling_errors_cost_less_than_transient_errors() {
+        let report = Simulation::builder()
+            .retry_config(standard_retries(3))
+            .exchanges([
+                ScriptedExchange::status(429),
+                ScriptedExchange::status(429),
+                ScriptedExchange::status(429),
+            ])
+            .build()
+            .run();
+
+        match report.result.expect_err("throttled") {
+            SdkError::ServiceError(context) => assert_eq!(
+                Some(429),
+                context
+                    .err()
+                    .downcast_ref::<SimulatedServiceError>()
+                    .map(SimulatedServiceError::status)
+            ),
+            err => panic!("expected a service error, got {err:?}"),
+        }
+        assert_eq!(report.attempts.len(), 3);
+        assert_eq!(report.token_bucket_permits, 490);
+        assert_eq!(
+            report.attempts[2].retry_decision,
+            Some(ShouldAttempt::No),
+            "out of attempts"
+        );
+    }
+
+    #[test]
+    fn empty_token_bucket_stops_retries() {
+        let report = Simulation::builder()
+            .retry_config(standard_retries(5))
+            .token_bucket(TokenBucket::new(10))
+            .exchanges([
+                ScriptedExchange::status(500),
+                ScriptedExchange::status(500),
+                ScriptedExchange::status(200),
+            ])
+            .build()
+            .run();
+
+        assert!(report.result.is_err());
+        assert_eq!(report.attempts.len(), 2);
+        assert_eq!(report.attempts[1].token_bucket_permits, Some(0));
+        assert_eq!(report.unused_exchanges, 1);
+    }
+
+    #[test]
+    fn attempt_timeouts_race_slow_responses() {
+        let report = Simulation::builder()
+            .retry_config(standard_retries(2))
+            .timeout_config(
+                TimeoutConfig::builder()
+                    .operation_attempt_timeout(Duration::from_secs(2))
+                    .build(),
+            )
+            .exchanges([
+                ScriptedExchange::status(200).with_latency(Duration::from_secs(5)),
+                ScriptedExchange::status(200).with_latency(Duration::from_millis(1500)),
+            ])
+            .build()
+            .run();
+
+        assert!(report.result.is_ok());
+        assert_eq!(report.attempts[0].ended_at, Some(Duration::from_secs(2)));
+        assert_eq!(report.backoff_delays(), vec![Duration::from_secs(1)]);
+        assert_eq!(report.elapsed, Duration::from_millis(4500));
+        // The timed out attempt never got to its completion hooks
+        assert_eq!(
+            report
+                .hooks
+                .iter()
+                .filter(|hook| **hook == "read_after_attempt")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn operation_timeout_ends_the_simulation() {
+        let report = Simulation::builder()
+            .retry_config(standard_retries(3))
+            .timeout_config(
+                TimeoutConfig::builder()
+                    .operation_timeout(Duration::from_secs(3))
+                    .build(),
+            )
+            .exchanges([
+                ScriptedExchange::status(503),
+                ScriptedExchange::status(503).with_latency(Duration::from_secs(10)),
+            ])
+            .build()
+            .run();
+
+        assert!(matches!(report.result, Err(SdkError::TimeoutError(_))));
+        assert_eq!(report.elapsed, Duration::from_secs(3));
+        assert_eq!(report.attempts.len(), 2);
+        assert_eq!(report.attempts[1].ended_at, None);
+    }
+
+    #[test]
+    fn running_out_of_exchanges_is_a_dispatch_failure() {
+        let report = Simulation::builder()
+            .retry_config(RetryConfig::disabled())
+            .build()
+            .run();
+
+        assert!(matches!(report.result, Err(SdkError::DispatchFailure(_))));
+    }
+
+    #[test]
+    fn custom_interceptors_fire_after_the_recorder() {
+        #[derive(Debug)]
+        struct FailBeforeTransmit;
+        impl Interceptor for FailBeforeTransmit {
+            fn read_before_transmit(
+                &self,
+                _context: &BeforeTransmitInterceptorContextRef<'_>,
+                _runtime_components: &RuntimeComponents,
+                _cfg: &mut ConfigBag,
+            ) -> Result<(), BoxError> {
+                Err("nope".into())
+            }
+        }
+
+        let report = Simulation::builder()
+            .retry_config(RetryConfig::disabled())
+            .interceptor(FailBeforeTransmit)
+            .exchange(ScriptedExchange::status(200))
+            .build()
+            .run();
+
+        assert!(report.result.is_err());
+        assert_eq!(report.unused_exchanges, 1);
+        assert_eq!(
+            &report.hooks[report.hooks.len() - 5..],
+            &[
+                "read_before_transmit",
+                "modify_before_attempt_completion",
+                "read_after_attempt",
+                "modify_before_completion",
+                "read_after_execution",
+            ]
+        );
+    }
+}
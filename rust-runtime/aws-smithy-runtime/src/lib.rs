@@ -7,8 +7,13 @@
 //!
 //! # Crate Features
 //!
+//! - `connector-hyper`: Enables the [`HyperConnector`](client::connectors::hyper_connector::HyperConnector),
+//!   a connector built on `hyper` that generated clients use by default.
+//! - `tls-rustls`: Enables HTTPS for the `HyperConnector` using `rustls`.
 //! - `http-auth`: Enables auth scheme and identity resolver implementations for HTTP API Key,
 //!   Basic Auth, Bearer Token, and Digest Auth.
+//! - `request-compression`: Enables the interceptor that compresses request bodies for operations
+//!   modeled with the `@requestCompression` trait.
 //! - `test-util`: Enables utilities for unit tests. DO NOT ENABLE IN PRODUCTION.
 
 #![warn(
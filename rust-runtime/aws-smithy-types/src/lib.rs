@@ -22,7 +22,9 @@ pub mod date_time;
 pub mod endpoint;
 pub mod error;
 pub mod primitive;
+pub mod request_compression;
 pub mod retry;
+pub mod stalled_stream_protection;
 pub mod timeout;
 
 //TODO(enableNewSmithyRuntimeLaunch): Unhide this module when switching to the orchestrator
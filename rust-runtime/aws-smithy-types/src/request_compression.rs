@@ -0,0 +1,12 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Limits for request compression, shared by the client runtime and SDK config loading.
+
+/// The default minimum size, in bytes, that a request body must have to be compressed.
+pub const DEFAULT_MIN_COMPRESSION_SIZE_BYTES: u32 = 10_240;
+
+/// The largest allowed minimum size, in bytes, that a request body must have to be compressed.
+pub const MAX_MIN_COMPRESSION_SIZE_BYTES: u32 = 10_485_760;
@@ -111,6 +111,15 @@ impl FromStr for RetryMode {
     }
 }
 
+impl fmt::Display for RetryMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryMode::Standard => write!(f, "standard"),
+            RetryMode::Adaptive => write!(f, "adaptive"),
+        }
+    }
+}
+
 /// Failure to parse a `RetryMode` from string.
 #[derive(Debug)]
 pub struct RetryModeParseError {
@@ -482,6 +491,13 @@ mod tests {
         // );
     }
 
+    #[test]
+    fn retry_mode_display_round_trips() {
+        for mode in [RetryMode::Standard, RetryMode::Adaptive] {
+            assert_eq!(Some(mode), RetryMode::from_str(&mode.to_string()).ok());
+        }
+    }
+
     #[test]
     fn retry_mode_from_str_ignores_whitespace_before_and_after() {
         assert_eq!(
@@ -0,0 +1,251 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! This module defines types that configure stalled stream protection.
+//!
+//! Stalled stream protection fails a request attempt when its request or response body stops
+//! making progress, rather than letting a transfer that trickles along at a few bytes per second
+//! run forever.
+
+use crate::config_bag::{Storable, StoreReplace};
+use std::time::Duration;
+
+const DEFAULT_GRACE_PERIOD: Duration = Duration::from_secs(5);
+/// Shortest grace period that throughput is measured over
+const MINIMUM_GRACE_PERIOD: Duration = Duration::from_secs(1);
+const DEFAULT_MINIMUM_THROUGHPUT: u64 = 1;
+
+/// Builder for [`StalledStreamProtectionConfig`].
+#[non_exhaustive]
+#[derive(Clone, Debug, Default)]
+pub struct StalledStreamProtectionConfigBuilder {
+    upload_enabled: Option<bool>,
+    download_enabled: Option<bool>,
+    grace_period: Option<Duration>,
+    minimum_throughput: Option<u64>,
+}
+
+impl StalledStreamProtectionConfigBuilder {
+    /// Creates a new builder with no settings set.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Sets whether stalled stream protection is applied to request bodies.
+    ///
+    /// Defaults to `true`.
+    pub fn upload_enabled(mut self, upload_enabled: bool) -> Self {
+        self.upload_enabled = Some(upload_enabled);
+        self
+    }
+
+    /// Sets whether stalled stream protection is applied to request bodies.
+    ///
+    /// Defaults to `true`.
+    pub fn set_upload_enabled(&mut self, upload_enabled: Option<bool>) -> &mut Self {
+        self.upload_enabled = upload_enabled;
+        self
+    }
+
+    /// Sets whether stalled stream protection is applied to response bodies.
+    ///
+    /// Defaults to `true`.
+    pub fn download_enabled(mut self, download_enabled: bool) -> Self {
+        self.download_enabled = Some(download_enabled);
+        self
+    }
+
+    /// Sets whether stalled stream protection is applied to response bodies.
+    ///
+    /// Defaults to `true`.
+    pub fn set_download_enabled(&mut self, download_enabled: Option<bool>) -> &mut Self {
+        self.download_enabled = download_enabled;
+        self
+    }
+
+    /// Sets the grace period.
+    ///
+    /// Throughput is measured over windows of this length, so a stream is only considered
+    /// stalled once it has been below the minimum throughput for at least the grace period.
+    /// Defaults to five seconds. Grace periods shorter than one second are raised to one second.
+    pub fn grace_period(mut self, grace_period: Duration) -> Self {
+        self.grace_period = Some(grace_period);
+        self
+    }
+
+    /// Sets the grace period.
+    ///
+    /// Throughput is measured over windows of this length, so a stream is only considered
+    /// stalled once it has been below the minimum throughput for at least the grace period.
+    /// Defaults to five seconds. Grace periods shorter than one second are raised to one second.
+    pub fn set_grace_period(&mut self, grace_period: Option<Duration>) -> &mut Self {
+        self.grace_period = grace_period;
+        self
+    }
+
+    /// Sets the minimum throughput in bytes per second.
+    ///
+    /// Defaults to one byte per second, which means that only streams making no progress at all
+    /// are considered stalled.
+    pub fn minimum_throughput(mut self, bytes_per_second: u64) -> Self {
+        self.minimum_throughput = Some(bytes_per_second);
+        self
+    }
+
+    /// Sets the minimum throughput in bytes per second.
+    ///
+    /// Defaults to one byte per second, which means that only streams making no progress at all
+    /// are considered stalled.
+    pub fn set_minimum_throughput(&mut self, bytes_per_second: Option<u64>) -> &mut Self {
+        self.minimum_throughput = bytes_per_second;
+        self
+    }
+
+    /// Merges two stalled stream protection config builders together.
+    ///
+    /// Values from `other` will only be used as a fallback for values from `self`.
+    pub fn take_unset_from(self, other: Self) -> Self {
+        Self {
+            upload_enabled: self.upload_enabled.or(other.upload_enabled),
+            download_enabled: self.download_enabled.or(other.download_enabled),
+            grace_period: self.grace_period.or(other.grace_period),
+            minimum_throughput: self.minimum_throughput.or(other.minimum_throughput),
+        }
+    }
+
+    /// Builds a `StalledStreamProtectionConfig`.
+    pub fn build(self) -> StalledStreamProtectionConfig {
+        let grace_period = self
+            .grace_period
+            .unwrap_or(DEFAULT_GRACE_PERIOD)
+            .max(MINIMUM_GRACE_PERIOD);
+        StalledStreamProtectionConfig {
+            upload_enabled: self.upload_enabled.unwrap_or(true),
+            download_enabled: self.download_enabled.unwrap_or(true),
+            grace_period,
+            minimum_throughput: self
+                .minimum_throughput
+                .unwrap_or(DEFAULT_MINIMUM_THROUGHPUT),
+        }
+    }
+}
+
+impl From<StalledStreamProtectionConfig> for StalledStreamProtectionConfigBuilder {
+    fn from(config: StalledStreamProtectionConfig) -> Self {
+        StalledStreamProtectionConfigBuilder {
+            upload_enabled: Some(config.upload_enabled),
+            download_enabled: Some(config.download_enabled),
+            grace_period: Some(config.grace_period),
+            minimum_throughput: Some(config.minimum_throughput),
+        }
+    }
+}
+
+/// Configuration for stalled stream protection
+///
+/// When placed in the config bag, the orchestrator monitors the throughput of request and
+/// response bodies and fails the attempt with a retryable error if it drops below
+/// [`minimum_throughput`](Self::minimum_throughput) for longer than the
+/// [`grace_period`](Self::grace_period).
+///
+/// # Example
+///
+/// ```rust
+/// # use std::time::Duration;
+/// use aws_smithy_types::stalled_stream_protection::StalledStreamProtectionConfig;
+///
+/// let config = StalledStreamProtectionConfig::builder()
+///     .grace_period(Duration::from_secs(10))
+///     .minimum_throughput(1024)
+///     .upload_enabled(false)
+///     .build();
+///
+/// assert_eq!(config.grace_period(), Duration::from_secs(10));
+/// assert_eq!(config.minimum_throughput(), 1024);
+/// assert!(!config.upload_enabled());
+/// assert!(config.download_enabled());
+/// ```
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StalledStreamProtectionConfig {
+    upload_enabled: bool,
+    download_enabled: bool,
+    grace_period: Duration,
+    minimum_throughput: u64,
+}
+
+impl Storable for StalledStreamProtectionConfig {
+    type Storer = StoreReplace<StalledStreamProtectionConfig>;
+}
+
+impl StalledStreamProtectionConfig {
+    /// Returns a builder to create a `StalledStreamProtectionConfig`.
+    pub fn builder() -> StalledStreamProtectionConfigBuilder {
+        StalledStreamProtectionConfigBuilder::new()
+    }
+
+    /// Returns a config that enables stalled stream protection with the default settings.
+    pub fn enabled() -> StalledStreamProtectionConfig {
+        StalledStreamProtectionConfigBuilder::new().build()
+    }
+
+    /// Returns a config that disables stalled stream protection for both uploads and downloads.
+    pub fn disabled() -> StalledStreamProtectionConfig {
+        StalledStreamProtectionConfigBuilder::new()
+            .upload_enabled(false)
+            .download_enabled(false)
+            .build()
+    }
+
+    /// Returns a builder equivalent of this `StalledStreamProtectionConfig`.
+    pub fn to_builder(&self) -> StalledStreamProtectionConfigBuilder {
+        StalledStreamProtectionConfigBuilder::from(self.clone())
+    }
+
+    /// Returns true if stalled stream protection is applied to request bodies.
+    pub fn upload_enabled(&self) -> bool {
+        self.upload_enabled
+    }
+
+    /// Returns true if stalled stream protection is applied to response bodies.
+    pub fn download_enabled(&self) -> bool {
+        self.download_enabled
+    }
+
+    /// Returns true if stalled stream protection is enabled for either direction.
+    pub fn is_enabled(&self) -> bool {
+        self.upload_enabled || self.download_enabled
+    }
+
+    /// Returns the grace period that throughput is measured over.
+    pub fn grace_period(&self) -> Duration {
+        self.grace_period
+    }
+
+    /// Returns the minimum throughput in bytes per second.
+    pub fn minimum_throughput(&self) -> u64 {
+        self.minimum_throughput
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::StalledStreamProtectionConfig;
+    use std::time::Duration;
+
+    #[test]
+    fn short_grace_periods_are_raised_to_the_minimum() {
+        for grace_period in [Duration::ZERO, Duration::from_millis(999)] {
+            let config = StalledStreamProtectionConfig::builder()
+                .grace_period(grace_period)
+                .build();
+            assert_eq!(Duration::from_secs(1), config.grace_period());
+        }
+        let config = StalledStreamProtectionConfig::builder()
+            .grace_period(Duration::from_millis(1500))
+            .build();
+        assert_eq!(Duration::from_millis(1500), config.grace_period());
+    }
+}